@@ -2,6 +2,7 @@
 pragma solidity ^0.8.24;
 
 import {IERC20} from "./interfaces/IERC20.sol";
+import {IFlashLoanSimplePool} from "./interfaces/IFlashLoanSimplePool.sol";
 
 /// @title MonadAtomicArb
 /// @notice Atomic arbitrage contract for Monad mainnet
@@ -33,6 +34,10 @@ contract MonadAtomicArb {
     error SwapFailed(uint8 swapIndex);
     error Unprofitable(uint256 wmonBefore, uint256 wmonAfter);
     error InvalidRouter();
+    error UntrustedFlashloan();
+
+    // Lender of the in-flight flashloan (zero outside executeArbFlashloan)
+    address private activeLender;
 
     event ArbExecuted(
         uint8 indexed sellRouter,
@@ -66,6 +71,12 @@ contract MonadAtomicArb {
         if (!success) revert SwapFailed(1);
     }
 
+    /// @notice Execute swap 1 from memory calldata (flashloan callback path)
+    function _executeSwap1Memory(Router sellRouter, bytes memory sellRouterData) internal {
+        (bool success,) = _getRouterAddress(sellRouter).call(sellRouterData);
+        if (!success) revert SwapFailed(1);
+    }
+
     /// @notice Execute swap 2 (buy WMON with USDC) using actual USDC balance
     function _executeSwap2(Router buyRouter, uint24 buyPoolFee, uint256 minWmonOut) internal {
         uint256 usdcToSwap = IERC20(USDC).balanceOf(address(this));
@@ -226,6 +237,63 @@ contract MonadAtomicArb {
         emit ArbExecuted(uint8(sellRouter), uint8(buyRouter), wmonBefore, wmonAfter, profit);
     }
 
+    /// @notice Execute atomic arbitrage on borrowed WMON (flashloan-funded)
+    /// @dev Swaps run in executeOperation; profit is measured after repaying amount + premium
+    /// @param lender Aave V3-style pool exposing flashLoanSimple
+    /// @param amount WMON to borrow (must match the amount encoded in sellRouterData)
+    /// @param minProfit Minimum WMON profit after the flashloan premium
+    function executeArbFlashloan(
+        address lender,
+        uint256 amount,
+        Router sellRouter,
+        bytes calldata sellRouterData,
+        Router buyRouter,
+        uint24 buyPoolFee,
+        uint256 minWmonOut,
+        uint256 minProfit
+    ) external onlyOwner returns (int256 profit) {
+        uint256 wmonBefore = IERC20(WMON).balanceOf(address(this));
+
+        activeLender = lender;
+        IFlashLoanSimplePool(lender).flashLoanSimple(
+            address(this),
+            WMON,
+            amount,
+            abi.encode(sellRouter, sellRouterData, buyRouter, buyPoolFee, minWmonOut),
+            0
+        );
+        activeLender = address(0);
+
+        uint256 wmonAfter = IERC20(WMON).balanceOf(address(this));
+        profit = int256(wmonAfter) - int256(wmonBefore);
+
+        if (wmonAfter < wmonBefore + minProfit) {
+            revert Unprofitable(wmonBefore, wmonAfter);
+        }
+
+        emit ArbExecuted(uint8(sellRouter), uint8(buyRouter), amount, amount + uint256(profit), profit);
+    }
+
+    /// @notice Flashloan callback: run both swaps and approve repayment
+    function executeOperation(
+        address asset,
+        uint256 amount,
+        uint256 premium,
+        address initiator,
+        bytes calldata params
+    ) external returns (bool) {
+        if (msg.sender != activeLender || initiator != address(this)) revert UntrustedFlashloan();
+
+        (Router sellRouter, bytes memory sellRouterData, Router buyRouter, uint24 buyPoolFee, uint256 minWmonOut) =
+            abi.decode(params, (Router, bytes, Router, uint24, uint256));
+
+        _executeSwap1Memory(sellRouter, sellRouterData);
+        _executeSwap2(buyRouter, buyPoolFee, minWmonOut);
+
+        IERC20(asset).approve(msg.sender, amount + premium);
+        return true;
+    }
+
     /// @notice Withdraw tokens (emergency or profit collection)
     function withdrawToken(address token, uint256 amount) external onlyOwner {
         IERC20(token).transfer(owner, amount);
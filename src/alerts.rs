@@ -0,0 +1,125 @@
+//! Webhook alerts for unattended runs
+//!
+//! Posts short text messages to `ALERT_WEBHOOK_URL` when an arb executes or a
+//! ProdArb safety limit trips. The body carries both `content` (Discord) and
+//! `text` (Slack, Telegram `sendMessage?chat_id=...`) so one URL works for all.
+//!
+//! Sends run in a background task; a failed POST is logged and never reaches
+//! the trading loop. Terminal alerts (the bot is about to exit) are awaited
+//! instead, so the runtime shutting down can't drop them.
+
+use alloy::primitives::Address;
+use eyre::Result;
+use std::time::Duration;
+
+use crate::config::{get_tokens, TokenPair};
+
+/// Upper bound on an awaited (terminal) alert
+const SEND_NOW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// POST `msg` to a webhook URL
+pub async fn send_webhook(url: &str, msg: &str) -> Result<()> {
+    let body = serde_json::json!({ "content": msg, "text": msg });
+    crate::get_http_client()
+        .post(url)
+        .json(&body)
+        .timeout(SEND_NOW_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Alert sender bound to one wallet and pair
+pub struct Alerter {
+    url: String,
+    wallet: Address,
+    pair: String,
+}
+
+impl Alerter {
+    /// Build from `ALERT_WEBHOOK_URL` when `enabled`; warns and returns None if unset
+    pub fn from_env(enabled: bool, wallet: Address, pair: &TokenPair) -> Option<Self> {
+        if !enabled {
+            return None;
+        }
+        match std::env::var("ALERT_WEBHOOK_URL") {
+            Ok(url) if !url.is_empty() => Some(Self { url, wallet, pair: pair_label(pair) }),
+            _ => {
+                eprintln!("  --alerts set but ALERT_WEBHOOK_URL is empty; alerts disabled");
+                None
+            }
+        }
+    }
+
+    fn format(&self, msg: &str) -> String {
+        format!("[monad-arb] {} | wallet {:?}\n{}", self.pair, self.wallet, msg)
+    }
+
+    /// Fire-and-forget send with the wallet/pair header prepended
+    pub fn send(&self, msg: String) {
+        let url = self.url.clone();
+        let text = self.format(&msg);
+        tokio::spawn(async move {
+            if let Err(e) = send_webhook(&url, &text).await {
+                eprintln!("  Alert webhook failed: {}", e);
+            }
+        });
+    }
+
+    /// Awaited send for alerts right before the bot exits, bounded by `SEND_NOW_TIMEOUT`
+    pub async fn send_now(&self, msg: String) {
+        match tokio::time::timeout(SEND_NOW_TIMEOUT, send_webhook(&self.url, &self.format(&msg))).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("  Alert webhook failed: {}", e),
+            Err(_) => eprintln!("  Alert webhook timed out after {}s", SEND_NOW_TIMEOUT.as_secs()),
+        }
+    }
+
+    pub fn executed(&self, sell_dex: &str, buy_dex: &str, net_spread_bps: i32, pnl_wmon: f64, tx_hashes: &[&str]) {
+        let hashes: Vec<&str> = tx_hashes.iter().copied().filter(|h| !h.is_empty()).collect();
+        self.send(format!(
+            "Arb executed: sell {} / buy {} at {} bps net\nP&L: {:+.6} WMON\nTx: {}",
+            sell_dex,
+            buy_dex,
+            net_spread_bps,
+            pnl_wmon,
+            if hashes.is_empty() { "-".to_string() } else { hashes.join(", ") }
+        ));
+    }
+
+    pub async fn max_daily_loss(&self, cumulative_pnl: f64, limit: f64) {
+        self.send_now(format!(
+            "STOPPED: max daily loss exceeded ({:+.6} WMON, limit -{} WMON)",
+            cumulative_pnl, limit
+        ))
+        .await;
+    }
+
+    pub fn drawdown_pause(&self, drawdown: f64, limit: f64, cumulative_pnl: f64, pause_secs: u64) {
+        self.send(format!(
+            "PAUSED {}s: drawdown {:.6} WMON from session high exceeds {} WMON (P&L {:+.6} WMON)",
+            pause_secs, drawdown, limit, cumulative_pnl
+        ));
+    }
+
+    pub fn failures_pause(&self, consecutive_failures: u32, last_net_spread_bps: i32, pause_secs: u64) {
+        self.send(format!(
+            "PAUSED {}s: {} consecutive failures (last net spread {} bps)",
+            pause_secs, consecutive_failures, last_net_spread_bps
+        ));
+    }
+}
+
+/// "WMON/USDC" from the token registry, falling back to addresses
+fn pair_label(pair: &TokenPair) -> String {
+    let tokens = get_tokens();
+    let symbol = |addr: Address| {
+        tokens
+            .iter()
+            .find(|t| t.address == addr)
+            .map(|t| t.symbol.to_string())
+            .unwrap_or_else(|| format!("{:?}", addr))
+    };
+    format!("{}/{}", symbol(pair.base), symbol(pair.quote))
+}
@@ -0,0 +1,244 @@
+//! Offline backtest over logged JSONL
+//!
+//! Replays `mev_validation_*.jsonl` (block lifecycles) or `arb_stats_*.jsonl`
+//! (execution records) against a spread threshold and a slippage assumption,
+//! so `min_spread_bps` can be tuned without touching the chain.
+//!
+//! Lifecycle records trigger on the spread at Proposed and realize the spread
+//! at Finalized (what was left by the time the trade would land). Arb-stats
+//! records only have the pre-execution spread, so that is used for both.
+
+use eyre::{eyre, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::mev_validation::BlockLifecycle;
+use crate::stats::{ArbExecutionRecord, SessionSummary};
+
+/// Which log a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Lifecycle,
+    ArbStats,
+}
+
+/// One historical opportunity, normalized across both log formats
+#[derive(Debug, Clone)]
+pub struct BacktestSample {
+    pub format: RecordFormat,
+    /// Net spread when the opportunity was seen (threshold is applied to this)
+    pub trigger_spread_bps: i32,
+    /// Net spread the trade would have captured
+    pub realized_spread_bps: i32,
+    /// Logged on-chain result, when the record was actually executed
+    pub actual_profit_wmon: Option<f64>,
+}
+
+/// Parse one JSONL line, detecting the format by the fields present
+pub fn parse_line(line: &str) -> Option<BacktestSample> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    let obj = value.as_object()?;
+
+    if obj.contains_key("pre") {
+        let record: ArbExecutionRecord = serde_json::from_value(value).ok()?;
+        return Some(BacktestSample {
+            format: RecordFormat::ArbStats,
+            trigger_spread_bps: record.pre.net_spread_bps,
+            realized_spread_bps: record.pre.net_spread_bps,
+            actual_profit_wmon: record.post.map(|p| p.net_profit_wmon),
+        });
+    }
+
+    if obj.contains_key("block_number") && obj.contains_key("proposed") {
+        let lifecycle: BlockLifecycle = serde_json::from_value(value).ok()?;
+        let trigger = lifecycle
+            .spread_at_proposed_bps
+            .or_else(|| lifecycle.proposed.as_ref().map(|s| s.best_spread_bps))?;
+        let realized = lifecycle
+            .spread_at_finalized_bps
+            .or_else(|| lifecycle.finalized.as_ref().map(|s| s.best_spread_bps))
+            .unwrap_or(trigger);
+        return Some(BacktestSample {
+            format: RecordFormat::Lifecycle,
+            trigger_spread_bps: trigger,
+            realized_spread_bps: realized,
+            actual_profit_wmon: None,
+        });
+    }
+
+    None
+}
+
+/// Session summary line written at the end of an arb-stats session
+pub fn parse_session_summary(line: &str) -> Option<SessionSummary> {
+    let mut value: serde_json::Value = serde_json::from_str(line).ok()?;
+    serde_json::from_value(value.get_mut("session_summary")?.take()).ok()
+}
+
+/// Aggregate result of a backtest run
+#[derive(Debug, Default)]
+pub struct BacktestResult {
+    pub total_records: usize,
+    pub skipped_lines: usize,
+    pub triggered: usize,
+    pub wins: usize,
+    pub cumulative_pnl_wmon: f64,
+    pub best_trade_wmon: f64,
+    pub worst_trade_wmon: f64,
+    /// Sum of logged on-chain results for triggered records (arb-stats only)
+    pub actual_pnl_wmon: f64,
+    pub actual_count: usize,
+}
+
+impl BacktestResult {
+    pub fn win_rate(&self) -> f64 {
+        if self.triggered == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.triggered as f64 * 100.0
+        }
+    }
+}
+
+/// Apply threshold and slippage to samples; P&L is `amount * (realized - slippage)`
+pub fn simulate(samples: &[BacktestSample], min_spread_bps: i32, slippage_bps: u32, amount: f64) -> BacktestResult {
+    let mut result = BacktestResult {
+        total_records: samples.len(),
+        ..Default::default()
+    };
+
+    for sample in samples.iter().filter(|s| s.trigger_spread_bps >= min_spread_bps) {
+        let captured_bps = sample.realized_spread_bps - slippage_bps as i32;
+        let pnl = amount * captured_bps as f64 / 10_000.0;
+
+        if result.triggered == 0 {
+            result.best_trade_wmon = pnl;
+            result.worst_trade_wmon = pnl;
+        } else {
+            result.best_trade_wmon = result.best_trade_wmon.max(pnl);
+            result.worst_trade_wmon = result.worst_trade_wmon.min(pnl);
+        }
+
+        result.triggered += 1;
+        result.cumulative_pnl_wmon += pnl;
+        if pnl > 0.0 {
+            result.wins += 1;
+        }
+
+        if let Some(actual) = sample.actual_profit_wmon {
+            result.actual_pnl_wmon += actual;
+            result.actual_count += 1;
+        }
+    }
+
+    result
+}
+
+/// Run the backtest command
+pub fn run_backtest(file: &str, min_spread_bps: i32, slippage_bps: u32, amount: f64) -> Result<()> {
+    let reader = BufReader::new(File::open(file).map_err(|e| eyre!("Failed to open {}: {}", file, e))?);
+
+    let mut samples = Vec::new();
+    let mut summaries = Vec::new();
+    let mut skipped = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(summary) = parse_session_summary(&line) {
+            summaries.push(summary);
+            continue;
+        }
+        match parse_line(&line) {
+            Some(sample) => samples.push(sample),
+            None => skipped += 1,
+        }
+    }
+
+    if samples.is_empty() {
+        return Err(eyre!("No lifecycle or arb-stats records found in {}", file));
+    }
+
+    let format = match samples[0].format {
+        RecordFormat::Lifecycle => "mev_validation (block lifecycle)",
+        RecordFormat::ArbStats => "arb_stats (execution records)",
+    };
+
+    let mut result = simulate(&samples, min_spread_bps, slippage_bps, amount);
+    result.skipped_lines = skipped;
+
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("  BACKTEST | {}", file);
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Format:             {}", format);
+    println!("  Records:            {} ({} unparsed lines skipped)", result.total_records, result.skipped_lines);
+    println!("  Min spread:         {} bps", min_spread_bps);
+    println!("  Slippage assumed:   {} bps", slippage_bps);
+    println!("  Amount per trade:   {} WMON", amount);
+    println!("───────────────────────────────────────────────────────────────");
+    println!("  Triggered:          {}", result.triggered);
+    println!("  Win rate:           {:.1}% ({}/{})", result.win_rate(), result.wins, result.triggered);
+    println!("  Simulated P&L:      {:+.6} WMON", result.cumulative_pnl_wmon);
+    if result.triggered > 0 {
+        println!("  Avg per trade:      {:+.6} WMON", result.cumulative_pnl_wmon / result.triggered as f64);
+        println!("  Best / worst:       {:+.6} / {:+.6} WMON", result.best_trade_wmon, result.worst_trade_wmon);
+    }
+    if result.actual_count > 0 {
+        println!("  Logged on-chain:    {:+.6} WMON over {} executions", result.actual_pnl_wmon, result.actual_count);
+    }
+    if !summaries.is_empty() {
+        // Sum logged slippage across sessions in the file
+        let mut swap1 = [0u64; 5];
+        let mut swap2 = [0u64; 5];
+        for summary in &summaries {
+            for (i, (_, n)) in summary.swap1_slippage.buckets().iter().enumerate() {
+                swap1[i] += n;
+            }
+            for (i, (_, n)) in summary.swap2_slippage.buckets().iter().enumerate() {
+                swap2[i] += n;
+            }
+        }
+        println!("───────────────────────────────────────────────────────────────");
+        println!("  Logged slippage ({} sessions)   swap1 / swap2", summaries.len());
+        for (i, (label, _)) in summaries[0].swap1_slippage.buckets().iter().enumerate() {
+            println!("    {:<8} bps          {:>6} / {:<6}", label, swap1[i], swap2[i]);
+        }
+    }
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_format_and_applies_threshold() {
+        let lifecycle = r#"{"block_number":100,"proposed":null,"voted":null,"finalized":null,"verified":null,
+            "proposed_to_finalized_ms":400,"spread_at_proposed_bps":15,"spread_at_finalized_bps":8,
+            "spread_delta_bps":-7,"spread_persisted":false}"#
+            .replace('\n', "");
+        let sample = parse_line(&lifecycle).unwrap();
+        assert_eq!(sample.format, RecordFormat::Lifecycle);
+        assert_eq!((sample.trigger_spread_bps, sample.realized_spread_bps), (15, 8));
+
+        assert!(parse_line(r#"{"unrelated":1}"#).is_none());
+
+        let samples = vec![
+            sample,
+            BacktestSample {
+                format: RecordFormat::Lifecycle,
+                trigger_spread_bps: 5,
+                realized_spread_bps: 5,
+                actual_profit_wmon: None,
+            },
+        ];
+        // Only the 15 bps sample triggers; captures 8 - 3 = 5 bps of 1 WMON
+        let result = simulate(&samples, 10, 3, 1.0);
+        assert_eq!(result.triggered, 1);
+        assert_eq!(result.wins, 1);
+        assert!((result.cumulative_pnl_wmon - 0.0005).abs() < 1e-12);
+    }
+}
@@ -1,4 +1,18 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, Bytes, B256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::execution::SwapDirection;
+use crate::pools::PoolPrice;
+use crate::gas::CompetitiveRoute;
+use crate::multicall::aggregate_raw;
+use crate::spread_filter::{RouteDecay, RouteFilterOverride};
 
 // ============== MONAD MAINNET CONFIGURATION ==============
 // Chain ID: 143
@@ -10,14 +24,148 @@ pub const WMON_ADDRESS: Address = alloy::primitives::address!("3bd359C1119dA7Da1
 pub const USDC_ADDRESS: Address = alloy::primitives::address!("754704Bc059F8C67012fEd69BC8A327a5aafb603");
 pub const MULTICALL3_ADDRESS: Address = alloy::primitives::address!("cA11bde05977b3631167028862bE2a173976CA11");
 
-// TODO: Update this address after deploying MonadAtomicArb contract
-// Atomic Arbitrage Contract (deployed by user - UPDATE THIS AFTER DEPLOYMENT)
-pub const ATOMIC_ARB_CONTRACT: Address = alloy::primitives::address!("7299daB2965c0A6ce471a8284a1D05bB483e05b2");
+// Atomic Arbitrage Contract (deployed by user). A redeploy can be picked up
+// without rebuilding via the ATOMIC_ARB_CONTRACT env var.
+pub const DEFAULT_ATOMIC_ARB_CONTRACT: Address = alloy::primitives::address!("7299daB2965c0A6ce471a8284a1D05bB483e05b2");
+
+/// Atomic arb contract: `ATOMIC_ARB_CONTRACT` env var if set, else the compiled
+/// default. An unparsable override disables the contract (ZERO) rather than
+/// silently trading through the old one.
+pub fn atomic_arb_contract() -> Address {
+    static CONTRACT: OnceLock<Address> = OnceLock::new();
+    *CONTRACT.get_or_init(|| match std::env::var("ATOMIC_ARB_CONTRACT") {
+        Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("ATOMIC_ARB_CONTRACT={:?} is not an address; atomic arb disabled", value);
+            Address::ZERO
+        }),
+        Err(_) => DEFAULT_ATOMIC_ARB_CONTRACT,
+    })
+}
 
 // Token decimals
 pub const WMON_DECIMALS: u8 = 18;
 pub const USDC_DECIMALS: u8 = 6;
 
+/// Token pair traded by the swap/arb paths.
+/// SwapDirection is relative to `base`: Sell = base -> quote, Buy = quote -> base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenPair {
+    pub base: Address,
+    pub quote: Address,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+}
+
+impl TokenPair {
+    /// Default pair (all configured pools are WMON/USDC)
+    pub const fn wmon_usdc() -> Self {
+        Self {
+            base: WMON_ADDRESS,
+            quote: USDC_ADDRESS,
+            base_decimals: WMON_DECIMALS,
+            quote_decimals: USDC_DECIMALS,
+        }
+    }
+}
+
+/// Token metadata (symbol is the lookup key for --pair)
+#[derive(Debug, Clone)]
+pub struct TokenConfig {
+    pub symbol: &'static str,
+    pub address: Address,
+    pub decimals: u8,
+}
+
+fn default_tokens() -> Vec<TokenConfig> {
+    vec![
+        TokenConfig { symbol: "WMON", address: WMON_ADDRESS, decimals: WMON_DECIMALS },
+        TokenConfig { symbol: "USDC", address: USDC_ADDRESS, decimals: USDC_DECIMALS },
+    ]
+}
+
+pub fn get_tokens() -> Vec<TokenConfig> {
+    match APP_CONFIG.get() {
+        Some(cfg) => cfg.tokens.clone(),
+        None => default_tokens(),
+    }
+}
+
+pub fn get_token_by_symbol(symbol: &str) -> Option<TokenConfig> {
+    get_tokens().into_iter().find(|t| t.symbol.eq_ignore_ascii_case(symbol))
+}
+
+/// Get token pair by CLI name "<base>-<quote>", e.g. "wmon-usdc" ("/" also accepted)
+pub fn get_token_pair(name: &str) -> Option<TokenPair> {
+    let (base, quote) = name.split_once(['-', '/'])?;
+    let base = get_token_by_symbol(base)?;
+    let quote = get_token_by_symbol(quote)?;
+    Some(TokenPair {
+        base: base.address,
+        quote: quote.address,
+        base_decimals: base.decimals,
+        quote_decimals: quote.decimals,
+    })
+}
+
+sol! {
+    function symbol() external view returns (string);
+    function decimals() external view returns (uint8);
+}
+
+/// (symbol, decimals) per token, read once from chain
+static TOKEN_METADATA: Mutex<BTreeMap<Address, (String, u8)>> = Mutex::new(BTreeMap::new());
+
+/// Decode `symbol()` (string, or bytes32 on older tokens) and `decimals()` results
+fn decode_token_metadata(symbol: Option<&Bytes>, decimals: Option<&Bytes>) -> Option<(String, u8)> {
+    let decimals = decimalsCall::abi_decode_returns(decimals?).ok()?;
+    let symbol = symbol?;
+    let symbol = symbolCall::abi_decode_returns(symbol).ok().or_else(|| {
+        let raw = B256::try_from(symbol.as_ref()).ok()?;
+        Some(String::from_utf8_lossy(raw.as_slice()).trim_end_matches('\0').to_string())
+    })?;
+    Some((symbol, decimals))
+}
+
+/// `token`'s on-chain symbol and decimals (cached). If the calls fail, WMON and
+/// USDC fall back to their compiled metadata; other tokens are an error.
+pub async fn fetch_token_metadata<P: Provider>(provider: &P, token: Address) -> Result<(String, u8)> {
+    if let Some(cached) = TOKEN_METADATA.lock().unwrap_or_else(|e| e.into_inner()).get(&token) {
+        return Ok(cached.clone());
+    }
+    let calls = [
+        (token, Bytes::from(symbolCall {}.abi_encode())),
+        (token, Bytes::from(decimalsCall {}.abi_encode())),
+    ];
+    let fetched = aggregate_raw(provider, &calls)
+        .await
+        .and_then(|r| decode_token_metadata(r[0].as_ref(), r[1].as_ref()).ok_or_else(|| eyre!("symbol()/decimals() reverted")));
+    let metadata = match fetched {
+        Ok(metadata) => metadata,
+        Err(e) => match default_tokens().into_iter().find(|t| t.address == token) {
+            Some(t) => {
+                tracing::warn!("Token metadata for {:?} unavailable ({}), using compiled {} / {}", token, e, t.symbol, t.decimals);
+                (t.symbol.to_string(), t.decimals)
+            }
+            None => return Err(eyre!("Token metadata for {:?} unavailable: {}", token, e)),
+        },
+    };
+    TOKEN_METADATA.lock().unwrap_or_else(|e| e.into_inner()).insert(token, metadata.clone());
+    Ok(metadata)
+}
+
+/// `pair` with decimals read from chain. A configured value that disagrees is
+/// replaced (with a warning) rather than silently mis-scaling every amount.
+pub async fn resolve_pair_metadata<P: Provider>(provider: &P, pair: TokenPair) -> Result<TokenPair> {
+    let (base, quote) = tokio::try_join!(fetch_token_metadata(provider, pair.base), fetch_token_metadata(provider, pair.quote))?;
+    for ((symbol, onchain), configured) in [(&base, pair.base_decimals), (&quote, pair.quote_decimals)] {
+        if *onchain != configured {
+            tracing::warn!("{} has {} decimals on chain but {} configured; using {}", symbol, onchain, configured, onchain);
+        }
+    }
+    println!("  Pair: {} ({} dec) / {} ({} dec)", base.0, base.1, quote.0, quote.1);
+    Ok(TokenPair { base_decimals: base.1, quote_decimals: quote.1, ..pair })
+}
+
 // Default polling interval in milliseconds
 // NOTE: For local node, use NodeConfig.poll_interval instead (100ms)
 pub const POLL_INTERVAL_MS: u64 = 1000;
@@ -44,8 +192,8 @@ impl PoolConfig {
     }
 }
 
-// V3 Pool Configurations
-pub fn get_v3_pools() -> Vec<PoolConfig> {
+// Compiled-in pool defaults (overlaid by --config file, see load_from_file)
+fn default_pools() -> Vec<PoolConfig> {
     vec![
         PoolConfig {
             name: "Uniswap",
@@ -65,35 +213,77 @@ pub fn get_v3_pools() -> Vec<PoolConfig> {
             pool_type: PoolType::PancakeV3,
             fee_bps: 25, // 0.25%
         },
+        // LFJ Pool (Liquidity Book / DLMM)
+        PoolConfig {
+            name: "LFJ",
+            address: alloy::primitives::address!("5e60bc3f7a7303bc4dfe4dc2220bdc90bc04fe22"),
+            pool_type: PoolType::LiquidityBook,
+            fee_bps: 10, // Bin step 10 = ~0.10% base fee (verified from pool contract)
+        },
+        // Monday Trade Pool
+        PoolConfig {
+            name: "MondayTrade",
+            address: alloy::primitives::address!("8f889ba499c0a176fb8f233d9d35b1c132eb868c"),
+            pool_type: PoolType::MondayTrade,
+            fee_bps: 5, // 0.05% fee (NOT 30!)
+        },
     ]
 }
 
+// Get all pools (loaded config if present, else compiled defaults),
+// plus any fee-tier pools found at startup
+pub fn get_all_pools() -> Vec<PoolConfig> {
+    let mut pools = match APP_CONFIG.get() {
+        Some(cfg) => cfg.pools.clone(),
+        None => default_pools(),
+    };
+    pools.extend(get_fee_tier_venues().into_iter().map(|(pool, _)| pool));
+    for pool in pools.iter_mut().filter(|p| matches!(p.pool_type, PoolType::UniswapV3 | PoolType::PancakeV3)) {
+        if let Some(fee) = validated_fee(pool.address) {
+            pool.fee_bps = fee / 100;
+        }
+    }
+    pools
+}
+
+// V3 Pool Configurations
+pub fn get_v3_pools() -> Vec<PoolConfig> {
+    get_all_pools()
+        .into_iter()
+        .filter(|p| matches!(p.pool_type, PoolType::UniswapV3 | PoolType::PancakeV3))
+        .collect()
+}
+
 // LFJ Pool (Liquidity Book / DLMM)
 pub fn get_lfj_pool() -> PoolConfig {
-    PoolConfig {
-        name: "LFJ",
-        address: alloy::primitives::address!("5e60bc3f7a7303bc4dfe4dc2220bdc90bc04fe22"),
-        pool_type: PoolType::LiquidityBook,
-        fee_bps: 10, // Bin step 10 = ~0.10% base fee (verified from pool contract)
+    first_pool_of_type(PoolType::LiquidityBook)
+}
+
+// Every LFJ pool, including other bin steps found at startup
+pub fn get_lfj_pools() -> Vec<PoolConfig> {
+    let pools: Vec<PoolConfig> = get_all_pools()
+        .into_iter()
+        .filter(|p| p.pool_type == PoolType::LiquidityBook)
+        .collect();
+    if pools.is_empty() {
+        vec![get_lfj_pool()]
+    } else {
+        pools
     }
 }
 
 // Monday Trade Pool
 pub fn get_monday_trade_pool() -> PoolConfig {
-    PoolConfig {
-        name: "MondayTrade",
-        address: alloy::primitives::address!("8f889ba499c0a176fb8f233d9d35b1c132eb868c"),
-        pool_type: PoolType::MondayTrade,
-        fee_bps: 5, // 0.05% fee (NOT 30!)
-    }
+    first_pool_of_type(PoolType::MondayTrade)
 }
 
-// Get all pools
-pub fn get_all_pools() -> Vec<PoolConfig> {
-    let mut pools = get_v3_pools();
-    pools.push(get_lfj_pool());
-    pools.push(get_monday_trade_pool());
-    pools
+// Falls back to the compiled default if the config file retyped the entry
+fn first_pool_of_type(pool_type: PoolType) -> PoolConfig {
+    get_all_pools()
+        .into_iter()
+        .chain(default_pools())
+        .find(|p| p.pool_type == pool_type)
+        .expect("compiled defaults include one pool of every type")
 }
 
 // ============== ROUTER ADDRESSES ==============
@@ -111,6 +301,43 @@ pub enum RouterType {
     PancakeV3,
     LfjLB,
     MondayTrade,
+    /// Universal Router `V4_SWAP` into the singleton PoolManager (needs `v4_pool`)
+    UniswapV4,
+}
+
+/// The PoolKey parts a V4 pool adds on top of the fee tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V4PoolParams {
+    pub tick_spacing: i32,
+    /// Hooks contract (zero for hookless pools)
+    pub hooks: Address,
+    /// Pool is keyed on native MON (address(0)) rather than WMON
+    pub native: bool,
+}
+
+impl V4PoolParams {
+    /// Hookless WMON pool with the standard tick spacing for `fee`
+    pub fn standard(fee: u32) -> Self {
+        let tick_spacing = match fee {
+            100 => 1,
+            500 => 10,
+            3000 => 60,
+            10000 => 200,
+            // Dynamic/custom fees: ~fee/50, the ratio of the standard tiers
+            _ => (fee / 50).max(1) as i32,
+        };
+        Self { tick_spacing, hooks: Address::ZERO, native: false }
+    }
+}
+
+/// How swap transactions to a router are priced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GasMode {
+    /// Type-2 tx with max_fee_per_gas / max_priority_fee_per_gas
+    #[default]
+    Eip1559,
+    /// Type-0 tx with a single gas_price
+    Legacy,
 }
 
 #[derive(Debug, Clone)]
@@ -120,9 +347,45 @@ pub struct RouterConfig {
     pub router_type: RouterType,
     pub pool_address: Address,  // The specific pool to use
     pub pool_fee: u32,          // Fee tier for V3 pools (in hundredths of bps, e.g., 3000 = 0.3%)
+    pub quoter: Option<Address>, // QuoterV2 for read-only quotes (simulate-arb); set via --config
+    pub gas_mode: GasMode,
+    pub v4_pool: Option<V4PoolParams>, // PoolKey extras, UniswapV4 routers only
+    pub usdc_buffer_bps: u32,   // Swap 1 USDC held back from an exact-input swap 2 (see DEFAULT_USDC_BUFFER_BPS)
+    pub supports_native: bool,  // Router wraps/unwraps native MON itself (SellMon/BuyMon --use-native)
 }
 
-pub fn get_routers() -> Vec<RouterConfig> {
+impl RouterConfig {
+    /// Swap fee as a fraction. LFJ's `pool_fee` is its bin step, which is the
+    /// base fee in bps; everywhere else it is in hundredths of a bp.
+    pub fn fee_fraction(&self) -> f64 {
+        match self.router_type {
+            RouterType::LfjLB => self.pool_fee as f64 / 10_000.0,
+            _ => self.pool_fee as f64 / 1_000_000.0,
+        }
+    }
+
+    /// Share of swap 1's USDC an exact-input swap 2 through this router may sell
+    pub fn usdc_spend_fraction(&self) -> f64 {
+        1.0 - self.usdc_buffer_bps as f64 / 10_000.0
+    }
+}
+
+/// Default `usdc_buffer_bps`. Swap 2 sells the USDC balance swap 1 added, but
+/// that delta can sit a few wei above what the router can actually pull:
+/// routers that round down internally (or fee-on-transfer tokens) then revert
+/// with "transfer amount exceeds balance". Holding back 0.1% leaves dust for
+/// rebalancing instead; routers that need more set it via --config.
+pub const DEFAULT_USDC_BUFFER_BPS: u32 = 10;
+
+/// Routers whose swap functions take MON as the TX value and can pay out
+/// MON: SwapRouter02 and SmartRouter (WETH9 = WMON, `unwrapWETH9`) and the
+/// LB router (`swapExactNATIVEForTokens`)
+pub fn default_supports_native(router_type: RouterType) -> bool {
+    matches!(router_type, RouterType::UniswapV3 | RouterType::PancakeV3 | RouterType::LfjLB)
+}
+
+// Compiled-in router defaults (overlaid by --config file, see load_from_file)
+fn default_routers() -> Vec<RouterConfig> {
     vec![
         RouterConfig {
             name: "Uniswap",
@@ -130,6 +393,11 @@ pub fn get_routers() -> Vec<RouterConfig> {
             router_type: RouterType::UniswapV3,
             pool_address: alloy::primitives::address!("659bd0bc4167ba25c62e05656f78043e7ed4a9da"),
             pool_fee: 3000,  // 0.30%
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: None,
+            usdc_buffer_bps: DEFAULT_USDC_BUFFER_BPS,
+            supports_native: default_supports_native(RouterType::UniswapV3),
         },
         RouterConfig {
             name: "PancakeSwap1",
@@ -137,6 +405,11 @@ pub fn get_routers() -> Vec<RouterConfig> {
             router_type: RouterType::PancakeV3,
             pool_address: alloy::primitives::address!("63e48B725540A3Db24ACF6682a29f877808C53F2"),
             pool_fee: 500,  // 0.05%
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: None,
+            usdc_buffer_bps: DEFAULT_USDC_BUFFER_BPS,
+            supports_native: default_supports_native(RouterType::PancakeV3),
         },
         RouterConfig {
             name: "PancakeSwap2",
@@ -144,6 +417,11 @@ pub fn get_routers() -> Vec<RouterConfig> {
             router_type: RouterType::PancakeV3,
             pool_address: alloy::primitives::address!("85717A98d195c9306BBf7c9523Ba71F044Fea0f7"),
             pool_fee: 2500,  // 0.25%
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: None,
+            usdc_buffer_bps: DEFAULT_USDC_BUFFER_BPS,
+            supports_native: default_supports_native(RouterType::PancakeV3),
         },
         RouterConfig {
             name: "LFJ",
@@ -151,6 +429,11 @@ pub fn get_routers() -> Vec<RouterConfig> {
             router_type: RouterType::LfjLB,
             pool_address: alloy::primitives::address!("5e60bc3f7a7303bc4dfe4dc2220bdc90bc04fe22"),
             pool_fee: 10,  // Bin step (verified from pool contract)
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: None,
+            usdc_buffer_bps: DEFAULT_USDC_BUFFER_BPS,
+            supports_native: default_supports_native(RouterType::LfjLB),
         },
         RouterConfig {
             name: "MondayTrade",
@@ -158,10 +441,598 @@ pub fn get_routers() -> Vec<RouterConfig> {
             router_type: RouterType::MondayTrade,
             pool_address: alloy::primitives::address!("8f889ba499c0a176fb8f233d9d35b1c132eb868c"),
             pool_fee: 500,  // 0.05% fee tier (NOT 3000!)
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: None,
+            usdc_buffer_bps: DEFAULT_USDC_BUFFER_BPS,
+            supports_native: default_supports_native(RouterType::MondayTrade),
         },
     ]
 }
 
+pub fn get_routers() -> Vec<RouterConfig> {
+    let mut routers = match APP_CONFIG.get() {
+        Some(cfg) => cfg.routers.clone(),
+        None => default_routers(),
+    };
+    routers.extend(get_fee_tier_venues().into_iter().map(|(_, router)| router));
+    for router in routers.iter_mut().filter(|r| matches!(r.router_type, RouterType::UniswapV3 | RouterType::PancakeV3)) {
+        if let Some(fee) = validated_fee(router.pool_address) {
+            router.pool_fee = fee;
+        }
+    }
+    routers
+}
+
 pub fn get_router_by_name(name: &str) -> Option<RouterConfig> {
     get_routers().into_iter().find(|r| r.name.to_lowercase() == name.to_lowercase())
 }
+
+/// Router for DEX `name` on whichever of its fee tiers gives the best
+/// fee-adjusted price for `direction` in `prices` (highest when selling the
+/// base, lowest when buying it). Falls back to `get_router_by_name`.
+pub fn get_best_tier_router(name: &str, direction: SwapDirection, prices: &[PoolPrice]) -> Option<RouterConfig> {
+    let effective_price = |router: &RouterConfig| {
+        let price = prices.iter().find(|p| p.pool_name.eq_ignore_ascii_case(router.name))?.price;
+        let fee = router.fee_fraction();
+        Some(match direction {
+            SwapDirection::Sell => price * (1.0 - fee),
+            SwapDirection::Buy => price / (1.0 - fee),
+        })
+    };
+
+    get_routers()
+        .into_iter()
+        .filter(|r| dex_name(r.name).eq_ignore_ascii_case(name))
+        .filter_map(|r| effective_price(&r).map(|p| (r, p)))
+        .max_by(|(_, a), (_, b)| match direction {
+            SwapDirection::Sell => a.total_cmp(b),
+            SwapDirection::Buy => b.total_cmp(a),
+        })
+        .map(|(r, _)| r)
+        .or_else(|| get_router_by_name(name))
+}
+
+// ============== FEE TIERS ==============
+
+/// Uniswap V3 fee tiers (hundredths of a bp) checked when a pair has no
+/// `[[fee_tiers]]` entry in the config file
+pub const DEFAULT_FEE_TIERS: &[u32] = &[100, 500, 3000, 10000];
+
+/// Fee tiers to look for on V3-style DEXes for one pair (config file `[[fee_tiers]]`)
+#[derive(Debug, Clone)]
+pub struct PairFeeTiers {
+    pub pair: TokenPair,
+    pub tiers: Vec<u32>,
+}
+
+/// Extra-tier pools found at startup (see `pools::v3_pool::discover_fee_tiers`)
+static FEE_TIER_VENUES: OnceLock<Vec<(PoolConfig, RouterConfig)>> = OnceLock::new();
+
+pub fn get_fee_tiers(pair: &TokenPair) -> Vec<u32> {
+    APP_CONFIG
+        .get()
+        .and_then(|cfg| cfg.fee_tiers.iter().find(|t| t.pair == *pair))
+        .map(|t| t.tiers.clone())
+        .unwrap_or_else(|| DEFAULT_FEE_TIERS.to_vec())
+}
+
+/// LFJ bin steps (bps) checked when an LFJ pool has no `bin_steps` in the config file
+pub const DEFAULT_BIN_STEPS: &[u16] = &[1, 5, 10, 15, 20, 25, 50, 100];
+
+/// Bin steps to look for alongside LFJ pool `pool_name` (config file `[[pools]] bin_steps`)
+pub fn get_bin_steps(pool_name: &str) -> Vec<u16> {
+    APP_CONFIG
+        .get()
+        .and_then(|cfg| cfg.bin_steps.get(pool_name))
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_BIN_STEPS.to_vec())
+}
+
+/// Pool/router name for a DEX's pool on another fee tier, e.g. "Uniswap@500"
+pub fn tier_name(dex: &str, fee: u32) -> String {
+    format!("{}@{}", dex, fee)
+}
+
+/// DEX a pool or router name belongs to ("Uniswap@500" -> "Uniswap")
+pub fn dex_name(name: &str) -> &str {
+    name.split_once('@').map_or(name, |(dex, _)| dex)
+}
+
+/// Per-route spread filter overrides from the config file
+pub fn get_route_filters() -> Vec<RouteFilterOverride> {
+    APP_CONFIG.get().map(|cfg| cfg.route_filters.clone()).unwrap_or_default()
+}
+
+pub fn get_route_decay() -> Vec<RouteDecay> {
+    APP_CONFIG.get().map(|cfg| cfg.route_decay.clone()).unwrap_or_default()
+}
+
+pub fn get_competitive_routes() -> Vec<CompetitiveRoute> {
+    APP_CONFIG.get().map(|cfg| cfg.competitive_routes.clone()).unwrap_or_default()
+}
+
+pub fn get_fee_tier_venues() -> Vec<(PoolConfig, RouterConfig)> {
+    FEE_TIER_VENUES.get().cloned().unwrap_or_default()
+}
+
+/// Register discovered fee-tier pools so the getters above include them
+pub fn set_fee_tier_venues(venues: Vec<(PoolConfig, RouterConfig)>) -> Result<()> {
+    FEE_TIER_VENUES.set(venues).map_err(|_| eyre!("Fee tiers already initialized"))
+}
+
+/// V3 pool fees read from the pool contracts at startup (1e-6 units)
+static VALIDATED_FEES: OnceLock<HashMap<Address, u32>> = OnceLock::new();
+
+/// On-chain fee for `pool`, if startup validation read one
+pub fn validated_fee(pool: Address) -> Option<u32> {
+    VALIDATED_FEES.get()?.get(&pool).copied()
+}
+
+/// Cache the on-chain V3 fees; the pool and router getters use them over configured values
+pub fn set_validated_fees(fees: HashMap<Address, u32>) -> Result<()> {
+    VALIDATED_FEES.set(fees).map_err(|_| eyre!("Pool fees already validated"))
+}
+
+// ============== CONFIG FILE ==============
+
+/// Runtime configuration: compiled defaults overlaid with a --config file
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub tokens: Vec<TokenConfig>,
+    pub pools: Vec<PoolConfig>,
+    pub routers: Vec<RouterConfig>,
+    pub fee_tiers: Vec<PairFeeTiers>,
+    pub route_filters: Vec<RouteFilterOverride>,
+    pub route_decay: Vec<RouteDecay>,
+    pub competitive_routes: Vec<CompetitiveRoute>,
+    /// LFJ bin steps to discover, by pool name
+    pub bin_steps: HashMap<String, Vec<u16>>,
+}
+
+static APP_CONFIG: OnceLock<AppConfig> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+struct FileConfig {
+    #[serde(default)]
+    tokens: Vec<FileToken>,
+    #[serde(default)]
+    pools: Vec<FilePool>,
+    #[serde(default)]
+    routers: Vec<FileRouter>,
+    #[serde(default)]
+    fee_tiers: Vec<FileFeeTiers>,
+    #[serde(default)]
+    route_filters: Vec<RouteFilterOverride>,
+    #[serde(default)]
+    route_decay: Vec<RouteDecay>,
+    #[serde(default)]
+    competitive_routes: Vec<CompetitiveRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileToken {
+    symbol: String,
+    address: Address,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct FilePool {
+    name: String,
+    address: Address,
+    #[serde(rename = "type")]
+    pool_type: String,
+    fee_bps: u32,
+    /// LFJ only: other bin steps of the same pair to track with --fee-tiers
+    #[serde(default)]
+    bin_steps: Vec<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileFeeTiers {
+    pair: String,
+    tiers: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileRouter {
+    name: String,
+    address: Address,
+    #[serde(rename = "type")]
+    router_type: String,
+    pool_address: Address,
+    pool_fee: u32,
+    #[serde(default)]
+    quoter: Option<Address>,
+    #[serde(default)]
+    gas_mode: Option<String>,
+    // Uniswap V4 PoolKey extras (tick_spacing defaults from pool_fee)
+    #[serde(default)]
+    tick_spacing: Option<i32>,
+    #[serde(default)]
+    hooks: Option<Address>,
+    #[serde(default)]
+    native: bool,
+    #[serde(default)]
+    usdc_buffer_bps: Option<u32>,
+    #[serde(default)]
+    supports_native: Option<bool>,
+}
+
+/// Normalize a type string: "Uniswap_V3", "uniswap-v3" and "uniswapv3" all match
+fn normalize_type(s: &str) -> String {
+    s.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn parse_pool_type(s: &str) -> Option<PoolType> {
+    match normalize_type(s).as_str() {
+        "uniswapv3" => Some(PoolType::UniswapV3),
+        "pancakev3" => Some(PoolType::PancakeV3),
+        "lfj" | "liquiditybook" => Some(PoolType::LiquidityBook),
+        "mondaytrade" => Some(PoolType::MondayTrade),
+        _ => None,
+    }
+}
+
+fn parse_router_type(s: &str) -> Option<RouterType> {
+    match normalize_type(s).as_str() {
+        "uniswapv3" => Some(RouterType::UniswapV3),
+        "pancakev3" => Some(RouterType::PancakeV3),
+        "lfj" | "lfjlb" => Some(RouterType::LfjLB),
+        "mondaytrade" => Some(RouterType::MondayTrade),
+        "uniswapv4" => Some(RouterType::UniswapV4),
+        _ => None,
+    }
+}
+
+fn parse_gas_mode(s: &str) -> Option<GasMode> {
+    match normalize_type(s).as_str() {
+        "eip1559" | "1559" => Some(GasMode::Eip1559),
+        "legacy" => Some(GasMode::Legacy),
+        _ => None,
+    }
+}
+
+// Config is loaded once at startup, so leaking names to satisfy &'static str is fine
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+/// Replace entries with a matching name (case insensitive), append the rest
+fn overlay<T>(defaults: &mut Vec<T>, entries: Vec<T>, name: fn(&T) -> &str) {
+    for entry in entries {
+        match defaults.iter_mut().find(|d| name(d).eq_ignore_ascii_case(name(&entry))) {
+            Some(existing) => *existing = entry,
+            None => defaults.push(entry),
+        }
+    }
+}
+
+/// Load a monad-arb.toml and overlay it on the compiled defaults.
+///
+/// ```toml
+/// [[tokens]]
+/// symbol = "WMON"
+/// address = "0x3bd359C1119dA7Da1D913D1C4D2B7c461115433A"
+/// decimals = 18
+///
+/// [[pools]]
+/// name = "Uniswap"
+/// address = "0x659bd0bc4167ba25c62e05656f78043e7ed4a9da"
+/// type = "uniswap_v3"      # uniswap_v3 | pancake_v3 | lfj | monday_trade
+/// fee_bps = 30
+///
+/// [[pools]]
+/// name = "LFJ"
+/// address = "0x5e60bc3f7a7303bc4dfe4dc2220bdc90bc04fe22"
+/// type = "lfj"
+/// fee_bps = 10
+/// bin_steps = [10, 20, 25]  # optional, other bin-step pools --fee-tiers tracks
+///
+/// [[routers]]
+/// name = "Uniswap"
+/// address = "0xfE31F71C1b106EAc32F1A19239c9a9A72ddfb900"
+/// type = "uniswap_v3"      # uniswap_v3 | pancake_v3 | lfj | monday_trade | uniswap_v4
+/// pool_address = "0x659bd0bc4167ba25c62e05656f78043e7ed4a9da"
+/// pool_fee = 3000
+/// quoter = "0x..."         # optional QuoterV2, enables simulate-arb
+/// gas_mode = "legacy"      # optional: eip1559 (default) | legacy
+/// usdc_buffer_bps = 25     # optional: swap 1 USDC held back from swap 2 (default 10)
+/// supports_native = false  # optional: native MON swaps (default true for uniswap_v3, pancake_v3, lfj)
+///
+/// # Uniswap V4 has no compiled-in default; register it here. `address` is
+/// # the Universal Router, `pool_address` the PoolManager, and the PoolKey is
+/// # (WMON or native MON, USDC, pool_fee, tick_spacing, hooks).
+/// [[routers]]
+/// name = "UniswapV4"
+/// address = "0x..."        # Universal Router
+/// type = "uniswap_v4"
+/// pool_address = "0x..."   # PoolManager
+/// pool_fee = 3000
+/// tick_spacing = 60        # optional, standard spacing for pool_fee
+/// hooks = "0x..."          # optional, default none
+/// native = true            # optional, pool uses address(0) for MON
+///
+/// # Fee tiers probed by --fee-tiers on Uniswap/Pancake V3 (default 100, 500, 3000, 10000)
+/// [[fee_tiers]]
+/// pair = "wmon-usdc"
+/// tiers = [100, 500, 2500, 3000, 10000]
+///
+/// # AutoArb --track-velocity overrides for one route (unset fields stay global)
+/// [[route_filters]]
+/// buy_pool = "PancakeSwap1"
+/// sell_pool = "LFJ"
+/// min_velocity = 25.0
+/// max_baseline = 1
+/// max_acceleration = 40.0
+///
+/// # AutoArb expected Proposed→Finalized decay for one route (AnalyzeLogs --group-by decay)
+/// [[route_decay]]
+/// buy_pool = "LFJ"
+/// sell_pool = "Uniswap"
+/// decay_bps = 4.5
+///
+/// # AutoArb bids --competitive-priority-multiplier on this route's priority fee
+/// [[competitive_routes]]
+/// buy_pool = "LFJ"
+/// sell_pool = "Uniswap"
+/// ```
+pub fn load_from_file(path: &Path) -> Result<AppConfig> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read config file {}", path.display()))?;
+    parse_config(&contents).wrap_err_with(|| format!("Invalid config file {}", path.display()))
+}
+
+fn parse_config(contents: &str) -> Result<AppConfig> {
+    let file: FileConfig = toml::from_str(contents)?;
+
+    let mut tokens = default_tokens();
+    let file_tokens = file
+        .tokens
+        .into_iter()
+        .map(|t| TokenConfig { symbol: leak(t.symbol), address: t.address, decimals: t.decimals })
+        .collect();
+    overlay(&mut tokens, file_tokens, |t| t.symbol);
+
+    let mut pools = default_pools();
+    let mut file_pools = Vec::with_capacity(file.pools.len());
+    let mut bin_steps = HashMap::new();
+    for p in file.pools {
+        let pool_type = parse_pool_type(&p.pool_type).ok_or_else(|| {
+            eyre!(
+                "pool '{}': unknown type '{}' (expected uniswap_v3, pancake_v3, lfj, monday_trade)",
+                p.name, p.pool_type
+            )
+        })?;
+        if !p.bin_steps.is_empty() {
+            if pool_type != PoolType::LiquidityBook {
+                return Err(eyre!("pool '{}': bin_steps only applies to lfj pools", p.name));
+            }
+            bin_steps.insert(p.name.clone(), p.bin_steps);
+        }
+        file_pools.push(PoolConfig { name: leak(p.name), address: p.address, pool_type, fee_bps: p.fee_bps });
+    }
+    overlay(&mut pools, file_pools, |p| p.name);
+
+    let mut routers = default_routers();
+    let mut file_routers = Vec::with_capacity(file.routers.len());
+    for r in file.routers {
+        let router_type = parse_router_type(&r.router_type).ok_or_else(|| {
+            eyre!(
+                "router '{}': unknown type '{}' (expected uniswap_v3, pancake_v3, lfj, monday_trade, uniswap_v4)",
+                r.name, r.router_type
+            )
+        })?;
+        let gas_mode = match r.gas_mode.as_deref() {
+            Some(m) => parse_gas_mode(m).ok_or_else(|| {
+                eyre!("router '{}': unknown gas_mode '{}' (expected eip1559, legacy)", r.name, m)
+            })?,
+            None => GasMode::default(),
+        };
+        let v4_pool = (router_type == RouterType::UniswapV4).then(|| {
+            let standard = V4PoolParams::standard(r.pool_fee);
+            V4PoolParams {
+                tick_spacing: r.tick_spacing.unwrap_or(standard.tick_spacing),
+                hooks: r.hooks.unwrap_or(Address::ZERO),
+                native: r.native,
+            }
+        });
+        let usdc_buffer_bps = r.usdc_buffer_bps.unwrap_or(DEFAULT_USDC_BUFFER_BPS);
+        if usdc_buffer_bps >= 10_000 {
+            return Err(eyre!("router '{}': usdc_buffer_bps must be below 10000, got {}", r.name, usdc_buffer_bps));
+        }
+        file_routers.push(RouterConfig {
+            name: leak(r.name),
+            address: r.address,
+            router_type,
+            pool_address: r.pool_address,
+            pool_fee: r.pool_fee,
+            quoter: r.quoter,
+            gas_mode,
+            v4_pool,
+            usdc_buffer_bps,
+            supports_native: r.supports_native.unwrap_or_else(|| default_supports_native(router_type)),
+        });
+    }
+    overlay(&mut routers, file_routers, |r| r.name);
+
+    // Resolve pair names against the merged token list (not via get_token_pair,
+    // the config isn't installed yet)
+    let find_token = |symbol: &str| tokens.iter().find(|t| t.symbol.eq_ignore_ascii_case(symbol));
+    let mut fee_tiers = Vec::with_capacity(file.fee_tiers.len());
+    for t in file.fee_tiers {
+        let (base, quote) = t
+            .pair
+            .split_once(['-', '/'])
+            .and_then(|(b, q)| Some((find_token(b)?, find_token(q)?)))
+            .ok_or_else(|| eyre!("fee_tiers: unknown pair '{}'", t.pair))?;
+        let pair = TokenPair {
+            base: base.address,
+            quote: quote.address,
+            base_decimals: base.decimals,
+            quote_decimals: quote.decimals,
+        };
+        fee_tiers.push(PairFeeTiers { pair, tiers: t.tiers });
+    }
+
+    Ok(AppConfig {
+        tokens,
+        pools,
+        routers,
+        fee_tiers,
+        route_filters: file.route_filters,
+        route_decay: file.route_decay,
+        competitive_routes: file.competitive_routes,
+        bin_steps,
+    })
+}
+
+/// Install a loaded config. Must be called before any getter is used.
+pub fn set_app_config(cfg: AppConfig) -> Result<()> {
+    APP_CONFIG.set(cfg).map_err(|_| eyre!("Config already initialized"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    #[test]
+    fn test_decode_token_metadata_string_and_bytes32_symbol() {
+        let decimals = Bytes::from(alloy::primitives::U256::from(6).abi_encode());
+        let symbol = Bytes::from("USDC".to_string().abi_encode());
+        assert_eq!(decode_token_metadata(Some(&symbol), Some(&decimals)), Some(("USDC".to_string(), 6)));
+
+        let mut raw = [0u8; 32];
+        raw[..3].copy_from_slice(b"MKR");
+        let symbol = Bytes::from(raw.to_vec());
+        assert_eq!(decode_token_metadata(Some(&symbol), Some(&decimals)), Some(("MKR".to_string(), 6)));
+
+        assert_eq!(decode_token_metadata(Some(&symbol), None), None);
+    }
+
+    #[test]
+    fn test_overlay_replaces_and_appends() {
+        let cfg = parse_config(
+            r#"
+            [[routers]]
+            name = "uniswap"
+            address = "0x0000000000000000000000000000000000000001"
+            type = "uniswap_v3"
+            pool_address = "0x0000000000000000000000000000000000000002"
+            pool_fee = 500
+
+            [[pools]]
+            name = "NewPool"
+            address = "0x0000000000000000000000000000000000000003"
+            type = "pancake-v3"
+            fee_bps = 1
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.routers.len(), default_routers().len());
+        let uni = cfg.routers.iter().find(|r| r.name == "uniswap").unwrap();
+        assert_eq!(uni.pool_fee, 500);
+        assert_eq!(cfg.pools.len(), default_pools().len() + 1);
+        assert_eq!(cfg.pools.last().unwrap().pool_type, PoolType::PancakeV3);
+    }
+
+    #[test]
+    fn test_v4_router_pool_key_defaults() {
+        let cfg = parse_config(
+            r#"
+            [[routers]]
+            name = "UniswapV4"
+            address = "0x0000000000000000000000000000000000000001"
+            type = "uniswap_v4"
+            pool_address = "0x0000000000000000000000000000000000000002"
+            pool_fee = 500
+            native = true
+            usdc_buffer_bps = 25
+            "#,
+        )
+        .unwrap();
+
+        let v4 = cfg.routers.iter().find(|r| r.name.eq_ignore_ascii_case("uniswapv4")).unwrap();
+        assert_eq!(v4.router_type, RouterType::UniswapV4);
+        assert_eq!(v4.v4_pool, Some(V4PoolParams { tick_spacing: 10, hooks: Address::ZERO, native: true }));
+        assert!(cfg.routers.iter().filter(|r| r.router_type != RouterType::UniswapV4).all(|r| r.v4_pool.is_none()));
+
+        // Per-router swap 2 buffer; compiled-in routers keep the default
+        assert!((v4.usdc_spend_fraction() - 0.9975).abs() < 1e-12);
+        assert!(cfg.routers.iter().filter(|r| r.router_type != RouterType::UniswapV4)
+            .all(|r| r.usdc_buffer_bps == DEFAULT_USDC_BUFFER_BPS));
+    }
+
+    #[test]
+    fn test_lfj_bin_steps_only_on_lfj_pools() {
+        let cfg = parse_config(
+            r#"
+            [[pools]]
+            name = "LFJ"
+            address = "0x5e60bc3f7a7303bc4dfe4dc2220bdc90bc04fe22"
+            type = "lfj"
+            fee_bps = 10
+            bin_steps = [10, 25]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.bin_steps["LFJ"], vec![10, 25]);
+
+        let err = parse_config(
+            r#"
+            [[pools]]
+            name = "Uniswap"
+            address = "0x659bd0bc4167ba25c62e05656f78043e7ed4a9da"
+            type = "uniswap_v3"
+            fee_bps = 30
+            bin_steps = [10]
+            "#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("bin_steps"));
+    }
+
+    #[test]
+    fn test_fee_tiers_per_pair_and_dex_name() {
+        let cfg = parse_config(
+            r#"
+            [[fee_tiers]]
+            pair = "WMON/USDC"
+            tiers = [500, 2500]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.fee_tiers.len(), 1);
+        assert_eq!(cfg.fee_tiers[0].pair, TokenPair::wmon_usdc());
+        assert_eq!(cfg.fee_tiers[0].tiers, vec![500, 2500]);
+        assert!(parse_config("[[fee_tiers]]\npair = \"wmon-xyz\"\ntiers = [500]").is_err());
+
+        assert_eq!(dex_name(&tier_name("Uniswap", 500)), "Uniswap");
+        assert_eq!(dex_name("LFJ"), "LFJ");
+    }
+
+    #[test]
+    fn test_unknown_router_type_names_entry() {
+        let err = parse_config(
+            r#"
+            [[routers]]
+            name = "Kuru"
+            address = "0x0000000000000000000000000000000000000001"
+            type = "orderbook"
+            pool_address = "0x0000000000000000000000000000000000000002"
+            pool_fee = 0
+            "#,
+        )
+        .unwrap_err();
+
+        let msg = err.to_string();
+        assert!(msg.contains("Kuru") && msg.contains("orderbook"), "{}", msg);
+    }
+}
@@ -1,10 +1,11 @@
 use chrono::Local;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
+use crate::config::dex_name;
 use crate::pools::PoolPrice;
 
 /// Default log file name for ARB opportunities
@@ -67,17 +68,123 @@ pub struct SpreadOpportunity {
     pub sell_fee_bps: u32,
     pub gross_spread_pct: f64,
     pub net_spread_pct: f64,
+    /// Block both legs were read at
+    pub block_number: Option<u64>,
 }
 
-/// Calculate all spread opportunities between pools
-pub fn calculate_spreads(prices: &[PoolPrice]) -> Vec<SpreadOpportunity> {
+/// Readings per pool that the sanity median is taken over
+const PRICE_SANITY_WINDOW: usize = 51;
+
+/// Readings a pool needs before its band is enforced
+const PRICE_SANITY_MIN_SAMPLES: usize = 5;
+
+/// Circuit breaker for `--price-sanity-pct`: a reading further than `band_pct`
+/// from its pool's rolling median is rejected. Every valid reading enters the
+/// window, so a sustained move is accepted once it dominates the median while
+/// a single manipulated read never is.
+#[derive(Debug)]
+pub struct PriceSanity {
+    band_pct: f64,
+    /// Pool -> (block of the last recorded reading, recent readings)
+    history: HashMap<String, (Option<u64>, VecDeque<f64>)>,
+}
+
+impl PriceSanity {
+    pub fn new(band_pct: f64) -> Self {
+        Self { band_pct, history: HashMap::new() }
+    }
+
+    /// Why `price` should be dropped, if it should; records it for the median
+    pub fn check(&mut self, price: &PoolPrice) -> Option<String> {
+        if !price.is_price_valid() {
+            return Some(format!("invalid price {}", price.price));
+        }
+        let (last_block, window) = self.history.entry(price.pool_name.clone()).or_default();
+
+        let mut rejection = None;
+        if window.len() >= PRICE_SANITY_MIN_SAMPLES {
+            let mut sorted: Vec<f64> = window.iter().copied().collect();
+            sorted.sort_by(f64::total_cmp);
+            let median = sorted[sorted.len() / 2];
+            let deviation_pct = (price.price / median - 1.0).abs() * 100.0;
+            if deviation_pct > self.band_pct {
+                rejection = Some(format!("{:.6} is {:.1}% from session median {:.6} (band {}%)",
+                    price.price, deviation_pct, median, self.band_pct));
+            }
+        }
+
+        // One sample per pool per block: several spread passes may see the same read
+        if price.block_number.is_none() || *last_block != price.block_number {
+            *last_block = price.block_number;
+            window.push_back(price.price);
+            if window.len() > PRICE_SANITY_WINDOW {
+                window.pop_front();
+            }
+        }
+        rejection
+    }
+}
+
+static PRICE_SANITY: Mutex<Option<PriceSanity>> = Mutex::new(None);
+
+/// Enable the `--price-sanity-pct` band for every `calculate_spreads` call
+pub fn set_price_sanity_band(band_pct: Option<f64>) {
+    *PRICE_SANITY.lock().unwrap_or_else(|e| e.into_inner()) = band_pct.map(PriceSanity::new);
+}
+
+/// Prices fit to trade on: always valid (finite, positive), and within the
+/// sanity band when one is set. Rejections are logged.
+fn sane_prices(prices: &[PoolPrice]) -> Vec<&PoolPrice> {
+    let mut sanity = PRICE_SANITY.lock().unwrap_or_else(|e| e.into_inner());
+    prices
+        .iter()
+        .filter(|p| {
+            let rejection = match sanity.as_mut() {
+                Some(sanity) => sanity.check(p),
+                None => (!p.is_price_valid()).then(|| format!("invalid price {}", p.price)),
+            };
+            if let Some(reason) = &rejection {
+                tracing::warn!(pool = %p.pool_name, block = ?p.block_number, "Dropping {} price: {}", p.pool_name, reason);
+            }
+            rejection.is_none()
+        })
+        .collect()
+}
+
+/// Calculate all spread opportunities between pools.
+///
+/// Pools with no in-range liquidity are skipped: their slot0 price can't be
+/// traded against. With `min_liquidity` set, pairs where either pool's
+/// `liquidity` is below it (or unknown) are dropped too, so thin pools don't
+/// surface phantom spreads.
+///
+/// Pairs whose legs were read at different blocks are dropped: the spread
+/// never existed at any one block.
+///
+/// A DEX with several fee-tier pools (`--fee-tiers`) contributes only its
+/// best tier to each DEX pair.
+///
+/// Invalid prices, and prices outside the `--price-sanity-pct` band, are
+/// dropped first (see `PriceSanity`).
+pub fn calculate_spreads(prices: &[PoolPrice], min_liquidity: Option<u128>) -> Vec<SpreadOpportunity> {
     let mut spreads = Vec::new();
+    let prices = sane_prices(prices);
+
+    let deep_enough = |p: &PoolPrice| match min_liquidity {
+        Some(min) => p.liquidity.is_some_and(|l| l >= min),
+        None => p.has_sufficient_liquidity(),
+    };
 
-    for buy in prices.iter() {
-        for sell in prices.iter() {
+    for buy in prices.iter().copied().filter(|p| deep_enough(p)) {
+        for sell in prices.iter().copied().filter(|p| deep_enough(p)) {
             if buy.pool_name == sell.pool_name {
                 continue;
             }
+            if let (Some(b), Some(s)) = (buy.block_number, sell.block_number) {
+                if b != s {
+                    continue;
+                }
+            }
 
             // Show all pairs (positive and negative spreads)
             let gross_spread_pct = ((sell.price - buy.price) / buy.price) * 100.0;
@@ -96,6 +203,7 @@ pub fn calculate_spreads(prices: &[PoolPrice]) -> Vec<SpreadOpportunity> {
                 sell_fee_bps: sell.fee_bps,
                 gross_spread_pct,
                 net_spread_pct,
+                block_number: buy.block_number.or(sell.block_number),
             });
         }
     }
@@ -103,6 +211,10 @@ pub fn calculate_spreads(prices: &[PoolPrice]) -> Vec<SpreadOpportunity> {
     // Sort by net spread descending (best opportunities first)
     spreads.sort_by(|a, b| b.net_spread_pct.partial_cmp(&a.net_spread_pct).unwrap());
 
+    // Sorted, so the first spread seen for a DEX pair uses its best tiers
+    let mut seen = HashSet::new();
+    spreads.retain(|s| seen.insert((dex_name(&s.buy_pool).to_string(), dex_name(&s.sell_pool).to_string())));
+
     spreads
 }
 
@@ -205,7 +317,7 @@ pub fn display_prices(prices: &[PoolPrice], elapsed_ms: u128) {
     println!();
 
     // Spread opportunities
-    let spreads = calculate_spreads(&sorted_prices);
+    let spreads = calculate_spreads(&sorted_prices, None);
 
     // Log profitable arb opportunities (net > 0.1%) to stderr so they persist
     log_arb_opportunities(&spreads, &timestamp.to_string());
@@ -257,3 +369,55 @@ pub fn display_prices(prices: &[PoolPrice], elapsed_ms: u128) {
         "═".repeat(67)
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_spreads_reject_mismatched_blocks() {
+        let price = |name: &str, price: f64, block: u64| PoolPrice {
+            pool_name: name.to_string(),
+            price,
+            fee_bps: 5,
+            liquidity: None,
+            tick: None,
+            fetched_at: Instant::now(),
+            block_number: Some(block),
+        };
+
+        let same = calculate_spreads(&[price("Uniswap", 0.030, 7), price("LFJ", 0.031, 7)], None);
+        assert_eq!(same.len(), 2);
+        assert!(same.iter().all(|s| s.block_number == Some(7)));
+
+        let mixed = calculate_spreads(&[price("Uniswap", 0.030, 7), price("LFJ", 0.031, 8)], None);
+        assert!(mixed.is_empty());
+    }
+
+    #[test]
+    fn test_price_sanity_rejects_outlier_until_sustained() {
+        let price = |price: f64, block: u64| PoolPrice {
+            pool_name: "Uniswap".to_string(),
+            price,
+            fee_bps: 5,
+            liquidity: None,
+            tick: None,
+            fetched_at: Instant::now(),
+            block_number: Some(block),
+        };
+        let mut sanity = PriceSanity::new(10.0);
+        assert!(sanity.check(&price(0.0, 1)).is_some());
+        for block in 1..=5 {
+            assert!(sanity.check(&price(0.030, block)).is_none());
+        }
+
+        // A single manipulated read is dropped, normal reads still pass
+        assert!(sanity.check(&price(3.0, 6)).is_some());
+        assert!(sanity.check(&price(0.031, 7)).is_none());
+
+        // A real move is accepted once it holds the median
+        let accepted_at = (8..40).find(|&block| sanity.check(&price(0.040, block)).is_none()).unwrap();
+        assert!(accepted_at > 8);
+    }
+}
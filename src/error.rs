@@ -0,0 +1,144 @@
+//! Typed errors for the execution layer
+//!
+//! The execution entry points (`execute_fast_arb`, `execute_atomic_arb`,
+//! `execute_swap`, ...) return `ArbResult`, and failed arbs carry an
+//! `ArbError` in their result, so callers can tell an RPC timeout from a
+//! revert or an empty wallet without matching on message text. `eyre` stays
+//! at the CLI boundary: `ArbError` is a std error, so `?` in a command
+//! handler converts it back.
+//!
+//! Helpers that still return `eyre` (multicall, calldata builders, nonce
+//! sync) are classified by message when `?` brings them into an `ArbResult`.
+
+use alloy::transports::{RpcError, TransportErrorKind};
+use std::fmt;
+
+use crate::execution::revert::revert_reason_from_rpc_error;
+
+pub type ArbResult<T> = std::result::Result<T, ArbError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArbError {
+    /// A send, receipt wait or RPC call ran out of time
+    RpcTimeout(String),
+    /// `op` (a swap, its simulation, the arb transaction) reverted
+    Revert { op: String, reason: String },
+    /// Wallet or contract can't cover the trade or its gas
+    InsufficientBalance(String),
+    /// The node rejected the nonce and resyncing didn't recover
+    NonceDesync(String),
+    /// Prices outlived their max age or the latency budget
+    PriceStale(String),
+    /// Any other RPC or transport failure
+    Rpc(String),
+    /// Config, calldata and decoding errors
+    Other(String),
+}
+
+impl ArbError {
+    pub fn revert(op: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::Revert { op: op.into(), reason: reason.into() }
+    }
+
+    /// Classify `err` as a failure of `context` ("Swap 1 send", "Gas estimation")
+    pub fn with_context(err: impl Into<ArbError>, context: &str) -> Self {
+        match err.into() {
+            Self::Revert { reason, .. } => Self::revert(context, reason),
+            Self::RpcTimeout(m) => Self::RpcTimeout(format!("{} failed: {}", context, m)),
+            Self::InsufficientBalance(m) => Self::InsufficientBalance(format!("{} failed: {}", context, m)),
+            Self::NonceDesync(m) => Self::NonceDesync(format!("{} failed: {}", context, m)),
+            Self::PriceStale(m) => Self::PriceStale(format!("{} failed: {}", context, m)),
+            Self::Rpc(m) => Self::Rpc(format!("{} failed: {}", context, m)),
+            Self::Other(m) => Self::Other(format!("{} failed: {}", context, m)),
+        }
+    }
+
+    /// Short variant name for logs and CSV
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RpcTimeout(_) => "rpc_timeout",
+            Self::Revert { .. } => "revert",
+            Self::InsufficientBalance(_) => "insufficient_balance",
+            Self::NonceDesync(_) => "nonce_desync",
+            Self::PriceStale(_) => "price_stale",
+            Self::Rpc(_) => "rpc",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Classify an error message from a node or an `eyre` helper
+    pub fn from_message(message: String) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("nonce too low") || lower.contains("nonce too high") || lower.contains("invalid nonce") {
+            Self::NonceDesync(message)
+        } else if lower.contains("insufficient funds") || (lower.contains("insufficient") && lower.contains("balance")) {
+            Self::InsufficientBalance(message)
+        } else if lower.contains("timeout") || lower.contains("timed out") {
+            Self::RpcTimeout(message)
+        } else if let Some(reason) = message.strip_prefix("execution reverted") {
+            Self::revert("Call", reason.trim_start_matches(':').trim())
+        } else {
+            Self::Other(message)
+        }
+    }
+}
+
+impl fmt::Display for ArbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Revert { op, reason } => write!(f, "{} reverted: {}", op, reason),
+            Self::RpcTimeout(message)
+            | Self::InsufficientBalance(message)
+            | Self::NonceDesync(message)
+            | Self::PriceStale(message)
+            | Self::Rpc(message)
+            | Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ArbError {}
+
+impl From<RpcError<TransportErrorKind>> for ArbError {
+    fn from(err: RpcError<TransportErrorKind>) -> Self {
+        if err.as_error_resp().is_some_and(|payload| payload.as_revert_data().is_some()) {
+            return Self::revert("Call", revert_reason_from_rpc_error(&err));
+        }
+        match Self::from_message(revert_reason_from_rpc_error(&err)) {
+            Self::Other(message) => Self::Rpc(message),
+            classified => classified,
+        }
+    }
+}
+
+impl From<eyre::Report> for ArbError {
+    fn from(report: eyre::Report) -> Self {
+        if let Some(err) = report.downcast_ref::<ArbError>() {
+            return err.clone();
+        }
+        let message = format!("{:#}", report);
+        match report.downcast::<RpcError<TransportErrorKind>>() {
+            Ok(err) => err.into(),
+            Err(_) => Self::from_message(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_node_messages_and_round_trips_eyre() {
+        assert_eq!(ArbError::from_message("nonce too low: next nonce 5".into()).kind(), "nonce_desync");
+        assert_eq!(ArbError::from_message("insufficient funds for gas * price + value".into()).kind(), "insufficient_balance");
+        assert_eq!(ArbError::from_message("Swap 1 send timeout".into()).kind(), "rpc_timeout");
+        assert_eq!(ArbError::from_message("execution reverted: STF".into()), ArbError::revert("Call", "STF"));
+        assert_eq!(ArbError::from_message("Unknown router".into()).kind(), "other");
+
+        let stale = ArbError::PriceStale("Prices are 900ms old".into());
+        let report: eyre::Report = stale.clone().into();
+        assert_eq!(ArbError::from(report), stale);
+        assert_eq!(ArbError::revert("Swap 2", "Too little received").to_string(), "Swap 2 reverted: Too little received");
+    }
+}
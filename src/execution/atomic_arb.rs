@@ -13,6 +13,10 @@
 //! - Deferred post-balance queries (async logging)
 //! - Pre-built calldata templates
 //! - Spread-aware gas price bidding
+//!
+//! Outside turbo, both legs are re-quoted (`QuoteFetcher::AtomicQuote`) just
+//! before building the calldata, and the min-outs come from the quoted fills
+//! rather than spot prices, which overstate them by the price impact.
 
 use alloy::network::TransactionBuilder;
 use alloy::primitives::{Address, Bytes, U256, Uint};
@@ -22,19 +26,26 @@ use alloy::sol_types::SolCall;
 use chrono::Local;
 use eyre::{eyre, Result};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::timeout;
 
 use crate::config::{
-    RouterConfig, RouterType, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS,
-    ATOMIC_ARB_CONTRACT,
+    RouterConfig, RouterType, TokenPair, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS,
+    atomic_arb_contract,
 };
 use crate::gas_cache::{
     GasDecision, RouteKey, cache_gas_estimate, gas_strategy, calculate_gas_price,
 };
-use crate::nonce::next_nonce;
+use crate::error::{ArbError, ArbResult};
+use crate::gas::GasFees;
+use crate::logging::{self, EVENT_TARGET};
+use crate::nonce::next_nonce_for;
+use crate::simulation::quote_fetcher::ArbQuote;
+use crate::simulation::QuoteFetcher;
+use crate::stats::{record_receipt_outcome, ReceiptOutcome};
 use super::routers::build_swap_calldata;
+use super::submit;
 use super::SwapDirection;
 
 // Monad mainnet chain ID
@@ -44,6 +55,9 @@ const MONAD_CHAIN_ID: u64 = 143;
 const RECEIPT_POLL_MS: u64 = 5; // Was 20ms - saves 50-100ms average
 const RECEIPT_TIMEOUT_MS: u64 = 10_000; // 10 seconds max
 
+// Background receipt watch: give up after this (turbo --await-receipt)
+const BACKGROUND_RECEIPT_TIMEOUT: Duration = Duration::from_secs(5);
+
 // Default gas buffer when no cache available
 const DEFAULT_GAS_BUFFER_PERCENT: u64 = 12;
 
@@ -57,13 +71,16 @@ pub enum ContractRouter {
     LFJ = 3,
 }
 
-impl From<RouterType> for ContractRouter {
-    fn from(rt: RouterType) -> Self {
+impl TryFrom<RouterType> for ContractRouter {
+    type Error = eyre::Report;
+
+    fn try_from(rt: RouterType) -> Result<Self> {
         match rt {
-            RouterType::UniswapV3 => ContractRouter::Uniswap,
-            RouterType::PancakeV3 => ContractRouter::PancakeSwap,
-            RouterType::MondayTrade => ContractRouter::MondayTrade,
-            RouterType::LfjLB => ContractRouter::LFJ,
+            RouterType::UniswapV3 => Ok(ContractRouter::Uniswap),
+            RouterType::PancakeV3 => Ok(ContractRouter::PancakeSwap),
+            RouterType::MondayTrade => Ok(ContractRouter::MondayTrade),
+            RouterType::LfjLB => Ok(ContractRouter::LFJ),
+            RouterType::UniswapV4 => Err(eyre!("Uniswap V4 is not supported by the atomic arb contract")),
         }
     }
 }
@@ -150,14 +167,84 @@ sol! {
         uint256 minWmonOut
     ) external returns (int256 profit);
 
+    #[derive(Debug)]
+    function executeArbFlashloan(
+        address lender,
+        uint256 amount,
+        uint8 sellRouter,
+        bytes calldata sellRouterData,
+        uint8 buyRouter,
+        uint24 buyPoolFee,
+        uint256 minWmonOut,
+        uint256 minProfit
+    ) external returns (int256 profit);
+
     #[derive(Debug)]
     function getBalances() external view returns (uint256 wmon, uint256 usdc);
 
+    #[derive(Debug)]
+    function owner() external view returns (address);
+
+    #[derive(Debug)]
+    function withdrawToken(address token, uint256 amount) external;
+
     // Custom errors for decoding
     error SwapFailed(uint8 swapIndex);
     error Unprofitable(uint256 wmonBefore, uint256 wmonAfter);
 }
 
+/// The two legs, size and limits of one atomic arb
+#[derive(Clone, Copy)]
+pub struct AtomicArbTrade<'a> {
+    /// Router to sell WMON on (higher price)
+    pub sell_router: &'a RouterConfig,
+    /// Router to buy WMON on (lower price)
+    pub buy_router: &'a RouterConfig,
+    /// Amount of WMON to arb
+    pub amount: f64,
+    pub sell_price: f64,
+    pub buy_price: f64,
+    pub slippage_bps: u32,
+    /// Minimum profit required (0 = any profit)
+    pub min_profit_bps: i32,
+    /// Current spread, for the gas strategy
+    pub spread_bps: i32,
+}
+
+/// What `execute_atomic_arb` does once the TX is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptMode {
+    /// Poll for the receipt and report the real outcome
+    Wait,
+    /// Return as soon as the TX is sent; the outcome is never learned
+    FireAndForget,
+    /// Return as soon as the TX is sent; a background task fetches the
+    /// receipt, logs it and records it in the session stats
+    Background,
+}
+
+/// How much `execute_atomic_arb` trades latency for accuracy
+#[derive(Debug, Clone, Copy)]
+pub struct AtomicExecOptions {
+    /// Skip the spread floor and profit checks (for testing)
+    pub force: bool,
+    /// Re-quote both legs for the min-outs instead of trusting the spot prices
+    pub requote: bool,
+    pub receipt_mode: ReceiptMode,
+}
+
+impl AtomicExecOptions {
+    /// Re-quote the legs and wait for the receipt
+    pub fn careful(force: bool) -> Self {
+        Self { force, requote: true, receipt_mode: ReceiptMode::Wait }
+    }
+
+    /// Turbo: trade on the spot prices to save the quote round-trip
+    pub fn turbo(force: bool, receipt_mode: ReceiptMode) -> Self {
+        Self { force, requote: false, receipt_mode }
+    }
+}
+
 /// Result of atomic arbitrage execution (Turbo optimized)
 #[derive(Debug, Clone)]
 pub struct AtomicArbResult {
@@ -180,7 +267,7 @@ pub struct AtomicArbResult {
     pub spread_bps: i32,
     /// Gas source (cached or fresh)
     pub gas_source: String,
-    pub error: Option<String>,
+    pub error: Option<ArbError>,
 }
 
 impl AtomicArbResult {
@@ -190,6 +277,63 @@ impl AtomicArbResult {
     }
 }
 
+/// Minimum WMON the final leg must return, in wei: the slippage floor
+/// raised to `amount * (1 + min_profit_bps / 10000)`, so the contract
+/// reverts atomically unless the arb clears `min_profit_bps` regardless of
+/// what happens between quote and inclusion. `--force` sets the profit
+/// floor to 0, leaving only the slippage floor.
+pub fn final_leg_min_out(amount: f64, slippage_min_out: f64, min_profit_bps: i32, force: bool) -> U256 {
+    let profit_floor = if force {
+        0.0
+    } else {
+        (amount * (1.0 + min_profit_bps as f64 / 10000.0)).max(0.0)
+    };
+    to_wei(slippage_min_out.max(profit_floor), WMON_DECIMALS)
+}
+
+/// Expected (USDC out of leg 1, WMON back from leg 2) for `amount`: the
+/// fresh quote's fills when there is one, else spot prices
+fn expected_leg_outputs(amount: f64, sell_price: f64, buy_price: f64, quote: Option<&ArbQuote>) -> (f64, f64) {
+    match quote {
+        Some(q) => (q.usdc_intermediate, q.wmon_out),
+        None => {
+            let usdc = amount * sell_price;
+            (usdc, usdc / buy_price)
+        }
+    }
+}
+
+/// executeArb calldata, or executeArbUnchecked (no on-chain minProfit) when forced
+fn build_execute_arb_calldata(
+    sell_router: u8,
+    sell_router_data: Bytes,
+    buy_router: u8,
+    buy_pool_fee: u32,
+    min_wmon_out: U256,
+    min_profit: U256,
+    force: bool,
+) -> Bytes {
+    let buy_pool_fee: Uint<24, 1> = Uint::from(buy_pool_fee);
+    if force {
+        Bytes::from(executeArbUncheckedCall {
+            sellRouter: sell_router,
+            sellRouterData: sell_router_data,
+            buyRouter: buy_router,
+            buyPoolFee: buy_pool_fee,
+            minWmonOut: min_wmon_out,
+        }.abi_encode())
+    } else {
+        Bytes::from(executeArbCall {
+            sellRouter: sell_router,
+            sellRouterData: sell_router_data,
+            buyRouter: buy_router,
+            buyPoolFee: buy_pool_fee,
+            minWmonOut: min_wmon_out,
+            minProfit: min_profit,
+        }.abi_encode())
+    }
+}
+
 /// Convert human amount to U256 with proper decimals
 fn to_wei(amount: f64, decimals: u8) -> U256 {
     let multiplier = U256::from(10u64).pow(U256::from(decimals));
@@ -208,7 +352,7 @@ fn from_wei(amount: U256, decimals: u8) -> f64 {
 pub async fn query_contract_balances<P: Provider>(provider: &P) -> Result<(f64, f64)> {
     let call = getBalancesCall {};
     let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(ATOMIC_ARB_CONTRACT)
+        .to(atomic_arb_contract())
         .input(alloy::rpc::types::TransactionInput::new(Bytes::from(call.abi_encode())));
 
     let result = provider.call(tx).await?;
@@ -217,6 +361,70 @@ pub async fn query_contract_balances<P: Provider>(provider: &P) -> Result<(f64,
     Ok((from_wei(decoded.wmon, WMON_DECIMALS), from_wei(decoded.usdc, USDC_DECIMALS)))
 }
 
+/// What the startup check found at the configured arb contract address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractStatus {
+    Ready,
+    NotConfigured,
+    /// Nothing deployed at the address (wrong chain, or not deployed yet)
+    NoCode,
+    /// Deployed, but its dispatcher lacks these functions (not MonadAtomicArb, or an old build)
+    MissingFunctions(Vec<&'static str>),
+}
+
+/// Functions the bot calls on the contract, by selector
+const EXPECTED_FUNCTIONS: [(&str, [u8; 4]); 4] = [
+    ("executeArb", executeArbCall::SELECTOR),
+    ("getBalances", getBalancesCall::SELECTOR),
+    ("owner", ownerCall::SELECTOR),
+    ("withdrawToken", withdrawTokenCall::SELECTOR),
+];
+
+fn contract_status_from_code(code: &[u8]) -> ContractStatus {
+    if code.is_empty() {
+        return ContractStatus::NoCode;
+    }
+    let missing: Vec<&'static str> = EXPECTED_FUNCTIONS
+        .iter()
+        .filter(|(_, selector)| !code.windows(4).any(|w| w == selector))
+        .map(|(name, _)| *name)
+        .collect();
+    if missing.is_empty() { ContractStatus::Ready } else { ContractStatus::MissingFunctions(missing) }
+}
+
+/// Check that the arb contract is deployed and exposes the functions the bot
+/// calls, warning if not. Anything but `Ready` means atomic arb should be skipped.
+pub async fn verify_atomic_contract<P: Provider>(provider: &P) -> Result<ContractStatus> {
+    let contract = atomic_arb_contract();
+    if contract == Address::ZERO {
+        return Ok(ContractStatus::NotConfigured);
+    }
+    let status = contract_status_from_code(&provider.get_code_at(contract).await?);
+    match &status {
+        ContractStatus::Ready | ContractStatus::NotConfigured => {}
+        ContractStatus::NoCode => tracing::warn!(
+            "Arb contract {:?} has no code: not deployed on this chain? Set ATOMIC_ARB_CONTRACT to the new address",
+            contract
+        ),
+        ContractStatus::MissingFunctions(missing) => tracing::warn!(
+            "Arb contract {:?} lacks {}: not a MonadAtomicArb deployment? Set ATOMIC_ARB_CONTRACT to the new address",
+            contract,
+            missing.join(", ")
+        ),
+    }
+    Ok(status)
+}
+
+/// Contract owner (only the owner may call executeArb)
+pub async fn query_contract_owner<P: Provider>(provider: &P) -> Result<Address> {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(atomic_arb_contract())
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(ownerCall {}.abi_encode())));
+
+    let result = provider.call(tx).await?;
+    Ok(ownerCall::abi_decode_returns(&result)?)
+}
+
 /// Build swap calldata for the contract to forward to router
 fn build_router_calldata(
     router: &RouterConfig,
@@ -236,13 +444,12 @@ fn build_router_calldata(
 
     // IMPORTANT: recipient is the CONTRACT address, not the wallet
     build_swap_calldata(
-        router.router_type,
+        router,
         token_in,
         token_out,
         amount_in,
         amount_out_min,
-        ATOMIC_ARB_CONTRACT,  // Contract receives tokens, not wallet
-        router.pool_fee,
+        atomic_arb_contract(),  // Contract receives tokens, not wallet
         deadline,
     )
 }
@@ -257,35 +464,24 @@ fn build_router_calldata(
 ///
 /// # Arguments
 /// * `provider_with_signer` - Provider with wallet for sending transactions
-/// * `sell_router` - Router to sell WMON on (higher price)
-/// * `buy_router` - Router to buy WMON on (lower price)
-/// * `amount` - Amount of WMON to arb
-/// * `sell_price` - Expected price on sell DEX
-/// * `buy_price` - Expected price on buy DEX
-/// * `slippage_bps` - Slippage tolerance in basis points
-/// * `min_profit_bps` - Minimum profit required (0 = any profit)
-/// * `gas_price` - Pre-fetched gas price
-/// * `spread_bps` - Current spread in basis points (for gas strategy)
-/// * `force` - If true, skip profit check (for testing)
+/// * `trade` - Legs, size, slippage and profit floor
+/// * `gas` - Pre-resolved fees from the gas strategy
+/// * `opts` - Re-quote and receipt handling. Without a receipt the result is
+///   reported as sent with the estimated profit.
 pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     provider_with_signer: &P,
     signer_address: Address,
-    sell_router: &RouterConfig,
-    buy_router: &RouterConfig,
-    amount: f64,
-    sell_price: f64,
-    buy_price: f64,
-    slippage_bps: u32,
-    min_profit_bps: i32,
-    gas_price: u128,
-    spread_bps: i32,
-    force: bool,
-) -> Result<AtomicArbResult> {
+    trade: AtomicArbTrade<'_>,
+    gas: GasFees,
+    opts: AtomicExecOptions,
+) -> ArbResult<AtomicArbResult> {
+    let AtomicArbTrade { sell_router, buy_router, amount, sell_price, buy_price, slippage_bps, min_profit_bps, spread_bps } = trade;
+    let AtomicExecOptions { force, requote, receipt_mode } = opts;
     let start = std::time::Instant::now();
 
     // Validate contract address is set
-    if ATOMIC_ARB_CONTRACT == Address::ZERO {
-        return Err(eyre!("ATOMIC_ARB_CONTRACT not set in config.rs. Deploy contract first!"));
+    if atomic_arb_contract() == Address::ZERO {
+        return Err(ArbError::Other("Atomic arb contract not set (config.rs or ATOMIC_ARB_CONTRACT env). Deploy contract first!".to_string()));
     }
 
     // TURBO: Early exit if spread is too low to be profitable
@@ -307,8 +503,8 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
             wmon_in: amount,
             spread_bps,
             gas_source: "Skipped".to_string(),
-            error: Some(format!("Spread {} bps below minimum {} bps - skipping (use --force to override)",
-                spread_bps, MIN_PROFITABLE_SPREAD_BPS)),
+            error: Some(ArbError::Other(format!("Spread {} bps below minimum {} bps - skipping (use --force to override)",
+                spread_bps, MIN_PROFITABLE_SPREAD_BPS))),
         });
     }
 
@@ -316,19 +512,40 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     // This saves ~50-100ms
     println!("  [TURBO] Skipping pre-balance query, using estimated profit");
 
+    // Fresh fills for both legs; routers without a quoter keep the spot estimate
+    let quote = if requote {
+        let quote_start = std::time::Instant::now();
+        match QuoteFetcher::AtomicQuote
+            .quote_arb(provider_with_signer, &TokenPair::wmon_usdc(), sell_router, buy_router, amount)
+            .await
+        {
+            Ok(q) => {
+                println!("  Re-quoted at block {} in {:?}: {:.6} USDC -> {:.6} WMON",
+                    q.block_number, quote_start.elapsed(), q.usdc_intermediate, q.wmon_out);
+                Some(q)
+            }
+            Err(e) => {
+                println!("  Re-quote unavailable ({}), using spot prices", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Calculate amounts
     let wmon_in_wei = to_wei(amount, WMON_DECIMALS);
-    let expected_usdc = amount * sell_price;
+    let (expected_usdc, expected_wmon_back) = expected_leg_outputs(amount, sell_price, buy_price, quote.as_ref());
     let slippage_mult = 1.0 - (slippage_bps as f64 / 10000.0);
     let min_usdc_out = expected_usdc * slippage_mult;
     let min_usdc_out_wei = to_wei(min_usdc_out, USDC_DECIMALS);
 
-    // Calculate minimum WMON output for swap 2 (slippage protection)
-    let expected_wmon_back = expected_usdc / buy_price;
+    // Minimum WMON output for swap 2: slippage protection, raised to the
+    // profit floor so the contract itself reverts below min_profit_bps
     let min_wmon_out = expected_wmon_back * slippage_mult;
-    let min_wmon_out_wei = to_wei(min_wmon_out, WMON_DECIMALS);
+    let min_wmon_out_wei = final_leg_min_out(amount, min_wmon_out, min_profit_bps, force);
 
-    // Calculate estimated profit (based on static prices)
+    // Calculate estimated profit (quoted fills, or static prices)
     let estimated_profit = expected_wmon_back - amount;
     let estimated_profit_bps = if amount > 0.0 {
         (estimated_profit / amount * 10000.0) as i32
@@ -348,8 +565,8 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     let buy_pool_fee: u32 = buy_router.pool_fee;
 
     // Use calldata template for common routes
-    let sell_router_id = ContractRouter::from(sell_router.router_type) as u8;
-    let buy_router_id = ContractRouter::from(buy_router.router_type) as u8;
+    let sell_router_id = ContractRouter::try_from(sell_router.router_type)? as u8;
+    let buy_router_id = ContractRouter::try_from(buy_router.router_type)? as u8;
     let _template = get_template(sell_router_id, buy_router_id, buy_pool_fee);
 
     println!("  [TURBO] Building atomic arb (spread: {} bps)...", spread_bps);
@@ -365,29 +582,19 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     )?;
 
     // Build executeArb call
-    let buy_pool_fee_u24: Uint<24, 1> = Uint::from(buy_pool_fee);
-
-    let calldata = if force {
-        println!("  Using UNCHECKED mode (force=true) - no profit check");
-        let execute_call = executeArbUncheckedCall {
-            sellRouter: sell_router_id,
-            sellRouterData: sell_calldata,
-            buyRouter: buy_router_id,
-            buyPoolFee: buy_pool_fee_u24,
-            minWmonOut: min_wmon_out_wei,
-        };
-        Bytes::from(execute_call.abi_encode())
-    } else {
-        let execute_call = executeArbCall {
-            sellRouter: sell_router_id,
-            sellRouterData: sell_calldata,
-            buyRouter: buy_router_id,
-            buyPoolFee: buy_pool_fee_u24,
-            minWmonOut: min_wmon_out_wei,
-            minProfit: min_profit_wei,
-        };
-        Bytes::from(execute_call.abi_encode())
-    };
+    if force {
+        println!("  Using UNCHECKED mode (force=true) - no profit check, slippage floor only");
+    }
+    println!("    Final leg min out: {:.6} WMON", from_wei(min_wmon_out_wei, WMON_DECIMALS));
+    let calldata = build_execute_arb_calldata(
+        sell_router_id,
+        sell_calldata,
+        buy_router_id,
+        buy_pool_fee,
+        min_wmon_out_wei,
+        min_profit_wei,
+        force,
+    );
 
     // TURBO: Spread-aware gas strategy
     let route_key = RouteKey::new(sell_router_id, buy_router_id);
@@ -401,7 +608,7 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
         GasDecision::FetchFresh { buffer_percent } => {
             println!("  [TURBO] Fetching fresh gas estimate (spread {} bps requires fresh)...", spread_bps);
             let estimate_tx = alloy::rpc::types::TransactionRequest::default()
-                .to(ATOMIC_ARB_CONTRACT)
+                .to(atomic_arb_contract())
                 .from(signer_address)
                 .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
 
@@ -429,7 +636,7 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
                         wmon_in: amount,
                         spread_bps,
                         gas_source: "Failed".to_string(),
-                        error: Some(format!("Gas estimation failed: {}", e)),
+                        error: Some(ArbError::with_context(e, "Gas estimation")),
                     });
                 }
             }
@@ -437,16 +644,16 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     };
 
     // TURBO: Spread-aware gas price bidding
-    let (max_fee, priority_fee) = calculate_gas_price(gas_price, spread_bps);
+    let (max_fee, priority_fee) = calculate_gas_price(gas, spread_bps);
     println!("  [TURBO] Gas price: max_fee={}, priority={} (spread boost)", max_fee, priority_fee);
 
     // Build and send transaction
     let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(ATOMIC_ARB_CONTRACT)
+        .to(atomic_arb_contract())
         .from(signer_address)
         .input(alloy::rpc::types::TransactionInput::new(calldata))
         .gas_limit(gas_estimate)
-        .nonce(next_nonce())
+        .nonce(next_nonce_for(signer_address))
         .max_fee_per_gas(max_fee)
         .max_priority_fee_per_gas(priority_fee)
         .with_chain_id(MONAD_CHAIN_ID);
@@ -454,11 +661,11 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
     println!("  Sending atomic arb transaction...");
     let send_start = std::time::Instant::now();
 
-    let pending = match timeout(
+    let tx_hash = match timeout(
         Duration::from_secs(10),
-        provider_with_signer.send_transaction(tx)
+        submit::send_with_nonce_recovery(provider_with_signer, tx)
     ).await {
-        Ok(Ok(p)) => p,
+        Ok(Ok((hash, _))) => hash,
         Ok(Err(e)) => {
             return Ok(AtomicArbResult {
                 tx_hash: String::new(),
@@ -475,7 +682,7 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
                 wmon_in: amount,
                 spread_bps,
                 gas_source,
-                error: Some(format!("Send failed: {}", e)),
+                error: Some(ArbError::with_context(e, "Send")),
             });
         }
         Err(_) => {
@@ -494,14 +701,37 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
                 wmon_in: amount,
                 spread_bps,
                 gas_source,
-                error: Some("Send timeout".to_string()),
+                error: Some(ArbError::RpcTimeout("Send timeout".to_string())),
             });
         }
     };
 
-    let tx_hash = *pending.tx_hash();
     println!("    TX sent: {:?} (in {:?})", tx_hash, send_start.elapsed());
 
+    if receipt_mode != ReceiptMode::Wait {
+        if receipt_mode == ReceiptMode::Background {
+            watch_receipt(provider_with_signer.clone(), tx_hash, format!("{}->{}", sell_router.name, buy_router.name));
+        }
+        println!("  [TURBO] Not waiting for receipt ({:?})", receipt_mode);
+        return Ok(AtomicArbResult {
+            tx_hash: format!("{:?}", tx_hash),
+            success: true,
+            estimated_profit_wmon: estimated_profit,
+            actual_profit_wmon: None,
+            profit_bps: estimated_profit_bps,
+            gas_used: 0,
+            gas_limit: gas_estimate,
+            gas_cost_mon: 0.0,
+            execution_time_ms: start.elapsed().as_millis(),
+            sell_dex: sell_router.name.to_string(),
+            buy_dex: buy_router.name.to_string(),
+            wmon_in: amount,
+            spread_bps,
+            gas_source,
+            error: None,
+        });
+    }
+
     // TURBO: Aggressive receipt polling (5ms instead of 20ms)
     println!("  [TURBO] Waiting for confirmation (5ms polling)...");
     let receipt = match timeout(
@@ -525,7 +755,7 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
                 wmon_in: amount,
                 spread_bps,
                 gas_source,
-                error: Some(format!("Receipt error: {}", e)),
+                error: Some(ArbError::with_context(e, "Receipt")),
             });
         }
         Err(_) => {
@@ -544,7 +774,7 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
                 wmon_in: amount,
                 spread_bps,
                 gas_source,
-                error: Some("Confirmation timeout".to_string()),
+                error: Some(ArbError::RpcTimeout("Confirmation timeout".to_string())),
             });
         }
     };
@@ -598,7 +828,9 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
             error: None,
         })
     } else {
-        println!("  Atomic arb REVERTED (likely unprofitable)");
+        let reason = super::decode_revert(provider_with_signer, tx_hash).await
+            .unwrap_or_else(|| "unprofitable or swap failed".to_string());
+        println!("  Atomic arb REVERTED: {}", reason);
 
         Ok(AtomicArbResult {
             tx_hash: format!("{:?}", tx_hash),
@@ -615,11 +847,183 @@ pub async fn execute_atomic_arb<P: Provider + Clone + Send + Sync + 'static>(
             wmon_in: amount,
             spread_bps,
             gas_source,
-            error: Some("Transaction reverted (unprofitable or swap failed)".to_string()),
+            error: Some(ArbError::revert("Transaction", reason)),
         })
     }
 }
 
+/// Check the deployed contract's dispatcher for a function selector
+async fn contract_has_selector<P: Provider>(provider: &P, selector: [u8; 4]) -> Result<bool> {
+    let code = provider.get_code_at(atomic_arb_contract()).await?;
+    Ok(code.windows(4).any(|w| w == selector))
+}
+
+/// Execute atomic arbitrage on flashloan-borrowed WMON instead of contract balance
+///
+/// The contract borrows `amount` WMON from `lender` (Aave V3-style
+/// `flashLoanSimple`), runs both swaps in the callback and repays amount + fee.
+/// `min_profit_bps` is checked against the estimate AFTER `flashloan_fee_bps`.
+/// Fails fast if the deployed contract predates `executeArbFlashloan`.
+pub async fn execute_atomic_arb_flashloan<P: Provider + Clone + Send + Sync + 'static>(
+    provider_with_signer: &P,
+    signer_address: Address,
+    lender: Address,
+    flashloan_fee_bps: u32,
+    trade: AtomicArbTrade<'_>,
+    gas: GasFees,
+) -> ArbResult<AtomicArbResult> {
+    let AtomicArbTrade { sell_router, buy_router, amount, sell_price, buy_price, slippage_bps, min_profit_bps, spread_bps } = trade;
+    let start = std::time::Instant::now();
+
+    if atomic_arb_contract() == Address::ZERO {
+        return Err(ArbError::Other("Atomic arb contract not set (config.rs or ATOMIC_ARB_CONTRACT env). Deploy contract first!".to_string()));
+    }
+    if lender == Address::ZERO {
+        return Err(ArbError::Other("Flashloan pool not set. Pass --flashloan-pool or set FLASHLOAN_POOL".to_string()));
+    }
+    if !contract_has_selector(provider_with_signer, executeArbFlashloanCall::SELECTOR).await? {
+        return Err(ArbError::Other(format!(
+            "Arb contract {:?} has no executeArbFlashloan entrypoint. Redeploy contracts/src/MonadAtomicArb.sol",
+            atomic_arb_contract()
+        )));
+    }
+
+    let sell_router_id = ContractRouter::try_from(sell_router.router_type)? as u8;
+    let buy_router_id = ContractRouter::try_from(buy_router.router_type)? as u8;
+
+    // Same sizing as execute_atomic_arb, with the flashloan premium taken off the profit
+    let wmon_in_wei = to_wei(amount, WMON_DECIMALS);
+    let expected_usdc = amount * sell_price;
+    let slippage_mult = 1.0 - (slippage_bps as f64 / 10000.0);
+    let min_usdc_out_wei = to_wei(expected_usdc * slippage_mult, USDC_DECIMALS);
+    let expected_wmon_back = expected_usdc / buy_price;
+    let min_wmon_out_wei = to_wei(expected_wmon_back * slippage_mult, WMON_DECIMALS);
+
+    let flashloan_fee_wmon = amount * flashloan_fee_bps as f64 / 10000.0;
+    let estimated_profit = expected_wmon_back - amount - flashloan_fee_wmon;
+    let estimated_profit_bps = if amount > 0.0 {
+        (estimated_profit / amount * 10000.0) as i32
+    } else {
+        0
+    };
+
+    let mut result = AtomicArbResult {
+        tx_hash: String::new(),
+        success: false,
+        estimated_profit_wmon: estimated_profit,
+        actual_profit_wmon: None,
+        profit_bps: 0,
+        gas_used: 0,
+        gas_limit: 0,
+        gas_cost_mon: 0.0,
+        execution_time_ms: 0,
+        sell_dex: sell_router.name.to_string(),
+        buy_dex: buy_router.name.to_string(),
+        wmon_in: amount,
+        spread_bps,
+        gas_source: "Fresh".to_string(),
+        error: None,
+    };
+
+    println!("  [FLASHLOAN] Borrowing {:.6} WMON from {:?} (fee {} bps)", amount, lender, flashloan_fee_bps);
+    println!("    Expected WMON back: {:.6}, profit after fee: {:.6} WMON ({} bps)",
+        expected_wmon_back, estimated_profit, estimated_profit_bps);
+
+    if estimated_profit_bps < min_profit_bps {
+        result.execution_time_ms = start.elapsed().as_millis();
+        result.gas_source = "Skipped".to_string();
+        result.error = Some(ArbError::Other(format!(
+            "Profit {} bps after {} bps flashloan fee below minimum {} bps - skipping",
+            estimated_profit_bps, flashloan_fee_bps, min_profit_bps
+        )));
+        return Ok(result);
+    }
+
+    let min_profit_wei = to_wei(amount * min_profit_bps.max(0) as f64 / 10000.0, WMON_DECIMALS);
+    let sell_calldata = build_router_calldata(sell_router, SwapDirection::Sell, wmon_in_wei, min_usdc_out_wei)?;
+    let calldata = Bytes::from(executeArbFlashloanCall {
+        lender,
+        amount: wmon_in_wei,
+        sellRouter: sell_router_id,
+        sellRouterData: sell_calldata,
+        buyRouter: buy_router_id,
+        buyPoolFee: Uint::from(buy_router.pool_fee),
+        minWmonOut: min_wmon_out_wei,
+        minProfit: min_profit_wei,
+    }.abi_encode());
+
+    // Always estimate fresh: the flashloan path is not in the gas cache
+    let estimate_tx = alloy::rpc::types::TransactionRequest::default()
+        .to(atomic_arb_contract())
+        .from(signer_address)
+        .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
+    let gas_estimate = match provider_with_signer.estimate_gas(estimate_tx).await {
+        Ok(est) => est * (100 + DEFAULT_GAS_BUFFER_PERCENT) / 100,
+        Err(e) => {
+            result.execution_time_ms = start.elapsed().as_millis();
+            result.gas_source = "Failed".to_string();
+            result.error = Some(ArbError::with_context(e, "Gas estimation"));
+            return Ok(result);
+        }
+    };
+    result.gas_limit = gas_estimate;
+
+    let (max_fee, priority_fee) = calculate_gas_price(gas, spread_bps);
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(atomic_arb_contract())
+        .from(signer_address)
+        .input(alloy::rpc::types::TransactionInput::new(calldata))
+        .gas_limit(gas_estimate)
+        .nonce(next_nonce_for(signer_address))
+        .max_fee_per_gas(max_fee)
+        .max_priority_fee_per_gas(priority_fee)
+        .with_chain_id(MONAD_CHAIN_ID);
+
+    println!("  Sending flashloan arb transaction (gas limit {})...", gas_estimate);
+    let tx_hash = match timeout(Duration::from_secs(10), submit::send_transaction(provider_with_signer, tx)).await {
+        Ok(Ok((hash, _))) => hash,
+        Ok(Err(e)) => {
+            result.execution_time_ms = start.elapsed().as_millis();
+            result.error = Some(ArbError::with_context(e, "Send"));
+            return Ok(result);
+        }
+        Err(_) => {
+            result.execution_time_ms = start.elapsed().as_millis();
+            result.error = Some(ArbError::RpcTimeout("Send timeout".to_string()));
+            return Ok(result);
+        }
+    };
+    result.tx_hash = format!("{:?}", tx_hash);
+    println!("    TX sent: {:?}", tx_hash);
+
+    let receipt = match wait_for_receipt_fast(provider_with_signer, tx_hash).await {
+        Ok(r) => r,
+        Err(e) => {
+            result.execution_time_ms = start.elapsed().as_millis();
+            result.error = Some(ArbError::with_context(e, "Receipt"));
+            return Ok(result);
+        }
+    };
+
+    let gas_cost_wei = U256::from(gas_estimate) * U256::from(receipt.effective_gas_price);
+    result.gas_used = receipt.gas_used;
+    result.gas_cost_mon = gas_cost_wei.to::<u128>() as f64 / 1e18;
+    result.execution_time_ms = start.elapsed().as_millis();
+
+    if receipt.status() {
+        println!("  [FLASHLOAN] Arb SUCCESS in {}ms", result.execution_time_ms);
+        result.success = true;
+        result.profit_bps = estimated_profit_bps;
+    } else {
+        let reason = super::decode_revert(provider_with_signer, tx_hash).await
+            .unwrap_or_else(|| "unprofitable or swap failed".to_string());
+        println!("  Flashloan arb REVERTED: {}", reason);
+        result.error = Some(ArbError::revert("Transaction", reason));
+    }
+
+    Ok(result)
+}
+
 /// Aggressive receipt polling (5ms intervals for Monad's fast blocks)
 /// Saves 50-100ms average compared to 20ms polling
 async fn wait_for_receipt_fast<P: Provider>(
@@ -641,6 +1045,35 @@ async fn wait_for_receipt_fast<P: Provider>(
     Err(eyre!("Receipt timeout after {}ms", RECEIPT_TIMEOUT_MS))
 }
 
+/// Background receipt watches still running
+static RECEIPT_WATCHES: Mutex<Vec<tokio::task::JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Fetch `tx_hash`'s receipt off the send path, log the outcome and add it to
+/// the session stats
+fn watch_receipt<P: Provider + Send + Sync + 'static>(provider: P, tx_hash: alloy::primitives::TxHash, route: String) {
+    let handle = tokio::spawn(async move {
+        let outcome = match timeout(BACKGROUND_RECEIPT_TIMEOUT, wait_for_receipt_fast(&provider, tx_hash)).await {
+            Ok(Ok(receipt)) if receipt.status() => ReceiptOutcome::Confirmed { gas_used: receipt.gas_used },
+            Ok(Ok(receipt)) => ReceiptOutcome::Reverted { gas_used: receipt.gas_used },
+            Ok(Err(_)) | Err(_) => ReceiptOutcome::TimedOut,
+        };
+        tracing::info!(target: EVENT_TARGET, pair = logging::pair(), tx = %tx_hash, route = %route, outcome = ?outcome,
+            "  [TURBO] Receipt {:?}: {:?}", tx_hash, outcome);
+        record_receipt_outcome(outcome);
+    });
+    let mut watches = RECEIPT_WATCHES.lock().unwrap_or_else(|e| e.into_inner());
+    watches.retain(|h| !h.is_finished());
+    watches.push(handle);
+}
+
+/// Wait for outstanding background receipt watches (each is bounded by its own timeout)
+pub async fn drain_receipt_watches() {
+    let watches = std::mem::take(&mut *RECEIPT_WATCHES.lock().unwrap_or_else(|e| e.into_inner()));
+    for watch in watches {
+        let _ = watch.await;
+    }
+}
+
 /// Print atomic arb result (TURBO version)
 pub fn print_atomic_arb_result(result: &AtomicArbResult) {
     let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
@@ -686,3 +1119,57 @@ pub fn print_atomic_arb_result(result: &AtomicArbResult) {
     println!();
     println!("===============================================================");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_status_from_code() {
+        assert_eq!(contract_status_from_code(&[]), ContractStatus::NoCode);
+
+        let mut code: Vec<u8> = EXPECTED_FUNCTIONS.iter().flat_map(|(_, s)| [&[0x63u8][..], &s[..]].concat()).collect();
+        assert_eq!(contract_status_from_code(&code), ContractStatus::Ready);
+
+        // An older build without withdrawToken
+        code.truncate(code.len() - 5);
+        assert_eq!(contract_status_from_code(&code), ContractStatus::MissingFunctions(vec!["withdrawToken"]));
+    }
+
+    #[test]
+    fn test_final_leg_min_out_enforces_profit_floor() {
+        // 100 WMON at 0.0303 / 0.03: expect 101 back, 150 bps slippage floor is 99.485
+        let (amount, slippage_min) = (100.0, 101.0 * (1.0 - 0.015));
+
+        // 30 bps profit floor (100.3) is above the slippage floor and wins
+        let wmon = |wei: U256| from_wei(wei, WMON_DECIMALS);
+        let min_out = final_leg_min_out(amount, slippage_min, 30, false);
+        assert!((wmon(min_out) - 100.3).abs() < 1e-9);
+        let calldata = build_execute_arb_calldata(0, Bytes::new(), 3, 500, min_out, U256::ZERO, false);
+        assert_eq!(executeArbCall::abi_decode(&calldata).unwrap().minWmonOut, min_out);
+
+        // --force drops the profit floor: only slippage protects the final leg
+        assert!((wmon(final_leg_min_out(amount, slippage_min, 30, true)) - 99.485).abs() < 1e-9);
+        // A loose slippage floor never undercuts break-even at 0 bps
+        assert!((wmon(final_leg_min_out(amount, 90.0, 0, false)) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expected_leg_outputs_prefer_quote() {
+        assert_eq!(expected_leg_outputs(100.0, 0.04, 0.025, None), (4.0, 160.0));
+
+        // Price impact: the quote fills below spot on both legs
+        let quote = ArbQuote {
+            block_number: 1,
+            amount_in: 100.0,
+            usdc_intermediate: 3.9,
+            wmon_out: 150.0,
+            sell_spot: 0.04,
+            buy_spot: 0.025,
+            sell_impact_bps: 250.0,
+            buy_impact_bps: 380.0,
+            gas_estimate: 0,
+        };
+        assert_eq!(expected_leg_outputs(100.0, 0.04, 0.025, Some(&quote)), (3.9, 150.0));
+    }
+}
@@ -0,0 +1,66 @@
+//! Arb direction guard
+//!
+//! A two-leg arb sells WMON on `sell_dex` and buys it back on `buy_dex`, so it
+//! can only profit when the sell pool quotes the higher price. With the roles
+//! reversed the trade is a guaranteed loss before fees; `check_arb_direction`
+//! stops it unless `--force`, or flips the roles with `--auto-direction`.
+
+use eyre::{eyre, Result};
+
+/// Error unless selling at `sell_price` and buying back at `buy_price` is a positive spread
+pub fn validate_arb_direction(sell_price: f64, buy_price: f64) -> Result<()> {
+    if sell_price <= buy_price {
+        return Err(eyre!(
+            "sell price {:.6} <= buy price {:.6}: the arb loses money before fees",
+            sell_price, buy_price
+        ));
+    }
+    Ok(())
+}
+
+/// Apply the direction guard for a `--sell-dex/--buy-dex` pair.
+/// Returns true when the caller should swap the sell and buy roles.
+pub fn check_arb_direction(
+    sell_dex: &str,
+    buy_dex: &str,
+    sell_price: f64,
+    buy_price: f64,
+    force: bool,
+    auto_direction: bool,
+) -> Result<bool> {
+    let Err(e) = validate_arb_direction(sell_price, buy_price) else {
+        return Ok(false);
+    };
+    if auto_direction && validate_arb_direction(buy_price, sell_price).is_ok() {
+        println!("  ↔ Auto-direction: {} quotes higher, selling there (--sell-dex {} --buy-dex {})",
+            buy_dex, buy_dex, sell_dex);
+        return Ok(true);
+    }
+    if force {
+        println!("\n⚠️  WARNING: {} (--force, executing anyway)", e);
+        return Ok(false);
+    }
+    Err(eyre!(
+        "{}. sell_dex should have HIGHER price than buy_dex. Try: --sell-dex {} --buy-dex {} (or --auto-direction, --force)",
+        e, buy_dex, sell_dex
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arb_direction_guard() {
+        assert!(validate_arb_direction(0.0301, 0.0300).is_ok());
+        assert!(validate_arb_direction(0.0300, 0.0300).is_err());
+
+        assert!(!check_arb_direction("uniswap", "lfj", 0.0301, 0.0300, false, false).unwrap());
+        let err = check_arb_direction("uniswap", "lfj", 0.0299, 0.0300, false, false).unwrap_err();
+        assert!(err.to_string().contains("--sell-dex lfj --buy-dex uniswap"));
+        assert!(check_arb_direction("uniswap", "lfj", 0.0299, 0.0300, false, true).unwrap());
+        assert!(!check_arb_direction("uniswap", "lfj", 0.0299, 0.0300, true, false).unwrap());
+        // Equal prices: nothing to flip to
+        assert!(check_arb_direction("uniswap", "lfj", 0.03, 0.03, false, true).is_err());
+    }
+}
@@ -7,10 +7,13 @@
 //! - 20ms polling (optimized for Monad's fast blocks)
 //! - Dynamic safety buffer based on slippage
 //! - Skipping approval/balance checks
+//! - Optionally (`--parallel`) buying from USDC inventory so both legs
+//!   confirm in the same block instead of one after the other
 //!
 //! CRITICAL MONAD GAS FIX:
 //! Monad charges gas_limit, NOT gas_used!
-//! We use eth_estimateGas + 10% buffer instead of hardcoded limits.
+//! We use the router's learned P95 gas (`gas::GasProfile`), or
+//! eth_estimateGas + buffer until it has history, instead of hardcoded limits.
 
 use alloy::network::TransactionBuilder;
 use alloy::primitives::{Address, Bytes, TxHash, U256};
@@ -21,12 +24,25 @@ use alloy::sol_types::SolCall;
 use chrono::Local;
 use eyre::{eyre, Result};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::time::{interval, timeout};
-
-use crate::config::{RouterConfig, RouterType, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS};
-use crate::nonce::next_nonce;
-use super::routers::build_swap_calldata;
+use tokio::time::timeout;
+use tracing::info;
+
+use crate::config::{GasMode, RouterConfig, RouterType, TokenPair};
+use crate::error::{ArbError, ArbResult};
+use crate::gas::{self, GasFees};
+use crate::logging::{self, EVENT_TARGET};
+use crate::multicall::query_balances_batched;
+use crate::node_config::receipt_wait;
+use crate::nonce::next_nonce_for;
+use crate::slippage::adaptive_slippage;
+use crate::wallet::cancel::cancel_request;
+use crate::wallet::permit::attach_self_permit;
+use super::routers::{apply_price_limit, build_swap_calldata, build_swap_calldata_exact_output, supports_exact_output, ExactOutputSwap};
 use super::SwapDirection;
+use super::swap::wait_for_receipt;
+use super::revert::{decode_revert, revert_reason_from_rpc_error};
+use super::submit;
+use crate::simulation::quote_fetcher::ArbQuote;
 
 // Monad mainnet chain ID
 const MONAD_CHAIN_ID: u64 = 143;
@@ -38,6 +54,9 @@ const GAS_BUFFER_PERCENT: u64 = 15;
 const FALLBACK_GAS_LIMIT_SIMPLE: u64 = 250_000;
 const FALLBACK_GAS_LIMIT_COMPLEX: u64 = 400_000;
 
+// Fee bump over swap 2's fees for the replace-by-fee cancel of a stuck swap 2
+const SWAP2_CANCEL_BUMP_PCT: u32 = 20;
+
 // ERC20 interface for approvals and balance queries
 sol! {
     #[derive(Debug)]
@@ -63,7 +82,15 @@ pub struct FastArbResult {
     pub swap2_tx_hash: String,
     pub swap2_gas_used: u64,
     pub swap2_gas_estimated: u64,  // Track estimated vs actual
+    /// Leg 2 filled, on the first attempt or the slippage retry
     pub swap2_success: bool,
+    /// Second swap 2 attempt after a slippage revert (--retry-slippage-bump)
+    pub swap2_retry_tx_hash: Option<String>,
+    /// USDC from swap 1 still held because swap 2 never filled
+    pub stranded_usdc: f64,
+    /// Swap 2 outlived its receipt timeout and was replaced at its nonce:
+    /// whether the cancel took the nonce (None = no cancel was needed)
+    pub swap2_cancelled: Option<bool>,
 
     // Amounts (estimated)
     pub wmon_in: f64,
@@ -84,6 +111,14 @@ pub struct FastArbResult {
     pub wmon_out_actual: Option<f64>,        // Filled after balance check
     pub estimation_error_bps: Option<i32>,   // (actual - estimated) / estimated * 10000
 
+    // Quote vs fill (see attach_quote)
+    /// USDC swap 2 sold as exact input; None for exactOutput, a retry, or no swap 2
+    pub swap2_usdc_in: Option<f64>,
+    /// Quoter's predicted swap 1 output
+    pub quoted_usdc_intermediate: Option<f64>,
+    /// Quoter's predicted swap 2 output for `swap2_usdc_in`
+    pub quoted_wmon_out: Option<f64>,
+
     // Profit/Loss
     pub gross_profit_wmon: f64,
     pub profit_bps: i32,
@@ -102,43 +137,61 @@ pub struct FastArbResult {
 
     // Overall success
     pub success: bool,
-    pub error: Option<String>,
+    pub error: Option<ArbError>,
+}
+
+impl FastArbResult {
+    /// Record the quoter's prediction for each leg. Leg 2 was quoted for the
+    /// quote's own USDC, so it is rescaled to what swap 2 actually sold.
+    pub fn attach_quote(&mut self, quote: &ArbQuote) {
+        self.quoted_usdc_intermediate = Some(quote.usdc_intermediate);
+        if let Some(usdc_in) = self.swap2_usdc_in.filter(|_| quote.usdc_intermediate > 0.0) {
+            self.quoted_wmon_out = Some(quote.wmon_out * usdc_in / quote.usdc_intermediate);
+        }
+    }
 }
 
 /// Convert human amount to U256 with proper decimals
-fn to_wei(amount: f64, decimals: u8) -> U256 {
+pub(super) fn to_wei(amount: f64, decimals: u8) -> U256 {
     let multiplier = U256::from(10u64).pow(U256::from(decimals));
     let amount_scaled = (amount * 1e18) as u128;
     U256::from(amount_scaled) * multiplier / U256::from(10u64).pow(U256::from(18u8))
 }
 
 /// Convert U256 to human-readable with proper decimals
-fn from_wei(amount: U256, decimals: u8) -> f64 {
+pub(super) fn from_wei(amount: U256, decimals: u8) -> f64 {
     let divisor = 10u64.pow(decimals as u32) as f64;
     let amount_u128: u128 = amount.try_into().unwrap_or(0);
     amount_u128 as f64 / divisor
 }
 
-/// Query USDC balance for a wallet
-async fn query_usdc_balance<P: Provider>(provider: &P, wallet: Address) -> Result<f64> {
-    let call = balanceOfCall { account: wallet };
-    let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(USDC_ADDRESS)
-        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(call.abi_encode())));
-    let result = provider.call(tx).await?;
-    let balance = U256::from_be_slice(&result);
-    Ok(from_wei(balance, USDC_DECIMALS))
+/// Query quote token (USDC) balance for a wallet
+async fn query_quote_balance<P: Provider>(provider: &P, pair: &TokenPair, wallet: Address) -> Result<f64> {
+    query_token_balance(provider, pair.quote, pair.quote_decimals, wallet).await
+}
+
+/// Query base token (WMON) balance for a wallet
+async fn query_base_balance<P: Provider>(provider: &P, pair: &TokenPair, wallet: Address) -> Result<f64> {
+    query_token_balance(provider, pair.base, pair.base_decimals, wallet).await
 }
 
-/// Query WMON balance for a wallet
-async fn query_wmon_balance<P: Provider>(provider: &P, wallet: Address) -> Result<f64> {
+/// Quote and base balances for a wallet in one round trip
+async fn query_pair_balances<P: Provider>(provider: &P, pair: &TokenPair, wallet: Address) -> Result<(f64, f64)> {
+    let tokens = [(pair.quote, pair.quote_decimals), (pair.base, pair.base_decimals)];
+    match query_balances_batched(provider, wallet, &tokens).await?[..] {
+        [quote, base] => Ok((quote, base)),
+        _ => Err(eyre!("expected two balances")),
+    }
+}
+
+async fn query_token_balance<P: Provider>(provider: &P, token: Address, decimals: u8, wallet: Address) -> Result<f64> {
     let call = balanceOfCall { account: wallet };
     let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(WMON_ADDRESS)
+        .to(token)
         .input(alloy::rpc::types::TransactionInput::new(Bytes::from(call.abi_encode())));
     let result = provider.call(tx).await?;
     let balance = U256::from_be_slice(&result);
-    Ok(from_wei(balance, WMON_DECIMALS))
+    Ok(from_wei(balance, decimals))
 }
 
 /// Get fallback gas limit for a router type (only used if estimation fails)
@@ -148,20 +201,26 @@ fn get_fallback_gas_limit(router_type: RouterType) -> u64 {
         RouterType::PancakeV3 => FALLBACK_GAS_LIMIT_COMPLEX, // Multicall wrapper
         RouterType::LfjLB => FALLBACK_GAS_LIMIT_COMPLEX,     // Complex path routing
         RouterType::MondayTrade => FALLBACK_GAS_LIMIT_SIMPLE,
+        RouterType::UniswapV4 => FALLBACK_GAS_LIMIT_COMPLEX,  // Universal Router + PoolManager unlock
     }
 }
 
-/// Estimate gas for a transaction using eth_estimateGas
-/// Returns estimated gas + buffer, or fallback if estimation fails
-async fn estimate_gas_with_buffer<P: Provider>(
+/// Gas limit for a swap on `router`: the learned P95 when the router has
+/// history, otherwise eth_estimateGas + buffer (or fallback if estimation fails)
+pub(super) async fn estimate_gas_with_buffer<P: Provider>(
     provider: &P,
-    to: Address,
+    router: &RouterConfig,
     from: Address,
     calldata: &Bytes,
-    router_type: RouterType,
 ) -> u64 {
+    if let Some(limit) = gas::profiled_gas_limit(router.name) {
+        println!("    Gas limit from profile: {} ({} P95 + buffer)", limit, router.name);
+        return limit;
+    }
+
+    let router_type = router.router_type;
     let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(to)
+        .to(router.address)
         .from(from)
         .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
 
@@ -180,57 +239,126 @@ async fn estimate_gas_with_buffer<P: Provider>(
     }
 }
 
+/// Dry-run calldata via eth_call against the latest block.
+/// Returns the decoded revert reason if the call would revert.
+async fn simulate_swap<P: Provider>(
+    provider: &P,
+    to: Address,
+    from: Address,
+    calldata: &Bytes,
+) -> std::result::Result<(), String> {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(to)
+        .from(from)
+        .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
+
+    provider
+        .call(tx)
+        .await
+        .map(|_| ())
+        .map_err(|e| revert_reason_from_rpc_error(&e))
+}
+
 /// Wait for transaction receipt with FAST 20ms polling
 /// Times out after 15 seconds (faster than standard 30s)
-async fn wait_for_receipt_fast<P: Provider>(
+pub(super) async fn wait_for_receipt_fast<P: Provider>(
     provider: &P,
     tx_hash: TxHash,
 ) -> Result<TransactionReceipt> {
-    let mut poll_interval = interval(Duration::from_millis(20)); // 20ms polling for Monad's fast blocks
-    let deadline = Duration::from_secs(15); // 15s vs 30s
-
-    timeout(deadline, async {
-        loop {
-            poll_interval.tick().await;
-            if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
-                return Ok::<_, eyre::Report>(receipt);
-            }
-        }
-    })
-    .await
-    .map_err(|_| eyre::eyre!("Transaction confirmation timeout after 15s"))?
+    // 20ms polling for Monad's fast blocks, 15s timeout (--receipt-poll-ms / --receipt-timeout-secs override)
+    let (poll, deadline) = receipt_wait(Duration::from_millis(20), Duration::from_secs(15));
+    wait_for_receipt(provider, tx_hash, poll, deadline).await
 }
 
 /// Pre-build swap transaction calldata
 pub fn build_fast_swap_tx(
     router: &RouterConfig,
+    pair: &TokenPair,
     direction: SwapDirection,
     amount_in: U256,
     amount_out_min: U256,
     recipient: Address,
 ) -> Result<Bytes> {
-    let (token_in, token_out) = match direction {
-        SwapDirection::Sell => (WMON_ADDRESS, USDC_ADDRESS),
-        SwapDirection::Buy => (USDC_ADDRESS, WMON_ADDRESS),
-    };
-
-    let deadline = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() + 300;
+    let (token_in, token_out, _, _) = direction.tokens(pair);
 
     build_swap_calldata(
-        router.router_type,
+        router,
         token_in,
         token_out,
         amount_in,
         amount_out_min,
         recipient,
-        router.pool_fee,
-        deadline,
+        swap_deadline(),
     )
 }
 
+/// Swap (and permit) deadline: five minutes out
+fn swap_deadline() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 300
+}
+
+/// Fully-populated swap transaction to `router`, priced per its `gas_mode`
+pub fn build_swap_request(
+    router: &RouterConfig,
+    from: Address,
+    calldata: Bytes,
+    gas_limit: u64,
+    nonce: u64,
+    gas: GasFees,
+) -> alloy::rpc::types::TransactionRequest {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(router.address)
+        .from(from)
+        .input(alloy::rpc::types::TransactionInput::new(calldata))
+        .gas_limit(gas_limit)
+        .nonce(nonce)
+        .with_chain_id(MONAD_CHAIN_ID);
+
+    match router.gas_mode {
+        GasMode::Eip1559 => tx
+            .max_fee_per_gas(gas.max_fee)
+            .max_priority_fee_per_gas(gas.priority_fee),
+        GasMode::Legacy => tx.gas_price(gas.max_fee),
+    }
+}
+
+/// What a fast arb trades: sell `amount` base on `sell_router`, buy it back on `buy_router`
+#[derive(Clone, Copy)]
+pub struct FastArbTrade<'a> {
+    /// Token pair (sell = base -> quote, buy = quote -> base)
+    pub pair: &'a TokenPair,
+    /// Router to sell WMON on (higher price)
+    pub sell_router: &'a RouterConfig,
+    /// Router to buy WMON on (lower price)
+    pub buy_router: &'a RouterConfig,
+    /// Amount of WMON to start with
+    pub amount: f64,
+    /// Expected price on sell DEX
+    pub sell_price: f64,
+    /// Expected price on buy DEX
+    pub buy_price: f64,
+}
+
+/// How a fast arb executes
+#[derive(Clone, Copy)]
+pub struct FastArbOptions {
+    /// Slippage tolerance in bps
+    pub slippage_bps: u32,
+    /// Pre-resolved fees from the gas strategy (same for both swaps)
+    pub gas: GasFees,
+    /// eth_call swap 1 before sending (adds one RPC round trip)
+    pub simulate: bool,
+    /// If swap 2 reverts but would fill with no min-out at `latest`
+    /// (slippage), retry it once from the held USDC with slippage widened by
+    /// this much (0 = no retry)
+    pub retry_slippage_bump_bps: u32,
+    /// `--adaptive-slippage`: scale `slippage_bps` with the trade's share of
+    /// pool depth, capped at this
+    pub max_adaptive_slippage_bps: Option<u32>,
+    /// Latency budget: swap 2 is skipped once it has passed
+    pub deadline: Option<std::time::Instant>,
+}
+
 /// Execute fast DEX-to-DEX arbitrage with ACTUAL balance tracking
 ///
 /// SLIPPAGE FIX: This version queries actual balances and builds swap 2
@@ -243,43 +371,49 @@ pub fn build_fast_swap_tx(
 /// # Arguments
 /// * `provider_with_signer` - Provider with wallet for sending transactions
 /// * `signer_address` - Wallet address
-/// * `sell_router` - Router to sell WMON on (higher price)
-/// * `buy_router` - Router to buy WMON on (lower price)
-/// * `amount` - Amount of WMON to start with
-/// * `sell_price` - Expected price on sell DEX
-/// * `buy_price` - Expected price on buy DEX
-/// * `slippage_bps` - Slippage tolerance in bps
-/// * `gas_price` - Pre-fetched gas price
+/// * `trade` - Pair, routers, size and expected prices
+/// * `opts` - Slippage, gas, retry and deadline settings
 pub async fn execute_fast_arb<P: Provider>(
     provider_with_signer: &P,
     signer_address: Address,
-    sell_router: &RouterConfig,
-    buy_router: &RouterConfig,
-    amount: f64,
-    sell_price: f64,
-    buy_price: f64,
-    slippage_bps: u32,
-    gas_price: u128,
-) -> Result<FastArbResult> {
+    trade: FastArbTrade<'_>,
+    opts: FastArbOptions,
+) -> ArbResult<FastArbResult> {
+    let FastArbTrade { pair, sell_router, buy_router, amount, sell_price, buy_price } = trade;
+    let FastArbOptions {
+        mut slippage_bps,
+        gas,
+        simulate,
+        retry_slippage_bump_bps,
+        max_adaptive_slippage_bps,
+        deadline,
+    } = opts;
     let total_start = std::time::Instant::now();
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 1: Query balances BEFORE swap 1
     // ═══════════════════════════════════════════════════════════════════════
     println!("  Querying initial balances...");
-    let usdc_before = query_usdc_balance(provider_with_signer, signer_address).await?;
-    let wmon_before = query_wmon_balance(provider_with_signer, signer_address).await?;
+    let (usdc_before, wmon_before) = query_pair_balances(provider_with_signer, pair, signer_address).await?;
     println!("    USDC before: {:.6}", usdc_before);
     println!("    WMON before: {:.6}", wmon_before);
 
+    if let Some(max_bps) = max_adaptive_slippage_bps {
+        let base_bps = slippage_bps;
+        slippage_bps = adaptive_slippage(
+            provider_with_signer, pair, sell_router.pool_address, buy_router.pool_address, amount, base_bps, max_bps,
+        ).await;
+        println!("    Adaptive slippage: {} bps (base {}, max {})", slippage_bps, base_bps, max_bps);
+    }
+
     // Calculate expected amounts (for logging and slippage calculation)
-    let wmon_in_wei = to_wei(amount, WMON_DECIMALS);
+    let wmon_in_wei = to_wei(amount, pair.base_decimals);
     let expected_usdc = amount * sell_price;
 
     // Calculate min USDC output with slippage
     let slippage_multiplier = 1.0 - (slippage_bps as f64 / 10000.0);
     let min_usdc_out = expected_usdc * slippage_multiplier;
-    let min_usdc_out_wei = to_wei(min_usdc_out, USDC_DECIMALS);
+    let min_usdc_out_wei = to_wei(min_usdc_out, pair.quote_decimals);
 
     println!("\n  Swap 1 parameters (Sell WMON -> USDC):");
     println!("    WMON In: {:.6}", amount);
@@ -291,77 +425,107 @@ pub async fn execute_fast_arb<P: Provider>(
     // ═══════════════════════════════════════════════════════════════════════
     let swap1_calldata = build_fast_swap_tx(
         sell_router,
+        pair,
         SwapDirection::Sell,
         wmon_in_wei,
         min_usdc_out_wei,
         signer_address,
     )?;
+    let swap1_calldata = apply_price_limit(sell_router, pair, pair.base, sell_price, slippage_bps, swap1_calldata)?;
+    let swap1_calldata = attach_self_permit(
+        provider_with_signer,
+        submit::local_signer(signer_address),
+        sell_router,
+        pair.base,
+        wmon_in_wei,
+        swap_deadline(),
+        swap1_calldata,
+    ).await;
+
+    // Optional dry-run: a revert here costs nothing, a revert on-chain costs gas_limit
+    if simulate {
+        println!("\n  Simulating swap 1...");
+        if let Err(reason) = simulate_swap(
+            provider_with_signer,
+            sell_router.address,
+            signer_address,
+            &swap1_calldata,
+        ).await {
+            println!("    ✗ Simulation reverted: {}", reason);
+            return Ok(create_error_result(
+                amount, usdc_before, wmon_before, 0, 0,
+                total_start.elapsed().as_millis(),
+                ArbError::revert("Swap 1 simulation", reason),
+            ));
+        }
+        println!("    ✓ Simulation passed");
+    }
 
     println!("\n  Estimating gas for swap 1...");
     let swap1_gas_limit = estimate_gas_with_buffer(
         provider_with_signer,
-        sell_router.address,
+        sell_router,
         signer_address,
         &swap1_calldata,
-        sell_router.router_type,
     ).await;
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 3: Send swap 1 and WAIT for receipt
     // ═══════════════════════════════════════════════════════════════════════
-    let swap1_nonce = next_nonce();
-    let swap1_tx = alloy::rpc::types::TransactionRequest::default()
-        .to(sell_router.address)
-        .from(signer_address)
-        .input(alloy::rpc::types::TransactionInput::new(swap1_calldata))
-        .gas_limit(swap1_gas_limit)
-        .nonce(swap1_nonce)
-        .max_fee_per_gas(gas_price + (gas_price / 10))
-        .max_priority_fee_per_gas(gas_price / 10)
-        .with_chain_id(MONAD_CHAIN_ID);
+    let swap1_nonce = next_nonce_for(signer_address);
+    let swap1_tx = build_swap_request(sell_router, signer_address, swap1_calldata, swap1_gas_limit, swap1_nonce, gas);
 
     println!("\n  Sending swap 1...");
     let swap1_start = std::time::Instant::now();
 
-    let swap1_pending = match timeout(
+    let swap1_hash = match timeout(
         Duration::from_secs(10),
-        provider_with_signer.send_transaction(swap1_tx)
+        submit::send_with_nonce_recovery(provider_with_signer, swap1_tx)
     ).await {
-        Ok(Ok(pending)) => pending,
+        Ok(Ok((hash, _))) => hash,
         Ok(Err(e)) => {
             return Ok(create_error_result(
                 amount, usdc_before, wmon_before, swap1_gas_limit, 0,
                 total_start.elapsed().as_millis(),
-                format!("Swap 1 send failed: {}", e),
+                ArbError::with_context(e, "Swap 1 send"),
             ));
         }
         Err(_) => {
             return Ok(create_error_result(
                 amount, usdc_before, wmon_before, swap1_gas_limit, 0,
                 total_start.elapsed().as_millis(),
-                "Swap 1 send timeout".to_string(),
+                ArbError::RpcTimeout("Swap 1 send timeout".to_string()),
             ));
         }
     };
 
-    let swap1_hash = *swap1_pending.tx_hash();
-    println!("    Swap 1 sent: {:?}", swap1_hash);
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 1, tx_hash = %swap1_hash,
+        "    Swap 1 sent: {:?}", swap1_hash);
 
     // Wait for swap 1 receipt
     println!("  Waiting for swap 1 confirmation...");
     let swap1_receipt = wait_for_receipt_fast(provider_with_signer, swap1_hash).await?;
     let swap1_time = swap1_start.elapsed().as_millis();
 
-    println!("    Swap 1 confirmed: {} (gas used: {}, limit: {})",
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 1, tx_hash = %swap1_hash,
+        success = swap1_receipt.status(), gas_used = swap1_receipt.gas_used, gas_limit = swap1_gas_limit,
+        confirm_ms = swap1_time as u64,
+        "    Swap 1 confirmed: {} (gas used: {}, limit: {})",
         if swap1_receipt.status() { "SUCCESS" } else { "REVERTED" },
         swap1_receipt.gas_used,
         swap1_gas_limit);
+    if swap1_receipt.status() {
+        gas::record_gas_used(sell_router.name, swap1_receipt.gas_used);
+    }
 
     // If swap 1 failed, return early
     if !swap1_receipt.status() {
-        let usdc_after = query_usdc_balance(provider_with_signer, signer_address).await.unwrap_or(usdc_before);
-        let wmon_after = query_wmon_balance(provider_with_signer, signer_address).await.unwrap_or(wmon_before);
+        let (usdc_after, wmon_after) = query_pair_balances(provider_with_signer, pair, signer_address).await
+            .unwrap_or((usdc_before, wmon_before));
         let swap1_gas_cost = U256::from(swap1_gas_limit) * U256::from(swap1_receipt.effective_gas_price);
+        let reason = decode_revert(provider_with_signer, swap1_hash).await
+            .unwrap_or_else(|| "unknown reason".to_string());
+        println!("    Revert reason: {}", reason);
 
         return Ok(FastArbResult {
             swap1_tx_hash: format!("{:?}", swap1_hash),
@@ -372,6 +536,9 @@ pub async fn execute_fast_arb<P: Provider>(
             swap2_gas_used: 0,
             swap2_gas_estimated: 0,
             swap2_success: false,
+            swap2_retry_tx_hash: None,
+            stranded_usdc: 0.0,
+            swap2_cancelled: None,
             wmon_in: amount,
             usdc_intermediate: 0.0,
             wmon_out: 0.0,
@@ -385,6 +552,9 @@ pub async fn execute_fast_arb<P: Provider>(
             swap2_slippage_bps: 0,
             wmon_out_actual: Some(wmon_after - wmon_before + amount),
             estimation_error_bps: None,
+            swap2_usdc_in: None,
+            quoted_usdc_intermediate: None,
+            quoted_wmon_out: None,
             gross_profit_wmon: wmon_after - wmon_before,
             profit_bps: if amount > 0.0 { ((wmon_after - wmon_before) / amount * 10000.0) as i32 } else { 0 },
             total_gas_cost_wei: swap1_gas_cost,
@@ -396,7 +566,7 @@ pub async fn execute_fast_arb<P: Provider>(
             swap2_time_ms: 0,
             execution_time_ms: total_start.elapsed().as_millis(),
             success: false,
-            error: Some("Swap 1 reverted".to_string()),
+            error: Some(ArbError::revert("Swap 1", reason)),
         });
     }
 
@@ -404,7 +574,7 @@ pub async fn execute_fast_arb<P: Provider>(
     // STEP 4: Query ACTUAL USDC balance after swap 1
     // ═══════════════════════════════════════════════════════════════════════
     println!("\n  Querying USDC balance after swap 1...");
-    let usdc_after_swap1 = query_usdc_balance(provider_with_signer, signer_address).await?;
+    let usdc_after_swap1 = query_quote_balance(provider_with_signer, pair, signer_address).await?;
     let actual_usdc_received = usdc_after_swap1 - usdc_before;
 
     // Calculate swap 1 slippage
@@ -417,31 +587,101 @@ pub async fn execute_fast_arb<P: Provider>(
     println!("    USDC after swap 1: {:.6}", usdc_after_swap1);
     println!("    Actual USDC received: {:.6} (expected: {:.6})", actual_usdc_received, expected_usdc);
     println!("    Swap 1 slippage: {} bps", swap1_slippage_bps);
+    let leg1 = Swap1Outcome {
+        wmon_in: amount,
+        usdc_before,
+        wmon_before,
+        tx_hash: swap1_hash,
+        gas_used: swap1_receipt.gas_used,
+        gas_limit: swap1_gas_limit,
+        effective_gas_price: swap1_receipt.effective_gas_price,
+        time_ms: swap1_time,
+        usdc_after: usdc_after_swap1,
+        usdc_received: actual_usdc_received,
+        slippage_bps: swap1_slippage_bps,
+    };
+
+    // Past the latency budget the spread has likely closed: keep the USDC for
+    // Rebalance instead of buying back at a worse price
+    if let Some(deadline) = deadline {
+        let now = std::time::Instant::now();
+        if now > deadline {
+            let elapsed_ms = total_start.elapsed().as_millis();
+            let overshoot_ms = now.duration_since(deadline).as_millis();
+            tracing::warn!(target: EVENT_TARGET, pair = logging::pair(), elapsed_ms = elapsed_ms as u64,
+                overshoot_ms = overshoot_ms as u64,
+                "    Latency budget exceeded by {} ms ({} ms elapsed) - skipping swap 2, USDC left for Rebalance",
+                overshoot_ms, elapsed_ms);
+            return Ok(swap2_not_filled_result(&leg1, wmon_before - amount, 0, String::new(), elapsed_ms,
+                ArbError::PriceStale(format!("Latency budget exceeded by {} ms after {} ms; swap 2 aborted", overshoot_ms, elapsed_ms))));
+        }
+    }
 
     // ═══════════════════════════════════════════════════════════════════════
-    // STEP 5: Build swap 2 with ACTUAL USDC amount (minus small buffer for dust)
+    // STEP 5: Build swap 2
+    // V3-style routers: exactOutput for exactly `amount` WMON, spending up to
+    // the USDC received (WMON inventory stays flat, P&L lands in USDC).
+    // LFJ, or if the received USDC can't cover `amount` at buy_price + fee:
+    // exactInput with the actual USDC received (minus the buy router's usdc_buffer_bps).
     // ═══════════════════════════════════════════════════════════════════════
-    let usdc_for_swap2 = actual_usdc_received * 0.999;  // 0.1% buffer for dust/rounding
-    let usdc_for_swap2_wei = to_wei(usdc_for_swap2, USDC_DECIMALS);
-
-    // Calculate expected WMON back and min output
-    let expected_wmon_back = usdc_for_swap2 / buy_price;
-    let min_wmon_out = expected_wmon_back * slippage_multiplier;
-    let min_wmon_out_wei = to_wei(min_wmon_out, WMON_DECIMALS);
-
-    println!("\n  Swap 2 parameters (Buy USDC -> WMON) - USING ACTUAL USDC:");
-    println!("    USDC In: {:.6} (actual received * 0.999)", usdc_for_swap2);
-    println!("    Expected WMON: {:.6}", expected_wmon_back);
-    println!("    Min WMON out: {:.6} ({}bps slippage)", min_wmon_out, slippage_bps);
-
-    // Build swap 2 calldata with ACTUAL USDC amount
-    let swap2_calldata = build_fast_swap_tx(
+    let buy_fee_fraction = buy_router.pool_fee as f64 / 1_000_000.0;
+    let usdc_needed_for_amount = amount * buy_price / (1.0 - buy_fee_fraction);
+    let use_exact_output = supports_exact_output(buy_router.router_type)
+        && usdc_needed_for_amount <= actual_usdc_received;
+
+    let (swap2_calldata, swap2_max_in_wei, expected_wmon_back) = if use_exact_output {
+        let usdc_max_wei = to_wei(actual_usdc_received, pair.quote_decimals);
+
+        println!("\n  Swap 2 parameters (Buy USDC -> WMON) - EXACT OUTPUT:");
+        println!("    WMON out: {:.6} (exact, = swap 1 input)", amount);
+        println!("    Max USDC in: {:.6} (all received, need ~{:.6})", actual_usdc_received, usdc_needed_for_amount);
+
+        let deadline = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 300;
+        let (token_in, token_out, _, _) = SwapDirection::Buy.tokens(pair);
+        let calldata = build_swap_calldata_exact_output(buy_router, ExactOutputSwap {
+            token_in,
+            token_out,
+            amount_out: to_wei(amount, pair.base_decimals),
+            amount_in_max: usdc_max_wei,
+            recipient: signer_address,
+            deadline,
+        })?;
+        (calldata, usdc_max_wei, amount)
+    } else {
+        let usdc_for_swap2 = actual_usdc_received * buy_router.usdc_spend_fraction();
+        let usdc_for_swap2_wei = to_wei(usdc_for_swap2, pair.quote_decimals);
+
+        // Calculate expected WMON back and min output
+        let expected_wmon_back = usdc_for_swap2 / buy_price;
+        let min_wmon_out = expected_wmon_back * slippage_multiplier;
+        let min_wmon_out_wei = to_wei(min_wmon_out, pair.base_decimals);
+
+        println!("\n  Swap 2 parameters (Buy USDC -> WMON) - USING ACTUAL USDC:");
+        println!("    USDC In: {:.6} (actual received less {} bps buffer)", usdc_for_swap2, buy_router.usdc_buffer_bps);
+        println!("    Expected WMON: {:.6}", expected_wmon_back);
+        println!("    Min WMON out: {:.6} ({}bps slippage)", min_wmon_out, slippage_bps);
+
+        // Build swap 2 calldata with ACTUAL USDC amount
+        let calldata = build_fast_swap_tx(
+            buy_router,
+            pair,
+            SwapDirection::Buy,
+            usdc_for_swap2_wei,
+            min_wmon_out_wei,
+            signer_address,
+        )?;
+        (calldata, usdc_for_swap2_wei, expected_wmon_back)
+    };
+    let swap2_calldata = apply_price_limit(buy_router, pair, pair.quote, buy_price, slippage_bps, swap2_calldata)?;
+    let swap2_calldata = attach_self_permit(
+        provider_with_signer,
+        submit::local_signer(signer_address),
         buy_router,
-        SwapDirection::Buy,
-        usdc_for_swap2_wei,
-        min_wmon_out_wei,
-        signer_address,
-    )?;
+        pair.quote,
+        swap2_max_in_wei,
+        swap_deadline(),
+        swap2_calldata,
+    ).await;
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 6: Estimate gas for swap 2 with new calldata
@@ -449,136 +689,110 @@ pub async fn execute_fast_arb<P: Provider>(
     println!("\n  Estimating gas for swap 2...");
     let swap2_gas_limit = estimate_gas_with_buffer(
         provider_with_signer,
-        buy_router.address,
+        buy_router,
         signer_address,
         &swap2_calldata,
-        buy_router.router_type,
     ).await;
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 7: Send swap 2 and wait for receipt
     // ═══════════════════════════════════════════════════════════════════════
-    let swap2_nonce = next_nonce();
-    let swap2_tx = alloy::rpc::types::TransactionRequest::default()
-        .to(buy_router.address)
-        .from(signer_address)
-        .input(alloy::rpc::types::TransactionInput::new(swap2_calldata))
-        .gas_limit(swap2_gas_limit)
-        .nonce(swap2_nonce)
-        .max_fee_per_gas(gas_price + (gas_price / 10))
-        .max_priority_fee_per_gas(gas_price / 10)
-        .with_chain_id(MONAD_CHAIN_ID);
+    let swap2_nonce = next_nonce_for(signer_address);
+    let swap2_tx = build_swap_request(buy_router, signer_address, swap2_calldata, swap2_gas_limit, swap2_nonce, gas);
 
     println!("\n  Sending swap 2...");
     let swap2_start = std::time::Instant::now();
 
-    let swap2_pending = match timeout(
+    let swap2_hash = match timeout(
         Duration::from_secs(10),
-        provider_with_signer.send_transaction(swap2_tx)
+        submit::send_with_nonce_recovery(provider_with_signer, swap2_tx)
     ).await {
-        Ok(Ok(pending)) => pending,
+        Ok(Ok((hash, _))) => hash,
         Ok(Err(e)) => {
             println!("    Swap 2 send failed: {}", e);
-            let wmon_after = query_wmon_balance(provider_with_signer, signer_address).await.unwrap_or(wmon_before);
-            let swap1_gas_cost = U256::from(swap1_gas_limit) * U256::from(swap1_receipt.effective_gas_price);
-
-            return Ok(FastArbResult {
-                swap1_tx_hash: format!("{:?}", swap1_hash),
-                swap1_gas_used: swap1_receipt.gas_used,
-                swap1_gas_estimated: swap1_gas_limit,
-                swap1_success: true,
-                swap2_tx_hash: String::new(),
-                swap2_gas_used: 0,
-                swap2_gas_estimated: swap2_gas_limit,
-                swap2_success: false,
-                wmon_in: amount,
-                usdc_intermediate: actual_usdc_received,
-                wmon_out: 0.0,
-                usdc_before,
-                usdc_after_swap1,
-                wmon_before,
-                wmon_after_swap2: wmon_after,
-                actual_usdc_received,
-                actual_wmon_received: wmon_after - wmon_before,
-                swap1_slippage_bps,
-                swap2_slippage_bps: 0,
-                wmon_out_actual: Some(0.0),
-                estimation_error_bps: None,
-                gross_profit_wmon: wmon_after - wmon_before,
-                profit_bps: if amount > 0.0 { ((wmon_after - wmon_before) / amount * 10000.0) as i32 } else { 0 },
-                total_gas_cost_wei: swap1_gas_cost,
-                total_gas_cost_mon: swap1_gas_cost.to::<u128>() as f64 / 1e18,
-                total_gas_used: swap1_receipt.gas_used,
-                total_gas_estimated: swap1_gas_limit,
-                total_time_ms: total_start.elapsed().as_millis(),
-                swap1_time_ms: swap1_time,
-                swap2_time_ms: 0,
-                execution_time_ms: total_start.elapsed().as_millis(),
-                success: false,
-                error: Some(format!("Swap 2 send failed: {}", e)),
-            });
+            let wmon_after = query_base_balance(provider_with_signer, pair, signer_address).await.unwrap_or(wmon_before);
+            return Ok(swap2_not_filled_result(&leg1, wmon_after, swap2_gas_limit, String::new(),
+                total_start.elapsed().as_millis(), ArbError::with_context(e, "Swap 2 send")));
         }
         Err(_) => {
-            let wmon_after = query_wmon_balance(provider_with_signer, signer_address).await.unwrap_or(wmon_before);
-            let swap1_gas_cost = U256::from(swap1_gas_limit) * U256::from(swap1_receipt.effective_gas_price);
-
-            return Ok(FastArbResult {
-                swap1_tx_hash: format!("{:?}", swap1_hash),
-                swap1_gas_used: swap1_receipt.gas_used,
-                swap1_gas_estimated: swap1_gas_limit,
-                swap1_success: true,
-                swap2_tx_hash: String::new(),
-                swap2_gas_used: 0,
-                swap2_gas_estimated: swap2_gas_limit,
-                swap2_success: false,
-                wmon_in: amount,
-                usdc_intermediate: actual_usdc_received,
-                wmon_out: 0.0,
-                usdc_before,
-                usdc_after_swap1,
-                wmon_before,
-                wmon_after_swap2: wmon_after,
-                actual_usdc_received,
-                actual_wmon_received: wmon_after - wmon_before,
-                swap1_slippage_bps,
-                swap2_slippage_bps: 0,
-                wmon_out_actual: Some(0.0),
-                estimation_error_bps: None,
-                gross_profit_wmon: wmon_after - wmon_before,
-                profit_bps: if amount > 0.0 { ((wmon_after - wmon_before) / amount * 10000.0) as i32 } else { 0 },
-                total_gas_cost_wei: swap1_gas_cost,
-                total_gas_cost_mon: swap1_gas_cost.to::<u128>() as f64 / 1e18,
-                total_gas_used: swap1_receipt.gas_used,
-                total_gas_estimated: swap1_gas_limit,
-                total_time_ms: total_start.elapsed().as_millis(),
-                swap1_time_ms: swap1_time,
-                swap2_time_ms: 0,
-                execution_time_ms: total_start.elapsed().as_millis(),
-                success: false,
-                error: Some("Swap 2 send timeout".to_string()),
-            });
+            let wmon_after = query_base_balance(provider_with_signer, pair, signer_address).await.unwrap_or(wmon_before);
+            return Ok(swap2_not_filled_result(&leg1, wmon_after, swap2_gas_limit, String::new(),
+                total_start.elapsed().as_millis(), ArbError::RpcTimeout("Swap 2 send timeout".to_string())));
         }
     };
 
-    let swap2_hash = *swap2_pending.tx_hash();
-    println!("    Swap 2 sent: {:?}", swap2_hash);
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, tx_hash = %swap2_hash,
+        "    Swap 2 sent: {:?}", swap2_hash);
 
     // Wait for swap 2 receipt
     println!("  Waiting for swap 2 confirmation...");
-    let swap2_receipt = wait_for_receipt_fast(provider_with_signer, swap2_hash).await?;
+    let swap2_receipt = match wait_for_receipt_fast(provider_with_signer, swap2_hash).await {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            // Left pending it could fill after the bot has moved on: cancel it
+            // (or catch it landing) so the arb ends completed or cancelled
+            println!("    Swap 2 not confirmed: {}", e);
+            match cancel_stuck_swap2(provider_with_signer, signer_address, swap2_nonce, swap2_hash, gas).await {
+                Swap2Cancel::Landed(receipt) => {
+                    println!("    Swap 2 mined before the cancel, continuing");
+                    *receipt
+                }
+                outcome => {
+                    let wmon_after = query_base_balance(provider_with_signer, pair, signer_address).await.unwrap_or(wmon_before);
+                    let mut result = swap2_not_filled_result(&leg1, wmon_after, swap2_gas_limit, format!("{:?}", swap2_hash),
+                        total_start.elapsed().as_millis(), ArbError::RpcTimeout(format!("Swap 2 confirmation timeout: {}", e)));
+                    result.swap2_cancelled = Some(matches!(outcome, Swap2Cancel::Cancelled));
+                    return Ok(result);
+                }
+            }
+        }
+    };
     let swap2_time = swap2_start.elapsed().as_millis();
 
-    println!("    Swap 2 confirmed: {} (gas used: {}, limit: {})",
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, tx_hash = %swap2_hash,
+        success = swap2_receipt.status(), gas_used = swap2_receipt.gas_used, gas_limit = swap2_gas_limit,
+        confirm_ms = swap2_time as u64,
+        "    Swap 2 confirmed: {} (gas used: {}, limit: {})",
         if swap2_receipt.status() { "SUCCESS" } else { "REVERTED" },
         swap2_receipt.gas_used,
         swap2_gas_limit);
+    if swap2_receipt.status() {
+        gas::record_gas_used(buy_router.name, swap2_receipt.gas_used);
+    }
+
+    // On a slippage revert swap 1's USDC is still in the wallet: retry once
+    // with wider slippage instead of leaving the position open
+    let mut swap2_revert_reason = None;
+    let mut retry: Option<Swap2Retry> = None;
+    if !swap2_receipt.status() {
+        if retry_slippage_bump_bps > 0 {
+            retry = retry_swap2(
+                provider_with_signer,
+                signer_address,
+                trade,
+                usdc_before,
+                slippage_bps + retry_slippage_bump_bps,
+                gas,
+            ).await;
+        }
+        // Decoded after the retry: the parent-block replay costs round trips
+        // while the USDC is exposed
+        let reason = decode_revert(provider_with_signer, swap2_hash).await
+            .unwrap_or_else(|| "unknown reason".to_string());
+        println!("    Swap 2 revert reason: {}", reason);
+        swap2_revert_reason = Some(reason);
+    }
+    let retry_receipt = retry.as_ref().and_then(|r| r.receipt.as_ref());
+    let leg2_success = swap2_receipt.status() || retry_receipt.is_some_and(|r| r.status());
+    // Only a first-attempt exact-input fill has a known input to compare a quote against
+    let swap2_usdc_in = (swap2_receipt.status() && !use_exact_output)
+        .then_some(actual_usdc_received * buy_router.usdc_spend_fraction());
 
     // ═══════════════════════════════════════════════════════════════════════
     // STEP 8: Query final balances and calculate actual P&L
     // ═══════════════════════════════════════════════════════════════════════
     println!("\n  Querying final balances...");
-    let usdc_final = query_usdc_balance(provider_with_signer, signer_address).await?;
-    let wmon_after_swap2 = query_wmon_balance(provider_with_signer, signer_address).await?;
+    let (usdc_final, wmon_after_swap2) = query_pair_balances(provider_with_signer, pair, signer_address).await?;
 
     let actual_wmon_received = wmon_after_swap2 - wmon_before;
     let usdc_dust = usdc_final - usdc_before;  // Should be ~0 if we used all USDC
@@ -600,17 +814,53 @@ pub async fn execute_fast_arb<P: Provider>(
     // STEP 9: Calculate gas costs and final result
     // ═══════════════════════════════════════════════════════════════════════
     let swap1_gas_cost = U256::from(swap1_gas_limit) * U256::from(swap1_receipt.effective_gas_price);
-    let swap2_gas_cost = U256::from(swap2_gas_limit) * U256::from(swap2_receipt.effective_gas_price);
+    let mut swap2_gas_cost = U256::from(swap2_gas_limit) * U256::from(swap2_receipt.effective_gas_price);
+    let mut total_gas_used = swap1_receipt.gas_used + swap2_receipt.gas_used;
+    let mut total_gas_estimated = swap1_gas_limit + swap2_gas_limit;
+    if let Some(retry) = &retry {
+        total_gas_estimated += retry.gas_limit;
+        if let Some(receipt) = &retry.receipt {
+            swap2_gas_cost += U256::from(retry.gas_limit) * U256::from(receipt.effective_gas_price);
+            total_gas_used += receipt.gas_used;
+        }
+    }
     let total_gas_cost_wei = swap1_gas_cost + swap2_gas_cost;
     let total_gas_cost_mon = total_gas_cost_wei.to::<u128>() as f64 / 1e18;
 
-    let both_success = swap1_receipt.status() && swap2_receipt.status();
-    let total_gas_used = swap1_receipt.gas_used + swap2_receipt.gas_used;
-    let total_gas_estimated = swap1_gas_limit + swap2_gas_limit;
+    let both_success = swap1_receipt.status() && leg2_success;
+    let error = if both_success {
+        None
+    } else {
+        // Swap 1 succeeded (checked above), so swap 2 is the one that reverted
+        let mut reason = swap2_revert_reason.unwrap_or_default();
+        match &retry {
+            Some(Swap2Retry { hash, receipt: Some(_), .. }) => {
+                let retry_reason = decode_revert(provider_with_signer, *hash).await
+                    .unwrap_or_else(|| "unknown reason".to_string());
+                println!("    Swap 2 retry revert reason: {}", retry_reason);
+                reason.push_str(&format!("; retry reverted: {}", retry_reason));
+            }
+            Some(Swap2Retry { hash, receipt: None, cancelled, .. }) => {
+                reason.push_str(&format!("; retry {:?} not confirmed ({})", hash,
+                    if *cancelled == Some(true) { "cancelled" } else { "may still fill" }));
+            }
+            None => {}
+        }
+        Some(ArbError::revert("Swap 2", reason))
+    };
+    let stranded_usdc = if leg2_success { 0.0 } else { usdc_dust.max(0.0) };
+    if stranded_usdc > 0.0 {
+        println!("    ⚠ STRANDED: {:.6} USDC from swap 1 still held - run Rebalance", stranded_usdc);
+    }
     let execution_time = total_start.elapsed().as_millis();
 
-    // Calculate profit from actual balance changes
-    let gross_profit = actual_wmon_received;
+    // Calculate profit from actual balance changes; with exactOutput the
+    // profit is the leftover USDC, valued at the buy price
+    let gross_profit = if use_exact_output && buy_price > 0.0 {
+        actual_wmon_received + usdc_dust / buy_price
+    } else {
+        actual_wmon_received
+    };
     let profit_bps = if amount > 0.0 {
         (gross_profit / amount * 10000.0) as i32
     } else {
@@ -619,8 +869,9 @@ pub async fn execute_fast_arb<P: Provider>(
 
     // Log gas efficiency
     let gas_efficiency = (total_gas_used as f64 / total_gas_estimated as f64) * 100.0;
-    println!("\n  GAS EFFICIENCY: {:.1}% (used {} of {} budgeted)",
-             gas_efficiency, total_gas_used, total_gas_estimated);
+    info!(target: EVENT_TARGET, pair = logging::pair(), gas_used = total_gas_used, gas_limit = total_gas_estimated,
+        "\n  GAS EFFICIENCY: {:.1}% (used {} of {} budgeted)",
+        gas_efficiency, total_gas_used, total_gas_estimated);
 
     let result = FastArbResult {
         swap1_tx_hash: format!("{:?}", swap1_hash),
@@ -630,7 +881,10 @@ pub async fn execute_fast_arb<P: Provider>(
         swap2_tx_hash: format!("{:?}", swap2_hash),
         swap2_gas_used: swap2_receipt.gas_used,
         swap2_gas_estimated: swap2_gas_limit,
-        swap2_success: swap2_receipt.status(),
+        swap2_success: leg2_success,
+        swap2_retry_tx_hash: retry.as_ref().map(|r| format!("{:?}", r.hash)),
+        stranded_usdc,
+        swap2_cancelled: retry.as_ref().and_then(|r| r.cancelled),
         wmon_in: amount,
         usdc_intermediate: actual_usdc_received,  // Now this is ACTUAL, not estimated
         wmon_out: actual_wmon_from_swap2,         // Now this is ACTUAL, not estimated
@@ -644,6 +898,9 @@ pub async fn execute_fast_arb<P: Provider>(
         swap2_slippage_bps,
         wmon_out_actual: Some(actual_wmon_from_swap2),
         estimation_error_bps: None,  // No longer relevant since we use actual values
+        swap2_usdc_in,
+        quoted_usdc_intermediate: None,
+        quoted_wmon_out: None,
         gross_profit_wmon: gross_profit,
         profit_bps,
         total_gas_cost_wei,
@@ -655,12 +912,568 @@ pub async fn execute_fast_arb<P: Provider>(
         swap2_time_ms: swap2_time,
         execution_time_ms: execution_time,
         success: both_success,
-        error: if both_success { None } else { Some("One or both swaps reverted".to_string()) },
+        error,
     };
 
     Ok(result)
 }
 
+/// How a swap 2 that outlived its receipt timeout ended
+enum Swap2Cancel {
+    /// Swap 2 itself took the nonce
+    Landed(Box<TransactionReceipt>),
+    /// The 0-value replacement took the nonce; swap 2 can no longer fill
+    Cancelled,
+    /// Neither confirmed: swap 2 may still fill later
+    Failed,
+}
+
+/// Replace a stuck swap 2 with a 0-value self-transfer at its nonce and
+/// `SWAP2_CANCEL_BUMP_PCT` higher fees
+async fn cancel_stuck_swap2<P: Provider>(
+    provider_with_signer: &P,
+    signer_address: Address,
+    nonce: u64,
+    swap2_hash: TxHash,
+    gas: GasFees,
+) -> Swap2Cancel {
+    if let Ok(Some(receipt)) = provider_with_signer.get_transaction_receipt(swap2_hash).await {
+        return Swap2Cancel::Landed(Box::new(receipt));
+    }
+
+    println!("    Cancelling swap 2 (nonce {}, fees +{}%)...", nonce, SWAP2_CANCEL_BUMP_PCT);
+    let tx = cancel_request(signer_address, nonce, gas.bumped(SWAP2_CANCEL_BUMP_PCT));
+    let failure = match timeout(Duration::from_secs(10), submit::send_transaction(provider_with_signer, tx)).await {
+        Ok(Ok((hash, _))) => match wait_for_receipt_fast(provider_with_signer, hash).await {
+            Ok(_) => {
+                info!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, nonce, tx_hash = %hash,
+                    "    Swap 2 cancelled: {:?} took nonce {}", hash, nonce);
+                return Swap2Cancel::Cancelled;
+            }
+            Err(e) => format!("replacement {:?} not confirmed: {}", hash, e),
+        },
+        Ok(Err(e)) => format!("replacement rejected: {}", e),
+        Err(_) => "replacement send timeout".to_string(),
+    };
+
+    // A rejected or unconfirmed replacement may mean swap 2 won the nonce
+    if let Ok(Some(receipt)) = provider_with_signer.get_transaction_receipt(swap2_hash).await {
+        return Swap2Cancel::Landed(Box::new(receipt));
+    }
+    tracing::warn!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, nonce, tx_hash = %swap2_hash,
+        "    Swap 2 cancel failed, it may still fill: {}", failure);
+    Swap2Cancel::Failed
+}
+
+/// A swap 2 retry that was sent
+struct Swap2Retry {
+    hash: TxHash,
+    gas_limit: u64,
+    /// None if it never confirmed
+    receipt: Option<TransactionReceipt>,
+    /// Unconfirmed retry: whether the replace-by-fee cancel took its nonce
+    cancelled: Option<bool>,
+}
+
+/// Second swap 2 attempt: exactInput with the USDC still held from swap 1
+/// (balance above `usdc_before`) at `slippage_bps`. None if nothing was sent.
+///
+/// Only retried if the swap fills with no min-out at `latest`, i.e. the price
+/// moved. The revert reason can't decide this: replaying the reverted TX on
+/// its parent block misses a race lost to a TX earlier in the same block.
+/// A retry that outlives its receipt timeout is cancelled like swap 2.
+async fn retry_swap2<P: Provider>(
+    provider_with_signer: &P,
+    signer_address: Address,
+    trade: FastArbTrade<'_>,
+    usdc_before: f64,
+    slippage_bps: u32,
+    gas: GasFees,
+) -> Option<Swap2Retry> {
+    let FastArbTrade { pair, buy_router, buy_price, .. } = trade;
+    let usdc_now = query_quote_balance(provider_with_signer, pair, signer_address).await.ok()?;
+    let usdc_in = (usdc_now - usdc_before) * buy_router.usdc_spend_fraction();
+    if usdc_in <= 0.0 || buy_price <= 0.0 {
+        println!("    No USDC left from swap 1 to retry with");
+        return None;
+    }
+    let min_wmon_out = usdc_in / buy_price * (1.0 - slippage_bps as f64 / 10000.0);
+    let usdc_in_wei = to_wei(usdc_in, pair.quote_decimals);
+
+    let probe = build_fast_swap_tx(buy_router, pair, SwapDirection::Buy, usdc_in_wei, U256::ZERO, signer_address).ok()?;
+    if let Err(reason) = simulate_swap(provider_with_signer, buy_router.address, signer_address, &probe).await {
+        println!("    Not retrying swap 2: it reverts even with no min-out ({})", reason);
+        return None;
+    }
+
+    println!("\n  Retrying swap 2 with {}bps slippage:", slippage_bps);
+    println!("    USDC In: {:.6} | Min WMON out: {:.6}", usdc_in, min_wmon_out);
+
+    let calldata = build_fast_swap_tx(
+        buy_router,
+        pair,
+        SwapDirection::Buy,
+        usdc_in_wei,
+        to_wei(min_wmon_out, pair.base_decimals),
+        signer_address,
+    ).ok()?;
+    let gas_limit = estimate_gas_with_buffer(
+        provider_with_signer,
+        buy_router,
+        signer_address,
+        &calldata,
+    ).await;
+    let nonce = next_nonce_for(signer_address);
+    let tx = build_swap_request(buy_router, signer_address, calldata, gas_limit, nonce, gas);
+
+    let sent = timeout(Duration::from_secs(10), submit::send_with_nonce_recovery(provider_with_signer, tx.clone())).await;
+    let hash = match sent {
+        Ok(Ok((hash, _))) => hash,
+        Ok(Err(e)) => {
+            println!("    Swap 2 retry send failed: {}", e);
+            submit::release_unsent_nonce(provider_with_signer, &tx).await;
+            return None;
+        }
+        Err(_) => {
+            println!("    Swap 2 retry send timeout");
+            submit::release_unsent_nonce(provider_with_signer, &tx).await;
+            return None;
+        }
+    };
+    let receipt = match wait_for_receipt_fast(provider_with_signer, hash).await {
+        Ok(receipt) => receipt,
+        Err(e) => {
+            println!("    Swap 2 retry {:?} not confirmed: {}", hash, e);
+            match cancel_stuck_swap2(provider_with_signer, signer_address, nonce, hash, gas).await {
+                Swap2Cancel::Landed(receipt) => {
+                    println!("    Swap 2 retry mined before the cancel, continuing");
+                    *receipt
+                }
+                outcome => {
+                    let cancelled = Some(matches!(outcome, Swap2Cancel::Cancelled));
+                    return Some(Swap2Retry { hash, gas_limit, receipt: None, cancelled });
+                }
+            }
+        }
+    };
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, retry = true, tx_hash = %hash,
+        success = receipt.status(), gas_used = receipt.gas_used, gas_limit = gas_limit,
+        "    Swap 2 retry confirmed: {} (gas used: {}, limit: {})",
+        if receipt.status() { "SUCCESS" } else { "REVERTED" }, receipt.gas_used, gas_limit);
+    Some(Swap2Retry { hash, gas_limit, receipt: Some(receipt), cancelled: None })
+}
+
+/// Submit/confirm timing of one leg sent by `execute_fast_arb_parallel`
+#[derive(Debug, Clone)]
+pub struct PendingLegResult {
+    pub leg: u8,
+    pub tx_hash: String,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+    pub success: bool,
+    /// send_transaction round trip
+    pub submit_ms: u128,
+    /// From submit until the receipt was seen
+    pub confirm_ms: u128,
+    pub error: Option<String>,
+}
+
+impl PendingLegResult {
+    fn unsent(leg: u8, nonce: u64, gas_limit: u64, submit_ms: u128, error: String) -> Self {
+        Self {
+            leg,
+            tx_hash: String::new(),
+            nonce,
+            gas_limit,
+            gas_used: 0,
+            effective_gas_price: 0,
+            success: false,
+            submit_ms,
+            confirm_ms: 0,
+            error: Some(error),
+        }
+    }
+
+    /// Monad charges the full gas limit
+    fn gas_cost(&self) -> U256 {
+        U256::from(self.gas_limit) * U256::from(self.effective_gas_price)
+    }
+}
+
+/// USDC the buy leg spends, if the wallet already holds it (parallel mode
+/// buys with inventory instead of waiting for swap 1's output)
+pub fn parallel_buy_amount(amount: f64, sell_price: f64, usdc_balance: f64) -> Option<f64> {
+    let usdc_in = amount * sell_price;
+    (usdc_in > 0.0 && usdc_balance >= usdc_in).then_some(usdc_in)
+}
+
+/// Send one leg (with nonce-too-low recovery), returning its hash and the submit latency
+async fn submit_leg<P: Provider>(
+    provider: &P,
+    tx: alloy::rpc::types::TransactionRequest,
+) -> (std::result::Result<TxHash, String>, u128) {
+    let start = std::time::Instant::now();
+    let sent = match timeout(Duration::from_secs(10), submit::send_with_nonce_recovery(provider, tx)).await {
+        Ok(Ok((hash, _))) => Ok(hash),
+        Ok(Err(e)) => Err(format!("send failed: {}", e)),
+        Err(_) => Err("send timeout".to_string()),
+    };
+    (sent, start.elapsed().as_millis())
+}
+
+/// Wait for a submitted leg and fill in its receipt fields
+async fn confirm_leg<P: Provider>(
+    provider: &P,
+    leg: u8,
+    nonce: u64,
+    gas_limit: u64,
+    tx_hash: TxHash,
+    submit_ms: u128,
+    sent_at: std::time::Instant,
+) -> PendingLegResult {
+    let receipt = wait_for_receipt_fast(provider, tx_hash).await;
+    let confirm_ms = sent_at.elapsed().as_millis();
+
+    let mut result = PendingLegResult {
+        leg,
+        tx_hash: format!("{:?}", tx_hash),
+        nonce,
+        gas_limit,
+        gas_used: 0,
+        effective_gas_price: 0,
+        success: false,
+        submit_ms,
+        confirm_ms,
+        error: None,
+    };
+    match receipt {
+        Ok(receipt) => {
+            result.gas_used = receipt.gas_used;
+            result.effective_gas_price = receipt.effective_gas_price;
+            result.success = receipt.status();
+            if !result.success {
+                let reason = decode_revert(provider, tx_hash).await
+                    .unwrap_or_else(|| "unknown reason".to_string());
+                result.error = Some(format!("reverted: {}", reason));
+            }
+        }
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result
+}
+
+/// Execute both legs at once when the wallet's USDC can fund the buy leg
+///
+/// Swap 2 spends `amount * sell_price` USDC from inventory rather than swap 1's
+/// output, so both TXs go out back-to-back with nonces N and N+1 and the
+/// receipts are awaited together. Falls back to sequential `execute_fast_arb`
+/// (with all of `opts`) when the USDC balance is short; the parallel legs use
+/// only its slippage, gas and simulate settings.
+///
+/// Returns the usual `FastArbResult` plus per-leg submit/confirm timings
+/// (empty when it fell back).
+pub async fn execute_fast_arb_parallel<P: Provider>(
+    provider_with_signer: &P,
+    signer_address: Address,
+    trade: FastArbTrade<'_>,
+    opts: FastArbOptions,
+) -> ArbResult<(FastArbResult, Vec<PendingLegResult>)> {
+    let FastArbTrade { pair, sell_router, buy_router, amount, sell_price, buy_price } = trade;
+    let FastArbOptions { slippage_bps, gas, simulate, .. } = opts;
+    let total_start = std::time::Instant::now();
+
+    println!("  Querying initial balances...");
+    let (usdc_before, wmon_before) = query_pair_balances(provider_with_signer, pair, signer_address).await?;
+    println!("    USDC before: {:.6}", usdc_before);
+    println!("    WMON before: {:.6}", wmon_before);
+
+    let Some(usdc_in) = parallel_buy_amount(amount, sell_price, usdc_before) else {
+        println!("  USDC inventory can't fund the buy leg ({:.6} < {:.6}), running sequentially",
+                 usdc_before, amount * sell_price);
+        let result = execute_fast_arb(provider_with_signer, signer_address, trade, opts).await?;
+        return Ok((result, Vec::new()));
+    };
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Build both legs up front: each is sized from inventory, not the other's output
+    // ═══════════════════════════════════════════════════════════════════════
+    let slippage_multiplier = 1.0 - (slippage_bps as f64 / 10000.0);
+    let expected_usdc = amount * sell_price;
+    let min_usdc_out = expected_usdc * slippage_multiplier;
+    let expected_wmon_back = if buy_price > 0.0 { usdc_in / buy_price } else { 0.0 };
+    let min_wmon_out = expected_wmon_back * slippage_multiplier;
+
+    println!("\n  Leg 1 (Sell WMON -> USDC): {:.6} WMON in, min {:.6} USDC out", amount, min_usdc_out);
+    println!("  Leg 2 (Buy USDC -> WMON):  {:.6} USDC in, min {:.6} WMON out (from inventory)", usdc_in, min_wmon_out);
+
+    let swap1_calldata = build_fast_swap_tx(
+        sell_router,
+        pair,
+        SwapDirection::Sell,
+        to_wei(amount, pair.base_decimals),
+        to_wei(min_usdc_out, pair.quote_decimals),
+        signer_address,
+    )?;
+    let swap2_calldata = build_fast_swap_tx(
+        buy_router,
+        pair,
+        SwapDirection::Buy,
+        to_wei(usdc_in, pair.quote_decimals),
+        to_wei(min_wmon_out, pair.base_decimals),
+        signer_address,
+    )?;
+    let swap1_calldata = apply_price_limit(sell_router, pair, pair.base, sell_price, slippage_bps, swap1_calldata)?;
+    let swap2_calldata = apply_price_limit(buy_router, pair, pair.quote, buy_price, slippage_bps, swap2_calldata)?;
+
+    if simulate {
+        println!("\n  Simulating both legs...");
+        let (sim1, sim2) = tokio::join!(
+            simulate_swap(provider_with_signer, sell_router.address, signer_address, &swap1_calldata),
+            simulate_swap(provider_with_signer, buy_router.address, signer_address, &swap2_calldata),
+        );
+        if let Some((leg, reason)) = sim1.err().map(|r| (1, r)).or(sim2.err().map(|r| (2, r))) {
+            println!("    ✗ Simulation reverted: {}", reason);
+            let result = create_error_result(
+                amount, usdc_before, wmon_before, 0, 0,
+                total_start.elapsed().as_millis(),
+                ArbError::revert(format!("Swap {} simulation", leg), reason),
+            );
+            return Ok((result, Vec::new()));
+        }
+        println!("    ✓ Simulation passed");
+    }
+
+    println!("\n  Estimating gas for both legs...");
+    let (swap1_gas_limit, swap2_gas_limit) = tokio::join!(
+        estimate_gas_with_buffer(provider_with_signer, sell_router, signer_address, &swap1_calldata),
+        estimate_gas_with_buffer(provider_with_signer, buy_router, signer_address, &swap2_calldata),
+    );
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Send back-to-back with nonces N, N+1. N+1 is only taken once swap 1 is
+    // accepted, and a leg that fails to send hands its nonce back: a reserved
+    // but unsent nonce would stall every later TX
+    // ═══════════════════════════════════════════════════════════════════════
+    let swap1_nonce = next_nonce_for(signer_address);
+    let swap1_tx = build_swap_request(sell_router, signer_address, swap1_calldata, swap1_gas_limit, swap1_nonce, gas);
+
+    println!("\n  Sending both legs (swap 1 nonce {})...", swap1_nonce);
+    let swap1_sent_at = std::time::Instant::now();
+    let (swap1_sent, swap1_submit_ms) = submit_leg(provider_with_signer, swap1_tx.clone()).await;
+    let swap1_hash = match swap1_sent {
+        Ok(hash) => hash,
+        Err(e) => {
+            // Swap 1 never went out, so swap 2 isn't sent and takes no nonce
+            submit::release_unsent_nonce(provider_with_signer, &swap1_tx).await;
+            let result = create_error_result(
+                amount, usdc_before, wmon_before, swap1_gas_limit, swap2_gas_limit,
+                total_start.elapsed().as_millis(),
+                ArbError::from_message(format!("Swap 1 {}", e)),
+            );
+            let legs = vec![PendingLegResult::unsent(1, swap1_nonce, swap1_gas_limit, swap1_submit_ms, e)];
+            return Ok((result, legs));
+        }
+    };
+    let swap2_nonce = next_nonce_for(signer_address);
+    let swap2_tx = build_swap_request(buy_router, signer_address, swap2_calldata, swap2_gas_limit, swap2_nonce, gas);
+    let swap2_sent_at = std::time::Instant::now();
+    let (swap2_sent, swap2_submit_ms) = submit_leg(provider_with_signer, swap2_tx.clone()).await;
+    info!(target: EVENT_TARGET, pair = logging::pair(), leg = 1, tx_hash = %swap1_hash, submit_ms = swap1_submit_ms as u64,
+        "    Swap 1 sent: {:?} ({}ms)", swap1_hash, swap1_submit_ms);
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Join on both receipts
+    // ═══════════════════════════════════════════════════════════════════════
+    let (leg1, leg2) = match swap2_sent {
+        Ok(swap2_hash) => {
+            info!(target: EVENT_TARGET, pair = logging::pair(), leg = 2, tx_hash = %swap2_hash, submit_ms = swap2_submit_ms as u64,
+                "    Swap 2 sent: {:?} ({}ms)", swap2_hash, swap2_submit_ms);
+            println!("  Waiting for both confirmations...");
+            tokio::join!(
+                confirm_leg(provider_with_signer, 1, swap1_nonce, swap1_gas_limit, swap1_hash, swap1_submit_ms, swap1_sent_at),
+                confirm_leg(provider_with_signer, 2, swap2_nonce, swap2_gas_limit, swap2_hash, swap2_submit_ms, swap2_sent_at),
+            )
+        }
+        Err(e) => {
+            println!("    Swap 2 {}", e);
+            submit::release_unsent_nonce(provider_with_signer, &swap2_tx).await;
+            println!("  Waiting for swap 1 confirmation...");
+            let leg1 = confirm_leg(provider_with_signer, 1, swap1_nonce, swap1_gas_limit, swap1_hash, swap1_submit_ms, swap1_sent_at).await;
+            (leg1, PendingLegResult::unsent(2, swap2_nonce, swap2_gas_limit, swap2_submit_ms, e))
+        }
+    };
+    if leg1.success {
+        gas::record_gas_used(sell_router.name, leg1.gas_used);
+    }
+    if leg2.success {
+        gas::record_gas_used(buy_router.name, leg2.gas_used);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Final balances: leg 1 adds USDC and leg 2 spends it, so attribute each
+    // leg's flow by undoing the other's known input
+    // ═══════════════════════════════════════════════════════════════════════
+    println!("\n  Querying final balances...");
+    let (usdc_final, wmon_final) = query_pair_balances(provider_with_signer, pair, signer_address).await?;
+    let usdc_delta = usdc_final - usdc_before;
+    let wmon_delta = wmon_final - wmon_before;
+
+    let actual_usdc_received = if leg1.success {
+        usdc_delta + if leg2.success { usdc_in } else { 0.0 }
+    } else {
+        0.0
+    };
+    let wmon_from_swap2 = if leg2.success {
+        wmon_delta + if leg1.success { amount } else { 0.0 }
+    } else {
+        0.0
+    };
+    let swap1_slippage_bps = if leg1.success && expected_usdc > 0.0 {
+        ((expected_usdc - actual_usdc_received) / expected_usdc * 10000.0) as i32
+    } else {
+        0
+    };
+    let swap2_slippage_bps = if leg2.success && expected_wmon_back > 0.0 {
+        ((expected_wmon_back - wmon_from_swap2) / expected_wmon_back * 10000.0) as i32
+    } else {
+        0
+    };
+
+    // P&L is split across both tokens; value the USDC side at the buy price
+    let gross_profit = if buy_price > 0.0 { wmon_delta + usdc_delta / buy_price } else { wmon_delta };
+    let profit_bps = if amount > 0.0 { (gross_profit / amount * 10000.0) as i32 } else { 0 };
+
+    println!("    USDC: {:.6} -> {:.6} ({:+.6})", usdc_before, usdc_final, usdc_delta);
+    println!("    WMON: {:.6} -> {:.6} ({:+.6})", wmon_before, wmon_final, wmon_delta);
+
+    let total_gas_cost_wei = leg1.gas_cost() + leg2.gas_cost();
+    let success = leg1.success && leg2.success;
+    let error = [&leg1, &leg2]
+        .iter()
+        .find_map(|leg| leg.error.as_ref().map(|e| match e.strip_prefix("reverted: ") {
+            Some(reason) => ArbError::revert(format!("Swap {}", leg.leg), reason),
+            None => ArbError::from_message(format!("Swap {} {}", leg.leg, e)),
+        }));
+    let execution_time = total_start.elapsed().as_millis();
+
+    let result = FastArbResult {
+        swap1_tx_hash: leg1.tx_hash.clone(),
+        swap1_gas_used: leg1.gas_used,
+        swap1_gas_estimated: swap1_gas_limit,
+        swap1_success: leg1.success,
+        swap2_tx_hash: leg2.tx_hash.clone(),
+        swap2_gas_used: leg2.gas_used,
+        swap2_gas_estimated: swap2_gas_limit,
+        swap2_success: leg2.success,
+        swap2_retry_tx_hash: None,
+        stranded_usdc: 0.0,
+        swap2_cancelled: None,
+        wmon_in: amount,
+        usdc_intermediate: actual_usdc_received,
+        wmon_out: wmon_from_swap2,
+        usdc_before,
+        usdc_after_swap1: usdc_before + actual_usdc_received,
+        wmon_before,
+        wmon_after_swap2: wmon_final,
+        actual_usdc_received,
+        actual_wmon_received: wmon_delta,
+        swap1_slippage_bps,
+        swap2_slippage_bps,
+        wmon_out_actual: Some(wmon_from_swap2),
+        estimation_error_bps: None,
+        swap2_usdc_in: None,
+        quoted_usdc_intermediate: None,
+        quoted_wmon_out: None,
+        gross_profit_wmon: gross_profit,
+        profit_bps,
+        total_gas_cost_wei,
+        total_gas_cost_mon: total_gas_cost_wei.to::<u128>() as f64 / 1e18,
+        total_gas_used: leg1.gas_used + leg2.gas_used,
+        total_gas_estimated: swap1_gas_limit + swap2_gas_limit,
+        total_time_ms: execution_time,
+        swap1_time_ms: leg1.confirm_ms,
+        swap2_time_ms: leg2.confirm_ms,
+        execution_time_ms: execution_time,
+        success,
+        error,
+    };
+
+    Ok((result, vec![leg1, leg2]))
+}
+
+/// Swap 1's confirmed outcome, carried into results where swap 2 never filled
+struct Swap1Outcome {
+    wmon_in: f64,
+    usdc_before: f64,
+    wmon_before: f64,
+    tx_hash: TxHash,
+    gas_used: u64,
+    gas_limit: u64,
+    effective_gas_price: u128,
+    time_ms: u128,
+    usdc_after: f64,
+    usdc_received: f64,
+    slippage_bps: i32,
+}
+
+/// Result for swap 1 confirmed but swap 2 not filled (send failed, send or
+/// confirmation timed out): swap 1's USDC is still held
+fn swap2_not_filled_result(
+    leg1: &Swap1Outcome,
+    wmon_after: f64,
+    swap2_gas_estimated: u64,
+    swap2_tx_hash: String,
+    elapsed_ms: u128,
+    error: ArbError,
+) -> FastArbResult {
+    let swap1_gas_cost = U256::from(leg1.gas_limit) * U256::from(leg1.effective_gas_price);
+    let wmon_delta = wmon_after - leg1.wmon_before;
+
+    FastArbResult {
+        swap1_tx_hash: format!("{:?}", leg1.tx_hash),
+        swap1_gas_used: leg1.gas_used,
+        swap1_gas_estimated: leg1.gas_limit,
+        swap1_success: true,
+        swap2_tx_hash,
+        swap2_gas_used: 0,
+        swap2_gas_estimated,
+        swap2_success: false,
+        swap2_retry_tx_hash: None,
+        stranded_usdc: leg1.usdc_received.max(0.0),
+        swap2_cancelled: None,
+        wmon_in: leg1.wmon_in,
+        usdc_intermediate: leg1.usdc_received,
+        wmon_out: 0.0,
+        usdc_before: leg1.usdc_before,
+        usdc_after_swap1: leg1.usdc_after,
+        wmon_before: leg1.wmon_before,
+        wmon_after_swap2: wmon_after,
+        actual_usdc_received: leg1.usdc_received,
+        actual_wmon_received: wmon_delta,
+        swap1_slippage_bps: leg1.slippage_bps,
+        swap2_slippage_bps: 0,
+        wmon_out_actual: Some(0.0),
+        estimation_error_bps: None,
+        swap2_usdc_in: None,
+        quoted_usdc_intermediate: None,
+        quoted_wmon_out: None,
+        gross_profit_wmon: wmon_delta,
+        profit_bps: if leg1.wmon_in > 0.0 { (wmon_delta / leg1.wmon_in * 10000.0) as i32 } else { 0 },
+        total_gas_cost_wei: swap1_gas_cost,
+        total_gas_cost_mon: swap1_gas_cost.to::<u128>() as f64 / 1e18,
+        total_gas_used: leg1.gas_used,
+        total_gas_estimated: leg1.gas_limit,
+        total_time_ms: elapsed_ms,
+        swap1_time_ms: leg1.time_ms,
+        swap2_time_ms: 0,
+        execution_time_ms: elapsed_ms,
+        success: false,
+        error: Some(error),
+    }
+}
+
 /// Helper to create an error result with the new fields
 fn create_error_result(
     amount: f64,
@@ -669,7 +1482,7 @@ fn create_error_result(
     swap1_gas_estimated: u64,
     swap2_gas_estimated: u64,
     elapsed_ms: u128,
-    error_msg: String,
+    error: ArbError,
 ) -> FastArbResult {
     FastArbResult {
         swap1_tx_hash: String::new(),
@@ -680,6 +1493,9 @@ fn create_error_result(
         swap2_gas_used: 0,
         swap2_gas_estimated,
         swap2_success: false,
+        swap2_retry_tx_hash: None,
+        stranded_usdc: 0.0,
+        swap2_cancelled: None,
         wmon_in: amount,
         usdc_intermediate: 0.0,
         wmon_out: 0.0,
@@ -693,6 +1509,9 @@ fn create_error_result(
         swap2_slippage_bps: 0,
         wmon_out_actual: None,
         estimation_error_bps: None,
+        swap2_usdc_in: None,
+        quoted_usdc_intermediate: None,
+        quoted_wmon_out: None,
         gross_profit_wmon: 0.0,
         profit_bps: 0,
         total_gas_cost_wei: U256::ZERO,
@@ -704,7 +1523,7 @@ fn create_error_result(
         swap2_time_ms: 0,
         execution_time_ms: elapsed_ms,
         success: false,
-        error: Some(error_msg),
+        error: Some(error),
     }
 }
 
@@ -730,6 +1549,14 @@ pub fn print_fast_arb_result(result: &FastArbResult, sell_dex: &str, buy_dex: &s
     println!("  SWAP 2 (Buy on {}):", buy_dex);
     println!("    Status:       {}", if result.swap2_success { "SUCCESS" } else { "REVERTED" });
     println!("    TX:           {}", result.swap2_tx_hash);
+    if let Some(hash) = &result.swap2_retry_tx_hash {
+        println!("    Retry TX:     {}", hash);
+    }
+    match result.swap2_cancelled {
+        Some(true) => println!("    Cancelled:    replaced at its nonce, cannot fill later"),
+        Some(false) => println!("    \x1b[1;31mCancel FAILED:  swap 2 may still fill later\x1b[0m"),
+        None => {}
+    }
     println!("    Gas Used:     {}", result.swap2_gas_used);
     println!("    Gas Limit:    {} (CHARGED on Monad!)", result.swap2_gas_estimated);
     println!("    Slippage:     {} bps", result.swap2_slippage_bps);
@@ -745,6 +1572,9 @@ pub fn print_fast_arb_result(result: &FastArbResult, sell_dex: &str, buy_dex: &s
     println!("    USDC after S1:   {:>12.6}", result.usdc_after_swap1);
     println!("    WMON before:     {:>12.6}", result.wmon_before);
     println!("    WMON after S2:   {:>12.6}", result.wmon_after_swap2);
+    if result.stranded_usdc > 0.0 {
+        println!("    \x1b[1;31mSTRANDED USDC:   {:>12.6} (run Rebalance)\x1b[0m", result.stranded_usdc);
+    }
     println!();
     println!("  PROFIT/LOSS (from actual balance change):");
     let profit_color = if result.gross_profit_wmon >= 0.0 { "32" } else { "31" };
@@ -766,19 +1596,269 @@ pub fn print_fast_arb_result(result: &FastArbResult, sell_dex: &str, buy_dex: &s
     println!("    Total Time:      {}ms", result.total_time_ms);
     println!();
 
-    if result.success {
-        if result.gross_profit_wmon > 0.0 {
-            println!("  \x1b[1;32mARBITRAGE SUCCESSFUL\x1b[0m");
-        } else {
-            println!("  \x1b[1;33mARBITRAGE COMPLETED (unprofitable)\x1b[0m");
-        }
-    } else {
-        println!("  \x1b[1;31mARBITRAGE FAILED\x1b[0m");
-        if let Some(ref err) = result.error {
-            println!("  Error: {}", err);
-        }
+    let (outcome, banner) = match (result.success, result.gross_profit_wmon > 0.0) {
+        (true, true) => ("success", "\x1b[1;32mARBITRAGE SUCCESSFUL\x1b[0m"),
+        (true, false) => ("unprofitable", "\x1b[1;33mARBITRAGE COMPLETED (unprofitable)\x1b[0m"),
+        (false, _) => ("failed", "\x1b[1;31mARBITRAGE FAILED\x1b[0m"),
+    };
+    info!(target: EVENT_TARGET, pair = logging::pair(), sell_dex, buy_dex, outcome,
+        net_bps = result.profit_bps, profit_wmon = result.gross_profit_wmon, gas_mon = result.total_gas_cost_mon,
+        tx_hash = %result.swap1_tx_hash, swap2_tx_hash = %result.swap2_tx_hash,
+        total_ms = result.total_time_ms as u64, error = result.error.as_ref().map(tracing::field::display),
+        error_kind = result.error.as_ref().map(ArbError::kind),
+        "  {}", banner);
+    if let (false, Some(err)) = (result.success, &result.error) {
+        println!("  Error: {}", err);
     }
 
     println!();
     println!("═══════════════════════════════════════════════════════════════");
-}
\ No newline at end of file
+}
+
+/// Per-leg latencies of a parallel fast arb
+pub fn print_pending_legs(legs: &[PendingLegResult]) {
+    if legs.is_empty() {
+        return;
+    }
+    println!("  LEG TIMING (parallel):");
+    for leg in legs {
+        info!(target: EVENT_TARGET, pair = logging::pair(), leg = leg.leg, nonce = leg.nonce, tx_hash = %leg.tx_hash,
+            submit_ms = leg.submit_ms as u64, confirm_ms = leg.confirm_ms as u64, success = leg.success,
+            "    Leg {} (nonce {}): submit {}ms | confirm {}ms | {}",
+            leg.leg,
+            leg.nonce,
+            leg.submit_ms,
+            leg.confirm_ms,
+            match (&leg.error, leg.success) {
+                (_, true) => "SUCCESS".to_string(),
+                (Some(e), false) => e.clone(),
+                (None, false) => "FAILED".to_string(),
+            });
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{get_router_by_name, get_token_pair};
+    use crate::execution::mock_provider::{MockLeg, MockProvider, MOCK_GAS_PRICE};
+
+    #[test]
+    fn test_swap_request_honors_gas_mode() {
+        let mut router = get_router_by_name("Uniswap").unwrap();
+        assert_eq!(router.gas_mode, GasMode::Eip1559);
+        let gas = GasFees { max_fee: 110, priority_fee: 10 };
+
+        let tx = build_swap_request(&router, Address::ZERO, Bytes::new(), 21_000, 7, gas);
+        assert_eq!((tx.max_fee_per_gas, tx.max_priority_fee_per_gas, tx.gas_price), (Some(110), Some(10), None));
+
+        router.gas_mode = GasMode::Legacy;
+        let tx = build_swap_request(&router, Address::ZERO, Bytes::new(), 21_000, 7, gas);
+        assert_eq!((tx.max_fee_per_gas, tx.max_priority_fee_per_gas, tx.gas_price), (None, None, Some(110)));
+        assert_eq!((tx.nonce, tx.gas, tx.chain_id), (Some(7), Some(21_000), Some(MONAD_CHAIN_ID)));
+    }
+
+    #[test]
+    fn test_parallel_buy_amount_requires_inventory() {
+        // 100 WMON at 0.03 needs 3 USDC on hand
+        assert_eq!(parallel_buy_amount(100.0, 0.03, 5.0), Some(3.0));
+        assert_eq!(parallel_buy_amount(100.0, 0.03, 2.99), None);
+        assert_eq!(parallel_buy_amount(0.0, 0.03, 5.0), None);
+    }
+
+    // Replays: sell 100 WMON on Uniswap at 0.03, buy back on LFJ (exactInput) at 0.0298
+    const GAS_ESTIMATE: u64 = 200_000;
+    const GAS_LIMIT: u64 = GAS_ESTIMATE * (100 + GAS_BUFFER_PERCENT) / 100;
+
+    async fn replay(wallet_byte: u8, legs: Vec<MockLeg>) -> (MockProvider, FastArbResult) {
+        replay_with(wallet_byte, legs, None, 0).await
+    }
+
+    async fn replay_with(
+        wallet_byte: u8,
+        legs: Vec<MockLeg>,
+        deadline: Option<std::time::Instant>,
+        retry_slippage_bump_bps: u32,
+    ) -> (MockProvider, FastArbResult) {
+        let pair = get_token_pair("WMON-USDC").unwrap();
+        let (sell, buy) = (get_router_by_name("Uniswap").unwrap(), get_router_by_name("LFJ").unwrap());
+        let wallet = Address::repeat_byte(wallet_byte);
+        let provider = MockProvider::new(&pair, 500.0, 10.0, GAS_ESTIMATE, legs);
+        provider.init_wallet(wallet).await;
+        let gas = GasFees { max_fee: MOCK_GAS_PRICE, priority_fee: 0 };
+
+        let trade = FastArbTrade { pair: &pair, sell_router: &sell, buy_router: &buy, amount: 100.0, sell_price: 0.03, buy_price: 0.0298 };
+        let opts = FastArbOptions {
+            slippage_bps: 100,
+            gas,
+            simulate: false,
+            retry_slippage_bump_bps,
+            max_adaptive_slippage_bps: None,
+            deadline,
+        };
+        let result = execute_fast_arb(&provider, wallet, trade, opts)
+            .await
+            .unwrap();
+        (provider, result)
+    }
+
+    #[tokio::test]
+    async fn test_fast_arb_replay_happy_path() {
+        // Swap 1 fills 100 bps under the 3.0 USDC expected; swap 2 spends the USDC (minus 0.1% dust buffer)
+        let (provider, result) = replay(0xA1, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::Fill { base_delta: 99.6, quote_delta: -2.97 * 0.999, gas_used: 160_000 },
+        ]).await;
+
+        assert!(result.success && result.swap1_success && result.swap2_success, "{:?}", result.error);
+        assert_eq!(provider.sent().len(), 2);
+        assert!((result.actual_usdc_received - 2.97).abs() < 1e-5);
+        // Positive = received less than expected
+        assert!((result.swap1_slippage_bps - 100).abs() <= 1);
+        assert!(result.swap2_slippage_bps < 0, "99.6 WMON beat the expected {:.4}", 2.97 * 0.999 / 0.0298);
+        assert!((result.wmon_out - 99.6).abs() < 1e-6);
+        assert!((result.gross_profit_wmon + 0.4).abs() < 1e-6);
+        assert!((result.profit_bps + 40).abs() <= 1);
+        assert_eq!((result.total_gas_used, result.total_gas_estimated), (310_000, 2 * GAS_LIMIT));
+        assert_eq!(result.total_gas_cost_wei, U256::from(2 * GAS_LIMIT) * U256::from(MOCK_GAS_PRICE));
+        assert_eq!(result.stranded_usdc, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_fast_arb_replay_swap1_revert() {
+        let (provider, result) = replay(0xA2, vec![MockLeg::Revert { gas_used: 40_000 }]).await;
+
+        assert!(!result.success && !result.swap1_success && !result.swap2_success);
+        assert_eq!(provider.sent().len(), 1);
+        assert!(result.swap2_tx_hash.is_empty());
+        assert!(matches!(&result.error, Some(ArbError::Revert { op, .. }) if op == "Swap 1"));
+        // Charged the full limit, nothing else moved
+        assert_eq!(result.total_gas_cost_wei, U256::from(GAS_LIMIT) * U256::from(MOCK_GAS_PRICE));
+        assert_eq!((result.gross_profit_wmon, result.profit_bps), (0.0, 0));
+        assert_eq!(provider.balance(get_token_pair("WMON-USDC").unwrap().base), 500.0);
+    }
+
+    // Paused clock: the 15s receipt timeout elapses instantly, without touching
+    // the process-wide --receipt-* overrides other tests run under
+    #[tokio::test(start_paused = true)]
+    async fn test_fast_arb_replay_swap2_timeout() {
+        let (provider, result) = replay(0xA3, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::NeverMined,
+        ]).await;
+
+        assert!(!result.success && result.swap1_success && !result.swap2_success);
+        assert_eq!(provider.sent().len(), 2);
+        assert!(!result.swap2_tx_hash.is_empty());
+        assert!(matches!(&result.error, Some(ArbError::RpcTimeout(m)) if m.contains("confirmation timeout")));
+        // Swap 1's USDC is exposed until swap 2 lands or is rebalanced
+        assert!((result.stranded_usdc - 2.97).abs() < 1e-5);
+        assert!((result.gross_profit_wmon + 100.0).abs() < 1e-6);
+        assert_eq!(result.profit_bps, -10000);
+        assert_eq!(result.total_gas_estimated, GAS_LIMIT);
+        // The node refused the replacement (no leg scripted for it)
+        assert_eq!(result.swap2_cancelled, Some(false));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fast_arb_replay_cancels_stuck_swap2() {
+        let (provider, result) = replay(0xA5, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::NeverMined,
+            MockLeg::Fill { base_delta: 0.0, quote_delta: 0.0, gas_used: 21_000 },
+        ]).await;
+
+        assert!(!result.success && result.swap1_success && !result.swap2_success);
+        assert_eq!(result.swap2_cancelled, Some(true));
+        let sent = provider.sent();
+        assert_eq!(sent.len(), 3);
+        // Same nonce as swap 2, strictly higher fees, 0-value self-transfer
+        assert_eq!(sent[2].nonce, sent[1].nonce);
+        assert!(sent[2].max_fee_per_gas > sent[1].max_fee_per_gas);
+        assert_eq!(sent[2].to, sent[2].from.map(Into::into));
+        assert!((result.stranded_usdc - 2.97).abs() < 1e-5);
+    }
+
+    #[tokio::test]
+    async fn test_fast_arb_skips_swap2_past_latency_budget() {
+        let (provider, result) = replay_with(0xA4, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+        ], Some(std::time::Instant::now()), 0).await;
+
+        assert!(!result.success && result.swap1_success && !result.swap2_success);
+        assert_eq!(provider.sent().len(), 1);
+        assert!(matches!(&result.error, Some(ArbError::PriceStale(m)) if m.starts_with("Latency budget exceeded")));
+        assert!((result.stranded_usdc - 2.97).abs() < 1e-5);
+        assert!((result.gross_profit_wmon + 100.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_fast_arb_retries_swap2_with_undecodable_revert() {
+        // Swap 2 reverts and the parent-block replay can't decode why (the mock
+        // has no eth_getTransactionByHash); it fills with no min-out, so retry
+        let (provider, result) = replay_with(0xA6, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::Revert { gas_used: 60_000 },
+            MockLeg::Fill { base_delta: 99.2, quote_delta: -2.97 * 0.999, gas_used: 160_000 },
+        ], None, 50).await;
+
+        assert!(result.swap1_success && result.swap2_success, "{:?}", result.error);
+        assert!(result.swap2_retry_tx_hash.is_some());
+        assert_eq!(result.stranded_usdc, 0.0);
+        let sent = provider.sent();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[2].nonce, sent[1].nonce.map(|n| n + 1));
+        assert_eq!(result.total_gas_estimated, 3 * GAS_LIMIT);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fast_arb_cancels_unconfirmed_swap2_retry() {
+        let (provider, result) = replay_with(0xA8, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::Revert { gas_used: 60_000 },
+            MockLeg::NeverMined,
+            MockLeg::Fill { base_delta: 0.0, quote_delta: 0.0, gas_used: 21_000 },
+        ], None, 50).await;
+
+        assert!(result.swap1_success && !result.swap2_success);
+        // The retry is recorded even though it never confirmed, and its nonce was cancelled
+        assert!(result.swap2_retry_tx_hash.is_some());
+        assert_eq!(result.swap2_cancelled, Some(true));
+        let sent = provider.sent();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[3].nonce, sent[2].nonce);
+        assert!((result.stranded_usdc - 2.97).abs() < 1e-5);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_arb_releases_nonce_of_rejected_swap2() {
+        let pair = get_token_pair("WMON-USDC").unwrap();
+        let (sell, buy) = (get_router_by_name("Uniswap").unwrap(), get_router_by_name("LFJ").unwrap());
+        let wallet = Address::repeat_byte(0xA7);
+        let provider = MockProvider::new(&pair, 500.0, 10.0, GAS_ESTIMATE, vec![
+            MockLeg::Fill { base_delta: -100.0, quote_delta: 2.97, gas_used: 150_000 },
+            MockLeg::Rejected,
+        ]);
+        provider.init_wallet(wallet).await;
+        // Only swap 1 reached the node
+        provider.push_pending_nonce(1);
+
+        let trade = FastArbTrade { pair: &pair, sell_router: &sell, buy_router: &buy, amount: 100.0, sell_price: 0.03, buy_price: 0.0298 };
+        let opts = FastArbOptions {
+            slippage_bps: 100,
+            gas: GasFees { max_fee: MOCK_GAS_PRICE, priority_fee: 0 },
+            simulate: false,
+            retry_slippage_bump_bps: 0,
+            max_adaptive_slippage_bps: None,
+            deadline: None,
+        };
+        let (result, legs) = execute_fast_arb_parallel(&provider, wallet, trade, opts).await.unwrap();
+
+        assert!(result.swap1_success && !result.swap2_success);
+        assert_eq!(provider.sent().len(), 1);
+        assert_eq!(legs[1].nonce, 1);
+        // Swap 2's nonce goes to the next TX instead of leaving a gap
+        assert_eq!(next_nonce_for(wallet), 1);
+    }
+}
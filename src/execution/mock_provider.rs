@@ -0,0 +1,226 @@
+//! Scripted `Provider` for replaying arb executions in tests
+//!
+//! Covers the surface `fast_arb` uses: `call` (ERC20 balanceOf, swap
+//! simulation), `estimate_gas`, `send_transaction` and
+//! `get_transaction_receipt`. Each sent TX consumes the next `MockLeg`,
+//! which decides its receipt and moves the wallet's token balances, so a
+//! test scripts the chain's side of an arb and asserts on the result.
+//! Anything else falls through to an empty mocked client and errors.
+
+use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+use alloy::network::Ethereum;
+use alloy::primitives::{Address, Bloom, Bytes, TxHash, B256, U64};
+use alloy::providers::{
+    Caller, EthCall, EthCallManyParams, EthCallParams, PendingTransactionBuilder, Provider, ProviderCall, RootProvider,
+};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::types::{TransactionReceipt, TransactionRequest};
+use alloy::sol_types::SolCall;
+use alloy::transports::mock::Asserter;
+use alloy::transports::{TransportErrorKind, TransportResult};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use super::fast_arb::{balanceOfCall, to_wei};
+use crate::config::TokenPair;
+use crate::nonce::init_nonce;
+
+/// Gas price on every mocked receipt (100 gwei)
+pub const MOCK_GAS_PRICE: u128 = 100_000_000_000;
+
+/// What happens to the next transaction sent
+#[derive(Debug, Clone, Copy)]
+pub enum MockLeg {
+    /// Mines successfully, moving the wallet's balances (token units, signed)
+    Fill { base_delta: f64, quote_delta: f64, gas_used: u64 },
+    /// Mines and reverts
+    Revert { gas_used: u64 },
+    /// Accepted by the node but never mined
+    NeverMined,
+    /// Refused by the node at send: errors, nothing is recorded as sent
+    Rejected,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Token -> wallet balance, in token units
+    balances: HashMap<Address, f64>,
+    decimals: HashMap<Address, u8>,
+    legs: VecDeque<MockLeg>,
+    receipts: HashMap<TxHash, TransactionReceipt>,
+    gas_estimate: u64,
+    sent: Vec<TransactionRequest>,
+}
+
+impl MockState {
+    fn balance_of(&self, token: Address) -> Option<Bytes> {
+        let balance = self.balances.get(&token)?;
+        let raw = to_wei(balance.max(0.0), self.decimals[&token]);
+        Some(Bytes::from(raw.to_be_bytes::<32>().to_vec()))
+    }
+
+    fn send(&mut self, base: Address, quote: Address, tx: TransactionRequest) -> TransportResult<TxHash> {
+        let leg = self
+            .legs
+            .pop_front()
+            .ok_or_else(|| TransportErrorKind::custom_str("mock: no scripted leg left for this transaction"))?;
+        if let MockLeg::Rejected = leg {
+            return Err(TransportErrorKind::custom_str("mock: transaction rejected"));
+        }
+        self.sent.push(tx);
+        let hash = B256::with_last_byte(self.sent.len() as u8);
+
+        let (success, gas_used) = match leg {
+            MockLeg::Fill { base_delta, quote_delta, gas_used } => {
+                *self.balances.entry(base).or_default() += base_delta;
+                *self.balances.entry(quote).or_default() += quote_delta;
+                (true, gas_used)
+            }
+            MockLeg::Revert { gas_used } => (false, gas_used),
+            MockLeg::NeverMined | MockLeg::Rejected => return Ok(hash),
+        };
+        self.receipts.insert(hash, mock_receipt(hash, success, gas_used));
+        Ok(hash)
+    }
+}
+
+fn mock_receipt(hash: TxHash, success: bool, gas_used: u64) -> TransactionReceipt {
+    let receipt = Receipt { status: Eip658Value::Eip658(success), cumulative_gas_used: gas_used, logs: vec![] };
+    TransactionReceipt {
+        inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom { receipt, logs_bloom: Bloom::ZERO }),
+        transaction_hash: hash,
+        transaction_index: Some(0),
+        block_hash: Some(B256::ZERO),
+        block_number: Some(1),
+        gas_used,
+        effective_gas_price: MOCK_GAS_PRICE,
+        blob_gas_used: None,
+        blob_gas_price: None,
+        from: Address::ZERO,
+        to: None,
+        contract_address: None,
+    }
+}
+
+/// Answers `eth_call` / `eth_estimateGas` from the shared state
+#[derive(Clone)]
+struct MockCaller(Arc<Mutex<MockState>>);
+
+impl Caller<Ethereum, Bytes> for MockCaller {
+    fn call(&self, params: EthCallParams<Ethereum>) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+        let tx = params.data();
+        let to = tx.to.and_then(|kind| kind.to().copied()).unwrap_or_default();
+        let is_balance_of = tx.input.input().is_some_and(|data| data.starts_with(&balanceOfCall::SELECTOR));
+        let state = self.0.lock().unwrap();
+        // balanceOf on a tracked token, anything else (swap simulation) succeeds empty
+        let output = if is_balance_of { state.balance_of(to).unwrap_or_default() } else { Bytes::new() };
+        Ok(ProviderCall::ready(Ok(output)))
+    }
+
+    fn estimate_gas(&self, _params: EthCallParams<Ethereum>) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, Bytes>> {
+        Err(TransportErrorKind::custom_str("mock: estimate_gas returns a quantity"))
+    }
+
+    fn call_many(&self, _params: EthCallManyParams<'_>) -> TransportResult<ProviderCall<EthCallManyParams<'static>, Bytes>> {
+        Err(TransportErrorKind::custom_str("mock: eth_callMany not supported"))
+    }
+}
+
+impl Caller<Ethereum, U64> for MockCaller {
+    fn call(&self, _params: EthCallParams<Ethereum>) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, U64>> {
+        Err(TransportErrorKind::custom_str("mock: eth_call returns bytes"))
+    }
+
+    fn estimate_gas(&self, _params: EthCallParams<Ethereum>) -> TransportResult<ProviderCall<EthCallParams<Ethereum>, U64>> {
+        Ok(ProviderCall::ready(Ok(U64::from(self.0.lock().unwrap().gas_estimate))))
+    }
+
+    fn call_many(&self, _params: EthCallManyParams<'_>) -> TransportResult<ProviderCall<EthCallManyParams<'static>, U64>> {
+        Err(TransportErrorKind::custom_str("mock: eth_callMany not supported"))
+    }
+}
+
+fn gas_to_u64(gas: U64) -> u64 {
+    gas.to()
+}
+
+/// In-memory chain for one wallet trading one pair
+pub struct MockProvider {
+    root: RootProvider,
+    asserter: Asserter,
+    pair: TokenPair,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockProvider {
+    /// Wallet starts with `base` / `quote` token units; every estimate_gas returns `gas_estimate`
+    pub fn new(pair: &TokenPair, base: f64, quote: f64, gas_estimate: u64, legs: Vec<MockLeg>) -> Self {
+        let asserter = Asserter::new();
+        let state = MockState {
+            balances: HashMap::from([(pair.base, base), (pair.quote, quote)]),
+            decimals: HashMap::from([(pair.base, pair.base_decimals), (pair.quote, pair.quote_decimals)]),
+            legs: legs.into(),
+            gas_estimate,
+            ..Default::default()
+        };
+        Self {
+            root: RootProvider::new(RpcClient::mocked(asserter.clone())),
+            asserter,
+            pair: *pair,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Register `wallet` with the nonce manager, starting at nonce 0
+    pub async fn init_wallet(&self, wallet: Address) {
+        self.asserter.push_success(&U64::ZERO);
+        init_nonce(self, wallet).await.expect("mock nonce init");
+    }
+
+    /// Answer the next `eth_getTransactionCount` (the only call scripted through the RPC client)
+    pub fn push_pending_nonce(&self, nonce: u64) {
+        self.asserter.push_success(&U64::from(nonce));
+    }
+
+    pub fn balance(&self, token: Address) -> f64 {
+        self.state.lock().unwrap().balances.get(&token).copied().unwrap_or_default()
+    }
+
+    /// Transactions sent so far, in order
+    pub fn sent(&self) -> Vec<TransactionRequest> {
+        self.state.lock().unwrap().sent.clone()
+    }
+}
+
+impl Provider for MockProvider {
+    fn root(&self) -> &RootProvider {
+        &self.root
+    }
+
+    fn call(&self, tx: TransactionRequest) -> EthCall<Ethereum, Bytes> {
+        EthCall::call(MockCaller(self.state.clone()), tx)
+    }
+
+    fn estimate_gas(&self, tx: TransactionRequest) -> EthCall<Ethereum, U64, u64> {
+        EthCall::gas_estimate(MockCaller(self.state.clone()), tx).map_resp(gas_to_u64 as fn(U64) -> u64)
+    }
+
+    fn get_transaction_receipt(&self, hash: TxHash) -> ProviderCall<(TxHash,), Option<TransactionReceipt>> {
+        ProviderCall::ready(Ok(self.state.lock().unwrap().receipts.get(&hash).cloned()))
+    }
+
+    fn send_transaction<'life0, 'async_trait>(
+        &'life0 self,
+        tx: TransactionRequest,
+    ) -> Pin<Box<dyn Future<Output = TransportResult<PendingTransactionBuilder<Ethereum>>> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        let sent = self.state.lock().unwrap().send(self.pair.base, self.pair.quote, tx);
+        let root = self.root.clone();
+        Box::pin(async move { sent.map(|hash| PendingTransactionBuilder::new(root, hash)) })
+    }
+}
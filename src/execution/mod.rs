@@ -3,9 +3,18 @@ pub mod swap;
 pub mod report;
 pub mod fast_arb;
 pub mod atomic_arb;
+pub mod multihop_arb;
+pub mod revert;
+pub mod direction;
+pub mod submit;
+#[cfg(test)]
+pub mod mock_provider;
 
-pub use swap::{SwapParams, SwapResult, SwapDirection, execute_swap, wait_for_next_block};
+pub use swap::{SwapParams, SwapResult, SwapDirection, execute_swap, execute_swap_native, wait_for_next_block};
 pub use report::print_swap_report;
 pub use routers::build_swap_calldata;
-pub use fast_arb::{execute_fast_arb, FastArbResult, print_fast_arb_result};
-pub use atomic_arb::{execute_atomic_arb, AtomicArbResult, print_atomic_arb_result, query_contract_balances};
+pub use fast_arb::{execute_fast_arb, execute_fast_arb_parallel, FastArbOptions, FastArbResult, FastArbTrade, print_fast_arb_result, print_pending_legs};
+pub use multihop_arb::{execute_multihop_arb, parse_route, print_multihop_result};
+pub use atomic_arb::{execute_atomic_arb, execute_atomic_arb_flashloan, AtomicArbResult, AtomicArbTrade, AtomicExecOptions, print_atomic_arb_result, query_contract_balances, query_contract_owner, verify_atomic_contract, ContractStatus, ReceiptMode, drain_receipt_watches};
+pub use revert::decode_revert;
+pub use direction::check_arb_direction;
@@ -0,0 +1,332 @@
+//! Multi-hop cyclic arbitrage (e.g. WMON -> USDC -> USDT -> WMON)
+//!
+//! A route is an ordered list of (router, pair) hops where each pair is
+//! written in trade direction: `base` is the token sold, `quote` the token
+//! received. Hops run one after another like `execute_fast_arb`: after each
+//! leg confirms, the realized balance change of the token received becomes
+//! the exact input of the next leg.
+//!
+//! Intermediate legs carry no minimum output (we have no price for pools
+//! outside the WMON/USDC set); the last leg requires getting back at least
+//! `amount` minus slippage, so an unprofitable cycle reverts there instead
+//! of completing at a loss.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::info;
+
+use crate::config::{get_router_by_name, get_token_pair, get_tokens, RouterConfig, TokenPair};
+use crate::error::ArbResult;
+use crate::gas::{self, GasFees};
+use crate::logging::{self, EVENT_TARGET};
+use crate::nonce::next_nonce_for;
+use super::fast_arb::{build_fast_swap_tx, build_swap_request, estimate_gas_with_buffer, from_wei, to_wei, wait_for_receipt_fast};
+use super::revert::decode_revert;
+use super::SwapDirection;
+
+sol! {
+    #[derive(Debug)]
+    function balanceOf(address account) external view returns (uint256);
+}
+
+/// One leg of a route: swap `pair.base` -> `pair.quote` on `router`
+pub type Hop = (RouterConfig, TokenPair);
+
+/// Outcome of one confirmed (or failed) hop
+#[derive(Debug, Clone)]
+pub struct HopResult {
+    pub router: &'static str,
+    pub token_in: String,
+    pub token_out: String,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    pub tx_hash: String,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub success: bool,
+    pub time_ms: u128,
+}
+
+/// Result of a multi-hop arb
+#[derive(Debug, Clone)]
+pub struct MultiHopResult {
+    pub hops: Vec<HopResult>,
+    pub start_token: String,
+    pub amount_in: f64,
+    /// Start token received by the last hop (0 if the route didn't complete)
+    pub amount_out: f64,
+    pub profit: f64,
+    pub profit_bps: i32,
+    pub total_gas_cost_mon: f64,
+    pub total_time_ms: u128,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Parse `--route`, e.g. "uniswap:wmon-usdc,lfj:usdc-usdt,pancakeswap1:usdt-wmon"
+pub fn parse_route(route: &str) -> Result<Vec<Hop>> {
+    let hops = route
+        .split(',')
+        .map(|hop| {
+            let (dex, pair) = hop
+                .trim()
+                .split_once(':')
+                .ok_or_else(|| eyre!("Bad hop '{}': expected <dex>:<tokenIn>-<tokenOut>", hop))?;
+            let router = get_router_by_name(dex).ok_or_else(|| eyre!("Unknown DEX in route: {}", dex))?;
+            let pair = get_token_pair(pair).ok_or_else(|| eyre!("Unknown token pair in route: {}", pair))?;
+            Ok((router, pair))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    validate_route(&hops)?;
+    Ok(hops)
+}
+
+/// Each hop must sell what the previous one received, and the last hop must
+/// return to the starting token
+pub fn validate_route(hops: &[Hop]) -> Result<()> {
+    if hops.len() < 2 {
+        return Err(eyre!("Route needs at least 2 hops, got {}", hops.len()));
+    }
+    for (i, hop) in hops.iter().enumerate() {
+        let (router, pair) = hop;
+        if pair.base == pair.quote {
+            return Err(eyre!("Hop {} ({}) swaps {} for itself", i + 1, router.name, token_symbol(pair.base)));
+        }
+        let (next_router, next_pair) = &hops[(i + 1) % hops.len()];
+        if pair.quote != next_pair.base {
+            return Err(eyre!(
+                "Route breaks between hop {} ({}) and hop {} ({}): receives {} but next hop sells {}",
+                i + 1,
+                router.name,
+                (i + 1) % hops.len() + 1,
+                next_router.name,
+                token_symbol(pair.quote),
+                token_symbol(next_pair.base),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Route as "WMON -[Uniswap]-> USDC -[LFJ]-> ..."
+pub fn format_route(hops: &[Hop]) -> String {
+    let mut out = hops.first().map(|(_, p)| token_symbol(p.base)).unwrap_or_default();
+    for (router, pair) in hops {
+        out.push_str(&format!(" -[{}]-> {}", router.name, token_symbol(pair.quote)));
+    }
+    out
+}
+
+fn token_symbol(address: Address) -> String {
+    get_tokens()
+        .into_iter()
+        .find(|t| t.address == address)
+        .map(|t| t.symbol.to_string())
+        .unwrap_or_else(|| format!("{:?}", address))
+}
+
+async fn query_balance_raw<P: Provider>(provider: &P, token: Address, wallet: Address) -> Result<U256> {
+    let call = balanceOfCall { account: wallet };
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(token)
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(call.abi_encode())));
+    let result = provider.call(tx).await?;
+    Ok(U256::from_be_slice(&result))
+}
+
+/// Execute a cyclic route starting with `amount` of the first hop's token
+///
+/// # Arguments
+/// * `hops` - Validated route (see `parse_route`)
+/// * `amount` - Amount of the start token sold by hop 1
+/// * `slippage_bps` - Max loss tolerated on the whole cycle (last hop's min out)
+/// * `gas` - Pre-resolved fees from the gas strategy (same for every hop)
+pub async fn execute_multihop_arb<P: Provider>(
+    provider_with_signer: &P,
+    signer_address: Address,
+    hops: &[Hop],
+    amount: f64,
+    slippage_bps: u32,
+    gas: GasFees,
+) -> ArbResult<MultiHopResult> {
+    validate_route(hops)?;
+    let total_start = std::time::Instant::now();
+
+    let start_token = hops[0].1.base;
+    let start_decimals = hops[0].1.base_decimals;
+    let min_final_out = to_wei(amount * (1.0 - slippage_bps as f64 / 10000.0), start_decimals);
+
+    let mut results: Vec<HopResult> = Vec::with_capacity(hops.len());
+    let mut total_gas_cost = U256::ZERO;
+    let mut amount_in_wei = to_wei(amount, start_decimals);
+    let mut error = None;
+
+    for (i, (router, pair)) in hops.iter().enumerate() {
+        let is_last = i + 1 == hops.len();
+        let token_in = token_symbol(pair.base);
+        let token_out = token_symbol(pair.quote);
+        let amount_in = from_wei(amount_in_wei, pair.base_decimals);
+        let min_out = if is_last { min_final_out } else { U256::ZERO };
+
+        println!("\n  Hop {}/{} ({}): {:.6} {} -> {}", i + 1, hops.len(), router.name, amount_in, token_in, token_out);
+        if is_last {
+            println!("    Min out: {:.6} {} ({}bps slippage on the cycle)",
+                from_wei(min_out, pair.quote_decimals), token_out, slippage_bps);
+        }
+
+        let out_before = query_balance_raw(provider_with_signer, pair.quote, signer_address).await?;
+
+        let calldata = build_fast_swap_tx(router, pair, SwapDirection::Sell, amount_in_wei, min_out, signer_address)?;
+        let gas_limit = estimate_gas_with_buffer(provider_with_signer, router, signer_address, &calldata).await;
+        let tx = build_swap_request(router, signer_address, calldata, gas_limit, next_nonce_for(signer_address), gas);
+
+        let hop_start = std::time::Instant::now();
+        let pending = match timeout(Duration::from_secs(10), provider_with_signer.send_transaction(tx)).await {
+            Ok(Ok(pending)) => pending,
+            Ok(Err(e)) => {
+                error = Some(format!("Hop {} send failed: {}", i + 1, e));
+                break;
+            }
+            Err(_) => {
+                error = Some(format!("Hop {} send timeout", i + 1));
+                break;
+            }
+        };
+        let tx_hash = *pending.tx_hash();
+        info!(target: EVENT_TARGET, pair = logging::pair(), leg = i as u64 + 1, tx_hash = %tx_hash,
+            "    Hop {} sent: {:?}", i + 1, tx_hash);
+
+        let receipt = wait_for_receipt_fast(provider_with_signer, tx_hash).await?;
+        let time_ms = hop_start.elapsed().as_millis();
+        total_gas_cost += U256::from(gas_limit) * U256::from(receipt.effective_gas_price);
+
+        info!(target: EVENT_TARGET, pair = logging::pair(), leg = i as u64 + 1, tx_hash = %tx_hash,
+            success = receipt.status(), gas_used = receipt.gas_used, gas_limit = gas_limit, confirm_ms = time_ms as u64,
+            "    Hop {} confirmed: {} (gas used: {}, limit: {})",
+            i + 1, if receipt.status() { "SUCCESS" } else { "REVERTED" }, receipt.gas_used, gas_limit);
+        if receipt.status() {
+            gas::record_gas_used(router.name, receipt.gas_used);
+        }
+
+        // Realized output feeds the next hop
+        let out_after = query_balance_raw(provider_with_signer, pair.quote, signer_address).await?;
+        let received = out_after.saturating_sub(out_before);
+
+        results.push(HopResult {
+            router: router.name,
+            token_in,
+            token_out: token_out.clone(),
+            amount_in,
+            amount_out: from_wei(received, pair.quote_decimals),
+            tx_hash: format!("{:?}", tx_hash),
+            gas_used: receipt.gas_used,
+            gas_limit,
+            success: receipt.status(),
+            time_ms,
+        });
+
+        if !receipt.status() {
+            let reason = decode_revert(provider_with_signer, tx_hash).await
+                .unwrap_or_else(|| "unknown reason".to_string());
+            println!("    Revert reason: {}", reason);
+            error = Some(format!("Hop {} reverted: {}", i + 1, reason));
+            break;
+        }
+        println!("    Received: {:.6} {}", from_wei(received, pair.quote_decimals), token_out);
+        if received.is_zero() {
+            error = Some(format!("Hop {} returned no {}", i + 1, token_out));
+            break;
+        }
+        amount_in_wei = received;
+    }
+
+    let success = error.is_none();
+    let amount_out = if success {
+        results.last().map(|h| h.amount_out).unwrap_or(0.0)
+    } else {
+        0.0
+    };
+    let profit = if success { amount_out - amount } else { 0.0 };
+    if !success {
+        // Output of the last completed hop is now sitting in the wallet
+        if let Some(held) = results.iter().rev().find(|h| h.success) {
+            if held.token_out != token_symbol(start_token) {
+                println!("  ⚠ Route stopped mid-way: holding {:.6} {}", held.amount_out, held.token_out);
+            }
+        }
+    }
+
+    Ok(MultiHopResult {
+        hops: results,
+        start_token: token_symbol(start_token),
+        amount_in: amount,
+        amount_out,
+        profit,
+        profit_bps: if amount > 0.0 { (profit / amount * 10000.0) as i32 } else { 0 },
+        total_gas_cost_mon: total_gas_cost.to::<u128>() as f64 / 1e18,
+        total_time_ms: total_start.elapsed().as_millis(),
+        success,
+        error,
+    })
+}
+
+/// Print a multi-hop arb result
+pub fn print_multihop_result(result: &MultiHopResult) {
+    let start_symbol = &result.start_token;
+    println!("\n══════════════════════════════════════════════════════════════");
+    println!("  MULTI-HOP ARB RESULT: {}", if result.success { "SUCCESS" } else { "FAILED" });
+    println!("══════════════════════════════════════════════════════════════");
+    for (i, hop) in result.hops.iter().enumerate() {
+        println!("  Hop {} {:<14} {:>14.6} {} -> {:>14.6} {}  {} ({}ms, gas {}/{})",
+            i + 1, hop.router, hop.amount_in, hop.token_in, hop.amount_out, hop.token_out,
+            if hop.success { "✓" } else { "✗" }, hop.time_ms, hop.gas_used, hop.gas_limit);
+        println!("        tx: {}", hop.tx_hash);
+    }
+    println!("  ──────────────────────────────────────────────────────────────");
+    println!("  {} in:  {:.6}", start_symbol, result.amount_in);
+    println!("  {} out: {:.6}", start_symbol, result.amount_out);
+    println!("  Profit: {:+.6} {} ({} bps)", result.profit, start_symbol, result.profit_bps);
+    println!("  Gas:    {:.6} MON", result.total_gas_cost_mon);
+    println!("  Time:   {}ms", result.total_time_ms);
+    if let Some(e) = &result.error {
+        println!("  Error:  {}", e);
+    }
+    info!(target: EVENT_TARGET, pair = logging::pair(), outcome = if result.success { "success" } else { "failed" },
+        hops = result.hops.len() as u64, profit = result.profit, profit_bps = result.profit_bps,
+        gas_mon = result.total_gas_cost_mon, total_ms = result.total_time_ms as u64,
+        "══════════════════════════════════════════════════════════════");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::get_routers;
+
+    fn pair(base: Address, quote: Address) -> TokenPair {
+        TokenPair { base, quote, base_decimals: 18, quote_decimals: 6 }
+    }
+
+    #[test]
+    fn test_validate_route_continuity() {
+        let router = get_routers().remove(0);
+        let (a, b, c) = (Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3));
+
+        let cycle = vec![(router.clone(), pair(a, b)), (router.clone(), pair(b, c)), (router.clone(), pair(c, a))];
+        assert!(validate_route(&cycle).is_ok());
+
+        // b -> c followed by a hop selling a
+        let broken = vec![(router.clone(), pair(a, b)), (router.clone(), pair(c, a))];
+        assert!(validate_route(&broken).is_err());
+
+        // doesn't return to a
+        let open = vec![(router.clone(), pair(a, b)), (router.clone(), pair(b, c))];
+        assert!(validate_route(&open).is_err());
+
+        assert!(validate_route(&cycle[..1]).is_err());
+    }
+}
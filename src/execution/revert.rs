@@ -0,0 +1,138 @@
+//! Revert reason decoding
+//!
+//! On Monad a revert still costs the full gas_limit, so knowing WHY a swap
+//! reverts (slippage vs allowance vs balance) matters.
+
+use alloy::eips::BlockId;
+use alloy::primitives::TxHash;
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::{decode_revert_reason, SolError};
+use alloy::transports::{RpcError, TransportErrorKind};
+
+// LFJ LBRouter custom errors (Uniswap/PancakeSwap routers revert with strings)
+sol! {
+    error LBRouter__InsufficientAmountOut(uint256 amountOutMin, uint256 amountOut);
+    error LBRouter__MaxAmountInExceeded(uint256 amountInMax, uint256 amountIn);
+    error LBRouter__DeadlineExceeded(uint256 deadline, uint256 currentTimestamp);
+    error LBRouter__SwapOverflows(uint256 id);
+}
+
+/// Annotate terse router revert strings with what they usually mean
+fn explain_reason(reason: &str) -> Option<&'static str> {
+    let r = reason.trim_start_matches("revert: ").trim();
+    match r {
+        "Too little received" => Some("slippage: output below amountOutMinimum"),
+        "Too much requested" => Some("slippage: input above amountInMaximum"),
+        "STF" => Some("transferFrom failed: allowance or balance"),
+        "ST" | "TF" => Some("token transfer failed: balance"),
+        "Transaction too old" => Some("deadline exceeded"),
+        "SPL" => Some("price limit reached"),
+        _ => None,
+    }
+}
+
+/// Decode known router custom errors by selector
+fn decode_custom_error(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let selector: [u8; 4] = data[..4].try_into().ok()?;
+    let reason = if selector == LBRouter__InsufficientAmountOut::SELECTOR {
+        let e = LBRouter__InsufficientAmountOut::abi_decode(data).ok()?;
+        format!("LBRouter__InsufficientAmountOut (min {}, got {}) - slippage", e.amountOutMin, e.amountOut)
+    } else if selector == LBRouter__MaxAmountInExceeded::SELECTOR {
+        let e = LBRouter__MaxAmountInExceeded::abi_decode(data).ok()?;
+        format!("LBRouter__MaxAmountInExceeded (max {}, needed {}) - slippage", e.amountInMax, e.amountIn)
+    } else if selector == LBRouter__DeadlineExceeded::SELECTOR {
+        "LBRouter__DeadlineExceeded - deadline exceeded".to_string()
+    } else if selector == LBRouter__SwapOverflows::SELECTOR {
+        "LBRouter__SwapOverflows - not enough liquidity".to_string()
+    } else {
+        return None;
+    };
+    Some(reason)
+}
+
+/// Decode raw revert data into a readable reason.
+/// Handles Error(string), Panic(uint256) and known router custom errors;
+/// falls back to the 4-byte selector.
+pub fn decode_revert_data(data: &[u8]) -> String {
+    if data.is_empty() {
+        return "reverted without reason".to_string();
+    }
+    if let Some(reason) = decode_custom_error(data) {
+        return reason;
+    }
+    if let Some(reason) = decode_revert_reason(data) {
+        return match explain_reason(&reason) {
+            Some(hint) => format!("{} ({})", reason, hint),
+            None => reason,
+        };
+    }
+    if data.len() >= 4 {
+        return format!("custom error 0x{}", alloy::primitives::hex::encode(&data[..4]));
+    }
+    format!("revert data 0x{}", alloy::primitives::hex::encode(data))
+}
+
+/// Extract a revert reason from an eth_call / eth_estimateGas RPC error
+pub fn revert_reason_from_rpc_error(err: &RpcError<TransportErrorKind>) -> String {
+    match err.as_error_resp() {
+        Some(payload) => match payload.as_revert_data() {
+            Some(data) => decode_revert_data(&data),
+            None => payload.message.to_string(),
+        },
+        None => err.to_string(),
+    }
+}
+
+/// Re-simulate a mined, reverted transaction to recover its revert reason.
+///
+/// Replays the tx via eth_call against the parent of its block (the closest
+/// available state to "just before" it). Returns None if the tx can't be
+/// found or the replay no longer reverts.
+///
+/// Transactions mined ahead of it in the same block are not replayed, so a
+/// revert caused by one of them (a price move, a spent nonce or allowance)
+/// can come back as None or as a different reason.
+pub async fn decode_revert<P: Provider>(provider: &P, tx_hash: TxHash) -> Option<String> {
+    let tx = provider.get_transaction_by_hash(tx_hash).await.ok()??;
+    let block = tx.block_number?;
+    let request = tx.into_request();
+
+    match provider.call(request).block(BlockId::number(block.saturating_sub(1))).await {
+        Ok(_) => None,
+        Err(e) => Some(revert_reason_from_rpc_error(&e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::{Revert, SolError};
+
+    #[test]
+    fn test_decode_error_string() {
+        let data = Revert::from("Too little received").abi_encode();
+        assert!(decode_revert_data(&data).contains("Too little received"));
+    }
+
+    #[test]
+    fn test_decode_router_errors() {
+        let data = Revert::from("STF").abi_encode();
+        assert!(decode_revert_data(&data).contains("allowance"));
+
+        let data = LBRouter__InsufficientAmountOut {
+            amountOutMin: alloy::primitives::U256::from(100),
+            amountOut: alloy::primitives::U256::from(90),
+        }
+        .abi_encode();
+        assert!(decode_revert_data(&data).starts_with("LBRouter__InsufficientAmountOut (min 100, got 90)"));
+    }
+
+    #[test]
+    fn test_decode_unknown_selector() {
+        assert_eq!(decode_revert_data(&[0xde, 0xad, 0xbe, 0xef, 0x00]), "custom error 0xdeadbeef");
+    }
+}
@@ -1,7 +1,9 @@
 use alloy::primitives::{Address, Bytes, U256};
 use alloy::sol;
 use alloy::sol_types::SolCall;
-use eyre::Result;
+use eyre::{eyre, Result};
+
+use super::ExactInputSwap;
 
 // LFJ Liquidity Book Router V2.2 Interface
 // Note: LFJ uses a path-based routing system (NOT Uniswap V3 style)
@@ -38,6 +40,25 @@ sol! {
         address to,
         uint256 deadline
     ) external returns (uint256 amountOut);
+
+    /// Swaps exact MON (the TX value) for tokens; path starts at WMON
+    #[derive(Debug)]
+    function swapExactNATIVEForTokens(
+        uint256 amountOutMin,
+        Path memory path,
+        address to,
+        uint256 deadline
+    ) external payable returns (uint256 amountOut);
+
+    /// Swaps exact tokens for MON; path ends at WMON
+    #[derive(Debug)]
+    function swapExactTokensForNATIVE(
+        uint256 amountIn,
+        uint256 amountOutMinNATIVE,
+        Path memory path,
+        address to,
+        uint256 deadline
+    ) external returns (uint256 amountOut);
 }
 
 // LFJ Bin Steps (Fee Tiers):
@@ -83,12 +104,36 @@ pub fn build_swap_with_bin_step(
     println!("  [LFJ DEBUG] amount_out_min: {}", amount_out_min);
     println!("  [LFJ DEBUG] bin_step: {}", bin_step);
 
-    // For LFJ, we need to specify the path with proper version and bin step
-    // Version 3 = V2_2 (Liquidity Book V2.2 - current version)
+    let calldata = build_swap_path(&[token_in, token_out], &[bin_step], amount_in, amount_out_min, recipient, deadline)?;
+
+    // Debug: print the selector (first 4 bytes)
+    if calldata.len() >= 4 {
+        println!("  [LFJ DEBUG] Selector: 0x{:02x}{:02x}{:02x}{:02x}",
+            calldata[0], calldata[1], calldata[2], calldata[3]);
+    }
+
+    Ok(calldata)
+}
+
+/// Build swap calldata over a full path: `bin_steps[i]` picks the pair between
+/// `token_path[i]` and `token_path[i + 1]`
+pub fn build_swap_path(
+    token_path: &[Address],
+    bin_steps: &[u64],
+    amount_in: U256,
+    amount_out_min: U256,
+    recipient: Address,
+    deadline: u64,
+) -> Result<Bytes> {
+    if token_path.len() < 2 || bin_steps.len() != token_path.len() - 1 {
+        return Err(eyre!("LFJ path needs one bin step per hop ({} tokens, {} bin steps)", token_path.len(), bin_steps.len()));
+    }
+
+    // Version 3 = V2_2 (Liquidity Book V2.2 - current version) for every hop
     let path = Path {
-        pairBinSteps: vec![U256::from(bin_step)],
-        versions: vec![3],  // V2_2 = 3 (current Liquidity Book version)
-        tokenPath: vec![token_in, token_out],
+        pairBinSteps: bin_steps.iter().map(|&step| U256::from(step)).collect(),
+        versions: vec![3; bin_steps.len()],
+        tokenPath: token_path.to_vec(),
     };
 
     let call = swapExactTokensForTokensCall {
@@ -99,15 +144,21 @@ pub fn build_swap_with_bin_step(
         deadline: U256::from(deadline),
     };
 
-    let calldata = call.abi_encode();
-
-    // Debug: print the selector (first 4 bytes)
-    if calldata.len() >= 4 {
-        println!("  [LFJ DEBUG] Selector: 0x{:02x}{:02x}{:02x}{:02x}",
-            calldata[0], calldata[1], calldata[2], calldata[3]);
-    }
+    Ok(Bytes::from(call.abi_encode()))
+}
 
-    Ok(Bytes::from(calldata))
+/// Single-hop swap with native MON in (`native_in`, sent as the TX value) or
+/// out; `token_in`/`token_out` still name WMON for the native side
+pub fn build_native_swap(native_in: bool, swap: ExactInputSwap, bin_step: u64) -> Result<Bytes> {
+    let ExactInputSwap { token_in, token_out, amount_in, amount_out_min, recipient, deadline } = swap;
+    let path = Path { pairBinSteps: vec![U256::from(bin_step)], versions: vec![3], tokenPath: vec![token_in, token_out] };
+    let deadline = U256::from(deadline);
+    Ok(Bytes::from(if native_in {
+        swapExactNATIVEForTokensCall { amountOutMin: amount_out_min, path, to: recipient, deadline }.abi_encode()
+    } else {
+        swapExactTokensForNATIVECall { amountIn: amount_in, amountOutMinNATIVE: amount_out_min, path, to: recipient, deadline }
+            .abi_encode()
+    }))
 }
 
 #[cfg(test)]
@@ -149,4 +200,16 @@ mod tests {
         }
         println!();
     }
+
+    #[test]
+    fn test_multi_hop_path_encodes_every_bin_step() {
+        let tokens = [Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3)];
+        let calldata = build_swap_path(&tokens, &[10, 25], U256::from(1), U256::ZERO, Address::ZERO, 0).unwrap();
+        let call = swapExactTokensForTokensCall::abi_decode(&calldata).unwrap();
+        assert_eq!(call.path.pairBinSteps, vec![U256::from(10), U256::from(25)]);
+        assert_eq!(call.path.versions, vec![3, 3]);
+        assert_eq!(call.path.tokenPath, tokens.to_vec());
+
+        assert!(build_swap_path(&tokens, &[10], U256::from(1), U256::ZERO, Address::ZERO, 0).is_err());
+    }
 }
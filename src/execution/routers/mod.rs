@@ -2,24 +2,43 @@ pub mod uniswap_v3;
 pub mod pancake_v3;
 pub mod lfj;
 pub mod monday;
+pub mod uniswap_v4;
+pub mod self_test;
 
-use alloy::primitives::{Address, Bytes, U256};
-use eyre::Result;
+use alloy::primitives::{Address, Bytes, U160, U256};
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::config::RouterType;
+use crate::config::{RouterConfig, RouterType, TokenPair, V4PoolParams};
+
+/// `--price-limit`: V3 swaps carry a sqrtPriceLimitX96 derived from slippage
+static PRICE_LIMIT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_price_limit(enabled: bool) {
+    PRICE_LIMIT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn price_limit_enabled() -> bool {
+    PRICE_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Uniswap V3 TickMath bounds; a limit must lie strictly between them
+const MIN_SQRT_RATIO: U160 = U160::from_limbs([4295128739, 0, 0]);
+const MAX_SQRT_RATIO: U160 = U160::from_limbs([6743329769008885220, 17280870778742802505, 4294805859]);
 
 /// Build swap calldata for the appropriate router
 pub fn build_swap_calldata(
-    router_type: RouterType,
+    router: &RouterConfig,
     token_in: Address,
     token_out: Address,
     amount_in: U256,
     amount_out_min: U256,
     recipient: Address,
-    pool_fee: u32,
     deadline: u64,
 ) -> Result<Bytes> {
-    match router_type {
+    let pool_fee = router.pool_fee;
+    match router.router_type {
         RouterType::UniswapV3 => {
             uniswap_v3::build_exact_input_single(
                 token_in, token_out, pool_fee, recipient, amount_in, amount_out_min
@@ -41,5 +60,248 @@ pub fn build_swap_calldata(
                 token_in, token_out, pool_fee, recipient, amount_in, amount_out_min, deadline
             )
         }
+        RouterType::UniswapV4 => {
+            let pool = router.v4_pool.unwrap_or_else(|| V4PoolParams::standard(pool_fee));
+            let key = uniswap_v4::V4SwapKey { token_in, token_out, fee: pool_fee, pool };
+            uniswap_v4::build_exact_input_single(key, recipient, amount_in, amount_out_min, deadline)
+        }
+    }
+}
+
+/// Which side of a swap is native MON rather than WMON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NativeLeg {
+    /// MON goes in as the TX value; the router wraps it
+    In,
+    /// The router unwraps the WMON out and pays MON
+    Out,
+}
+
+/// SwapRouter02 / SmartRouter recipient meaning the router itself (`Constants.ADDRESS_THIS`)
+const ROUTER_ADDRESS_THIS: Address = Address::with_last_byte(2);
+
+/// Whether `router` can swap native MON without a separate wrap or unwrap
+pub fn supports_native(router: &RouterConfig) -> bool {
+    router.supports_native && matches!(router.router_type, RouterType::UniswapV3 | RouterType::PancakeV3 | RouterType::LfjLB)
+}
+
+/// Tokens, amounts and payout of an exact-input swap
+#[derive(Debug, Clone, Copy)]
+pub struct ExactInputSwap {
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub recipient: Address,
+    pub deadline: u64,
+}
+
+/// `build_swap_calldata` with the `leg` side in native MON, and the value the
+/// TX must carry (`amount_in` for native input, else zero). `token_in` and
+/// `token_out` still name WMON. `price_limit` bounds the inner V3 swap.
+pub fn build_native_swap_calldata(
+    router: &RouterConfig,
+    leg: NativeLeg,
+    swap: ExactInputSwap,
+    price_limit: Option<U160>,
+) -> Result<(Bytes, U256)> {
+    let ExactInputSwap { token_in, token_out, amount_in, amount_out_min, recipient, deadline } = swap;
+    if !supports_native(router) {
+        return Err(eyre!("{} has no native MON swap path", router.name));
+    }
+    let value = if leg == NativeLeg::In { amount_in } else { U256::ZERO };
+    if router.router_type == RouterType::LfjLB {
+        let calldata = lfj::build_native_swap(leg == NativeLeg::In, swap, router.pool_fee as u64)?;
+        return Ok((calldata, value));
+    }
+
+    // V3 routers wrap msg.value themselves; to pay out MON the swap pays the
+    // router, then unwrapWETH9 forwards the WMON as MON
+    let swap_recipient = if leg == NativeLeg::Out { ROUTER_ADDRESS_THIS } else { recipient };
+    let mut calldata = build_swap_calldata(router, token_in, token_out, amount_in, amount_out_min, swap_recipient, deadline)?;
+    if let Some(limit) = price_limit {
+        calldata = with_price_limit(router.router_type, calldata, limit)?;
+    }
+    if leg == NativeLeg::Out {
+        let unwrap = Bytes::from(uniswap_v3::unwrapWETH9Call { amountMinimum: amount_out_min, recipient }.abi_encode());
+        calldata = match router.router_type {
+            RouterType::UniswapV3 => Bytes::from(uniswap_v3::multicallCall { data: vec![calldata, unwrap] }.abi_encode()),
+            _ => {
+                let mut call = pancake_v3::multicallCall::abi_decode(&calldata)?;
+                call.data.push(unwrap);
+                Bytes::from(call.abi_encode())
+            }
+        };
+    }
+    Ok((calldata, value))
+}
+
+/// Whether the router's swap params take a usable sqrtPriceLimitX96 (LFJ has
+/// no such field; Monday and V4 are left unlimited)
+pub fn supports_price_limit(router_type: RouterType) -> bool {
+    matches!(router_type, RouterType::UniswapV3 | RouterType::PancakeV3)
+}
+
+/// sqrtPriceLimitX96 that stops a `token_in` swap on `pair` once the pool price
+/// has moved `slippage_bps` from `price` (quote per base). Selling token0
+/// (zeroForOne) pushes the price down, so the limit sits below; buying token0
+/// pushes it up, so the limit sits above.
+pub fn sqrt_price_limit(pair: &TokenPair, token_in: Address, price: f64, slippage_bps: u32) -> U160 {
+    let base_is_token0 = pair.base < pair.quote;
+    let decimals = 10f64.powi(pair.quote_decimals as i32 - pair.base_decimals as i32);
+    // token1 per token0, in raw units
+    let ratio = if base_is_token0 { price * decimals } else { 1.0 / (price * decimals) };
+    let zero_for_one = (token_in == pair.base) == base_is_token0;
+    let slippage = slippage_bps as f64 / 10_000.0;
+    let limit_ratio = if zero_for_one { ratio * (1.0 - slippage) } else { ratio * (1.0 + slippage) };
+
+    let sqrt_x96 = limit_ratio.max(0.0).sqrt() * 2f64.powi(96);
+    let limit = U160::try_from(sqrt_x96).unwrap_or(MAX_SQRT_RATIO);
+    limit.clamp(MIN_SQRT_RATIO + U160::from(1), MAX_SQRT_RATIO - U160::from(1))
+}
+
+fn set_inner_price_limit(inner: &[u8], limit: U160) -> Result<Bytes> {
+    // Uniswap SwapRouter02 and Pancake SmartRouter share these encodings
+    if let Ok(mut call) = uniswap_v3::exactInputSingleCall::abi_decode(inner) {
+        call.params.sqrtPriceLimitX96 = limit;
+        return Ok(Bytes::from(call.abi_encode()));
+    }
+    if let Ok(mut call) = uniswap_v3::exactOutputSingleCall::abi_decode(inner) {
+        call.params.sqrtPriceLimitX96 = limit;
+        return Ok(Bytes::from(call.abi_encode()));
+    }
+    Err(eyre!("Not an exactInputSingle/exactOutputSingle call"))
+}
+
+/// Set sqrtPriceLimitX96 on swap calldata built by this module
+pub fn with_price_limit(router_type: RouterType, calldata: Bytes, limit: U160) -> Result<Bytes> {
+    match router_type {
+        RouterType::UniswapV3 => set_inner_price_limit(&calldata, limit),
+        RouterType::PancakeV3 => {
+            let mut call = pancake_v3::multicallCall::abi_decode(&calldata)?;
+            call.data = call.data.iter().map(|inner| set_inner_price_limit(inner, limit)).collect::<Result<_>>()?;
+            Ok(Bytes::from(call.abi_encode()))
+        }
+        other => Err(eyre!("{:?} router has no sqrtPriceLimitX96", other)),
+    }
+}
+
+/// `calldata` with a price limit `slippage_bps` from `price` when `--price-limit`
+/// is on and the router supports one; otherwise unchanged
+pub fn apply_price_limit(
+    router: &RouterConfig,
+    pair: &TokenPair,
+    token_in: Address,
+    price: f64,
+    slippage_bps: u32,
+    calldata: Bytes,
+) -> Result<Bytes> {
+    match price_limit_for(router, pair, token_in, price, slippage_bps) {
+        Some(limit) => with_price_limit(router.router_type, calldata, limit),
+        None => Ok(calldata),
+    }
+}
+
+/// The limit `apply_price_limit` would set, if any
+pub fn price_limit_for(router: &RouterConfig, pair: &TokenPair, token_in: Address, price: f64, slippage_bps: u32) -> Option<U160> {
+    (price_limit_enabled() && supports_price_limit(router.router_type) && price > 0.0)
+        .then(|| sqrt_price_limit(pair, token_in, price, slippage_bps))
+}
+
+/// Whether the router can do exactOutputSingle (V3-style routers only)
+pub fn supports_exact_output(router_type: RouterType) -> bool {
+    !matches!(router_type, RouterType::LfjLB | RouterType::UniswapV4)
+}
+
+/// Tokens, amounts and payout of an exactOutput swap
+#[derive(Debug, Clone, Copy)]
+pub struct ExactOutputSwap {
+    pub token_in: Address,
+    pub token_out: Address,
+    /// Exact amount to receive
+    pub amount_out: U256,
+    /// Most `token_in` the swap may spend
+    pub amount_in_max: U256,
+    pub recipient: Address,
+    pub deadline: u64,
+}
+
+/// Build exactOutput calldata: receive exactly `amount_out`, spend at most `amount_in_max`
+pub fn build_swap_calldata_exact_output(router: &RouterConfig, swap: ExactOutputSwap) -> Result<Bytes> {
+    let ExactOutputSwap { token_in, token_out, amount_out, amount_in_max, recipient, deadline } = swap;
+    let pool_fee = router.pool_fee;
+    match router.router_type {
+        RouterType::UniswapV3 => {
+            uniswap_v3::build_exact_output_single(
+                token_in, token_out, pool_fee, recipient, amount_out, amount_in_max
+            )
+        }
+        RouterType::PancakeV3 => {
+            pancake_v3::build_exact_output_single(
+                token_in, token_out, pool_fee, recipient, amount_out, amount_in_max, deadline
+            )
+        }
+        RouterType::MondayTrade => {
+            monday::build_exact_output_single(
+                token_in, token_out, pool_fee, recipient, amount_out, amount_in_max, deadline
+            )
+        }
+        RouterType::LfjLB => Err(eyre!("exactOutput not supported for LFJ router")),
+        RouterType::UniswapV4 => Err(eyre!("exactOutput not supported for Uniswap V4 router")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price::sqrt_price_x96_to_price;
+
+    #[test]
+    fn test_price_limit_direction_and_round_trip() {
+        let pair = TokenPair::wmon_usdc();
+        let price = 0.03;
+        let at_price = sqrt_price_limit(&pair, pair.base, price, 0);
+        assert!((sqrt_price_x96_to_price(at_price) - price).abs() / price < 1e-9);
+
+        // Selling WMON (token0) moves the price down, buying moves it up
+        let sell = sqrt_price_limit(&pair, pair.base, price, 100);
+        let buy = sqrt_price_limit(&pair, pair.quote, price, 100);
+        assert!(sell < at_price && buy > at_price);
+        assert!((sqrt_price_x96_to_price(sell) - price * 0.99).abs() / price < 1e-9);
+
+        let swap = pancake_v3::build_exact_input_single(pair.base, pair.quote, 500, Address::ZERO, U256::from(1), U256::ZERO, 1).unwrap();
+        let limited = with_price_limit(RouterType::PancakeV3, swap, sell).unwrap();
+        let inner = &pancake_v3::multicallCall::abi_decode(&limited).unwrap().data[0];
+        assert_eq!(pancake_v3::exactInputSingleCall::abi_decode(inner).unwrap().params.sqrtPriceLimitX96, sell);
+        assert!(with_price_limit(RouterType::LfjLB, Bytes::new(), sell).is_err());
+    }
+
+    #[test]
+    fn test_native_swaps_carry_value_and_unwrap() {
+        let pair = TokenPair::wmon_usdc();
+        let routers = crate::config::get_routers();
+        let router = |name: &str| routers.iter().find(|r| r.name == name).unwrap().clone();
+        let wallet = Address::repeat_byte(0x11);
+        let (amount, min_out) = (U256::from(5_000_000_000_000_000_000u128), U256::from(100_000));
+        let sell = ExactInputSwap { token_in: pair.base, token_out: pair.quote, amount_in: amount, amount_out_min: min_out, recipient: wallet, deadline: 1 };
+        let buy = ExactInputSwap { token_in: pair.quote, token_out: pair.base, amount_in: min_out, amount_out_min: amount, ..sell };
+
+        // Selling MON: value is the input and the swap pays the wallet directly
+        let (calldata, value) = build_native_swap_calldata(&router("Uniswap"), NativeLeg::In, sell, None).unwrap();
+        assert_eq!(value, amount);
+        assert_eq!(uniswap_v3::exactInputSingleCall::abi_decode(&calldata).unwrap().params.recipient, wallet);
+
+        // Buying MON on Pancake: the swap pays the router, which unwraps to the wallet
+        let (calldata, value) = build_native_swap_calldata(&router("PancakeSwap1"), NativeLeg::Out, buy, None).unwrap();
+        assert_eq!(value, U256::ZERO);
+        let call = pancake_v3::multicallCall::abi_decode(&calldata).unwrap();
+        assert_eq!(pancake_v3::exactInputSingleCall::abi_decode(&call.data[0]).unwrap().params.recipient, ROUTER_ADDRESS_THIS);
+        assert_eq!(uniswap_v3::unwrapWETH9Call::abi_decode(&call.data[1]).unwrap().recipient, wallet);
+
+        let (calldata, value) = build_native_swap_calldata(&router("LFJ"), NativeLeg::In, sell, None).unwrap();
+        assert_eq!(value, amount);
+        assert_eq!(calldata[..4], lfj::swapExactNATIVEForTokensCall::SELECTOR);
+
+        assert!(build_native_swap_calldata(&router("MondayTrade"), NativeLeg::In, sell, None).is_err());
     }
 }
@@ -27,6 +27,26 @@ sol! {
         external
         payable
         returns (uint256 amountOut);
+
+    /// ExactOutputSingleParams for original ISwapRouter (deadline in struct)
+    #[derive(Debug)]
+    struct ExactOutputSingleParams {
+        address tokenIn;
+        address tokenOut;
+        uint24 fee;
+        address recipient;
+        uint256 deadline;
+        uint256 amountOut;
+        uint256 amountInMaximum;
+        uint160 sqrtPriceLimitX96;
+    }
+
+    /// Swaps minimum input for exact output
+    #[derive(Debug)]
+    function exactOutputSingle(ExactOutputSingleParams calldata params)
+        external
+        payable
+        returns (uint256 amountIn);
 }
 
 /// Build swap calldata for Monday Trade router (original ISwapRouter style - deadline IN struct)
@@ -56,6 +76,30 @@ pub fn build_exact_input_single(
     Ok(Bytes::from(calldata))
 }
 
+/// Build exactOutputSingle calldata for Monday Trade router (deadline IN struct)
+pub fn build_exact_output_single(
+    token_in: Address,
+    token_out: Address,
+    fee: u32,
+    recipient: Address,
+    amount_out: U256,
+    amount_in_max: U256,
+    deadline: u64,
+) -> Result<Bytes> {
+    let params = ExactOutputSingleParams {
+        tokenIn: token_in,
+        tokenOut: token_out,
+        fee: Uint::from(fee),
+        recipient,
+        deadline: U256::from(deadline),
+        amountOut: amount_out,
+        amountInMaximum: amount_in_max,
+        sqrtPriceLimitX96: U160::ZERO,
+    };
+
+    Ok(Bytes::from(exactOutputSingleCall { params }.abi_encode()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,6 +22,23 @@ sol! {
         payable
         returns (uint256 amountOut);
 
+    #[derive(Debug)]
+    struct ExactOutputSingleParams {
+        address tokenIn;
+        address tokenOut;
+        uint24 fee;
+        address recipient;
+        uint256 amountOut;
+        uint256 amountInMaximum;
+        uint160 sqrtPriceLimitX96;
+    }
+
+    #[derive(Debug)]
+    function exactOutputSingle(ExactOutputSingleParams calldata params)
+        external
+        payable
+        returns (uint256 amountIn);
+
     // REQUIRED: Multicall wrapper with deadline
     #[derive(Debug)]
     function multicall(uint256 deadline, bytes[] calldata data)
@@ -63,6 +80,35 @@ pub fn build_exact_input_single(
     Ok(Bytes::from(calldata))
 }
 
+pub fn build_exact_output_single(
+    token_in: Address,
+    token_out: Address,
+    fee: u32,
+    recipient: Address,
+    amount_out: U256,
+    amount_in_max: U256,
+    deadline: u64,
+) -> Result<Bytes> {
+    let params = ExactOutputSingleParams {
+        tokenIn: token_in,
+        tokenOut: token_out,
+        fee: Uint::from(fee),
+        recipient,
+        amountOut: amount_out,
+        amountInMaximum: amount_in_max,
+        sqrtPriceLimitX96: U160::ZERO,
+    };
+
+    // Same multicall(deadline) wrapper as exactInputSingle
+    let inner_calldata = exactOutputSingleCall { params }.abi_encode();
+    let calldata = multicallCall {
+        deadline: U256::from(deadline),
+        data: vec![Bytes::from(inner_calldata)],
+    }.abi_encode();
+
+    Ok(Bytes::from(calldata))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,158 @@
+//! Offline check that every configured router builds the calldata its type expects
+//!
+//! `SelfTest` runs `build_swap_calldata` for each router and direction with
+//! dummy amounts, then decodes the result: the selector must be the function
+//! that router type calls (for PancakeSwap, `multicall` wrapping
+//! `exactInputSingle`) and the encoded tokens must be the pair's, in order.
+//! Nothing touches the chain, so this checks the config against the builders,
+//! not the deployed contract.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::{SolCall, SolValue};
+use eyre::{eyre, Result};
+
+use super::{build_swap_calldata, lfj, monday, pancake_v3, uniswap_v3, uniswap_v4};
+use crate::config::{RouterConfig, RouterType, TokenPair, WMON_ADDRESS};
+use crate::execution::SwapDirection;
+
+/// Dummy recipient; never used on chain
+const RECIPIENT: Address = Address::repeat_byte(0x11);
+
+/// Function a router type's swap calldata must call
+pub fn expected_function(router_type: RouterType) -> (&'static str, [u8; 4]) {
+    match router_type {
+        RouterType::UniswapV3 => ("exactInputSingle", uniswap_v3::exactInputSingleCall::SELECTOR),
+        RouterType::PancakeV3 => ("multicall(exactInputSingle)", pancake_v3::multicallCall::SELECTOR),
+        RouterType::LfjLB => ("swapExactTokensForTokens", lfj::swapExactTokensForTokensCall::SELECTOR),
+        RouterType::MondayTrade => ("exactInputSingle", monday::exactInputSingleCall::SELECTOR),
+        RouterType::UniswapV4 => ("execute(V4_SWAP)", uniswap_v4::executeCall::SELECTOR),
+    }
+}
+
+/// (token_in, token_out) encoded in calldata built for `router_type`
+fn decode_tokens(router_type: RouterType, calldata: &[u8]) -> Result<(Address, Address)> {
+    match router_type {
+        RouterType::UniswapV3 => {
+            let p = uniswap_v3::exactInputSingleCall::abi_decode(calldata)?.params;
+            Ok((p.tokenIn, p.tokenOut))
+        }
+        RouterType::PancakeV3 => {
+            let call = pancake_v3::multicallCall::abi_decode(calldata)?;
+            let [inner] = &call.data[..] else {
+                return Err(eyre!("multicall wraps {} calls, expected 1", call.data.len()));
+            };
+            let p = pancake_v3::exactInputSingleCall::abi_decode(inner)?.params;
+            Ok((p.tokenIn, p.tokenOut))
+        }
+        RouterType::LfjLB => {
+            let call = lfj::swapExactTokensForTokensCall::abi_decode(calldata)?;
+            match call.path.tokenPath[..] {
+                [token_in, token_out] => Ok((token_in, token_out)),
+                _ => Err(eyre!("path has {} tokens, expected 2", call.path.tokenPath.len())),
+            }
+        }
+        RouterType::MondayTrade => {
+            let p = monday::exactInputSingleCall::abi_decode(calldata)?.params;
+            Ok((p.tokenIn, p.tokenOut))
+        }
+        RouterType::UniswapV4 => {
+            let call = uniswap_v4::executeCall::abi_decode(calldata)?;
+            let index = call
+                .commands
+                .iter()
+                .position(|c| *c == uniswap_v4::V4_SWAP)
+                .ok_or_else(|| eyre!("no V4_SWAP command"))?;
+            let input = call.inputs.get(index).ok_or_else(|| eyre!("V4_SWAP has no input"))?;
+            let (_actions, params) = <(Bytes, Vec<Bytes>)>::abi_decode_params(input)?;
+            let first = params.first().ok_or_else(|| eyre!("V4_SWAP has no actions"))?;
+            let swap = uniswap_v4::ExactInputSingleParams::abi_decode(first)?;
+            let key = swap.poolKey;
+            let (currency_in, currency_out) =
+                if swap.zeroForOne { (key.currency0, key.currency1) } else { (key.currency1, key.currency0) };
+            // Native pools key WMON as address(0)
+            let token = |c: Address| if c == Address::ZERO { WMON_ADDRESS } else { c };
+            Ok((token(currency_in), token(currency_out)))
+        }
+    }
+}
+
+/// Build one swap for `router` and check its selector and tokens
+pub fn check_router(router: &RouterConfig, pair: &TokenPair, direction: SwapDirection) -> Result<()> {
+    let (token_in, token_out, decimals_in, _) = direction.tokens(pair);
+    let amount_in = U256::from(10u64).pow(U256::from(decimals_in));
+    let calldata = build_swap_calldata(router, token_in, token_out, amount_in, U256::from(1), RECIPIENT, u64::MAX)?;
+
+    let (name, selector) = expected_function(router.router_type);
+    if calldata.len() < 4 || calldata[..4] != selector {
+        return Err(eyre!("selector 0x{} is not {}", alloy::hex::encode(calldata.get(..4).unwrap_or(&calldata)), name));
+    }
+
+    let (encoded_in, encoded_out) = decode_tokens(router.router_type, &calldata)?;
+    if (encoded_in, encoded_out) != (token_in, token_out) {
+        return Err(eyre!("encodes {:?} -> {:?}, expected {:?} -> {:?}", encoded_in, encoded_out, token_in, token_out));
+    }
+    Ok(())
+}
+
+/// Check every router in both directions and print a table; Err if any fail
+pub fn run_self_test(routers: &[RouterConfig], pair: &TokenPair) -> Result<()> {
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("  ROUTER CALLDATA SELF-TEST (offline)");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  {:<18} {:<12} {:<28} {:<5} {:<5}", "Router", "Type", "Function", "Sell", "Buy");
+    println!("  ───────────────────────────────────────────────────────────────");
+
+    let mut failures = Vec::new();
+    for router in routers {
+        let (function, _) = expected_function(router.router_type);
+        let mut cells = Vec::new();
+        for direction in [SwapDirection::Sell, SwapDirection::Buy] {
+            match check_router(router, pair, direction) {
+                Ok(()) => cells.push("PASS"),
+                Err(e) => {
+                    cells.push("FAIL");
+                    failures.push(format!("{} {:?}: {}", router.name, direction, e));
+                }
+            }
+        }
+        println!("  {:<18} {:<12} {:<28} {:<5} {:<5}",
+            router.name, format!("{:?}", router.router_type), function, cells[0], cells[1]);
+    }
+
+    println!("───────────────────────────────────────────────────────────────");
+    for failure in &failures {
+        println!("  ❌ {}", failure);
+    }
+    println!("  {} router(s), {} check(s) failed", routers.len(), failures.len());
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(eyre!("{} router calldata check(s) failed", failures.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::get_routers;
+
+    #[test]
+    fn test_default_routers_pass_and_wrong_type_fails() {
+        let pair = TokenPair::wmon_usdc();
+        for router in get_routers() {
+            for direction in [SwapDirection::Sell, SwapDirection::Buy] {
+                check_router(&router, &pair, direction).unwrap();
+            }
+        }
+
+        let mut v4 = get_routers().remove(0);
+        v4.router_type = RouterType::UniswapV4;
+        check_router(&v4, &pair, SwapDirection::Sell).unwrap();
+
+        // Selector check catches calldata that doesn't match the expected function
+        let calldata = build_swap_calldata(&v4, WMON_ADDRESS, pair.quote, U256::from(1), U256::ZERO, RECIPIENT, 0).unwrap();
+        assert!(decode_tokens(RouterType::LfjLB, &calldata).is_err());
+    }
+}
@@ -21,6 +21,34 @@ sol! {
         external
         payable
         returns (uint256 amountOut);
+
+    #[derive(Debug)]
+    struct ExactOutputSingleParams {
+        address tokenIn;
+        address tokenOut;
+        uint24 fee;
+        address recipient;
+        uint256 amountOut;
+        uint256 amountInMaximum;
+        uint160 sqrtPriceLimitX96;
+    }
+
+    #[derive(Debug)]
+    function exactOutputSingle(ExactOutputSingleParams calldata params)
+        external
+        payable
+        returns (uint256 amountIn);
+
+    #[derive(Debug)]
+    function multicall(bytes[] calldata data)
+        external
+        payable
+        returns (bytes[] memory results);
+
+    /// Unwrap the router's WMON balance and send it to `recipient` as MON
+    /// (same on PancakeSwap's SmartRouter)
+    #[derive(Debug)]
+    function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
 }
 
 pub fn build_exact_input_single(
@@ -47,6 +75,27 @@ pub fn build_exact_input_single(
     Ok(Bytes::from(calldata))
 }
 
+pub fn build_exact_output_single(
+    token_in: Address,
+    token_out: Address,
+    fee: u32,
+    recipient: Address,
+    amount_out: U256,
+    amount_in_max: U256,
+) -> Result<Bytes> {
+    let params = ExactOutputSingleParams {
+        tokenIn: token_in,
+        tokenOut: token_out,
+        fee: Uint::from(fee),
+        recipient,
+        amountOut: amount_out,
+        amountInMaximum: amount_in_max,
+        sqrtPriceLimitX96: U160::ZERO,
+    };
+
+    Ok(Bytes::from(exactOutputSingleCall { params }.abi_encode()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +109,14 @@ mod tests {
         println!("Uniswap Selector: 0x{:02x}{:02x}{:02x}{:02x}",
             calldata[0], calldata[1], calldata[2], calldata[3]);
     }
+
+    #[test]
+    fn test_exact_output_selector() {
+        // SwapRouter02 exactOutputSingle((address,address,uint24,address,uint256,uint256,uint160))
+        let calldata = build_exact_output_single(
+            Address::ZERO, Address::ZERO, 3000, Address::ZERO,
+            U256::from(1000000u64), U256::from(1100000u64)
+        ).unwrap();
+        assert_eq!(&calldata[..4], &[0x50, 0x23, 0xb4, 0xdf]);
+    }
 }
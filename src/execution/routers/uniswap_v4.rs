@@ -0,0 +1,194 @@
+//! Uniswap V4 swaps through the Universal Router
+//!
+//! V4 pools live in the singleton PoolManager and are identified by a PoolKey
+//! (currency0, currency1, fee, tickSpacing, hooks) rather than an address.
+//! A swap is one `execute(commands, inputs, deadline)` call whose `V4_SWAP`
+//! command runs the V4 actions SWAP_EXACT_IN_SINGLE -> SETTLE -> TAKE.
+//!
+//! The bot always holds WMON. For pools keyed on native MON (address(0)) the
+//! router unwraps WMON before the swap (sell) or wraps the MON output back to
+//! WMON for the recipient (buy), so no msg.value is needed.
+//!
+//! Tokens are pulled via Permit2: approve WMON/USDC to `PERMIT2`, then
+//! `Permit2.approve(token, universalRouter, ...)` (see `build_permit2_approve`).
+
+use alloy::primitives::{address, aliases::I24, Address, Bytes, U160, U256};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+use eyre::{eyre, Result};
+
+use crate::config::{V4PoolParams, WMON_ADDRESS};
+
+/// Canonical Permit2 (same CREATE2 address on every chain)
+pub const PERMIT2: Address = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+
+// Universal Router commands
+const PERMIT2_TRANSFER_FROM: u8 = 0x02;
+const WRAP_ETH: u8 = 0x0b;
+const UNWRAP_WETH: u8 = 0x0c;
+pub(super) const V4_SWAP: u8 = 0x10;
+
+// V4 router actions
+const SWAP_EXACT_IN_SINGLE: u8 = 0x06;
+const SETTLE: u8 = 0x0b;
+const TAKE: u8 = 0x0e;
+
+// Universal Router recipient/amount sentinels
+const ADDRESS_THIS: Address = address!("0000000000000000000000000000000000000002");
+const OPEN_DELTA: U256 = U256::ZERO;
+
+sol! {
+    #[derive(Debug)]
+    struct PoolKey {
+        address currency0;
+        address currency1;
+        uint24 fee;
+        int24 tickSpacing;
+        address hooks;
+    }
+
+    #[derive(Debug)]
+    struct ExactInputSingleParams {
+        PoolKey poolKey;
+        bool zeroForOne;
+        uint128 amountIn;
+        uint128 amountOutMinimum;
+        bytes hookData;
+    }
+
+    #[derive(Debug)]
+    function execute(bytes commands, bytes[] inputs, uint256 deadline) external payable;
+
+    #[derive(Debug)]
+    function approve(address token, address spender, uint160 amount, uint48 expiration) external;
+}
+
+/// The PoolKey of a swap, with the currencies given in swap order
+#[derive(Debug, Clone, Copy)]
+pub struct V4SwapKey {
+    pub token_in: Address,
+    pub token_out: Address,
+    pub fee: u32,
+    pub pool: V4PoolParams,
+}
+
+/// Build `execute` calldata for an exact-input swap of `key.token_in` -> `key.token_out`
+pub fn build_exact_input_single(
+    key: V4SwapKey,
+    recipient: Address,
+    amount_in: U256,
+    amount_out_min: U256,
+    deadline: u64,
+) -> Result<Bytes> {
+    let V4SwapKey { token_in, token_out, fee, pool } = key;
+    let amount_in_128: u128 = amount_in.try_into().map_err(|_| eyre!("V4 amountIn exceeds uint128"))?;
+    let amount_out_min_128: u128 = amount_out_min.try_into().map_err(|_| eyre!("V4 amountOutMinimum exceeds uint128"))?;
+
+    // WMON is addressed as address(0) in native pools
+    let currency = |token: Address| if pool.native && token == WMON_ADDRESS { Address::ZERO } else { token };
+    let (currency_in, currency_out) = (currency(token_in), currency(token_out));
+    let (currency0, currency1) = if currency_in < currency_out {
+        (currency_in, currency_out)
+    } else {
+        (currency_out, currency_in)
+    };
+
+    let swap = ExactInputSingleParams {
+        poolKey: PoolKey {
+            currency0,
+            currency1,
+            fee: alloy::primitives::Uint::from(fee),
+            tickSpacing: I24::try_from(pool.tick_spacing).map_err(|_| eyre!("tickSpacing out of int24 range"))?,
+            hooks: pool.hooks,
+        },
+        zeroForOne: currency_in == currency0,
+        amountIn: amount_in_128,
+        amountOutMinimum: amount_out_min_128,
+        hookData: Bytes::new(),
+    };
+
+    let native_in = currency_in == Address::ZERO;
+    let native_out = currency_out == Address::ZERO;
+
+    // Native input is paid from the router's own (unwrapped) balance, ERC20 input
+    // by the caller through Permit2
+    let settle = (currency_in, OPEN_DELTA, !native_in).abi_encode_params();
+    // Native output goes to the router so it can be wrapped for the recipient
+    let take = (currency_out, if native_out { ADDRESS_THIS } else { recipient }, OPEN_DELTA).abi_encode_params();
+
+    let actions = Bytes::from(vec![SWAP_EXACT_IN_SINGLE, SETTLE, TAKE]);
+    let params = vec![Bytes::from(swap.abi_encode()), Bytes::from(settle), Bytes::from(take)];
+    let v4_input = Bytes::from((actions, params).abi_encode_params());
+
+    let mut commands = Vec::new();
+    let mut inputs = Vec::new();
+    if native_in {
+        let amount_160 = U160::from(amount_in_128);
+        commands.push(PERMIT2_TRANSFER_FROM);
+        inputs.push(Bytes::from((WMON_ADDRESS, ADDRESS_THIS, amount_160).abi_encode_params()));
+        commands.push(UNWRAP_WETH);
+        inputs.push(Bytes::from((ADDRESS_THIS, amount_in).abi_encode_params()));
+    }
+    commands.push(V4_SWAP);
+    inputs.push(v4_input);
+    if native_out {
+        // CONTRACT_BALANCE: wrap everything the swap paid out
+        commands.push(WRAP_ETH);
+        inputs.push(Bytes::from((recipient, U256::from(1) << 255).abi_encode_params()));
+    }
+
+    let call = executeCall {
+        commands: Bytes::from(commands),
+        inputs,
+        deadline: U256::from(deadline),
+    };
+    Ok(Bytes::from(call.abi_encode()))
+}
+
+/// Permit2 allowance for the Universal Router (send to `PERMIT2`)
+pub fn build_permit2_approve(token: Address, universal_router: Address) -> Bytes {
+    let call = approveCall {
+        token,
+        spender: universal_router,
+        amount: U160::MAX,
+        expiration: alloy::primitives::Uint::<48, 1>::MAX,
+    };
+    Bytes::from(call.abi_encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::USDC_ADDRESS;
+
+    fn decode(calldata: &Bytes) -> executeCall {
+        executeCall::abi_decode(calldata).unwrap()
+    }
+
+    #[test]
+    fn test_commands_for_wrapped_and_native_pools() {
+        let amount = U256::from(10u64.pow(18));
+        let wrapped = V4PoolParams::standard(3000);
+        assert_eq!(wrapped.tick_spacing, 60);
+
+        let sell_key = |pool| V4SwapKey { token_in: WMON_ADDRESS, token_out: USDC_ADDRESS, fee: 3000, pool };
+        let call = decode(&build_exact_input_single(sell_key(wrapped), Address::ZERO, amount, U256::ZERO, 1).unwrap());
+        assert_eq!(call.commands.as_ref(), &[V4_SWAP]);
+
+        // Native pool: sell unwraps WMON first, buy wraps the MON output
+        let native = V4PoolParams { native: true, ..wrapped };
+        let sell = decode(&build_exact_input_single(sell_key(native), Address::ZERO, amount, U256::ZERO, 1).unwrap());
+        assert_eq!(sell.commands.as_ref(), &[PERMIT2_TRANSFER_FROM, UNWRAP_WETH, V4_SWAP]);
+
+        let buy_key = V4SwapKey { token_in: USDC_ADDRESS, token_out: WMON_ADDRESS, fee: 3000, pool: native };
+        let buy = decode(&build_exact_input_single(buy_key, Address::ZERO, U256::from(1_000_000u64), U256::ZERO, 1).unwrap());
+        assert_eq!(buy.commands.as_ref(), &[V4_SWAP, WRAP_ETH]);
+
+        // Native MON sorts first, so selling it is zeroForOne
+        let (actions, params) = <(Bytes, Vec<Bytes>)>::abi_decode_params(&sell.inputs[2]).unwrap();
+        assert_eq!(actions.as_ref(), &[SWAP_EXACT_IN_SINGLE, SETTLE, TAKE]);
+        let swap = ExactInputSingleParams::abi_decode(&params[0]).unwrap();
+        assert_eq!(swap.poolKey.currency0, Address::ZERO);
+        assert!(swap.zeroForOne);
+    }
+}
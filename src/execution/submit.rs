@@ -0,0 +1,284 @@
+//! Where arb transactions go: the public mempool or a private relay
+//!
+//! `--submit private --relay-url <url>` signs each arb transaction locally and
+//! POSTs the raw bytes to the relay's `eth_sendRawTransaction`, keeping it out
+//! of the public mempool until it lands. If the relay rejects it (or can't be
+//! reached) the same signed transaction is broadcast through the node instead,
+//! with a warning. The mode is set once at startup; the default is public.
+//!
+//! `send_with_nonce_recovery` adds recovery from nonce desync on top:
+//! "nonce too low" resyncs the sender's counter from the chain and resends
+//! once with a fresh nonce. "already known" means the node already holds
+//! this exact transaction, so its hash is returned for the caller to wait on.
+//! Signing is deterministic, so the local signers recompute that hash.
+//! When a send still fails, `release_unsent_nonce` hands the nonce back.
+//! Accepted sends record their nonce for `NONCE_PERSIST` (`nonce::mark_sent`).
+
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, Bytes, TxHash};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use eyre::{eyre, Result};
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::nonce;
+
+/// Relay request timeout; the caller's send timeout still bounds the fallback
+const RELAY_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitMode {
+    Public,
+    /// Relay JSON-RPC URL
+    Private(String),
+}
+
+impl SubmitMode {
+    /// From `--submit` and `--relay-url`
+    pub fn from_args(submit: &str, relay_url: Option<&str>) -> Result<Self> {
+        match (submit.to_lowercase().as_str(), relay_url) {
+            ("public", _) => Ok(Self::Public),
+            ("private", Some(url)) => Ok(Self::Private(url.to_string())),
+            ("private", None) => Err(eyre!("--submit private requires --relay-url")),
+            _ => Err(eyre!("Unknown submit mode '{}'. Expected public or private", submit)),
+        }
+    }
+}
+
+/// Path a transaction actually took
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitPath {
+    Public,
+    Private,
+    /// Relay rejected it; broadcast publicly instead
+    PublicFallback,
+}
+
+impl fmt::Display for SubmitPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Public => write!(f, "public mempool"),
+            Self::Private => write!(f, "private relay"),
+            Self::PublicFallback => write!(f, "public mempool (relay fallback)"),
+        }
+    }
+}
+
+struct PrivateRelay {
+    url: String,
+    client: reqwest::Client,
+}
+
+static RELAY: OnceLock<PrivateRelay> = OnceLock::new();
+
+/// Every wallet that may send an arb, picked by the transaction's `from`
+static WALLET: OnceLock<EthereumWallet> = OnceLock::new();
+
+/// The same wallets' keys, for signing off-chain messages (permits)
+static SIGNERS: OnceLock<Vec<PrivateKeySigner>> = OnceLock::new();
+
+/// Install the submit mode for this run; `signers` are the wallets arbs send from
+pub fn init_submit_mode(mode: SubmitMode, signers: &[PrivateKeySigner]) -> Result<()> {
+    let (first, rest) = signers.split_first().ok_or_else(|| eyre!("Transaction submission needs a signer"))?;
+    let mut wallet = EthereumWallet::from(first.clone());
+    for signer in rest {
+        wallet.register_signer(signer.clone());
+    }
+    WALLET.set(wallet).map_err(|_| eyre!("Submit mode already set"))?;
+    let _ = SIGNERS.set(signers.to_vec());
+
+    let SubmitMode::Private(url) = mode else { return Ok(()) };
+    println!("  Submit:          private relay {}", url);
+    RELAY
+        .set(PrivateRelay { url, client: reqwest::Client::new() })
+        .map_err(|_| eyre!("Submit mode already set"))
+}
+
+/// Key for `address`, if it is one of the arb wallets
+pub fn local_signer(address: Address) -> Option<&'static PrivateKeySigner> {
+    SIGNERS.get()?.iter().find(|s| s.address() == address)
+}
+
+/// Send errors that mean the nonce, not the transaction, is the problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendErrorKind {
+    /// The nonce was already used: the local counter is behind the chain
+    NonceTooLow,
+    /// The node already holds this exact transaction
+    AlreadyKnown,
+    Other,
+}
+
+/// Classify a `send_transaction` error by the node's message
+pub fn classify_send_error(error: &eyre::Report) -> SendErrorKind {
+    let message = format!("{:#}", error).to_lowercase();
+    if message.contains("nonce too low") {
+        SendErrorKind::NonceTooLow
+    } else if message.contains("already known") || message.contains("known transaction") {
+        SendErrorKind::AlreadyKnown
+    } else {
+        SendErrorKind::Other
+    }
+}
+
+/// Decode an `eth_sendRawTransaction` response into the hash, or the relay's error
+pub fn parse_relay_response(body: &serde_json::Value) -> Result<TxHash> {
+    if let Some(error) = body.get("error") {
+        let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+        return Err(eyre!("relay rejected transaction: {}", message));
+    }
+    body.get("result")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.parse().ok())
+        .ok_or_else(|| eyre!("relay returned no transaction hash"))
+}
+
+async fn post_to_relay(relay: &PrivateRelay, raw: &Bytes) -> Result<TxHash> {
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_sendRawTransaction",
+        "params": [raw],
+    });
+    let response = relay.client.post(&relay.url).json(&request).timeout(RELAY_TIMEOUT).send().await?;
+    let body: serde_json::Value = response.json().await?;
+    parse_relay_response(&body)
+}
+
+/// Send a fully-populated transaction the configured way: through
+/// `provider_with_signer` in public mode, or signed here and posted to the
+/// relay in private mode
+pub async fn send_transaction<P: Provider>(
+    provider_with_signer: &P,
+    tx: TransactionRequest,
+) -> Result<(TxHash, SubmitPath)> {
+    let sender = tx.from.zip(tx.nonce);
+    let sent = send_unrecorded(provider_with_signer, tx).await?;
+    if let Some((from, nonce)) = sender {
+        nonce::mark_sent(from, nonce, sent.0);
+    }
+    Ok(sent)
+}
+
+/// `send_transaction` without recording the nonce as sent
+async fn send_unrecorded<P: Provider>(
+    provider_with_signer: &P,
+    tx: TransactionRequest,
+) -> Result<(TxHash, SubmitPath)> {
+    let Some(relay) = RELAY.get() else {
+        let pending = provider_with_signer.send_transaction(tx).await?;
+        return Ok((*pending.tx_hash(), SubmitPath::Public));
+    };
+
+    let wallet = WALLET.get().ok_or_else(|| eyre!("Private relay has no signer"))?;
+    let envelope = tx.build(wallet).await.map_err(|e| eyre!("Failed to sign transaction: {}", e))?;
+    let raw = Bytes::from(envelope.encoded_2718());
+
+    match post_to_relay(relay, &raw).await {
+        Ok(hash) => {
+            println!("    Submitted via {}", SubmitPath::Private);
+            Ok((hash, SubmitPath::Private))
+        }
+        Err(e) => {
+            tracing::warn!("Private relay failed ({}), broadcasting publicly", e);
+            println!("    ⚠️  Relay failed: {} - falling back to public mempool", e);
+            let pending = provider_with_signer.send_raw_transaction(&raw).await?;
+            println!("    Submitted via {}", SubmitPath::PublicFallback);
+            Ok((*pending.tx_hash(), SubmitPath::PublicFallback))
+        }
+    }
+}
+
+/// Hash of `tx` once signed by the installed wallets, if there are any
+async fn signed_hash(tx: TransactionRequest) -> Option<TxHash> {
+    let envelope = tx.build(WALLET.get()?).await.ok()?;
+    Some(*envelope.tx_hash())
+}
+
+/// `send_transaction`, recovering from "nonce too low" (one resend with a
+/// fresh nonce) and "already known" (the pending transaction's hash). Other
+/// errors are returned unchanged.
+pub async fn send_with_nonce_recovery<P: Provider>(
+    provider_with_signer: &P,
+    tx: TransactionRequest,
+) -> Result<(TxHash, SubmitPath)> {
+    let error = match send_transaction(provider_with_signer, tx.clone()).await {
+        Ok(sent) => return Ok(sent),
+        Err(e) => e,
+    };
+
+    match (classify_send_error(&error), tx.from) {
+        (SendErrorKind::NonceTooLow, Some(from)) => {
+            let pending = nonce::resync(provider_with_signer, from).await?;
+            let fresh = nonce::next_nonce_for(from);
+            tracing::warn!("Nonce {:?} too low for {:?} (chain pending {}), resending with {}", tx.nonce, from, pending, fresh);
+            println!("    ⚠️  Nonce too low - resynced to {}, resending with nonce {}", pending, fresh);
+            send_transaction(provider_with_signer, tx.nonce(fresh)).await
+        }
+        (SendErrorKind::AlreadyKnown, _) => {
+            let sender = tx.from.zip(tx.nonce);
+            let hash = signed_hash(tx).await.ok_or(error)?;
+            if let Some((from, nonce)) = sender {
+                nonce::mark_sent(from, nonce, hash);
+            }
+            println!("    Transaction already known to the node - waiting for {:?}", hash);
+            let path = if RELAY.get().is_some() { SubmitPath::PublicFallback } else { SubmitPath::Public };
+            Ok((hash, path))
+        }
+        _ => Err(error),
+    }
+}
+
+/// After `tx` failed to send: give its nonce back so later TXs don't stall
+/// behind the gap. A send that errored or timed out may still have reached
+/// the node, so the nonce is only released while the chain's pending nonce
+/// hasn't passed it.
+pub async fn release_unsent_nonce<P: Provider>(provider: &P, tx: &TransactionRequest) {
+    let (Some(from), Some(nonce)) = (tx.from, tx.nonce) else { return };
+    match provider.get_transaction_count(from).pending().await {
+        Ok(pending) if pending > nonce => {}
+        Ok(_) => {
+            if nonce::release(from, nonce) {
+                println!("    Nonce {} was not used - released for the next transaction", nonce);
+            } else {
+                tracing::warn!("Nonce {} for {:?} was not sent and later nonces are taken; they wait until it is reused", nonce, from);
+            }
+        }
+        Err(e) => tracing::warn!("Could not check whether nonce {} for {:?} reached the node: {}", nonce, from, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_mode_and_relay_response() {
+        assert_eq!(SubmitMode::from_args("public", None).unwrap(), SubmitMode::Public);
+        assert_eq!(
+            SubmitMode::from_args("Private", Some("https://relay.example")).unwrap(),
+            SubmitMode::Private("https://relay.example".into())
+        );
+        assert!(SubmitMode::from_args("private", None).is_err());
+        assert!(SubmitMode::from_args("bundle", None).is_err());
+
+        let hash = TxHash::with_last_byte(7);
+        let ok = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": hash.to_string() });
+        assert_eq!(parse_relay_response(&ok).unwrap(), hash);
+        let rejected = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32000, "message": "nonce too low" } });
+        assert!(parse_relay_response(&rejected).unwrap_err().to_string().contains("nonce too low"));
+    }
+
+    #[test]
+    fn test_classify_send_error() {
+        let classify = |message: &str| classify_send_error(&eyre!("{}", message));
+        assert_eq!(classify("server returned an error response: error code -32000: Nonce too low"), SendErrorKind::NonceTooLow);
+        assert_eq!(classify("error code -32000: already known"), SendErrorKind::AlreadyKnown);
+        assert_eq!(classify("relay rejected transaction: nonce too low"), SendErrorKind::NonceTooLow);
+        assert_eq!(classify("insufficient funds for gas * price + value"), SendErrorKind::Other);
+    }
+}
@@ -1,4 +1,3 @@
-use alloy::network::TransactionBuilder;
 use alloy::primitives::{Address, Bytes, TxHash, U256};
 use alloy::providers::Provider;
 use alloy::rpc::types::TransactionReceipt;
@@ -8,13 +7,14 @@ use eyre::{eyre, Result};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::{interval, timeout};
 
-use crate::config::{RouterConfig, RouterType, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS};
-use crate::node_config::NodeConfig;
+use crate::config::{RouterConfig, RouterType, TokenPair};
+use crate::error::{ArbError, ArbResult};
+use crate::node_config::{receipt_wait, NodeConfig};
 use crate::nonce::next_nonce;
-use super::routers::build_swap_calldata;
-
-// Monad mainnet chain ID
-const MONAD_CHAIN_ID: u64 = 143;
+use crate::gas::GasFees;
+use super::fast_arb::build_swap_request;
+use super::routers::{apply_price_limit, build_native_swap_calldata, build_swap_calldata, price_limit_for, ExactInputSwap, NativeLeg};
+use super::routers::uniswap_v4::PERMIT2;
 
 // Gas estimation buffer (10% for Monad - keep tight to minimize costs!)
 const GAS_BUFFER_PERCENT: u64 = 10;
@@ -41,9 +41,20 @@ pub enum SwapDirection {
     Sell,  // WMON -> USDC
 }
 
+impl SwapDirection {
+    /// (token_in, token_out, decimals_in, decimals_out) for this direction on `pair`
+    pub fn tokens(&self, pair: &TokenPair) -> (Address, Address, u8, u8) {
+        match self {
+            SwapDirection::Sell => (pair.base, pair.quote, pair.base_decimals, pair.quote_decimals),
+            SwapDirection::Buy => (pair.quote, pair.base, pair.quote_decimals, pair.base_decimals),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SwapParams {
     pub router: RouterConfig,
+    pub pair: TokenPair,
     pub direction: SwapDirection,
     pub amount_in: f64,          // Human-readable amount
     pub slippage_bps: u32,       // e.g., 100 = 1%
@@ -93,6 +104,7 @@ fn get_fallback_gas_limit(router_type: RouterType) -> u64 {
         RouterType::PancakeV3 => FALLBACK_GAS_LIMIT_COMPLEX, // Multicall wrapper
         RouterType::LfjLB => FALLBACK_GAS_LIMIT_COMPLEX,     // Complex path routing
         RouterType::MondayTrade => FALLBACK_GAS_LIMIT_SIMPLE,
+        RouterType::UniswapV4 => FALLBACK_GAS_LIMIT_COMPLEX,  // Universal Router + PoolManager unlock
     }
 }
 
@@ -106,11 +118,13 @@ async fn estimate_gas_with_buffer<P: Provider>(
     to: Address,
     from: Address,
     calldata: &Bytes,
+    value: U256,
     router_type: RouterType,
 ) -> u64 {
     let tx = alloy::rpc::types::TransactionRequest::default()
         .to(to)
         .from(from)
+        .value(value)
         .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
 
     match provider.estimate_gas(tx).await {
@@ -129,16 +143,17 @@ async fn estimate_gas_with_buffer<P: Provider>(
     }
 }
 
-/// Wait for transaction receipt with fast polling (100ms interval)
-/// Times out after 30 seconds
-async fn wait_for_receipt_fast<P: Provider>(
+/// Poll for a receipt every `poll` until `deadline`. On timeout, one last
+/// direct lookup: a slow RPC can time out on a TX that did mine, and
+/// treating that as a failure would misreport the arb.
+pub async fn wait_for_receipt<P: Provider>(
     provider: &P,
     tx_hash: TxHash,
+    poll: Duration,
+    deadline: Duration,
 ) -> Result<TransactionReceipt> {
-    let mut poll_interval = interval(Duration::from_millis(100));
-    let deadline = Duration::from_secs(30);
-
-    timeout(deadline, async {
+    let polled = timeout(deadline, async {
+        let mut poll_interval = interval(poll);
         loop {
             poll_interval.tick().await;
             if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
@@ -146,38 +161,40 @@ async fn wait_for_receipt_fast<P: Provider>(
             }
         }
     })
-    .await
-    .map_err(|_| eyre::eyre!("Transaction confirmation timeout after 30s"))?
+    .await;
+
+    match polled {
+        Ok(result) => result,
+        Err(_) => match provider.get_transaction_receipt(tx_hash).await {
+            Ok(Some(receipt)) => {
+                println!("    Receipt arrived after the {:?} timeout (final lookup)", deadline);
+                Ok(receipt)
+            }
+            _ => Err(eyre!("Transaction confirmation timeout after {:?}", deadline)),
+        },
+    }
+}
+
+/// Wait for transaction receipt (100ms polling, 30s timeout unless overridden)
+async fn wait_for_receipt_fast<P: Provider>(
+    provider: &P,
+    tx_hash: TxHash,
+) -> Result<TransactionReceipt> {
+    let (poll, deadline) = receipt_wait(Duration::from_millis(100), Duration::from_secs(30));
+    wait_for_receipt(provider, tx_hash, poll, deadline).await
 }
 
-/// Wait for transaction receipt with node-aware polling interval
-/// Uses NodeConfig settings for optimal polling based on local vs remote node
+/// Wait for transaction receipt with node-aware polling interval and timeout
+/// (`NodeConfig::receipt_poll_interval` / `receipt_timeout`)
 ///
-/// Local node: 5ms polling, 200 max attempts (1 second total)
-/// Remote node: 100ms polling, 300 max attempts (30 seconds total)
+/// Local node: 2ms polling, 5s timeout
+/// Remote node: 100ms polling, 30s timeout
 pub async fn wait_for_receipt_optimized<P: Provider>(
     provider: &P,
     tx_hash: TxHash,
     config: &NodeConfig,
 ) -> Result<TransactionReceipt> {
-    let max_attempts = if config.is_local { 200 } else { 300 };
-    let poll_interval = config.receipt_poll_interval;
-    let timeout_secs = if config.is_local { 5 } else { 30 };
-
-    let deadline = Duration::from_secs(timeout_secs);
-
-    timeout(deadline, async {
-        let mut interval_timer = interval(poll_interval);
-        for _ in 0..max_attempts {
-            interval_timer.tick().await;
-            if let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? {
-                return Ok::<_, eyre::Report>(receipt);
-            }
-        }
-        Err(eyre::eyre!("Max polling attempts ({}) reached", max_attempts))
-    })
-    .await
-    .map_err(|_| eyre::eyre!("Transaction confirmation timeout after {}s", timeout_secs))?
+    wait_for_receipt(provider, tx_hash, config.receipt_poll_interval, config.receipt_timeout).await
 }
 
 /// Wait for the next block using WebSocket subscription (for Monad state commitment)
@@ -253,14 +270,41 @@ pub async fn execute_swap<P: Provider, S: Provider>(
     params: SwapParams,
     gas_price: u128,
     skip_balance_check: bool,
-) -> Result<SwapResult> {
+) -> ArbResult<SwapResult> {
+    swap(provider, provider_with_signer, signer_address, params, gas_price, skip_balance_check, None).await
+}
+
+/// `execute_swap` with the WMON side in native MON: a Sell sends MON as the
+/// TX value, a Buy has the router unwrap and pay out MON. Saves the separate
+/// wrap/unwrap TX; the router must pass `routers::supports_native`.
+pub async fn execute_swap_native<P: Provider, S: Provider>(
+    provider: &P,
+    provider_with_signer: &S,
+    signer_address: Address,
+    params: SwapParams,
+    gas_price: u128,
+    skip_balance_check: bool,
+) -> ArbResult<SwapResult> {
+    let leg = match params.direction {
+        SwapDirection::Sell => NativeLeg::In,
+        SwapDirection::Buy => NativeLeg::Out,
+    };
+    swap(provider, provider_with_signer, signer_address, params, gas_price, skip_balance_check, Some(leg)).await
+}
+
+async fn swap<P: Provider, S: Provider>(
+    provider: &P,
+    provider_with_signer: &S,
+    signer_address: Address,
+    params: SwapParams,
+    gas_price: u128,
+    skip_balance_check: bool,
+    native: Option<NativeLeg>,
+) -> ArbResult<SwapResult> {
     let wallet_address = signer_address;
 
     // Determine token addresses and decimals based on direction
-    let (token_in, token_out, decimals_in, decimals_out) = match params.direction {
-        SwapDirection::Sell => (WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS),
-        SwapDirection::Buy => (USDC_ADDRESS, WMON_ADDRESS, USDC_DECIMALS, WMON_DECIMALS),
-    };
+    let (token_in, token_out, decimals_in, decimals_out) = params.direction.tokens(&params.pair);
 
     // Convert to wei
     let amount_in = to_wei(params.amount_in, decimals_in);
@@ -288,7 +332,12 @@ pub async fn execute_swap<P: Provider, S: Provider>(
     println!("    Min Out ({:.2}% slip): {:.6}", params.slippage_bps as f64 / 100.0, min_out);
 
     // Check approval (does NOT send TX - run prepare-arb first)
-    check_approval(provider, wallet_address, token_in, params.router.address, amount_in).await?;
+    // V4's Universal Router pulls tokens through Permit2
+    // Native MON input needs no allowance
+    let spender = if params.router.router_type == RouterType::UniswapV4 { PERMIT2 } else { params.router.address };
+    if native != Some(NativeLeg::In) {
+        check_approval(provider, wallet_address, token_in, spender, amount_in).await?;
+    }
 
     // Get deadline (5 minutes from now)
     let deadline = SystemTime::now()
@@ -308,22 +357,36 @@ pub async fn execute_swap<P: Provider, S: Provider>(
     println!("═══════════════════════════════════════════════════════════════");
 
     // Build swap calldata
-    let calldata = build_swap_calldata(
-        params.router.router_type,
-        token_in,
-        token_out,
-        amount_in,
-        amount_out_min,
-        wallet_address,
-        params.router.pool_fee,
-        deadline,
-    )?;
+    let (calldata, value) = match native {
+        Some(leg) => {
+            let limit = price_limit_for(&params.router, &params.pair, token_in, params.expected_price, params.slippage_bps);
+            let swap = ExactInputSwap { token_in, token_out, amount_in, amount_out_min, recipient: wallet_address, deadline };
+            let (calldata, value) = build_native_swap_calldata(&params.router, leg, swap, limit)?;
+            println!("  native MON:     {:?} (value {})", leg, value);
+            (calldata, value)
+        }
+        None => {
+            let calldata = build_swap_calldata(
+                &params.router,
+                token_in,
+                token_out,
+                amount_in,
+                amount_out_min,
+                wallet_address,
+                deadline,
+            )?;
+            let calldata = apply_price_limit(&params.router, &params.pair, token_in, params.expected_price, params.slippage_bps, calldata)?;
+            (calldata, U256::ZERO)
+        }
+    };
 
     println!("  → Executing swap on {}...", params.router.name);
 
-    // Check balance before (skip if skip_balance_check is true)
+    // Check balance before (skip if skip_balance_check is true); MON out lands in the native balance
     let balance_before = if skip_balance_check {
         U256::ZERO
+    } else if native == Some(NativeLeg::Out) {
+        provider.get_balance(wallet_address).await?
     } else {
         let balance_before_call = balanceOfCall { account: wallet_address };
         let balance_tx = alloy::rpc::types::TransactionRequest::default()
@@ -343,6 +406,7 @@ pub async fn execute_swap<P: Provider, S: Provider>(
         params.router.address,
         wallet_address,
         &Bytes::from(calldata.clone()),
+        value,
         params.router.router_type,
     ).await;
 
@@ -350,15 +414,11 @@ pub async fn execute_swap<P: Provider, S: Provider>(
              gas_limit, gas_price / 1_000_000_000);
 
     // Build transaction with ALL fields set to prevent filler RPC calls
-    let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(params.router.address)
-        .from(wallet_address)
-        .input(alloy::rpc::types::TransactionInput::new(calldata))
-        .gas_limit(gas_limit)
-        .nonce(next_nonce())
-        .max_fee_per_gas(gas_price + (gas_price / 10))  // Add 10% buffer
-        .max_priority_fee_per_gas(gas_price / 10)       // 10% tip
-        .with_chain_id(MONAD_CHAIN_ID);
+    let gas = GasFees {
+        max_fee: gas_price + (gas_price / 10),  // Add 10% buffer
+        priority_fee: gas_price / 10,           // 10% tip
+    };
+    let tx = build_swap_request(&params.router, wallet_address, calldata, gas_limit, next_nonce(), gas).value(value);
 
     // Use pre-built provider with signer (passed in to avoid rebuilding per swap)
     let start = std::time::Instant::now();
@@ -366,7 +426,7 @@ pub async fn execute_swap<P: Provider, S: Provider>(
     // Add timeout to transaction send (prevents infinite hang)
     let send_result = match timeout(Duration::from_secs(15), provider_with_signer.send_transaction(tx)).await {
         Ok(result) => result,
-        Err(_) => return Err(eyre!("Transaction send timed out after 15s")),
+        Err(_) => return Err(ArbError::RpcTimeout("Transaction send timed out after 15s".to_string())),
     };
 
     match send_result {
@@ -378,6 +438,11 @@ pub async fn execute_swap<P: Provider, S: Provider>(
             let receipt = wait_for_receipt_fast(provider_with_signer, tx_hash).await?;
             let elapsed = start.elapsed();
 
+            // MONAD: Gas cost is based on gas_limit, not gas_used!
+            let gas_used = receipt.gas_used;
+            let gas_price_effective = receipt.effective_gas_price;
+            let gas_cost_wei = U256::from(gas_limit) * U256::from(gas_price_effective);
+
             // Check balance after (skip if skip_balance_check is true - use expected output)
             let amount_out = if skip_balance_check {
                 // Estimate output based on expected price (no RPC call)
@@ -386,6 +451,10 @@ pub async fn execute_swap<P: Provider, S: Provider>(
                     SwapDirection::Buy => params.amount_in / params.expected_price,
                 };
                 to_wei(expected_out, decimals_out)
+            } else if native == Some(NativeLeg::Out) {
+                // The MON balance also paid this TX's gas
+                let balance_after = provider.get_balance(wallet_address).await?;
+                (balance_after + gas_cost_wei).saturating_sub(balance_before)
             } else {
                 let balance_after_call = balanceOfCall { account: wallet_address };
                 let balance_tx = alloy::rpc::types::TransactionRequest::default()
@@ -405,11 +474,6 @@ pub async fn execute_swap<P: Provider, S: Provider>(
 
             let price_impact_bps = ((executed_price - params.expected_price) / params.expected_price * 10000.0) as i32;
 
-            // MONAD: Gas cost is based on gas_limit, not gas_used!
-            let gas_used = receipt.gas_used;
-            let gas_price_effective = receipt.effective_gas_price;
-            let gas_cost_wei = U256::from(gas_limit) * U256::from(gas_price_effective);
-
             println!("  ✓ Swap completed in {:?}", elapsed);
             println!("    TX: {:?}", receipt.transaction_hash);
             println!("    Gas used: {} / {} limit ({:.1}% efficiency)",
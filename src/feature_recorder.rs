@@ -0,0 +1,285 @@
+//! Spread feature stream for model training
+//!
+//! `--record-features` (AutoArb, MevValidate) appends one `poll` row per price
+//! poll to `spread_features_<timestamp>.jsonl`: every pool price, each pair's
+//! gross/net spread with its velocity and acceleration since the previous
+//! poll, the block and (MevValidate only) its commit state. When AutoArb
+//! executes a spread it later appends an `outcome` row with the realized
+//! profit and the `poll_id` the spread was seen in, so features join to
+//! labels on `poll_id`.
+//!
+//! The poll loop only clones the prices and spreads into a channel; velocity,
+//! serialization and file writes happen on a background thread.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::display::SpreadOpportunity;
+use crate::mev_validation::CommitState;
+use crate::pools::PoolPrice;
+
+/// One pool's reading in a poll row
+#[derive(Debug, Serialize)]
+pub struct PoolFeature {
+    pub pool_name: String,
+    pub price: f64,
+    pub fee_bps: u32,
+    pub liquidity: Option<u128>,
+    pub tick: Option<i32>,
+}
+
+/// One (buy, sell) pair's spread in a poll row
+#[derive(Debug, Serialize)]
+pub struct PairFeature {
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub gross_bps: f64,
+    pub net_bps: f64,
+    /// Net spread change since the previous poll, bps/sec (None on the pair's first poll)
+    pub velocity_bps_per_sec: Option<f64>,
+    /// Velocity change since the previous poll, bps/sec^2
+    pub acceleration: Option<f64>,
+}
+
+/// A line of `spread_features_*.jsonl`
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FeatureRecord {
+    Poll {
+        poll_id: u64,
+        unix_ms: u128,
+        block_number: Option<u64>,
+        commit_state: Option<CommitState>,
+        prices: Vec<PoolFeature>,
+        pairs: Vec<PairFeature>,
+    },
+    Outcome {
+        poll_id: u64,
+        unix_ms: u128,
+        buy_pool: String,
+        sell_pool: String,
+        success: bool,
+        /// Paper fill rather than a sent transaction
+        simulated: bool,
+        profit_wmon: f64,
+    },
+}
+
+/// Raw poll handed to the writer thread
+enum Observation {
+    Poll {
+        poll_id: u64,
+        unix_ms: u128,
+        block_number: Option<u64>,
+        commit_state: Option<CommitState>,
+        prices: Vec<PoolPrice>,
+        spreads: Vec<SpreadOpportunity>,
+    },
+    Outcome(FeatureRecord),
+}
+
+/// Per-pair history for velocity and acceleration: (net_bps, unix_ms, velocity)
+#[derive(Default)]
+struct PairMotion {
+    last: HashMap<(String, String), (f64, u128, Option<f64>)>,
+}
+
+impl PairMotion {
+    /// Feature rows for this poll's spreads; pairs missing from it start over
+    fn features(&mut self, unix_ms: u128, spreads: &[SpreadOpportunity]) -> Vec<PairFeature> {
+        let mut next = HashMap::with_capacity(spreads.len());
+        let pairs = spreads
+            .iter()
+            .map(|s| {
+                let key = (s.buy_pool.clone(), s.sell_pool.clone());
+                let net_bps = s.net_spread_pct * 100.0;
+                let (velocity, acceleration) = match self.last.get(&key) {
+                    Some(&(prev_bps, prev_ms, prev_velocity)) if unix_ms > prev_ms => {
+                        let secs = (unix_ms - prev_ms) as f64 / 1000.0;
+                        let velocity = (net_bps - prev_bps) / secs;
+                        (Some(velocity), prev_velocity.map(|v| (velocity - v) / secs))
+                    }
+                    _ => (None, None),
+                };
+                next.insert(key, (net_bps, unix_ms, velocity));
+                PairFeature {
+                    buy_pool: s.buy_pool.clone(),
+                    sell_pool: s.sell_pool.clone(),
+                    gross_bps: s.gross_spread_pct * 100.0,
+                    net_bps,
+                    velocity_bps_per_sec: velocity,
+                    acceleration,
+                }
+            })
+            .collect();
+        self.last = next;
+        pairs
+    }
+
+    fn record(&mut self, observation: Observation) -> FeatureRecord {
+        match observation {
+            Observation::Poll { poll_id, unix_ms, block_number, commit_state, prices, spreads } => FeatureRecord::Poll {
+                poll_id,
+                unix_ms,
+                block_number,
+                commit_state,
+                prices: prices
+                    .into_iter()
+                    .map(|p| PoolFeature {
+                        pool_name: p.pool_name,
+                        price: p.price,
+                        fee_bps: p.fee_bps,
+                        liquidity: p.liquidity,
+                        tick: p.tick,
+                    })
+                    .collect(),
+                pairs: self.features(unix_ms, &spreads),
+            },
+            Observation::Outcome(record) => record,
+        }
+    }
+}
+
+/// Writer thread: drain the channel, flushing whenever it runs dry
+fn write_features(mut writer: BufWriter<File>, rx: Receiver<Observation>) {
+    let mut motion = PairMotion::default();
+    while let Ok(first) = rx.recv() {
+        for observation in std::iter::once(first).chain(rx.try_iter()) {
+            if let Ok(json) = serde_json::to_string(&motion.record(observation)) {
+                let _ = writeln!(writer, "{}", json);
+            }
+        }
+        let _ = writer.flush();
+    }
+    let _ = writer.flush();
+}
+
+struct FeatureRecorder {
+    tx: Sender<Observation>,
+    writer: JoinHandle<()>,
+    next_poll: u64,
+}
+
+/// The running command's recorder, if `--record-features` is set
+static RECORDER: Mutex<Option<FeatureRecorder>> = Mutex::new(None);
+
+fn unix_ms() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+/// Start recording to a new `spread_features_<timestamp>.jsonl`; returns its path
+pub fn start() -> std::io::Result<String> {
+    let path = format!("spread_features_{}.jsonl", Local::now().format("%Y%m%d_%H%M%S"));
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let writer = BufWriter::with_capacity(64 * 1024, file);
+    let (tx, rx) = channel();
+    let writer = std::thread::spawn(move || write_features(writer, rx));
+    *RECORDER.lock().unwrap_or_else(|e| e.into_inner()) = Some(FeatureRecorder { tx, writer, next_poll: 0 });
+    Ok(path)
+}
+
+/// Queue a poll row; returns its `poll_id` (None when not recording)
+pub fn record_poll(
+    block_number: Option<u64>,
+    commit_state: Option<CommitState>,
+    prices: &[PoolPrice],
+    spreads: &[SpreadOpportunity],
+) -> Option<u64> {
+    let mut recorder = RECORDER.lock().unwrap_or_else(|e| e.into_inner());
+    let recorder = recorder.as_mut()?;
+    let poll_id = recorder.next_poll;
+    recorder.next_poll += 1;
+    let _ = recorder.tx.send(Observation::Poll {
+        poll_id,
+        unix_ms: unix_ms(),
+        block_number,
+        commit_state,
+        prices: prices.to_vec(),
+        spreads: spreads.to_vec(),
+    });
+    Some(poll_id)
+}
+
+/// Queue the realized profit of an execution on the spread seen in `poll_id`
+pub fn record_outcome(poll_id: Option<u64>, buy_pool: &str, sell_pool: &str, success: bool, simulated: bool, profit_wmon: f64) {
+    let Some(poll_id) = poll_id else { return };
+    if let Some(recorder) = RECORDER.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        let _ = recorder.tx.send(Observation::Outcome(FeatureRecord::Outcome {
+            poll_id,
+            unix_ms: unix_ms(),
+            buy_pool: buy_pool.to_string(),
+            sell_pool: sell_pool.to_string(),
+            success,
+            simulated,
+            profit_wmon,
+        }));
+    }
+}
+
+/// Stop recording and wait for queued rows to be written (call on shutdown)
+pub fn finish() {
+    let recorder = RECORDER.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(FeatureRecorder { tx, writer, .. }) = recorder {
+        drop(tx);
+        let _ = writer.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spread(buy: &str, sell: &str, net_pct: f64) -> SpreadOpportunity {
+        SpreadOpportunity {
+            buy_pool: buy.to_string(),
+            buy_price: 3.0,
+            buy_fee_bps: 5,
+            sell_pool: sell.to_string(),
+            sell_price: 3.01,
+            sell_fee_bps: 5,
+            gross_spread_pct: net_pct + 0.1,
+            net_spread_pct: net_pct,
+            block_number: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_pair_motion_and_record_tags() {
+        let mut motion = PairMotion::default();
+        let first = motion.features(1_000, &[spread("a", "b", 0.25)]);
+        assert_eq!((first[0].net_bps, first[0].velocity_bps_per_sec, first[0].acceleration), (25.0, None, None));
+
+        // +25 bps over 500 ms, then +50 bps over the next 500 ms
+        let second = motion.features(1_500, &[spread("a", "b", 0.5), spread("b", "a", -0.5)]);
+        assert_eq!((second[0].velocity_bps_per_sec, second[0].acceleration), (Some(50.0), None));
+        assert_eq!(second[1].velocity_bps_per_sec, None);
+        let third = motion.features(2_000, &[spread("a", "b", 1.0)]);
+        assert_eq!((third[0].velocity_bps_per_sec, third[0].acceleration), (Some(100.0), Some(100.0)));
+
+        // A pair that skipped a poll starts over
+        let fourth = motion.features(2_500, &[spread("b", "a", -0.25)]);
+        assert_eq!(fourth[0].velocity_bps_per_sec, None);
+
+        let outcome = FeatureRecord::Outcome {
+            poll_id: 3,
+            unix_ms: 2_600,
+            buy_pool: "a".into(),
+            sell_pool: "b".into(),
+            success: true,
+            simulated: false,
+            profit_wmon: 0.02,
+        };
+        let json: serde_json::Value = serde_json::to_value(&outcome).unwrap();
+        assert_eq!((json["kind"].as_str(), json["poll_id"].as_u64()), (Some("outcome"), Some(3)));
+    }
+}
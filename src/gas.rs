@@ -0,0 +1,442 @@
+//! Gas price strategies
+//!
+//! Replaces the fixed `gas_price + gas_price/10` bump with a selectable
+//! strategy (`--gas-strategy`):
+//! - `fixed:<gwei>`: constant max fee
+//! - `multiplier:<x>`: eth_gasPrice * x (default `multiplier:1.1`, the old bump)
+//! - `percentile[:<blocks>[:<pct>]]`: priority fee from the pct-th percentile
+//!   of recent `eth_feeHistory` rewards, on top of the next base fee
+//!
+//! `--max-gas-gwei` is a circuit breaker on top of any strategy: an arb
+//! whose max fee would exceed it is skipped rather than sent into a spike.
+//!
+//! Fee history is cached for `cache_ttl` (the bot's poll interval) so
+//! percentile mode doesn't add an RPC round-trip per arb.
+//!
+//! AutoArb multiplies the priority fee by `--competitive-priority-multiplier`
+//! on routes flagged competitive (`--competitive-routes` or
+//! `[[competitive_routes]]` in --config, e.g. from `AnalyzeLogs --group-by
+//! decay`), bidding for inclusion only where others are racing for the spread.
+//!
+//! Gas *limits* come from `GasProfile`: Monad charges the limit, so once a
+//! router has enough receipts in past `*arb_stats_*.jsonl` files (or this
+//! session) its limit is the observed P95 plus a small buffer instead of
+//! eth_estimateGas + 15%.
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::providers::Provider;
+use eyre::{eyre, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::stats::ArbExecutionRecord;
+
+pub const DEFAULT_GAS_STRATEGY: &str = "multiplier:1.1";
+
+/// Default `--max-gas-gwei`: far above normal Monad fees, so only a spike trips it
+pub const DEFAULT_MAX_GAS_GWEI: &str = "1000";
+
+/// Fallback when eth_gasPrice fails (100 gwei)
+const FALLBACK_GAS_PRICE: u128 = 100_000_000_000;
+
+/// How to price max_fee / max_priority_fee
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GasStrategy {
+    /// Constant max fee in gwei
+    Fixed(f64),
+    /// Network gas price times a multiplier; the excess is the priority fee
+    Multiplier(f64),
+    /// Percentile of recent priority fees over `lookback_blocks`
+    Percentile { lookback_blocks: u64, pct: f64 },
+}
+
+/// Resolved EIP-1559 fees in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasFees {
+    pub max_fee: u128,
+    pub priority_fee: u128,
+}
+
+impl GasFees {
+    /// Both fees raised by `pct` percent (replace-by-fee needs strictly higher fees)
+    pub fn bumped(&self, pct: u32) -> Self {
+        let bump = |fee: u128| fee + (fee * pct as u128).div_ceil(100);
+        GasFees { max_fee: bump(self.max_fee), priority_fee: bump(self.priority_fee) }
+    }
+}
+
+impl Default for GasStrategy {
+    fn default() -> Self {
+        Self::Multiplier(1.1)
+    }
+}
+
+impl FromStr for GasStrategy {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().split(':');
+        let kind = parts.next().unwrap_or_default().to_lowercase();
+        let args: Vec<&str> = parts.collect();
+        let num = |i: usize| -> Result<Option<f64>> {
+            args.get(i)
+                .map(|a| a.parse::<f64>().map_err(|_| eyre!("Invalid gas strategy value '{}' in '{}'", a, s)))
+                .transpose()
+        };
+
+        match kind.as_str() {
+            "fixed" => Ok(Self::Fixed(num(0)?.ok_or_else(|| eyre!("fixed needs gwei, e.g. fixed:52"))?)),
+            "multiplier" => Ok(Self::Multiplier(num(0)?.unwrap_or(1.1))),
+            "percentile" => Ok(Self::Percentile {
+                lookback_blocks: num(0)?.unwrap_or(20.0).max(1.0) as u64,
+                pct: num(1)?.unwrap_or(50.0).clamp(0.0, 100.0),
+            }),
+            _ => Err(eyre!(
+                "Unknown gas strategy '{}'. Expected fixed:<gwei>, multiplier:<x> or percentile[:<blocks>[:<pct>]]",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for GasStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed(gwei) => write!(f, "fixed:{}", gwei),
+            Self::Multiplier(x) => write!(f, "multiplier:{}", x),
+            Self::Percentile { lookback_blocks, pct } => write!(f, "percentile:{}:{}", lookback_blocks, pct),
+        }
+    }
+}
+
+// (fetched_at, lookback_blocks, pct, fees)
+type FeeHistoryCache = Option<(Instant, u64, f64, GasFees)>;
+static FEE_HISTORY_CACHE: Mutex<FeeHistoryCache> = Mutex::new(None);
+
+impl GasStrategy {
+    /// Fees from a plain eth_gasPrice reading. Percentile falls back to the
+    /// default multiplier here since it needs fee history.
+    pub fn fees_from_base(&self, base_gas_price: u128) -> GasFees {
+        match *self {
+            Self::Fixed(gwei) => {
+                let max_fee = (gwei * 1e9) as u128;
+                GasFees { max_fee, priority_fee: max_fee.saturating_sub(base_gas_price) }
+            }
+            Self::Multiplier(x) => {
+                let priority_fee = (base_gas_price as f64 * (x - 1.0).max(0.0)).round() as u128;
+                GasFees { max_fee: base_gas_price + priority_fee, priority_fee }
+            }
+            Self::Percentile { .. } => Self::default().fees_from_base(base_gas_price),
+        }
+    }
+
+    /// Resolve fees against the network
+    pub async fn resolve<P: Provider>(&self, provider: &P, cache_ttl: Duration) -> GasFees {
+        match *self {
+            Self::Fixed(_) => self.fees_from_base(0),
+            Self::Multiplier(_) => {
+                let gas_price = provider.get_gas_price().await.unwrap_or(FALLBACK_GAS_PRICE);
+                self.fees_from_base(gas_price)
+            }
+            Self::Percentile { lookback_blocks, pct } => {
+                if let Some((at, blocks, p, fees)) = *FEE_HISTORY_CACHE.lock().unwrap() {
+                    if blocks == lookback_blocks && p == pct && at.elapsed() < cache_ttl {
+                        return fees;
+                    }
+                }
+                match fees_from_history(provider, lookback_blocks, pct).await {
+                    Ok(fees) => {
+                        *FEE_HISTORY_CACHE.lock().unwrap() = Some((Instant::now(), lookback_blocks, pct, fees));
+                        fees
+                    }
+                    Err(e) => {
+                        tracing::warn!("eth_feeHistory failed ({}), falling back to {}", e, DEFAULT_GAS_STRATEGY);
+                        let gas_price = provider.get_gas_price().await.unwrap_or(FALLBACK_GAS_PRICE);
+                        Self::default().fees_from_base(gas_price)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Gas-price circuit breaker: true (after logging the skip) if `max_fee` wei
+/// is above `max_gas_gwei`
+pub fn gas_too_high(max_fee: u128, max_gas_gwei: f64) -> bool {
+    let max_fee_gwei = max_fee as f64 / 1e9;
+    if max_fee_gwei <= max_gas_gwei {
+        return false;
+    }
+    println!("  Gas too high, skipping: max_fee {:.2} gwei > --max-gas-gwei {}", max_fee_gwei, max_gas_gwei);
+    tracing::warn!(max_fee_gwei, max_gas_gwei, "gas too high, skipping");
+    true
+}
+
+/// Default `--competitive-priority-multiplier`
+pub const DEFAULT_COMPETITIVE_PRIORITY_MULTIPLIER: &str = "2";
+
+/// A contested route (`[[competitive_routes]]` in --config)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CompetitiveRoute {
+    pub buy_pool: String,
+    pub sell_pool: String,
+}
+
+/// Parse `--competitive-routes`: comma-separated `BUY>SELL` pool pairs
+pub fn parse_competitive_routes(s: &str) -> Result<Vec<CompetitiveRoute>> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(|r| match r.split_once('>') {
+            Some((buy, sell)) if !buy.trim().is_empty() && !sell.trim().is_empty() => Ok(CompetitiveRoute {
+                buy_pool: buy.trim().to_string(),
+                sell_pool: sell.trim().to_string(),
+            }),
+            _ => Err(eyre!("Invalid competitive route '{}'. Expected BUY_POOL>SELL_POOL", r)),
+        })
+        .collect()
+}
+
+/// Priority-fee multiplier for competitive routes; other routes keep the strategy's fees
+#[derive(Debug, Clone, Default)]
+pub struct PriorityEscalation {
+    multiplier: f64,
+    routes: HashSet<(String, String)>,
+}
+
+impl PriorityEscalation {
+    pub fn new(multiplier: f64, routes: Vec<CompetitiveRoute>) -> Self {
+        let routes = routes.into_iter().map(|r| (r.buy_pool, r.sell_pool)).collect();
+        Self { multiplier, routes }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.multiplier > 1.0 && !self.routes.is_empty()
+    }
+
+    pub fn route_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_competitive(&self, buy_pool: &str, sell_pool: &str) -> bool {
+        self.routes.contains(&(buy_pool.to_string(), sell_pool.to_string()))
+    }
+
+    /// Fees for one route: on a competitive route the priority fee is
+    /// multiplied and the max fee raised by the same amount, so the base fee
+    /// headroom is unchanged
+    pub fn fees_for(&self, gas: GasFees, buy_pool: &str, sell_pool: &str) -> GasFees {
+        if !self.is_enabled() || !self.is_competitive(buy_pool, sell_pool) {
+            return gas;
+        }
+        let priority_fee = (gas.priority_fee as f64 * self.multiplier).round() as u128;
+        GasFees {
+            max_fee: gas.max_fee + priority_fee.saturating_sub(gas.priority_fee),
+            priority_fee,
+        }
+    }
+}
+
+/// A router needs this many receipts before its learned limit is used
+const MIN_PROFILE_SAMPLES: usize = 10;
+
+/// Headroom over the observed P95
+const PROFILE_BUFFER_PERCENT: u64 = 5;
+
+/// Receipts kept per router (oldest dropped first)
+const MAX_PROFILE_SAMPLES: usize = 500;
+
+/// Observed `gas_used` per router, for setting gas limits from history
+#[derive(Debug, Default)]
+pub struct GasProfile {
+    samples: HashMap<String, VecDeque<u64>>,
+}
+
+impl GasProfile {
+    /// Learn from every `*arb_stats_*.jsonl` in `dir`
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut profile = Self::default();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if !(name.contains("arb_stats_") && name.ends_with(".jsonl")) {
+                continue;
+            }
+            for line in BufReader::new(File::open(&path)?).lines() {
+                if let Ok(record) = serde_json::from_str::<ArbExecutionRecord>(&line?) {
+                    profile.record_execution(&record);
+                }
+            }
+        }
+        Ok(profile)
+    }
+
+    pub fn record(&mut self, router: &str, gas_used: u64) {
+        let samples = self.samples.entry(router.to_string()).or_default();
+        if samples.len() == MAX_PROFILE_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(gas_used);
+    }
+
+    /// Per-leg gas of a real two-TX execution (atomic arbs have one combined
+    /// TX, paper fills none)
+    pub fn record_execution(&mut self, record: &ArbExecutionRecord) {
+        let Some(post) = &record.post else { return };
+        if post.simulated || post.swap2_tx_hash.is_empty() {
+            return;
+        }
+        if post.swap1_success && post.swap1_gas_used > 0 {
+            self.record(&record.pre.sell_dex, post.swap1_gas_used);
+        }
+        if post.swap2_success && post.swap2_gas_used > 0 {
+            self.record(&record.pre.buy_dex, post.swap2_gas_used);
+        }
+    }
+
+    /// 95th percentile gas used, once the router has enough samples
+    pub fn p95(&self, router: &str) -> Option<u64> {
+        let samples = self.samples.get(router).filter(|s| s.len() >= MIN_PROFILE_SAMPLES)?;
+        let mut sorted: Vec<u64> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let idx = (sorted.len() * 95).div_ceil(100) - 1;
+        Some(sorted[idx])
+    }
+
+    /// Gas limit for a router's swaps: P95 plus buffer
+    pub fn gas_limit(&self, router: &str) -> Option<u64> {
+        self.p95(router).map(|p95| p95 * (100 + PROFILE_BUFFER_PERCENT) / 100)
+    }
+
+    pub fn print_summary(&self) {
+        if self.samples.is_empty() {
+            return;
+        }
+        let mut routers: Vec<_> = self.samples.iter().collect();
+        routers.sort_by(|a, b| a.0.cmp(b.0));
+        println!("  Gas profile (from past stats):");
+        for (router, samples) in routers {
+            match self.gas_limit(router) {
+                Some(limit) => println!("    {:<16} limit {:>7} (p95 {} + {}%, {} receipts)",
+                    router, limit, self.p95(router).unwrap_or(0), PROFILE_BUFFER_PERCENT, samples.len()),
+                None => println!("    {:<16} estimating ({} of {} receipts needed)", router, samples.len(), MIN_PROFILE_SAMPLES),
+            }
+        }
+    }
+}
+
+static GAS_PROFILE: OnceLock<Mutex<GasProfile>> = OnceLock::new();
+
+fn gas_profile() -> &'static Mutex<GasProfile> {
+    GAS_PROFILE.get_or_init(Mutex::default)
+}
+
+/// Install the profile loaded at startup
+pub fn init_gas_profile(profile: GasProfile) {
+    *gas_profile().lock().unwrap() = profile;
+}
+
+/// Learned gas limit for a router, if it has enough history
+pub fn profiled_gas_limit(router: &str) -> Option<u64> {
+    gas_profile().lock().unwrap().gas_limit(router)
+}
+
+/// Add a confirmed swap's gas to the profile
+pub fn record_gas_used(router: &str, gas_used: u64) {
+    gas_profile().lock().unwrap().record(router, gas_used);
+}
+
+/// Median of the pct-th percentile rewards on top of the next base fee (+10% headroom)
+async fn fees_from_history<P: Provider>(provider: &P, lookback_blocks: u64, pct: f64) -> Result<GasFees> {
+    let history = provider
+        .get_fee_history(lookback_blocks, BlockNumberOrTag::Latest, &[pct])
+        .await?;
+
+    let next_base = *history
+        .base_fee_per_gas
+        .last()
+        .ok_or_else(|| eyre!("empty fee history"))?;
+
+    let mut rewards: Vec<u128> = history
+        .reward
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|r| r.first().copied())
+        .collect();
+    rewards.sort_unstable();
+    let priority_fee = rewards.get(rewards.len() / 2).copied().unwrap_or(next_base / 10);
+
+    Ok(GasFees {
+        max_fee: next_base + next_base / 10 + priority_fee,
+        priority_fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_default_matches_old_bump() {
+        assert_eq!("fixed:52".parse::<GasStrategy>().unwrap(), GasStrategy::Fixed(52.0));
+        assert_eq!(
+            "percentile:10".parse::<GasStrategy>().unwrap(),
+            GasStrategy::Percentile { lookback_blocks: 10, pct: 50.0 }
+        );
+        assert!("turbo".parse::<GasStrategy>().is_err());
+
+        // Default reproduces gas_price + gas_price/10 with a 10% tip
+        let base = 50_000_000_000u128;
+        let fees = DEFAULT_GAS_STRATEGY.parse::<GasStrategy>().unwrap().fees_from_base(base);
+        assert_eq!(fees, GasFees { max_fee: base + base / 10, priority_fee: base / 10 });
+
+        let bumped = GasFees { max_fee: 100, priority_fee: 1 }.bumped(25);
+        assert_eq!(bumped, GasFees { max_fee: 125, priority_fee: 2 });
+    }
+
+    #[test]
+    fn test_gas_ceiling() {
+        let max_gwei: f64 = DEFAULT_MAX_GAS_GWEI.parse().unwrap();
+        assert!(!gas_too_high(110_000_000_000, max_gwei));
+        assert!(!gas_too_high(50_000_000_000, 50.0));
+        assert!(gas_too_high(50_000_000_001, 50.0));
+    }
+
+    #[test]
+    fn test_priority_escalation_only_on_competitive_routes() {
+        let routes = parse_competitive_routes("LFJ>Uniswap, PancakeSwap1>LFJ").unwrap();
+        assert_eq!(routes.len(), 2);
+        assert!(parse_competitive_routes("LFJ-Uniswap").is_err());
+
+        let escalation = PriorityEscalation::new(2.0, routes);
+        let gas = GasFees { max_fee: 110, priority_fee: 10 };
+        assert_eq!(escalation.fees_for(gas, "LFJ", "Uniswap"), GasFees { max_fee: 120, priority_fee: 20 });
+        // Direction matters
+        assert_eq!(escalation.fees_for(gas, "Uniswap", "LFJ"), gas);
+
+        assert!(!PriorityEscalation::new(1.0, parse_competitive_routes("LFJ>Uniswap").unwrap()).is_enabled());
+    }
+
+    #[test]
+    fn test_gas_profile_p95_needs_min_samples() {
+        let mut profile = GasProfile::default();
+        for gas_used in 1..MIN_PROFILE_SAMPLES as u64 {
+            profile.record("PancakeSwap1", gas_used * 1_000 + 300_000);
+        }
+        assert_eq!(profile.gas_limit("PancakeSwap1"), None);
+
+        for gas_used in 300_000..300_011 {
+            profile.record("PancakeSwap1", gas_used);
+        }
+        // 20 samples: P95 is the 19th smallest
+        assert_eq!(profile.p95("PancakeSwap1"), Some(308_000));
+        assert_eq!(profile.gas_limit("PancakeSwap1"), Some(308_000 * 105 / 100));
+        assert_eq!(profile.gas_limit("LFJ"), None);
+    }
+}
@@ -7,6 +7,8 @@ use std::collections::HashMap;
 use std::sync::RwLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::gas::GasFees;
+
 /// Cache TTL in milliseconds (base: 30 seconds)
 const GAS_CACHE_TTL_MS: u128 = 30_000;
 
@@ -173,17 +175,14 @@ pub fn gas_strategy(spread_bps: i32, route: &RouteKey) -> GasDecision {
 
 /// Calculate gas price with spread-aware priority fee bidding
 ///
-/// When spreads are high, other bots are competing. Adjust priority fee accordingly.
-pub fn calculate_gas_price(base_gas_price: u128, spread_bps: i32) -> (u128, u128) {
-    // Base priority fee (10% of base gas price)
-    let base_priority = base_gas_price / 10;
-
+/// When spreads are high, other bots are competing. Boosts the strategy's fees accordingly.
+pub fn calculate_gas_price(fees: GasFees, spread_bps: i32) -> (u128, u128) {
     // Boost priority fee based on spread (more competition = higher spread)
     // Add 1 gwei per 10 bps of spread
-    let priority_boost = (spread_bps as u128 / 10) * 1_000_000_000; // gwei to wei
+    let priority_boost = (spread_bps.max(0) as u128 / 10) * 1_000_000_000; // gwei to wei
 
-    let priority_fee = base_priority + priority_boost;
-    let max_fee = base_gas_price + priority_fee;
+    let priority_fee = fees.priority_fee + priority_boost;
+    let max_fee = fees.max_fee + priority_boost;
 
     (max_fee, priority_fee)
 }
@@ -229,7 +228,8 @@ mod tests {
     #[test]
     fn test_calculate_gas_price() {
         let base = 1_000_000_000u128; // 1 gwei
-        let (max_fee, priority) = calculate_gas_price(base, 30);
+        let fees = crate::gas::GasStrategy::default().fees_from_base(base);
+        let (max_fee, priority) = calculate_gas_price(fees, 30);
 
         // priority = base/10 + (30/10)*1gwei = 0.1gwei + 3gwei = 3.1gwei
         assert_eq!(priority, 100_000_000 + 3_000_000_000);
@@ -3,7 +3,9 @@ use alloy::primitives::U64;
 use eyre::Result;
 use std::time::{Duration, Instant};
 
+use crate::multicall::fetch_prices_batched;
 use crate::node_config::MONAD_MAINNET_CHAIN_ID;
+use crate::pools::{CallType, PriceCall};
 
 /// Node health status for startup verification
 #[derive(Debug, Clone)]
@@ -136,3 +138,33 @@ pub async fn verify_node_ready<P: Provider>(provider: &P) -> Result<NodeHealth>
     println!();
     Ok(health)
 }
+
+/// Verify every configured pool returns a valid price (one multicall).
+/// Catches wrong pool addresses before the bot runs showing zeros.
+pub async fn verify_pools_ready<P: Provider>(provider: &P, price_calls: &[PriceCall]) -> Result<()> {
+    println!("Checking pools...");
+    let (prices, _, _) = fetch_prices_batched(provider, price_calls.to_vec()).await?;
+
+    // LFJ binStep and V3 liquidity calls fold into their pool's price
+    let mut failed = Vec::new();
+    for call in price_calls.iter().filter(|c| !matches!(c.call_type, CallType::LfjBinStep | CallType::V3Liquidity)) {
+        match prices.iter().find(|p| p.pool_name == call.pool_name) {
+            Some(price) if price.is_price_valid() && !price.has_sufficient_liquidity() => {
+                println!("  {:<20} OK ({:.6}) ⚠ no in-range liquidity at tick {}, skipped by spreads",
+                    call.pool_name, price.price, price.tick.unwrap_or_default());
+            }
+            Some(price) if price.is_price_valid() => {
+                println!("  {:<20} OK ({:.6})", call.pool_name, price.price);
+            }
+            Some(price) => failed.push(format!("{} ({:?}): invalid price {}", call.pool_name, call.pool_address, price.price)),
+            None => failed.push(format!("{} ({:?}): call failed or returned no data", call.pool_name, call.pool_address)),
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(eyre::eyre!("Pool check failed:\n  {}", failed.join("\n  ")));
+    }
+
+    println!();
+    Ok(())
+}
@@ -0,0 +1,330 @@
+//! Time-of-day spread heatmap over `mev_validation_*.jsonl`
+//!
+//! `AnalyzeLogs --group-by hour` buckets every actionable spread (best net
+//! spread at Proposed >= `--min-spread-bps`) into its UTC hour from the
+//! snapshot's `wall_clock`, overall and per route. Logs spanning several days
+//! fold into the same 24 buckets, so the output shows when opportunities are
+//! densest rather than when they happened.
+//!
+//! `wall_clock` carries a UTC offset in newer logs; older ones are local time
+//! without an offset and are converted with this machine's current zone.
+//!
+//! `AnalyzeLogs --group-by decay` instead averages how far each actionable
+//! route's spread fell from Proposed to Finalized and prints the result as
+//! `[[route_decay]]` entries for AutoArb's `--config`. Routes whose spreads
+//! are mostly captured (finalized under 5 bps) are also printed as
+//! `[[competitive_routes]]`, where AutoArb escalates its priority fee.
+//!
+//! `validated_execution_window` replays the same logs for `AutoArb --mode
+//! eta`, which needs the measured execution window.
+
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use eyre::{eyre, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+
+use crate::mev_validation::{BlockLifecycle, RunningStats, SpreadOutcome};
+
+/// Format `mev_validation` writes `wall_clock` in
+pub const WALL_CLOCK_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f%:z";
+
+/// Heatmap shading, least to most dense
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Routes shown in the per-route grid
+const MAX_ROUTES: usize = 10;
+
+/// Capture rate (%) above which a route is suggested as competitive; the
+/// "HIGH COMPETITION" line in the validation report
+const COMPETITIVE_CAPTURE_PCT: f64 = 70.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Hour,
+    Decay,
+}
+
+impl FromStr for GroupBy {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "hour" => Ok(Self::Hour),
+            "decay" => Ok(Self::Decay),
+            _ => Err(eyre!("Unknown grouping '{}'. Expected hour or decay", s)),
+        }
+    }
+}
+
+/// UTC hour of a `wall_clock` value
+pub fn parse_utc_hour(wall_clock: &str) -> Option<u32> {
+    if let Ok(dt) = DateTime::parse_from_str(wall_clock, WALL_CLOCK_FORMAT) {
+        return Some(dt.with_timezone(&Utc).hour());
+    }
+    let naive = NaiveDateTime::parse_from_str(wall_clock, "%Y-%m-%d %H:%M:%S%.3f")
+        .ok()
+        .or_else(|| {
+            let time = NaiveTime::parse_from_str(wall_clock, "%H:%M:%S%.3f").ok()?;
+            Some(Local::now().date_naive().and_time(time))
+        })?;
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    Some(local.with_timezone(&Utc).hour())
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HourBucket {
+    pub count: u64,
+    pub spread_sum_bps: i64,
+}
+
+impl HourBucket {
+    pub fn avg_bps(&self) -> f64 {
+        if self.count > 0 {
+            self.spread_sum_bps as f64 / self.count as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Actionable spreads by UTC hour, overall and per route
+#[derive(Debug, Default)]
+pub struct Heatmap {
+    pub hours: [HourBucket; 24],
+    pub routes: HashMap<String, [HourBucket; 24]>,
+    /// Lifecycles with a spread but no parseable wall clock
+    pub undated: u64,
+}
+
+impl Heatmap {
+    pub fn add(&mut self, hour: u32, route: &str, spread_bps: i32) {
+        let h = hour as usize % 24;
+        for bucket in [&mut self.hours[h], &mut self.routes.entry(route.to_string()).or_default()[h]] {
+            bucket.count += 1;
+            bucket.spread_sum_bps += spread_bps as i64;
+        }
+    }
+
+    /// Count a lifecycle if its Proposed spread (Finalized as fallback) is actionable
+    pub fn add_lifecycle(&mut self, lifecycle: &BlockLifecycle, min_spread_bps: i32) {
+        let Some(snap) = lifecycle.proposed.as_ref().or(lifecycle.finalized.as_ref()) else { return };
+        if snap.best_spread_bps < min_spread_bps {
+            return;
+        }
+        let route = snap
+            .best_pair
+            .as_ref()
+            .map(|(buy, sell)| format!("{}→{}", buy, sell))
+            .unwrap_or_else(|| "?".to_string());
+        match parse_utc_hour(&snap.wall_clock) {
+            Some(hour) => self.add(hour, &route, snap.best_spread_bps),
+            None => self.undated += 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.hours.iter().map(|b| b.count).sum()
+    }
+}
+
+/// Proposed→Finalized spread decay per route, summed over actionable lifecycles
+#[derive(Debug, Default)]
+pub struct RouteDecayStats {
+    /// (buy_pool, sell_pool) -> (count, decay sum in bps, captured count)
+    pub routes: HashMap<(String, String), (u64, i64, u64)>,
+}
+
+impl RouteDecayStats {
+    /// Count a lifecycle whose Proposed spread is actionable and that reached Finalized
+    pub fn add_lifecycle(&mut self, lifecycle: &BlockLifecycle, min_spread_bps: i32) {
+        let (Some(proposed), Some(finalized)) = (&lifecycle.proposed, &lifecycle.finalized) else { return };
+        let Some((buy, sell)) = &proposed.best_pair else { return };
+        if proposed.best_spread_bps < min_spread_bps {
+            return;
+        }
+        let entry = self.routes.entry((buy.clone(), sell.clone())).or_default();
+        entry.0 += 1;
+        entry.1 += (proposed.best_spread_bps - finalized.best_spread_bps) as i64;
+        if SpreadOutcome::classify(proposed.best_spread_bps, finalized.best_spread_bps) == SpreadOutcome::Captured {
+            entry.2 += 1;
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.routes.values().map(|(count, _, _)| count).sum()
+    }
+
+    /// Routes with their average decay and capture rate (%), most observed first
+    pub fn averages(&self) -> Vec<(&String, &String, u64, f64, f64)> {
+        let mut rows: Vec<_> = self
+            .routes
+            .iter()
+            .map(|((buy, sell), (count, sum, captured))| {
+                (buy, sell, *count, *sum as f64 / *count as f64, *captured as f64 / *count as f64 * 100.0)
+            })
+            .collect();
+        rows.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (a.0, a.1).cmp(&(b.0, b.1))));
+        rows
+    }
+}
+
+fn print_decay(decay: &RouteDecayStats) {
+    let rows = decay.averages();
+    println!("  {:<28} {:>6} {:>10} {:>9}", "Route", "Count", "Avg decay", "Captured");
+    println!("  ───────────────────────────────────────────────────────────────");
+    for (buy, sell, count, avg, captured) in &rows {
+        println!("  {:<28} {:>6} {:>9.1} {:>8.0}%", format!("{}→{}", buy, sell), count, avg, captured);
+    }
+
+    // Negative averages (spreads that widened) are floored: no bonus for luck
+    println!("\n  Paste into --config for AutoArb:\n");
+    for (buy, sell, _, avg, _) in &rows {
+        println!("[[route_decay]]");
+        println!("buy_pool = \"{}\"", buy);
+        println!("sell_pool = \"{}\"", sell);
+        println!("decay_bps = {:.1}\n", avg.max(0.0));
+    }
+    for (buy, sell, _, _, _) in rows.iter().filter(|r| r.4 > COMPETITIVE_CAPTURE_PCT) {
+        println!("[[competitive_routes]]");
+        println!("buy_pool = \"{}\"", buy);
+        println!("sell_pool = \"{}\"\n", sell);
+    }
+}
+
+/// Shade for `count` relative to the densest cell
+fn shade(count: u64, max: u64) -> char {
+    if count == 0 || max == 0 {
+        return SHADES[0];
+    }
+    let idx = (count * (SHADES.len() as u64 - 1)).div_ceil(max) as usize;
+    SHADES[idx.min(SHADES.len() - 1)]
+}
+
+fn print_heatmap(heatmap: &Heatmap) {
+    let max_hour = heatmap.hours.iter().map(|b| b.count).max().unwrap_or(0);
+
+    println!("  {:<6} {:>7} {:>9}  Density", "UTC", "Count", "Avg bps");
+    println!("  ───────────────────────────────────────────────────────────────");
+    for (hour, bucket) in heatmap.hours.iter().enumerate() {
+        let width = if max_hour > 0 { (bucket.count * 40).div_ceil(max_hour) as usize } else { 0 };
+        println!("  {:02}:00  {:>7} {:>9.1}  {}", hour, bucket.count, bucket.avg_bps(), "█".repeat(width));
+    }
+
+    let mut routes: Vec<(&String, u64)> = heatmap
+        .routes
+        .iter()
+        .map(|(route, hours)| (route, hours.iter().map(|b| b.count).sum()))
+        .collect();
+    routes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let max_cell = heatmap.routes.values().flat_map(|h| h.iter().map(|b| b.count)).max().unwrap_or(0);
+
+    println!("\n  By route (UTC hour, shade = count; {} = densest cell, {} total)",
+        SHADES[SHADES.len() - 1], max_cell);
+    println!("  {:<28} 000000000011111111112222  {:>6} {:>8}", "", "", "");
+    println!("  {:<28} 012345678901234567890123  {:>6} {:>8}", "Route", "Count", "Avg bps");
+    for (route, count) in routes.iter().take(MAX_ROUTES) {
+        let hours = &heatmap.routes[*route];
+        let row: String = hours.iter().map(|b| shade(b.count, max_cell)).collect();
+        let spread_sum: i64 = hours.iter().map(|b| b.spread_sum_bps).sum();
+        println!("  {:<28} {}  {:>6} {:>8.1}", route, row, count, spread_sum as f64 / *count as f64);
+    }
+    if routes.len() > MAX_ROUTES {
+        println!("  ... {} more route(s)", routes.len() - MAX_ROUTES);
+    }
+}
+
+/// Run the AnalyzeLogs command over one or more comma-separated files
+/// Feed every lifecycle in the comma-separated `files` to `f`; returns
+/// (records, unparsed lines skipped)
+fn read_lifecycles(files: &str, mut f: impl FnMut(&BlockLifecycle)) -> Result<(usize, usize)> {
+    let (mut records, mut skipped) = (0usize, 0usize);
+    for file in files.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        let reader = BufReader::new(File::open(file).map_err(|e| eyre!("Failed to open {}: {}", file, e))?);
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<BlockLifecycle>(&line) {
+                Ok(lifecycle) => {
+                    records += 1;
+                    f(&lifecycle);
+                }
+                Err(_) => skipped += 1,
+            }
+        }
+    }
+
+    if records == 0 {
+        return Err(eyre!("No block lifecycle records found in {}", files));
+    }
+    Ok((records, skipped))
+}
+
+/// `MevValidate`'s execution window over `files`, with the lifecycle count behind it
+pub fn validated_execution_window(files: &str) -> Result<(u128, u64)> {
+    let mut stats = RunningStats::new();
+    read_lifecycles(files, |lifecycle| stats.record(lifecycle))?;
+    let window = stats.execution_window_ms();
+    if window == 0 {
+        return Err(eyre!("No complete Proposed→Finalized timings in {}", files));
+    }
+    Ok((window, stats.complete_lifecycles))
+}
+
+pub fn run_analyze_logs(files: &str, group_by: GroupBy, min_spread_bps: i32) -> Result<()> {
+    let mut heatmap = Heatmap::default();
+    let mut decay = RouteDecayStats::default();
+    let (records, skipped) = read_lifecycles(files, |lifecycle| match group_by {
+        GroupBy::Hour => heatmap.add_lifecycle(lifecycle, min_spread_bps),
+        GroupBy::Decay => decay.add_lifecycle(lifecycle, min_spread_bps),
+    })?;
+
+    println!("\n═══════════════════════════════════════════════════════════════");
+    match group_by {
+        GroupBy::Hour => println!("  SPREAD HEATMAP | {}", files),
+        GroupBy::Decay => println!("  SPREAD DECAY BY ROUTE | {}", files),
+    }
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Lifecycles:         {} ({} unparsed lines skipped)", records, skipped);
+    match group_by {
+        GroupBy::Hour => println!("  Actionable (>= {} bps): {}{}", min_spread_bps, heatmap.total(),
+            if heatmap.undated > 0 { format!(" (+{} without a wall clock)", heatmap.undated) } else { String::new() }),
+        GroupBy::Decay => println!("  Actionable (>= {} bps) and finalized: {}", min_spread_bps, decay.total()),
+    }
+    println!("───────────────────────────────────────────────────────────────");
+    match group_by {
+        GroupBy::Hour => print_heatmap(&heatmap),
+        GroupBy::Decay => print_decay(&decay),
+    }
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_hour_buckets_fold_days() {
+        assert_eq!(parse_utc_hour("2025-03-01 23:30:00.000+02:00"), Some(21));
+        assert_eq!(parse_utc_hour("2025-03-02 01:15:00.000-03:00"), Some(4));
+        assert!(parse_utc_hour("2025-03-02 01:15:00.000").is_some());
+        assert_eq!(parse_utc_hour("not a time"), None);
+
+        // Same hour on different days lands in one bucket
+        let mut heatmap = Heatmap::default();
+        heatmap.add(parse_utc_hour("2025-03-01 14:05:00.000+00:00").unwrap(), "LFJ→Uniswap", 12);
+        heatmap.add(parse_utc_hour("2025-03-02 14:55:00.000+00:00").unwrap(), "LFJ→Uniswap", 20);
+        heatmap.add(3, "Uniswap→LFJ", 10);
+        assert_eq!(heatmap.hours[14].count, 2);
+        assert_eq!(heatmap.hours[14].avg_bps(), 16.0);
+        assert_eq!(heatmap.routes["LFJ→Uniswap"][14].count, 2);
+        assert_eq!(heatmap.total(), 3);
+        assert_eq!(shade(2, 2), '█');
+        assert_eq!(shade(0, 2), ' ');
+    }
+}
@@ -0,0 +1,101 @@
+//! Log output setup (`--log-format`)
+//!
+//! Arb timings and results are `tracing` events on the `EVENT_TARGET` target
+//! with structured fields (pair, net_bps, gas_mon, tx_hash, ...):
+//! - `pretty` (default): events print their message only, so the console
+//!   looks like the old println output; other logs keep the usual format
+//! - `json`: every event is one JSON object per line, fields flattened, for
+//!   shipping to a log aggregator without regex scraping
+//!
+//! `RUST_LOG` still overrides the filter in both modes.
+
+use eyre::{eyre, Result};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use tracing::Level;
+use tracing_subscriber::filter::{filter_fn, EnvFilter};
+use tracing_subscriber::fmt::format::debug_fn;
+use tracing_subscriber::prelude::*;
+
+/// Target for structured arb events
+pub const EVENT_TARGET: &str = "arb_event";
+
+/// Pair label attached to arb events (the --pair value)
+static PAIR: OnceLock<String> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(eyre!("Unknown log format '{}'. Expected pretty or json", s)),
+        }
+    }
+}
+
+/// Pair label for event fields
+pub fn pair() -> &'static str {
+    PAIR.get().map(String::as_str).unwrap_or("")
+}
+
+fn is_event(target: &str) -> bool {
+    target == EVENT_TARGET
+}
+
+/// Install the global subscriber
+pub fn init(format: LogFormat, pair: &str) -> Result<()> {
+    let _ = PAIR.set(pair.to_string());
+
+    match format {
+        LogFormat::Pretty => {
+            let logs = tracing_subscriber::fmt::layer()
+                .with_filter(EnvFilter::from_default_env())
+                .with_filter(filter_fn(|meta| !is_event(meta.target())));
+            // Message only: fields are for machines
+            let events = tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_level(false)
+                .with_target(false)
+                .fmt_fields(debug_fn(|writer, field, value| {
+                    if field.name() == "message" {
+                        write!(writer, "{:?}", value)
+                    } else {
+                        Ok(())
+                    }
+                }))
+                .with_filter(filter_fn(|meta| is_event(meta.target()) && *meta.level() <= Level::INFO));
+            tracing_subscriber::registry().with(logs).with(events).try_init()?;
+        }
+        LogFormat::Json => {
+            let filter = EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new(format!("warn,{}=info", EVENT_TARGET)));
+            tracing_subscriber::fmt()
+                .json()
+                .flatten_event(true)
+                .with_env_filter(filter)
+                .try_init()
+                .map_err(|e| eyre!("{}", e))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("Pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
@@ -8,8 +8,7 @@ use std::str::FromStr;
 use std::sync::OnceLock;
 use std::time::Duration;
 use tokio::time::interval;
-use tracing::{error, info, Level};
-use tracing_subscriber::FmtSubscriber;
+use tracing::{error, info};
 
 // Global HTTP client for connection reuse (Issue 7)
 static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
@@ -26,45 +25,67 @@ fn get_http_client() -> &'static Client {
     })
 }
 
+mod alerts;
+mod backtest;
 mod config;
 mod display;
+mod error;
 mod execution;
+mod feature_recorder;
+mod gas;
 mod gas_cache;
 mod health;
+mod log_analysis;
+mod logging;
+mod mempool;
+mod metrics;
 mod mev_validation;
 mod multicall;
 mod node_config;
 mod nonce;
 mod pools;
 mod price;
+mod price_recorder;
+mod rebalance;
+mod rpc_bench;
+mod rpc_failover;
+mod simulation;
+mod slippage;
 mod spread_display;
 mod spread_filter;
 mod spread_logger;
 mod spread_tracker;
 mod stats;
+mod tui;
 mod wallet;
 
 use config::{
-    get_all_pools, get_lfj_pool, get_monday_trade_pool, get_v3_pools, get_router_by_name,
-    POLL_INTERVAL_MS, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS, USDC_DECIMALS,
+    get_all_pools, get_lfj_pools, get_monday_trade_pool, get_v3_pools, get_router_by_name,
+    get_token_pair, POLL_INTERVAL_MS, WMON_ADDRESS, USDC_ADDRESS, WMON_DECIMALS,
     UNISWAP_SWAP_ROUTER, PANCAKE_SMART_ROUTER, LFJ_LB_ROUTER, MONDAY_SWAP_ROUTER,
-    RouterConfig, ATOMIC_ARB_CONTRACT,
+    RouterConfig, TokenPair, atomic_arb_contract,
 };
-use health::verify_node_ready;
-use node_config::NodeConfig;
+use health::{verify_node_ready, verify_pools_ready};
+use node_config::{AdaptivePoll, NodeConfig};
 use display::{display_prices, init_arb_log, calculate_spreads};
 use stats::{
     StatsLogger, ArbExecutionRecord, PreExecutionSnapshot, PostExecutionSnapshot,
     print_pre_execution, print_post_execution,
 };
-use execution::{SwapParams, SwapDirection, execute_swap, print_swap_report, build_swap_calldata, wait_for_next_block, execute_fast_arb, print_fast_arb_result, execute_atomic_arb, print_atomic_arb_result, query_contract_balances};
+use execution::{SwapParams, SwapDirection, execute_swap, execute_swap_native, print_swap_report, build_swap_calldata, wait_for_next_block, execute_fast_arb, execute_fast_arb_parallel, FastArbOptions, FastArbTrade, print_fast_arb_result, print_pending_legs, execute_atomic_arb, execute_atomic_arb_flashloan, AtomicArbTrade, AtomicExecOptions, print_atomic_arb_result, query_contract_balances, query_contract_owner, execute_multihop_arb, parse_route, print_multihop_result, check_arb_direction};
 use execution::report::print_comparison_report;
-use spread_filter::{SpreadFilterConfig, FilterResult};
-use spread_tracker::SpreadTracker;
-use multicall::fetch_prices_batched;
+use spread_filter::{ArbMode, DecayGate, EtaGate, RankBy, SpreadFilterConfig, FilterResult};
+use spread_tracker::{SpreadTracker, Warmup};
+use mev_validation::SpreadTier;
+use multicall::{fetch_prices_batched, fetch_prices_batched_retry, stream_prices_on_block};
+use gas::GasStrategy;
+use simulation::{print_arb_quote, QuoteFetcher};
+use simulation::paper::{PaperAccount, PaperFill};
+use simulation::profit_calculator::{self, PnlDenom, PnlReport};
 use nonce::init_nonce;
-use pools::{create_lfj_active_id_call, create_lfj_bin_step_call, create_slot0_call, PriceCall, PoolPrice};
-use wallet::{get_balances, print_balances, wrap_mon, unwrap_wmon, print_wrap_result};
+use pools::liquidity::attach_liquidity;
+use pools::{create_lfj_active_id_call, create_lfj_bin_step_call, create_slot0_call, create_v3_price_calls, oldest_price_age, PriceCall, PoolPrice};
+use wallet::{get_balances, print_balances, wrap_mon, unwrap_wmon, plan_top_up, print_wrap_result, WalletLease, WalletPool};
 
 #[derive(Parser)]
 #[command(name = "monad-arb")]
@@ -72,12 +93,65 @@ use wallet::{get_balances, print_balances, wrap_mon, unwrap_wmon, print_wrap_res
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Token pair to trade as <base>-<quote> (symbols from config)
+    #[arg(long, global = true, default_value = "wmon-usdc")]
+    pair: String,
+
+    /// Path to a monad-arb.toml overriding compiled token/pool/router addresses
+    #[arg(long, global = true)]
+    config: Option<std::path::PathBuf>,
+
+    /// Log output: pretty (console) or json (one event per line, structured fields)
+    #[arg(long, global = true, default_value = "pretty")]
+    log_format: String,
+
+    /// Give up waiting for a receipt after this long (default depends on the command)
+    #[arg(long, global = true)]
+    receipt_timeout_secs: Option<u64>,
+
+    /// Receipt polling interval (default depends on the command and node)
+    #[arg(long, global = true)]
+    receipt_poll_ms: Option<u64>,
+
+    /// Also track each V3 DEX's pools on the pair's other fee tiers (see [[fee_tiers]] in --config)
+    /// and LFJ's pools on its other bin steps (see bin_steps in --config)
+    #[arg(long, global = true)]
+    fee_tiers: bool,
+
+    /// Re-read the LFJ bin step every N price polls (1 = every poll, 0 = only on a decode anomaly)
+    #[arg(long, global = true, default_value_t = pools::lfj_pool::DEFAULT_BIN_STEP_REFRESH)]
+    refresh_binstep_every: u32,
+
+    /// Price calls per Multicall3 batch; larger pool sets are split into concurrent batches
+    #[arg(long, global = true, default_value_t = multicall::DEFAULT_MULTICALL_CHUNK)]
+    multicall_chunk: usize,
+
+    /// Drop pool prices more than this % from the pool's session median before computing spreads
+    #[arg(long, global = true)]
+    price_sanity_pct: Option<f64>,
+
+    /// Also bound V3 swaps (Uniswap, PancakeSwap) on chain with a sqrtPriceLimitX96 at --slippage from the quoted price
+    #[arg(long, global = true)]
+    price_limit: bool,
+
+    /// Where arb transactions go: public (mempool) or private (signed locally, sent to --relay-url)
+    #[arg(long, global = true, default_value = "public")]
+    submit: String,
+
+    /// Private relay JSON-RPC endpoint accepting eth_sendRawTransaction
+    #[arg(long, global = true)]
+    relay_url: Option<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Run price monitor (default)
-    Monitor,
+    Monitor {
+        /// Refresh on every Proposed block via monadNewHeads instead of polling
+        #[arg(long)]
+        stream: bool,
+    },
 
     /// Execute a test swap on a specific DEX
     TestSwap {
@@ -98,6 +172,25 @@ enum Commands {
         slippage: u32,
     },
 
+    /// Swap back towards a target WMON/USDC split after one-directional arbs
+    Rebalance {
+        /// Target WMON share of wallet value (WMON + USDC), in percent
+        #[arg(long, default_value = "50")]
+        target_wmon_pct: f64,
+
+        /// DEX name, or "auto" for the best fee-adjusted price
+        #[arg(long, default_value = "auto")]
+        dex: String,
+
+        /// Do nothing if the WMON share is within this many bps of the target
+        #[arg(long, default_value = "500")]
+        tolerance_bps: u32,
+
+        /// Slippage tolerance in bps
+        #[arg(long, default_value = "100")]
+        slippage: u32,
+    },
+
     /// Test swaps on all DEXes
     TestAll {
         /// Amount to swap per DEX
@@ -118,6 +211,19 @@ enum Commands {
     /// Show wallet balances (MON, WMON, USDC)
     Balance,
 
+    /// Replace a stuck pending TX with a 0-value self-transfer at the same nonce
+    CancelTx {
+        /// Nonce of the stuck transaction
+        #[arg(long)]
+        nonce: u64,
+        /// Raise the gas strategy's fees by this percent (must beat the stuck TX)
+        #[arg(long, default_value = "20")]
+        bump_pct: u32,
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
+    },
+
     /// Wrap MON to WMON
     Wrap {
         /// Amount of MON to wrap
@@ -132,9 +238,9 @@ enum Commands {
         amount: f64,
     },
 
-    /// Swap USDC to MON (buys WMON then unwraps)
+    /// Swap USDC (or --quote-token) to MON (buys WMON then unwraps)
     BuyMon {
-        /// Amount of USDC to spend
+        /// Amount of the quote token to spend
         #[arg(long)]
         amount: f64,
 
@@ -142,6 +248,10 @@ enum Commands {
         #[arg(long, default_value = "uniswap")]
         dex: String,
 
+        /// Token to pay with; needs a WMON pool on the DEX
+        #[arg(long, default_value = "usdc")]
+        quote_token: String,
+
         /// Slippage tolerance in bps (e.g., 100 = 1%)
         #[arg(long, default_value = "100")]
         slippage: u32,
@@ -149,9 +259,13 @@ enum Commands {
         /// Keep as WMON instead of unwrapping to MON
         #[arg(long, default_value = "false")]
         keep_wrapped: bool,
+
+        /// Have the router pay out MON directly, skipping the unwrap TX (routers with supports_native)
+        #[arg(long, default_value = "false")]
+        use_native: bool,
     },
 
-    /// Swap MON to USDC (wraps MON then sells WMON)
+    /// Swap MON to USDC (or --quote-token) (wraps MON then sells WMON)
     SellMon {
         /// Amount of MON to sell
         #[arg(long)]
@@ -161,6 +275,10 @@ enum Commands {
         #[arg(long, default_value = "uniswap")]
         dex: String,
 
+        /// Token to receive; needs a WMON pool on the DEX
+        #[arg(long, default_value = "usdc")]
+        quote_token: String,
+
         /// Slippage tolerance in bps (e.g., 100 = 1%)
         #[arg(long, default_value = "100")]
         slippage: u32,
@@ -168,6 +286,10 @@ enum Commands {
         /// Use WMON directly instead of wrapping MON first
         #[arg(long, default_value = "false")]
         use_wmon: bool,
+
+        /// Send MON as the TX value, skipping the wrap TX (routers with supports_native)
+        #[arg(long, default_value = "false")]
+        use_native: bool,
     },
 
     /// Test DEX-to-DEX arbitrage (sell on one DEX, buy on another)
@@ -187,11 +309,71 @@ enum Commands {
         /// Slippage tolerance in bps (e.g., 150 = 1.5%)
         #[arg(long, default_value = "150")]
         slippage: u32,
+
+        /// Execute even if sell_dex doesn't quote the higher price
+        #[arg(long)]
+        force: bool,
+
+        /// Swap --sell-dex/--buy-dex when the prices say the other way round
+        #[arg(long)]
+        auto_direction: bool,
+    },
+
+    /// Quote an arb without sending any TX (read-only TestArb, needs router quoters in --config)
+    SimulateArb {
+        /// DEX to sell WMON on (higher price)
+        #[arg(long)]
+        sell_dex: String,
+
+        /// DEX to buy WMON on (lower price)
+        #[arg(long)]
+        buy_dex: String,
+
+        /// Amount of WMON to start with
+        #[arg(long, default_value = "1.0")]
+        amount: f64,
+    },
+
+    /// Quote one trade size on every router and rank the fills (eth_call only)
+    CompareRouters {
+        /// Amount of the input token (WMON to sell, USDC to buy with)
+        #[arg(long, default_value = "1.0")]
+        amount: f64,
+
+        /// Direction: sell or buy
+        #[arg(long, default_value = "sell")]
+        direction: String,
+
+        /// Only these routers (comma-separated names, default all)
+        #[arg(long)]
+        routers: Option<String>,
+    },
+
+    /// Minimum net spread an arb of --amount needs to pay for gas, for fast and atomic execution
+    BreakEven {
+        /// Arb size in WMON
+        #[arg(long, default_value = "1.0")]
+        amount: f64,
+
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
     },
 
     /// Prepare wallet for arbitrage by approving all routers (one-time setup)
     PrepareArb,
 
+    /// Revoke the router approvals PrepareArb granted (sets allowances to 0)
+    RevokeArb {
+        /// Only this router (name as in --config, e.g. Uniswap)
+        #[arg(long)]
+        router: Option<String>,
+
+        /// Only this token (symbol, e.g. USDC)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
     /// Fast DEX-to-DEX arbitrage (optimized <1.5s execution)
     FastArb {
         #[arg(long)]
@@ -202,6 +384,56 @@ enum Commands {
         amount: f64,
         #[arg(long, default_value = "200")]
         slippage: u32,
+        /// Scale --slippage with trade size vs pool depth (--slippage applies at 1% of depth)
+        #[arg(long)]
+        adaptive_slippage: bool,
+        /// Ceiling for --adaptive-slippage (bps)
+        #[arg(long, default_value = slippage::DEFAULT_MAX_SLIPPAGE_BPS)]
+        max_slippage: u32,
+        /// Dry-run swap 1 via eth_call before sending (skips reverts, costs ~1 RTT)
+        #[arg(long)]
+        simulate: bool,
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
+        /// Fund the buy leg from USDC inventory and send both legs at once (nonces N, N+1)
+        #[arg(long)]
+        parallel: bool,
+        /// Quote sizes up to --amount (QuoterV2 on both routers) and trade the most profitable
+        #[arg(long)]
+        optimize_size: bool,
+        /// Sizes tried by --optimize-size
+        #[arg(long, default_value = "10")]
+        optimize_steps: u32,
+        /// Execute even if sell_dex doesn't quote the higher price
+        #[arg(long)]
+        force: bool,
+        /// Swap --sell-dex/--buy-dex when the prices say the other way round
+        #[arg(long)]
+        auto_direction: bool,
+        /// Skip the arb if its max fee per gas would exceed this (gwei)
+        #[arg(long, default_value = gas::DEFAULT_MAX_GAS_GWEI)]
+        max_gas_gwei: f64,
+        /// Shadow execution: send (and take balances, nonce and gas) from this RPC, e.g. an
+        /// `anvil --fork-url` node, while prices still come from MONAD_RPC_URL
+        #[arg(long)]
+        exec_rpc: Option<String>,
+    },
+
+    /// Cyclic arb across several pools/tokens, one TX per hop
+    MultiHopArb {
+        /// Hops as <dex>:<tokenIn>-<tokenOut>, e.g. uniswap:wmon-usdc,lfj:usdc-usdt,pancakeswap1:usdt-wmon
+        #[arg(long)]
+        route: String,
+        /// Amount of the route's first token to sell
+        #[arg(long, default_value = "1.0")]
+        amount: f64,
+        /// Max loss on the whole cycle (min out of the last hop)
+        #[arg(long, default_value = "200")]
+        slippage: u32,
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
     },
 
     /// Atomic arbitrage via smart contract (single TX, MEV-resistant)
@@ -214,11 +446,37 @@ enum Commands {
         amount: f64,
         #[arg(long, default_value = "150")]
         slippage: u32,
+        /// Profit floor: swap 2 must return amount * (1 + bps/10000) WMON or the contract reverts
         #[arg(long, default_value = "0")]
         min_profit_bps: i32,
-        /// Force execution even if unprofitable (for testing)
+        /// Force execution even if unprofitable or sell_dex quotes lower (for testing);
+        /// sets the on-chain profit floor to 0, leaving only --slippage
         #[arg(long, default_value = "false")]
         force: bool,
+        /// Swap --sell-dex/--buy-dex when the prices say the other way round
+        #[arg(long)]
+        auto_direction: bool,
+        /// Borrow the WMON via flashloan instead of using contract balance
+        #[arg(long, default_value = "false")]
+        flashloan: bool,
+        /// Flashloan pool address (Aave V3-style flashLoanSimple); defaults to FLASHLOAN_POOL env
+        #[arg(long)]
+        flashloan_pool: Option<String>,
+        /// Flashloan premium in bps, subtracted from profit before min_profit_bps check
+        #[arg(long, default_value = "5")]
+        flashloan_fee_bps: u32,
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
+        /// Skip the arb if its max fee per gas would exceed this (gwei)
+        #[arg(long, default_value = gas::DEFAULT_MAX_GAS_GWEI)]
+        max_gas_gwei: f64,
+        /// Fire the arb TX and return without waiting for its receipt
+        #[arg(long)]
+        turbo: bool,
+        /// With --turbo: fetch the receipt in the background and report success/revert + gas
+        #[arg(long)]
+        await_receipt: bool,
     },
 
     /// Automated arbitrage: monitors prices and executes when opportunity found
@@ -235,11 +493,19 @@ enum Commands {
         #[arg(long, default_value = "200")]
         slippage: u32,
 
+        /// Scale --slippage with trade size vs pool depth (--slippage applies at 1% of depth)
+        #[arg(long)]
+        adaptive_slippage: bool,
+
+        /// Ceiling for --adaptive-slippage (bps)
+        #[arg(long, default_value = slippage::DEFAULT_MAX_SLIPPAGE_BPS)]
+        max_slippage: u32,
+
         /// Maximum executions (0 = unlimited)
         #[arg(long, default_value = "1")]
         max_executions: u32,
 
-        /// Cooldown between executions in seconds
+        /// Cooldown between executions on the same route (buy/sell pool pair) in seconds
         #[arg(long, default_value = "10")]
         cooldown_secs: u64,
 
@@ -247,6 +513,11 @@ enum Commands {
         #[arg(long, default_value = "false")]
         dry_run: bool,
 
+        /// Paper trading: fill triggered arbs at quoted prices against a virtual
+        /// balance (starts at the contract's) instead of sending transactions
+        #[arg(long, conflicts_with = "dry_run")]
+        paper: bool,
+
         /// Force execution even if unprofitable (for testing)
         #[arg(long, default_value = "false")]
         force: bool,
@@ -274,6 +545,120 @@ enum Commands {
         /// Maximum baseline spread (bps) - skip if already elevated
         #[arg(long, default_value = "2")]
         max_baseline: i32,
+
+        /// Maximum |spread acceleration| (bps/sec^2) - skip sandwich-prone moves, 0 disables
+        #[arg(long, default_value = "0")]
+        max_acceleration: f64,
+
+        /// Max price impact (bps) per leg; amount is clamped to pool depth - 0 disables
+        #[arg(long, default_value = "0")]
+        max_impact_bps: u32,
+
+        /// Minimum expected net profit (WMON) after DEX fees and gas
+        #[arg(long, default_value = "0.0")]
+        min_net_profit_wmon: f64,
+
+        /// Serve Prometheus metrics on this port (requires --features metrics)
+        #[arg(long)]
+        metrics_port: Option<u16>,
+
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
+
+        /// Skip spreads where either pool's in-range liquidity is below this (raw L; LFJ uses reserve-derived L)
+        #[arg(long)]
+        min_liquidity: Option<u128>,
+
+        /// Skip when the learned probability (0-1) that this route's spread tier lasts
+        /// --survival-horizon-ms is below this - 0 disables. Estimates start at 0.
+        #[arg(long, default_value = "0")]
+        min_survival: f64,
+
+        /// How long a spread must persist to count as survived (~ execution latency)
+        #[arg(long, default_value = "500")]
+        survival_horizon_ms: u64,
+
+        /// Watch pending router swaps over WS and pre-arm routes a large swap will open
+        #[arg(long)]
+        watch_mempool: bool,
+
+        /// Pending swap size (WMON-equivalent) that pre-arms a route
+        #[arg(long, default_value = "5000")]
+        mempool_min_wmon: f64,
+
+        /// Skip execution if a price behind the chosen spread is older than this
+        #[arg(long, default_value = "500")]
+        max_price_age_ms: u64,
+
+        /// Retry a swap 2 that reverted on slippage once, with slippage widened by this many bps (0 = off)
+        #[arg(long, default_value = "0")]
+        retry_slippage_bump: u32,
+
+        /// Wrap wallet MON (and fund the contract, for atomic execution) when arb
+        /// WMON drops below this; tops up to threshold + amount
+        #[arg(long)]
+        auto_wrap_threshold: Option<f64>,
+
+        /// Most MON --auto-wrap-threshold may wrap in one session
+        #[arg(long, default_value = "100")]
+        max_wrap_per_session: f64,
+
+        /// Skip the arb if its max fee per gas would exceed this (gwei)
+        #[arg(long, default_value = gas::DEFAULT_MAX_GAS_GWEI)]
+        max_gas_gwei: f64,
+
+        /// Sweep contract WMON above the starting float to COLD_WALLET once the excess reaches this
+        #[arg(long)]
+        auto_sweep_profit: Option<f64>,
+
+        /// Expected spread decay (bps) before the arb lands; execute only if spread - decay >= min spread.
+        /// Per-route [[route_decay]] in --config overrides it
+        #[arg(long, default_value = "0")]
+        expected_decay_bps: f64,
+
+        /// Contested routes to bid a higher priority fee on, as BUY_POOL>SELL_POOL (comma-separated);
+        /// adds to [[competitive_routes]] in --config
+        #[arg(long)]
+        competitive_routes: Option<String>,
+
+        /// Priority fee multiplier on competitive routes
+        #[arg(long, default_value = gas::DEFAULT_COMPETITIVE_PRIORITY_MULTIPLIER)]
+        competitive_priority_multiplier: f64,
+
+        /// Append the best spread to this CSV every poll
+        /// (unix_ms,block,best_buy_pool,best_sell_pool,gross_bps,net_bps,velocity)
+        #[arg(long)]
+        spread_log: Option<String>,
+
+        /// poll: fire on every spread that passes the filters. eta: fire only if the ETA fits
+        /// the execution window from --validation-logs and the route's spread usually lasts the ETA
+        #[arg(long, default_value = "poll")]
+        mode: ArbMode,
+
+        /// Expected execution latency (ms) for --mode eta; updated from measured executions.
+        /// Also the survival horizon in eta mode (replaces --survival-horizon-ms)
+        #[arg(long, default_value = "400")]
+        eta_ms: u64,
+
+        /// MevValidate logs (comma-separated) to measure the execution window from (--mode eta).
+        /// Also sets the fast-arb latency budget: swap 2 is skipped once detection + window has passed
+        #[arg(long)]
+        validation_logs: Option<String>,
+
+        /// spread: take the highest net spread. executable: scale each spread by the share of
+        /// --amount both pools absorb within --max-impact-bps (or --slippage), so thin pools rank lower
+        #[arg(long, default_value = "spread")]
+        rank_by: RankBy,
+
+        /// Report P&L in wmon or usdc (valued at the latest WMON/USDC price, WMON shown alongside)
+        #[arg(long, default_value = "wmon")]
+        pnl_denom: PnlDenom,
+
+        /// Write every poll's prices and spread features, and each execution's realized profit,
+        /// to spread_features_<timestamp>.jsonl
+        #[arg(long)]
+        record_features: bool,
     },
 
     /// Production arbitrage bot with safety checks
@@ -290,13 +675,70 @@ enum Commands {
         #[arg(long, default_value = "100")]
         slippage: u32,
 
+        /// Scale --slippage with trade size vs pool depth (--slippage applies at 1% of depth)
+        #[arg(long)]
+        adaptive_slippage: bool,
+
+        /// Ceiling for --adaptive-slippage (bps)
+        #[arg(long, default_value = slippage::DEFAULT_MAX_SLIPPAGE_BPS)]
+        max_slippage: u32,
+
         /// Max daily loss in WMON (stops bot if exceeded)
         #[arg(long, default_value = "0.5")]
         max_daily_loss: f64,
 
+        /// Pause when P&L falls this far (WMON) below its session high; max_daily_loss still stops
+        #[arg(long)]
+        max_drawdown_wmon: Option<f64>,
+
+        /// How long a --max-drawdown-wmon pause lasts
+        #[arg(long, default_value = "300")]
+        drawdown_pause_secs: u64,
+
+        /// Cap on WMON in arbs not yet settled; skips an arb that would exceed it.
+        /// A swap 2 that never filled keeps its amount counted for the session
+        #[arg(long)]
+        max_exposure_wmon: Option<f64>,
+
         /// Max consecutive failures before pause
         #[arg(long, default_value = "3")]
         max_failures: u32,
+
+        /// Max price impact (bps) per leg; amount is clamped to pool depth - 0 disables
+        #[arg(long, default_value = "0")]
+        max_impact_bps: u32,
+
+        /// Serve Prometheus metrics on this port (requires --features metrics)
+        #[arg(long)]
+        metrics_port: Option<u16>,
+
+        /// Send webhook alerts to ALERT_WEBHOOK_URL (executions, loss stop, failure pause)
+        #[arg(long, default_value = "false")]
+        alerts: bool,
+
+        /// Gas pricing: fixed:<gwei>, multiplier:<x>, percentile[:<blocks>[:<pct>]]
+        #[arg(long, default_value = gas::DEFAULT_GAS_STRATEGY)]
+        gas_strategy: String,
+
+        /// Only report missing router allowances at startup instead of approving them
+        #[arg(long)]
+        no_auto_approve: bool,
+
+        /// Skip the arb if its max fee per gas would exceed this (gwei)
+        #[arg(long, default_value = gas::DEFAULT_MAX_GAS_GWEI)]
+        max_gas_gwei: f64,
+
+        /// Pause for 60s after this many consecutive --max-gas-gwei skips (0 = never pause)
+        #[arg(long, default_value = "0")]
+        gas_pause_after: u32,
+
+        /// Observe and build spread history for this long before trading (min 10)
+        #[arg(long, default_value = "30")]
+        warmup_secs: u64,
+
+        /// Report P&L in wmon or usdc (valued at the latest WMON/USDC price, WMON shown alongside)
+        #[arg(long, default_value = "wmon")]
+        pnl_denom: PnlDenom,
     },
 
     /// Fund the atomic arb contract with WMON
@@ -314,6 +756,9 @@ enum Commands {
     /// Check atomic arb contract balances
     ContractBalance,
 
+    /// Check every configured router builds the expected swap calldata (offline)
+    SelfTest,
+
     /// Test transaction revert to measure gas costs
     TestRevert {
         /// DEX to use: uniswap, pancakeswap1, pancakeswap2, lfj, mondaytrade
@@ -342,6 +787,78 @@ enum Commands {
         /// Output mode: "dashboard" (default), "log", "quiet"
         #[arg(long, default_value = "dashboard")]
         output: String,
+
+        /// Write each Proposed/Finalized snapshot's prices and spread features
+        /// to spread_features_<timestamp>.jsonl
+        #[arg(long)]
+        record_features: bool,
+    },
+
+    /// Append every pool price to a JSONL file on an interval (no WS, no trading)
+    RecordPrices {
+        /// Poll interval in milliseconds
+        #[arg(long, default_value = "1000")]
+        interval_ms: u64,
+
+        /// JSONL file to append to (dated per UTC day when --duration-secs is 0)
+        #[arg(long, default_value = "prices.jsonl")]
+        out: String,
+
+        /// How long to record in seconds (0 = until stopped)
+        #[arg(long, default_value = "0")]
+        duration_secs: u64,
+    },
+
+    /// Measure RPC round-trip and multicall latency (read-only)
+    BenchRpc {
+        /// Calls timed per RPC method
+        #[arg(long, default_value = "50")]
+        samples: u32,
+    },
+
+    /// Flatten an arb_stats JSONL into a CSV for spreadsheets
+    ExportCsv {
+        /// Path to arb_stats_*.jsonl
+        #[arg(long)]
+        input: String,
+
+        /// CSV file to write
+        #[arg(long)]
+        output: String,
+    },
+
+    /// Heatmap of actionable spreads by UTC hour, or per-route spread decay, from mev_validation logs
+    AnalyzeLogs {
+        /// Path(s) to mev_validation_*.jsonl, comma-separated
+        #[arg(long)]
+        file: String,
+
+        /// Grouping: hour (heatmap) or decay (per-route [[route_decay]] for --config)
+        #[arg(long, default_value = "hour")]
+        group_by: String,
+
+        /// Minimum net spread in bps for a block to count as actionable
+        #[arg(long, default_value = "10", allow_hyphen_values = true)]
+        min_spread_bps: i32,
+    },
+
+    /// Replay a mev_validation or arb_stats JSONL offline with a new threshold
+    Backtest {
+        /// Path to mev_validation_*.jsonl or arb_stats_*.jsonl
+        #[arg(long)]
+        file: String,
+
+        /// Minimum net spread in bps to count as triggered
+        #[arg(long, default_value = "10", allow_hyphen_values = true)]
+        min_spread_bps: i32,
+
+        /// Assumed slippage in bps subtracted from each captured spread
+        #[arg(long, default_value = "5")]
+        slippage_bps: u32,
+
+        /// WMON per simulated trade
+        #[arg(long, default_value = "1.0")]
+        amount: f64,
     },
 
     /// MEV Ultra - WebSocket block state trigger with execution
@@ -391,28 +908,125 @@ enum Commands {
         /// Enable sound alerts for HOT+ spreads
         #[arg(long, default_value = "false")]
         sound: bool,
+
+        /// Skip spreads where either pool's in-range liquidity is below this (raw L; LFJ uses reserve-derived L)
+        #[arg(long)]
+        min_liquidity: Option<u128>,
+
+        /// Watch several pairs in one sectioned view, e.g. wmon-usdc,weth-usdc,wmon-weth.
+        /// Pairs other than WMON/USDC use the V3 DEXes' pools found by fee-tier discovery.
+        #[arg(long)]
+        pairs: Option<String>,
+
+        /// Interactive dashboard with keybindings (requires --features tui)
+        #[arg(long, default_value = "false")]
+        tui: bool,
+
+        /// Append the best spread to this CSV every poll (single pair, non-TUI)
+        #[arg(long)]
+        spread_log: Option<String>,
+
+        /// Refresh on every Proposed block via monadNewHeads instead of the
+        /// --refresh-ms timer, which takes over while the WebSocket is down (single pair, non-TUI)
+        #[arg(long, default_value = "false")]
+        on_block: bool,
     },
 }
 
-async fn get_current_prices<P: alloy::providers::Provider>(provider: &P) -> Result<Vec<PoolPrice>> {
+impl Commands {
+    /// Commands that only read local files or config, so skip the startup pair/fee reads
+    fn is_offline(&self) -> bool {
+        matches!(
+            self,
+            Commands::Backtest { .. }
+                | Commands::ExportCsv { .. }
+                | Commands::SelfTest
+                | Commands::AnalyzeLogs { .. }
+                | Commands::BreakEven { .. }
+        )
+    }
+}
+
+/// Discover the V3 DEXes' other fee-tier pools and LFJ's other bin-step pools for `pair` and register them
+async fn init_fee_tiers(pair: &TokenPair) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(rpc_url.parse()?));
+
+    let tiers = config::get_fee_tiers(pair);
+    let mut venues = pools::v3_pool::discover_fee_tiers(&provider, pair, &tiers).await?;
+    println!("  Fee tiers {:?}: {} extra pool(s)", tiers, venues.len());
+    let bin_step_venues = pools::lfj_pool::discover_bin_steps(&provider, pair).await?;
+    println!("  LFJ bin steps: {} extra pool(s)", bin_step_venues.len());
+    venues.extend(bin_step_venues);
+    for (pool, _) in &venues {
+        println!("    {:<18} {:?} ({} bps)", pool.name, pool.address, pool.fee_bps);
+    }
+    config::set_fee_tier_venues(venues)
+}
+
+/// `pair` with on-chain decimals (without MONAD_RPC_URL the configured ones stand)
+async fn init_pair_metadata(pair: TokenPair) -> Result<TokenPair> {
+    let Ok(rpc_url) = std::env::var("MONAD_RPC_URL") else { return Ok(pair) };
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(rpc_url.parse()?));
+    config::resolve_pair_metadata(&provider, pair).await
+}
+
+/// Check configured V3 pool fees against each pool's `fee()` (best effort: a
+/// missing RPC or failed read leaves the configured fees in place)
+async fn init_pool_fees() {
+    let Ok(rpc_url) = std::env::var("MONAD_RPC_URL") else { return };
+    let provider = match rpc_url.parse() {
+        Ok(url) => ProviderBuilder::new().connect_client(rpc_failover::client(url)),
+        Err(e) => return tracing::warn!("Skipping pool fee check, bad MONAD_RPC_URL: {}", e),
+    };
+    if let Err(e) = simulation::fee_validator::validate_pool_fees(&provider).await {
+        tracing::warn!("Pool fee check failed, using configured fees: {}", e);
+    }
+}
+
+/// Price calls for all configured pools (V3 slot0 + liquidity, LFJ activeId per bin-step pool,
+/// Monday slot0 + liquidity). LFJ binStep is only included when its cache is empty or due a refresh.
+fn current_price_calls() -> Vec<PriceCall> {
     let mut price_calls: Vec<PriceCall> = Vec::new();
 
     for pool in get_v3_pools() {
-        price_calls.push(create_slot0_call(&pool));
+        price_calls.extend(create_v3_price_calls(&pool));
     }
 
-    let lfj_pool = get_lfj_pool();
-    price_calls.push(create_lfj_active_id_call(&lfj_pool));
-    price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+    for lfj_pool in get_lfj_pools() {
+        price_calls.push(create_lfj_active_id_call(&lfj_pool));
+        if pools::bin_step_due(lfj_pool.address) {
+            price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+        }
+    }
 
     let monday_pool = get_monday_trade_pool();
-    price_calls.push(create_slot0_call(&monday_pool));
+    price_calls.extend(create_v3_price_calls(&monday_pool));
+
+    price_calls
+}
 
-    let (prices, _) = fetch_prices_batched(provider, price_calls).await?;
+async fn get_current_prices<P: alloy::providers::Provider>(provider: &P) -> Result<Vec<PoolPrice>> {
+    let (prices, _, _) = fetch_prices_batched(provider, current_price_calls()).await?;
+    Ok(prices)
+}
+
+/// ProdArb: shortest --warmup-secs accepted, and the spread history it fills
+const MIN_PROD_WARMUP_SECS: u64 = 10;
+const PROD_SPREAD_HISTORY: usize = 50;
+
+/// Retries on flaky RPC so the bot loops don't skip polls on a single hiccup
+const PRICE_FETCH_RETRIES: u32 = 3;
+const PRICE_FETCH_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// `get_current_prices` with backoff on transient RPC errors
+async fn get_current_prices_retry<P: alloy::providers::Provider>(provider: &P) -> Result<Vec<PoolPrice>> {
+    let (prices, _, _) =
+        fetch_prices_batched_retry(provider, current_price_calls(), PRICE_FETCH_RETRIES, PRICE_FETCH_RETRY_DELAY).await?;
     Ok(prices)
 }
 
-async fn run_monitor() -> Result<()> {
+async fn run_monitor(stream: bool) -> Result<()> {
     use std::io::{stdout, Write};
 
     // Load node configuration (auto-detects local vs remote)
@@ -420,10 +1034,11 @@ async fn run_monitor() -> Result<()> {
     node_config.log_config();
 
     let url: reqwest::Url = node_config.rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
-    // Verify node health before starting
+    // Verify node health and pool configuration before starting
     verify_node_ready(&provider).await?;
+    verify_pools_ready(&provider, &current_price_calls()).await?;
 
     let all_pools = get_all_pools();
     info!("Monitoring {} pools", all_pools.len());
@@ -437,15 +1052,16 @@ async fn run_monitor() -> Result<()> {
     let mut price_calls: Vec<PriceCall> = Vec::new();
 
     for pool in get_v3_pools() {
-        price_calls.push(create_slot0_call(&pool));
+        price_calls.extend(create_v3_price_calls(&pool));
     }
 
-    let lfj_pool = get_lfj_pool();
-    price_calls.push(create_lfj_active_id_call(&lfj_pool));
-    price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+    for lfj_pool in get_lfj_pools() {
+        price_calls.push(create_lfj_active_id_call(&lfj_pool));
+        price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+    }
 
     let monday_pool = get_monday_trade_pool();
-    price_calls.push(create_slot0_call(&monday_pool));
+    price_calls.extend(create_v3_price_calls(&monday_pool));
 
     // Initialize spread display with 5bps threshold, 20 history
     let mut spread_display = spread_display::SpreadDisplay::new(5, 20);
@@ -458,19 +1074,46 @@ async fn run_monitor() -> Result<()> {
         spread_display::enter_alternate_screen();
     }
 
-    // Use node-aware polling interval (100ms for local, 1000ms for remote)
+    // Node-aware minimum interval (20ms local, 1000ms remote), stretched to multicall latency
     let poll_interval_ms = node_config.poll_interval.as_millis() as u64;
-    let mut poll_interval = interval(Duration::from_millis(poll_interval_ms));
+    let mut poll = AdaptivePoll::new(node_config.poll_interval);
 
-    println!("Starting price monitor with {}ms polling interval...\n",
-        poll_interval_ms);
+    // Block-driven streaming replaces the interval timer when --stream is set
+    let mut price_stream = if stream {
+        println!("Starting price monitor streaming on Proposed blocks ({})...\n", node_config.ws_url);
+        Some(stream_prices_on_block(&node_config.rpc_url, &node_config.ws_url, price_calls.clone())?)
+    } else {
+        println!("Starting price monitor with {}ms polling interval...\n",
+            poll_interval_ms);
+        None
+    };
 
     loop {
-        poll_interval.tick().await;
+        let streamed = match price_stream.as_mut() {
+            Some(rx) => rx.recv().await,
+            None => None,
+        };
+        let fetched = match streamed {
+            Some((_, prices)) => Ok(prices),
+            None if price_stream.take().is_some() => {
+                // Streaming task is gone; fall back to polling
+                Err(eyre::eyre!("Price stream closed, falling back to polling"))
+            }
+            None => {
+                poll.tick().await;
+                let fetch_start = std::time::Instant::now();
+                let fetched = fetch_prices_batched_retry(&provider, price_calls.clone(), PRICE_FETCH_RETRIES, PRICE_FETCH_RETRY_DELAY)
+                    .await
+                    .map(|(prices, _, _)| prices);
+                poll.record(fetch_start.elapsed());
+                spread_display.poll_interval = Some(poll.interval());
+                fetched
+            }
+        };
 
-        match fetch_prices_batched(&provider, price_calls.clone()).await {
-            Ok((prices, _elapsed_ms)) => {
-                let spreads = calculate_spreads(&prices);
+        match fetched {
+            Ok(prices) => {
+                let spreads = calculate_spreads(&prices, None);
                 spread_display.update(&spreads);
 
                 if interactive {
@@ -490,7 +1133,7 @@ async fn run_monitor() -> Result<()> {
                     spread_display::cursor_home();
                 }
                 println!("\x1b[1;31mError fetching prices: {}\x1b[0m", e);
-                println!("\nRetrying in {} ms...", poll_interval_ms);
+                println!("\nRetrying in {} ms...", poll.interval().as_millis());
             }
         }
     }
@@ -506,12 +1149,12 @@ async fn run_monitor() -> Result<()> {
     }
 }
 
-async fn run_test_swap(dex: &str, amount: f64, direction: &str, slippage: u32) -> Result<()> {
+async fn run_test_swap(pair: &TokenPair, dex: &str, amount: f64, direction: &str, slippage: u32) -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -522,7 +1165,7 @@ async fn run_test_swap(dex: &str, amount: f64, direction: &str, slippage: u32) -
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Fetch gas price ONCE (optimization: avoid RPC call per swap)
     let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
@@ -552,6 +1195,7 @@ async fn run_test_swap(dex: &str, amount: f64, direction: &str, slippage: u32) -
 
     let params = SwapParams {
         router,
+        pair: *pair,
         direction,
         amount_in: amount,
         slippage_bps: slippage,
@@ -577,26 +1221,92 @@ async fn run_test_swap(dex: &str, amount: f64, direction: &str, slippage: u32) -
     Ok(())
 }
 
-/// Test transaction revert to measure exact gas consumption on Monad
-async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()> {
-    use alloy::network::TransactionBuilder;
-    use alloy::primitives::U256;
-    use std::time::{SystemTime, UNIX_EPOCH};
-    use tokio::time::{timeout, Duration};
-
-    println!("╔══════════════════════════════════════════════════════════════╗");
-    println!("║          REVERT GAS TEST - MONAD GAS ANALYSIS                ║");
-    println!("╚══════════════════════════════════════════════════════════════╝");
-    println!();
-    println!("⚠️  WARNING: This WILL send a transaction that reverts!");
-    println!("   You will pay: gas_limit × gas_price = {} gas units", gas_limit);
-    println!();
+/// Rebalance wallet WMON/USDC to a target WMON share with a single swap
+async fn run_rebalance(pair: &TokenPair, target_wmon_pct: f64, dex: &str, tolerance_bps: u32, slippage: u32) -> Result<()> {
+    if !(0.0..=100.0).contains(&target_wmon_pct) {
+        return Err(eyre::eyre!("--target-wmon-pct must be between 0 and 100"));
+    }
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let signer_address = signer.address();
+
+    let before = get_balances(&provider, signer_address).await?;
+    print_balances(&before);
+
+    let prices = get_current_prices(&provider).await?;
+    // Value the wallet at the best mid price across pools
+    let mid_price = prices.iter().filter(|p| p.is_price_valid()).map(|p| p.price).fold(0.0, f64::max);
+
+    let Some(plan) = rebalance::plan_rebalance(before.wmon_human, before.usdc_human, mid_price, target_wmon_pct, tolerance_bps) else {
+        println!("  WMON share within {} bps of {:.2}% target. Nothing to do.", tolerance_bps, target_wmon_pct);
+        return Ok(());
+    };
+
+    let price = if dex.eq_ignore_ascii_case("auto") {
+        rebalance::cheapest_dex(&prices, plan.direction)
+    } else {
+        prices.iter().find(|p| p.pool_name.eq_ignore_ascii_case(dex))
+    }
+    .ok_or_else(|| eyre::eyre!("Could not get price for {}", dex))?;
+    let router = get_router_by_name(&price.pool_name)
+        .ok_or_else(|| eyre::eyre!("Unknown DEX: {}. Valid options: auto, uniswap, pancakeswap1, pancakeswap2, lfj, mondaytrade", price.pool_name))?;
+
+    println!("  WMON share: {:.2}% -> target {:.2}% (±{} bps)", plan.current_wmon_pct, target_wmon_pct, tolerance_bps);
+    println!("  Swap: {} {:.6} {} on {} @ {:.6} USDC/WMON",
+        if plan.direction == SwapDirection::Sell { "sell" } else { "buy with" },
+        plan.amount_in,
+        if plan.direction == SwapDirection::Sell { "WMON" } else { "USDC" },
+        router.name, price.price);
+
+    init_nonce(&provider, signer_address).await?;
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .connect_client(rpc_failover::client(url));
+    let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
+
+    let params = SwapParams {
+        router,
+        pair: *pair,
+        direction: plan.direction,
+        amount_in: plan.amount_in,
+        slippage_bps: slippage,
+        expected_price: price.price,
+    };
+    let result = execute_swap(&provider, &provider_with_signer, signer_address, params, gas_price, false).await?;
+    print_swap_report(&result);
+
+    let after = get_balances(&provider, signer_address).await?;
+    print_balances(&after);
+
+    Ok(())
+}
+
+/// Test transaction revert to measure exact gas consumption on Monad
+async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()> {
+    use alloy::network::TransactionBuilder;
+    use alloy::primitives::U256;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tokio::time::{timeout, Duration};
+
+    println!("╔══════════════════════════════════════════════════════════════╗");
+    println!("║          REVERT GAS TEST - MONAD GAS ANALYSIS                ║");
+    println!("╚══════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("⚠️  WARNING: This WILL send a transaction that reverts!");
+    println!("   You will pay: gas_limit × gas_price = {} gas units", gas_limit);
+    println!();
+
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -607,7 +1317,7 @@ async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()>
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Get gas price
     let gas_price = provider.get_gas_price().await.unwrap_or(50_000_000_000); // 50 gwei default
@@ -651,13 +1361,12 @@ async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()>
 
     // Build swap calldata (sell WMON -> USDC)
     let calldata = build_swap_calldata(
-        router.router_type,
+        &router,
         WMON_ADDRESS,
         USDC_ADDRESS,
         amount_in,
         amount_out_min,
         signer_address,
-        router.pool_fee,
         deadline,
     )?;
 
@@ -689,17 +1398,11 @@ async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()>
             println!("  Waiting for receipt...");
 
             // Wait for receipt with polling
-            let receipt_result = timeout(Duration::from_secs(60), async {
-                loop {
-                    if let Ok(Some(receipt)) = provider.get_transaction_receipt(tx_hash).await {
-                        return Ok::<_, eyre::Report>(receipt);
-                    }
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                }
-            }).await;
+            let (poll, deadline) = node_config::receipt_wait(Duration::from_millis(100), Duration::from_secs(60));
+            let receipt_result = execution::swap::wait_for_receipt(&provider, tx_hash, poll, deadline).await;
 
             match receipt_result {
-                Ok(Ok(receipt)) => {
+                Ok(receipt) => {
                     let elapsed = start.elapsed();
                     let status = receipt.status();
                     let gas_used = receipt.gas_used;
@@ -759,11 +1462,8 @@ async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()>
                         println!("     before the revert, but you paid for the full gas_limit.");
                     }
                 }
-                Ok(Err(e)) => {
-                    println!("  ✗ Error getting receipt: {:?}", e);
-                }
-                Err(_) => {
-                    println!("  ✗ Timeout waiting for receipt (60s)");
+                Err(e) => {
+                    println!("  ✗ Error getting receipt: {}", e);
                 }
             }
         }
@@ -787,12 +1487,12 @@ async fn run_test_revert(dex: &str, gas_limit: u64, method: &str) -> Result<()>
     Ok(())
 }
 
-async fn run_test_all(amount: f64, direction: &str, slippage: u32) -> Result<()> {
+async fn run_test_all(pair: &TokenPair, amount: f64, direction: &str, slippage: u32) -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -803,7 +1503,7 @@ async fn run_test_all(amount: f64, direction: &str, slippage: u32) -> Result<()>
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Fetch gas price ONCE (optimization: avoid RPC call per swap)
     let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
@@ -843,6 +1543,7 @@ async fn run_test_all(amount: f64, direction: &str, slippage: u32) -> Result<()>
 
         let params = SwapParams {
             router,
+            pair: *pair,
             direction,
             amount_in: amount,
             slippage_bps: slippage,
@@ -885,7 +1586,7 @@ async fn run_balance() -> Result<()> {
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
 
@@ -896,12 +1597,48 @@ async fn run_balance() -> Result<()> {
     Ok(())
 }
 
+async fn run_cancel_tx(nonce: u64, bump_pct: u32, gas_strategy: GasStrategy) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let signer_address = signer.address();
+    println!("Wallet: {:?}", signer_address);
+
+    let gas = gas_strategy.resolve(&provider, Duration::ZERO).await.bumped(bump_pct);
+    println!("\n══════════════════════════════════════════════════════════════");
+    println!("  CANCEL NONCE {}", nonce);
+    println!("══════════════════════════════════════════════════════════════");
+    println!("  Gas ({} +{}%): max_fee={} priority={}", gas_strategy, bump_pct, gas.max_fee, gas.priority_fee);
+
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .connect_client(rpc_failover::client(url));
+
+    match wallet::cancel::cancel_nonce(&provider_with_signer, signer_address, nonce, gas).await? {
+        wallet::cancel::CancelOutcome::AlreadyMined { confirmed_nonce } => {
+            println!("  Nonce {} already mined (confirmed nonce is {}). Nothing to cancel.", nonce, confirmed_nonce);
+        }
+        wallet::cancel::CancelOutcome::Replaced { tx_hash, gas_used } => {
+            println!("  ✓ Nonce {} replaced by {:?} (gas used: {})", nonce, tx_hash, gas_used);
+        }
+    }
+
+    let next = nonce::reconcile(&provider, signer_address).await?;
+    println!("  Next nonce is now {}", next);
+
+    Ok(())
+}
+
 async fn run_wrap(amount: f64) -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     init_nonce(&provider, signer.address()).await?;
@@ -927,7 +1664,7 @@ async fn run_unwrap(amount: f64) -> Result<()> {
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     init_nonce(&provider, signer.address()).await?;
@@ -948,12 +1685,54 @@ async fn run_unwrap(amount: f64) -> Result<()> {
     Ok(())
 }
 
-async fn run_buy_mon(amount: f64, dex: &str, slippage: u32, keep_wrapped: bool) -> Result<()> {
+/// Router, pair and current price for a BuyMon/SellMon trade of WMON against
+/// `quote_token` on `dex`. WMON/USDC uses the configured pools; any other
+/// quote needs a V3 pool for the pair on that DEX, found by fee-tier discovery.
+async fn resolve_mon_venue<P: Provider>(provider: &P, dex: &str, quote_token: &str) -> Result<(RouterConfig, TokenPair, f64)> {
+    let router = get_router_by_name(dex)
+        .ok_or_else(|| eyre::eyre!("Unknown DEX: {}. Valid options: uniswap, pancakeswap1, pancakeswap2, lfj, mondaytrade", dex))?;
+    let pair = get_token_pair(&format!("wmon-{}", quote_token))
+        .ok_or_else(|| eyre::eyre!("Unknown quote token: {}. Expected a known token symbol", quote_token))?;
+    if pair.quote == pair.base {
+        return Err(eyre::eyre!("Quote token must differ from WMON"));
+    }
+
+    println!("Fetching current prices...");
+    if pair == TokenPair::wmon_usdc() {
+        let prices = get_current_prices(provider).await?;
+        let price = prices.iter()
+            .find(|p| p.pool_name.to_lowercase() == dex.to_lowercase())
+            .ok_or_else(|| eyre::eyre!("Could not get price for {}", dex))?;
+        return Ok((router, pair, price.price));
+    }
+
+    let venues = pools::v3_pool::discover_fee_tiers(provider, &pair, &config::get_fee_tiers(&pair)).await?;
+    let (pool, router) = venues.into_iter()
+        .find(|(_, r)| r.address == router.address && r.router_type == router.router_type)
+        .ok_or_else(|| eyre::eyre!("No WMON/{} pool on {}", quote_token.to_uppercase(), dex))?;
+    println!("Using {} pool {:?}", pool.name, pool.address);
+    let (prices, _, _) = fetch_prices_batched(provider, vec![create_slot0_call(&pool)]).await?;
+    let price = prices.first()
+        .map(|p| price::rescale_for_pair(p.price, &pair))
+        .ok_or_else(|| eyre::eyre!("Could not get price for {}", pool.name))?;
+    Ok((router, pair, price))
+}
+
+/// Whether `--use-native` can take effect on `router`; says so when it can't
+fn native_swap_available(router: &RouterConfig) -> bool {
+    let available = execution::routers::supports_native(router);
+    if !available {
+        println!("  ⚠ {} has no native MON path (supports_native), falling back to WMON", router.name);
+    }
+    available
+}
+
+async fn run_buy_mon(amount: f64, dex: &str, quote_token: &str, slippage: u32, keep_wrapped: bool, use_native: bool) -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -964,54 +1743,55 @@ async fn run_buy_mon(amount: f64, dex: &str, slippage: u32, keep_wrapped: bool)
     let wallet = EthereumWallet::from(signer.clone());
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Fetch gas price ONCE (optimization: avoid RPC call per swap)
     let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
 
-    // Get router config
-    let router = get_router_by_name(dex)
-        .ok_or_else(|| eyre::eyre!("Unknown DEX: {}. Valid options: uniswap, pancakeswap1, pancakeswap2, lfj, mondaytrade", dex))?;
-
-    // Get current prices
-    println!("Fetching current prices...");
-    let prices = get_current_prices(&provider).await?;
-
-    let price = prices.iter()
-        .find(|p| p.pool_name.to_lowercase() == dex.to_lowercase())
-        .ok_or_else(|| eyre::eyre!("Could not get price for {}", dex))?;
+    // Router, pair and price for WMON/<quote> on this DEX
+    let (router, pair, price) = resolve_mon_venue(&provider, dex, quote_token).await?;
+    let quote = config::get_token_by_symbol(quote_token).map_or("USDC", |t| t.symbol);
 
-    println!("Current {} price: {:.6} USDC/WMON", dex, price.price);
+    println!("Current {} price: {:.6} {}/WMON", dex, price, quote);
 
     println!("\n══════════════════════════════════════════════════════════════");
-    println!("  BUYING MON WITH USDC (via {})", dex.to_uppercase());
+    println!("  BUYING MON WITH {} (via {})", quote, dex.to_uppercase());
     println!("══════════════════════════════════════════════════════════════");
 
-    // Step 1: Swap USDC -> WMON
+    let native = use_native && !keep_wrapped && native_swap_available(&router);
+
+    // Step 1: Swap quote -> WMON
     let params = SwapParams {
         router,
-        direction: SwapDirection::Buy,  // USDC -> WMON
+        pair,
+        direction: SwapDirection::Buy,  // quote -> WMON
         amount_in: amount,
         slippage_bps: slippage,
-        expected_price: price.price,
+        expected_price: price,
     };
 
-    let swap_result = execute_swap(
-        &provider,
-        &provider_with_signer,
-        signer_address,
-        params,
-        gas_price,
-        false,  // Don't skip balance check
-    ).await?;
+    let swap_result = if native {
+        execute_swap_native(&provider, &provider_with_signer, signer_address, params, gas_price, false).await?
+    } else {
+        execute_swap(
+            &provider,
+            &provider_with_signer,
+            signer_address,
+            params,
+            gas_price,
+            false,  // Don't skip balance check
+        ).await?
+    };
     print_swap_report(&swap_result);
 
     if !swap_result.success {
         return Err(eyre::eyre!("Swap failed: {:?}", swap_result.error));
     }
 
-    // Step 2: Unwrap WMON -> MON (unless keep_wrapped is true)
-    if !keep_wrapped && swap_result.amount_out_human > 0.0 {
+    // Step 2: Unwrap WMON -> MON (unless keep_wrapped is true or the router paid MON)
+    if native {
+        println!("\n  -> Router paid out MON directly (--use-native), no unwrap needed");
+    } else if !keep_wrapped && swap_result.amount_out_human > 0.0 {
         println!("\n  -> Unwrapping received WMON to MON...");
         let unwrap_result = unwrap_wmon(&provider, &signer, swap_result.amount_out_human, &rpc_url).await?;
         print_wrap_result(&unwrap_result);
@@ -1027,12 +1807,12 @@ async fn run_buy_mon(amount: f64, dex: &str, slippage: u32, keep_wrapped: bool)
     Ok(())
 }
 
-async fn run_sell_mon(amount: f64, dex: &str, slippage: u32, use_wmon: bool) -> Result<()> {
+async fn run_sell_mon(amount: f64, dex: &str, quote_token: &str, slippage: u32, use_wmon: bool, use_native: bool) -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -1043,31 +1823,27 @@ async fn run_sell_mon(amount: f64, dex: &str, slippage: u32, use_wmon: bool) ->
     let wallet = EthereumWallet::from(signer.clone());
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Fetch gas price ONCE (optimization: avoid RPC call per swap)
     let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
 
-    // Get router config
-    let router = get_router_by_name(dex)
-        .ok_or_else(|| eyre::eyre!("Unknown DEX: {}. Valid options: uniswap, pancakeswap1, pancakeswap2, lfj, mondaytrade", dex))?;
-
-    // Get current prices
-    println!("Fetching current prices...");
-    let prices = get_current_prices(&provider).await?;
-
-    let price = prices.iter()
-        .find(|p| p.pool_name.to_lowercase() == dex.to_lowercase())
-        .ok_or_else(|| eyre::eyre!("Could not get price for {}", dex))?;
+    // Router, pair and price for WMON/<quote> on this DEX
+    let (router, pair, price) = resolve_mon_venue(&provider, dex, quote_token).await?;
+    let quote = config::get_token_by_symbol(quote_token).map_or("USDC", |t| t.symbol);
 
-    println!("Current {} price: {:.6} USDC/WMON", dex, price.price);
+    println!("Current {} price: {:.6} {}/WMON", dex, price, quote);
 
     println!("\n══════════════════════════════════════════════════════════════");
-    println!("  SELLING MON FOR USDC (via {})", dex.to_uppercase());
+    println!("  SELLING MON FOR {} (via {})", quote, dex.to_uppercase());
     println!("══════════════════════════════════════════════════════════════");
 
-    // Step 1: Wrap MON -> WMON (unless use_wmon is true)
-    let wmon_amount = if !use_wmon {
+    // Step 1: Wrap MON -> WMON (unless use_wmon is true or the router takes MON)
+    let native = use_native && !use_wmon && native_swap_available(&router);
+    let wmon_amount = if native {
+        println!("\n  -> Sending MON as the TX value (--use-native), no wrap needed");
+        amount
+    } else if !use_wmon {
         println!("\n  -> Wrapping MON to WMON first...");
         let wrap_result = wrap_mon(&provider, &signer, amount, &rpc_url).await?;
         print_wrap_result(&wrap_result);
@@ -1081,23 +1857,28 @@ async fn run_sell_mon(amount: f64, dex: &str, slippage: u32, use_wmon: bool) ->
         amount
     };
 
-    // Step 2: Swap WMON -> USDC
+    // Step 2: Swap WMON -> quote
     let params = SwapParams {
         router,
-        direction: SwapDirection::Sell,  // WMON -> USDC
+        pair,
+        direction: SwapDirection::Sell,  // WMON -> quote
         amount_in: wmon_amount,
         slippage_bps: slippage,
-        expected_price: price.price,
+        expected_price: price,
     };
 
-    let swap_result = execute_swap(
-        &provider,
-        &provider_with_signer,
-        signer_address,
-        params,
-        gas_price,
-        false,  // Don't skip balance check
-    ).await?;
+    let swap_result = if native {
+        execute_swap_native(&provider, &provider_with_signer, signer_address, params, gas_price, false).await?
+    } else {
+        execute_swap(
+            &provider,
+            &provider_with_signer,
+            signer_address,
+            params,
+            gas_price,
+            false,  // Don't skip balance check
+        ).await?
+    };
     print_swap_report(&swap_result);
 
     // Show updated balances
@@ -1115,8 +1896,13 @@ fn to_wei(amount: f64, decimals: u8) -> alloy::primitives::U256 {
     alloy::primitives::U256::from(amount_scaled) * multiplier / alloy::primitives::U256::from(10u64).pow(alloy::primitives::U256::from(18u8))
 }
 
-/// Query actual USDC balance for a wallet
-async fn query_usdc_balance<P: Provider>(provider: &P, wallet_address: alloy::primitives::Address) -> Result<f64> {
+/// Query a wallet's ERC20 balance of `token`, in token units
+async fn query_token_balance<P: Provider>(
+    provider: &P,
+    token: alloy::primitives::Address,
+    decimals: u8,
+    wallet_address: alloy::primitives::Address,
+) -> Result<f64> {
     use alloy::sol;
     use alloy::sol_types::SolCall;
 
@@ -1127,19 +1913,20 @@ async fn query_usdc_balance<P: Provider>(provider: &P, wallet_address: alloy::pr
 
     let balance_call = balanceOfCall { account: wallet_address };
     let balance_tx = alloy::rpc::types::TransactionRequest::default()
-        .to(USDC_ADDRESS)
+        .to(token)
         .input(alloy::rpc::types::TransactionInput::new(
             alloy::primitives::Bytes::from(balance_call.abi_encode())
         ));
     let result = provider.call(balance_tx).await?;
     let balance_wei = alloy::primitives::U256::from_be_slice(&result);
-    let balance_human = (balance_wei.to::<u128>() as f64) / 1_000_000.0; // USDC has 6 decimals
+    let balance_human = (balance_wei.to::<u128>() as f64) / 10f64.powi(decimals as i32);
     Ok(balance_human)
 }
 
 /// Helper function to pre-build swap calldata without executing
 fn build_swap_calldata_only(
     router: &RouterConfig,
+    pair: &TokenPair,
     direction: SwapDirection,
     amount_in: alloy::primitives::U256,
     amount_out_min: alloy::primitives::U256,
@@ -1147,10 +1934,7 @@ fn build_swap_calldata_only(
 ) -> Result<alloy::primitives::Bytes> {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    let (token_in, token_out) = match direction {
-        SwapDirection::Sell => (WMON_ADDRESS, USDC_ADDRESS),
-        SwapDirection::Buy => (USDC_ADDRESS, WMON_ADDRESS),
-    };
+    let (token_in, token_out, _, _) = direction.tokens(pair);
 
     let deadline = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1158,25 +1942,229 @@ fn build_swap_calldata_only(
         .as_secs() + 300;
 
     build_swap_calldata(
-        router.router_type,
+        router,
         token_in,
         token_out,
         amount_in,
         amount_out_min,
         recipient,
-        router.pool_fee,
         deadline,
     )
 }
 
-async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32) -> Result<()> {
+/// `wallet`'s balance of the pair's quote token
+async fn wallet_quote_balance<P: Provider>(provider: &P, pair: &TokenPair, wallet: alloy::primitives::Address) -> Result<f64> {
+    let balances = multicall::query_balances_batched(provider, wallet, &[(pair.quote, pair.quote_decimals)]).await?;
+    balances.first().copied().ok_or_else(|| eyre::eyre!("No quote balance returned for {:?}", wallet))
+}
+
+/// Clamp `amount` to the largest WMON size both legs can take within
+/// `max_impact_bps` of price impact. 0 disables; RPC failures keep `amount`.
+async fn clamp_amount_to_liquidity<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    sell_pool: &str,
+    buy_pool: &str,
+    amount: f64,
+    max_impact_bps: u32,
+) -> f64 {
+    use pools::liquidity::{fetch_pool_liquidity, max_trade_for_slippage};
+
+    if max_impact_bps == 0 {
+        return amount;
+    }
+
+    let pools = get_all_pools();
+    let find = |name: &str| pools.iter().find(|p| p.name.eq_ignore_ascii_case(name)).cloned();
+    let (Some(sell), Some(buy)) = (find(sell_pool), find(buy_pool)) else {
+        return amount;
+    };
+
+    let (sell_liq, buy_liq) = tokio::join!(
+        fetch_pool_liquidity(provider, &sell, pair),
+        fetch_pool_liquidity(provider, &buy, pair),
+    );
+    let (sell_liq, buy_liq) = match (sell_liq, buy_liq) {
+        (Ok(s), Ok(b)) => (s, b),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("  ⚠ Liquidity check failed ({}), using requested amount", e);
+            return amount;
+        }
+    };
+
+    let max_sell = max_trade_for_slippage(&sell_liq, SwapDirection::Sell, max_impact_bps);
+    let max_buy = max_trade_for_slippage(&buy_liq, SwapDirection::Buy, max_impact_bps);
+    let max_amount = max_sell.min(max_buy);
+
+    if max_amount < amount {
+        println!("  Amount reduced by liquidity: {:.6} -> {:.6} WMON (-{:.6}, {:.1}%) [{} max {:.4}, {} max {:.4} @ {}bps impact]",
+            amount, max_amount, amount - max_amount, (amount - max_amount) / amount * 100.0,
+            sell_pool, max_sell, buy_pool, max_buy, max_impact_bps);
+        max_amount
+    } else {
+        amount
+    }
+}
+
+/// Quote sell-then-buy at the current block using only eth_call
+async fn run_simulate_arb(pair: &TokenPair, sell_dex: &str, buy_dex: &str, amount: f64) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+
+    let sell_router = get_router_by_name(sell_dex)
+        .ok_or_else(|| eyre::eyre!("Unknown sell DEX: {}", sell_dex))?;
+    let buy_router = get_router_by_name(buy_dex)
+        .ok_or_else(|| eyre::eyre!("Unknown buy DEX: {}", buy_dex))?;
+
+    let quote = QuoteFetcher::AtomicQuote
+        .quote_arb(&provider, pair, &sell_router, &buy_router, amount)
+        .await?;
+    print_arb_quote(&sell_router, &buy_router, &quote);
+
+    // Two-TX gas at the current price, also valued in USDC at the live MON price
+    let (gas_price, mon_price) = tokio::join!(provider.get_gas_price(), price::gas_token_to_quote(&provider));
+    let breakdown = profit_calculator::breakdown_from_quote(
+        &quote, sell_router.pool_fee / 100, buy_router.pool_fee / 100, profit_calculator::FAST_ARB_GAS_UNITS, gas_price?,
+    ).with_gas_token_price(mon_price?);
+    println!("  After gas (FastArb): {:+.6} WMON | gas {:.6} MON = {:.6} USDC (MON @ {:.6})\n",
+        breakdown.net_wmon, breakdown.gas_wmon, breakdown.gas_quote, breakdown.gas_token_price);
+    Ok(())
+}
+
+async fn run_compare_routers(pair: &TokenPair, amount: f64, direction: &str, routers: Option<&str>) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+
+    let direction = match direction.to_lowercase().as_str() {
+        "sell" => SwapDirection::Sell,
+        "buy" => SwapDirection::Buy,
+        _ => return Err(eyre::eyre!("Invalid direction. Use 'sell' or 'buy'")),
+    };
+    let routers = match routers {
+        Some(names) => names
+            .split(',')
+            .map(|name| get_router_by_name(name.trim()).ok_or_else(|| eyre::eyre!("Unknown router: {}", name.trim())))
+            .collect::<Result<Vec<_>>>()?,
+        None => config::get_routers(),
+    };
+
+    let comparison = QuoteFetcher::AtomicQuote
+        .quote_routers(&provider, pair, &routers, direction, amount)
+        .await?;
+
+    let (token_in, token_out) = match direction {
+        SwapDirection::Sell => ("WMON", "USDC"),
+        SwapDirection::Buy => ("USDC", "WMON"),
+    };
+    println!("\n═══════════════════════════════════════════════════════════════════════════════");
+    println!("  ROUTER COMPARISON | {} {} -> {} | block {}", amount, token_in, token_out, comparison.block_number);
+    println!("═══════════════════════════════════════════════════════════════════════════════");
+    println!("  {:<4} {:<16} {:>16} {:>12} {:>12} {:>10}", "#", "Router", format!("{} out", token_out), "Eff. price", "Spot", "Impact");
+    println!("───────────────────────────────────────────────────────────────────────────────");
+    for (i, quote) in comparison.quotes.iter().enumerate() {
+        println!("  {:<4} {:<16} {:>16.6} {:>12.6} {:>12.6} {:>7.1} bps",
+            i + 1, quote.router, quote.amount_out, quote.effective_price, quote.spot, quote.impact_bps());
+    }
+    for (router, reason) in &comparison.skipped {
+        println!("  -    {:<16} not quoted: {}", router, reason);
+    }
+    println!("───────────────────────────────────────────────────────────────────────────────");
+    if let Some((spot, fill)) = comparison.spot_vs_fill_mismatch() {
+        println!("  \x1b[1;33mBest spot is {} but best fill is {} at this size\x1b[0m ({:+.6} {} from depth)",
+            spot.router, fill.router, fill.amount_out - spot.amount_out, token_out);
+    } else if let Some(best) = comparison.quotes.first() {
+        println!("  Best fill: {} (also best spot after fees)", best.router);
+    }
+    println!("═══════════════════════════════════════════════════════════════════════════════\n");
+    Ok(())
+}
+
+/// Net spread at which `amount` WMON pays for its gas: fast arb bids the
+/// strategy's fee on two TXs, atomic arb the spread-boosted fee on one
+async fn run_break_even(amount: f64, gas_strategy: GasStrategy) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+
+    let (gas, mon_price) = tokio::join!(gas_strategy.resolve(&provider, Duration::ZERO), price::gas_token_to_quote(&provider));
+    let mon_price = mon_price.ok();
+
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("  BREAK-EVEN SPREAD | {} WMON", amount);
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Gas ({}): max_fee {:.2} gwei, priority {:.2} gwei", gas_strategy, gas.max_fee as f64 / 1e9, gas.priority_fee as f64 / 1e9);
+    match mon_price {
+        Some(price) => println!("  MON price: {:.6} USDC", price),
+        None => println!("  MON price: unavailable (gas shown in WMON only)"),
+    }
+
+    let modes = [
+        ("Fast (2 TX)", profit_calculator::FAST_ARB_GAS_UNITS, profit_calculator::break_even_net_bps(amount, profit_calculator::FAST_ARB_GAS_UNITS, |_| gas.max_fee), false),
+        ("Atomic (1 TX)", profit_calculator::ATOMIC_ARB_GAS_UNITS,
+            profit_calculator::break_even_net_bps(amount, profit_calculator::ATOMIC_ARB_GAS_UNITS, |bps| gas_cache::calculate_gas_price(gas, bps).0), true),
+    ];
+    println!("\n  {:<14} {:>10} {:>12} {:>12} {:>16}", "Mode", "Gas units", "Gas (WMON)", "Gas (USDC)", "Break-even net");
+    println!("  {}", "-".repeat(68));
+    for (name, gas_units, break_even, boosted) in &modes {
+        let Some(bps) = break_even else {
+            println!("  {:<14} {:>10} {:>12} {:>12} {:>16}", name, gas_units, "-", "-", "none");
+            continue;
+        };
+        let max_fee = if *boosted { gas_cache::calculate_gas_price(gas, bps.ceil() as i32).0 } else { gas.max_fee };
+        let gas_wmon = *gas_units as f64 * max_fee as f64 / 1e18;
+        let gas_usdc = mon_price.map(|p| format!("{:.6}", gas_wmon * p)).unwrap_or_else(|| "-".to_string());
+        println!("  {:<14} {:>10} {:>12.6} {:>12} {:>12.1} bps", name, gas_units, gas_wmon, gas_usdc, bps);
+    }
+    println!("  Atomic bids +1 gwei priority per 10 bps of spread, so its gas grows with the spread.");
+    if modes.iter().any(|(_, _, break_even, _)| break_even.is_none()) {
+        println!("  none: at this size gas outgrows any spread the arb could capture");
+    }
+
+    // Net spread is after both pools' fees; gross adds them back
+    let mut routers = config::get_routers();
+    routers.sort_by(|a, b| a.fee_fraction().total_cmp(&b.fee_fraction()));
+    println!("\n  Router fees:");
+    for router in &routers {
+        println!("    {:<16} {:>6.1} bps", router.name, router.fee_fraction() * 10_000.0);
+    }
+    if let [cheapest, second, ..] = routers.as_slice() {
+        let fee_bps = (cheapest.fee_fraction() + second.fee_fraction()) * 10_000.0;
+        let gross = |break_even: Option<f64>| break_even
+            .map(|bps| format!("{:.1} bps", bps + fee_bps))
+            .unwrap_or_else(|| "none".to_string());
+        println!("  Cheapest route ({} + {}, {:.1} bps fees) break-even gross: fast {} | atomic {}",
+            cheapest.name, second.name, fee_bps, gross(modes[0].2), gross(modes[1].2));
+    }
+
+    println!("\n  --min-spread-bps is a net spread (after pool fees). At this gas price set it above:");
+    for (name, _, break_even, _) in &modes {
+        match break_even {
+            Some(bps) => println!("    {:<14} {} bps", name, bps.ceil() as i64),
+            None => println!("    {:<14} - (no break-even at {} WMON; raise --amount)", name, amount),
+        }
+    }
+    println!("═══════════════════════════════════════════════════════════════\n");
+    Ok(())
+}
+
+async fn run_test_arb(
+    pair: &TokenPair,
+    sell_dex: &str,
+    buy_dex: &str,
+    amount: f64,
+    slippage: u32,
+    force: bool,
+    auto_direction: bool,
+) -> Result<()> {
     let arb_start = std::time::Instant::now();
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -1189,7 +2177,7 @@ async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Fetch gas price ONCE (saves ~100-300ms per swap)
     let t_gas = std::time::Instant::now();
@@ -1219,29 +2207,32 @@ async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
     println!("  {} price: {:.6} USDC/WMON", sell_dex, sell_price.price);
     println!("  {} price: {:.6} USDC/WMON", buy_dex, buy_price.price);
 
+    let (sell_dex, buy_dex, sell_router, buy_router, sell_price, buy_price) =
+        if check_arb_direction(sell_dex, buy_dex, sell_price.price, buy_price.price, force, auto_direction)? {
+            (buy_dex, sell_dex, buy_router, sell_router, buy_price, sell_price)
+        } else {
+            (sell_dex, buy_dex, sell_router, buy_router, sell_price, buy_price)
+        };
+
     let spread_bps = ((sell_price.price - buy_price.price) / buy_price.price * 10000.0) as i32;
     println!("  Spread: {} bps ({:.4}%)", spread_bps, spread_bps as f64 / 100.0);
 
-    if spread_bps <= 0 {
-        println!("\n⚠️  WARNING: Negative spread! sell_dex should have HIGHER price than buy_dex");
-        println!("  Consider swapping: --sell-dex {} --buy-dex {}", buy_dex, sell_dex);
-    }
-
     // ═══════════════════════════════════════════════════════════════════
     // PRE-CALCULATE SWAP 2 PARAMETERS (optimization: ready before swap 1)
     // ═══════════════════════════════════════════════════════════════════
     let expected_usdc = amount * sell_price.price;
-    let expected_usdc_wei = to_wei(expected_usdc, USDC_DECIMALS);
+    let expected_usdc_wei = to_wei(expected_usdc, pair.quote_decimals);
 
     // Pre-calculate expected WMON output from swap 2 (for slippage)
     let expected_wmon_back = expected_usdc / buy_price.price;
     let slippage_multiplier = 1.0 - (slippage as f64 / 10000.0);
     let min_wmon_out = expected_wmon_back * slippage_multiplier;
-    let min_wmon_out_wei = to_wei(min_wmon_out, WMON_DECIMALS);
+    let min_wmon_out_wei = to_wei(min_wmon_out, pair.base_decimals);
 
     // Pre-build swap 2 calldata (will be ready when swap 1 completes)
     let _swap2_calldata_prebuilt = build_swap_calldata_only(
         &buy_router,
+        pair,
         SwapDirection::Buy,
         expected_usdc_wei,  // Will use actual USDC if differs significantly
         min_wmon_out_wei,
@@ -1272,6 +2263,7 @@ async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
 
     let sell_params = SwapParams {
         router: sell_router,
+        pair: *pair,
         direction: SwapDirection::Sell,  // WMON -> USDC
         amount_in: amount,
         slippage_bps: slippage,
@@ -1316,7 +2308,7 @@ async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
     // Retry balance query up to 3 times with 200ms gaps
     let mut usdc_for_swap2 = 0.0;
     for attempt in 1..=3 {
-        match query_usdc_balance(&provider, signer_address).await {
+        match query_token_balance(&provider, pair.quote, pair.quote_decimals, signer_address).await {
             Ok(actual_balance) => {
                 let usdc_received = actual_balance - usdc_before;
                 if usdc_received > 0.0001 {
@@ -1352,6 +2344,7 @@ async fn run_test_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
 
     let buy_params = SwapParams {
         router: buy_router,
+        pair: *pair,
         direction: SwapDirection::Buy,  // USDC -> WMON
         amount_in: usdc_for_swap2,  // Use actual balance, not estimate
         slippage_bps: slippage,
@@ -1452,7 +2445,7 @@ async fn run_prepare_arb() -> Result<()> {
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let wallet_address = signer.address();
@@ -1464,7 +2457,7 @@ async fn run_prepare_arb() -> Result<()> {
     let wallet = EthereumWallet::from(signer.clone());
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     println!("══════════════════════════════════════════════════════════════");
     println!("  PREPARING WALLET FOR ARBITRAGE");
@@ -1531,6 +2524,46 @@ async fn run_prepare_arb() -> Result<()> {
         }
     }
 
+    // Uniswap V4 (configured via --config): tokens -> Permit2, Permit2 -> Universal Router
+    let v4_routers: Vec<RouterConfig> = config::get_routers()
+        .into_iter()
+        .filter(|r| r.router_type == config::RouterType::UniswapV4)
+        .collect();
+    let mut total_count = total_count;
+    for router in &v4_routers {
+        println!("\nApproving Permit2 for {}...", router.name);
+        for (token, token_name) in &tokens {
+            let approvals = [
+                (*token, Bytes::from(approveCall { spender: execution::routers::uniswap_v4::PERMIT2, amount: U256::MAX }.abi_encode()), "Permit2"),
+                (execution::routers::uniswap_v4::PERMIT2, execution::routers::uniswap_v4::build_permit2_approve(*token, router.address), "Universal Router"),
+            ];
+            for (to, calldata, spender_name) in approvals {
+                total_count += 1;
+                let tx = alloy::rpc::types::TransactionRequest::default()
+                    .to(to)
+                    .from(wallet_address)
+                    .input(alloy::rpc::types::TransactionInput::new(calldata))
+                    .gas_limit(100_000)
+                    .nonce(nonce::next_nonce())
+                    .max_fee_per_gas(gas_price + (gas_price / 10))
+                    .max_priority_fee_per_gas(gas_price / 10)
+                    .with_chain_id(MONAD_CHAIN_ID);
+
+                match provider_with_signer.send_transaction(tx).await {
+                    Ok(pending) => match pending.get_receipt().await {
+                        Ok(receipt) if receipt.status() => {
+                            println!("  ✓ {} {} approved (tx: {:?})", token_name, spender_name, receipt.transaction_hash);
+                            success_count += 1;
+                        }
+                        Ok(_) => println!("  ✗ {} {} approval reverted", token_name, spender_name),
+                        Err(e) => println!("  ✗ {} {} failed to get receipt: {}", token_name, spender_name, e),
+                    },
+                    Err(e) => println!("  ✗ {} {} failed to send tx: {}", token_name, spender_name, e),
+                }
+            }
+        }
+    }
+
     println!("\n══════════════════════════════════════════════════════════════");
     println!("  PREPARATION COMPLETE - {}/{} approvals successful", success_count, total_count);
     println!("══════════════════════════════════════════════════════════════");
@@ -1538,50 +2571,162 @@ async fn run_prepare_arb() -> Result<()> {
     Ok(())
 }
 
-async fn run_fast_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32) -> Result<()> {
-    let total_start = std::time::Instant::now();
+/// Undo `PrepareArb`: zero every router allowance, or only `router`'s / `token`'s
+async fn run_revoke_arb(router: Option<&str>, token: Option<&str>) -> Result<()> {
+    use alloy::network::EthereumWallet;
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
-    let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let routers: Vec<RouterConfig> = config::get_routers()
+        .into_iter()
+        .filter(|r| router.is_none_or(|name| r.name.eq_ignore_ascii_case(name)))
+        .collect();
+    if routers.is_empty() {
+        let names: Vec<_> = config::get_routers().iter().map(|r| r.name).collect();
+        return Err(eyre::eyre!("Unknown router '{}'. Configured: {}", router.unwrap_or_default(), names.join(", ")));
+    }
+    let tokens: Vec<config::TokenConfig> = config::get_tokens()
+        .into_iter()
+        .filter(|t| token.is_none_or(|symbol| t.symbol.eq_ignore_ascii_case(symbol)))
+        .collect();
+    if tokens.is_empty() {
+        let symbols: Vec<_> = config::get_tokens().iter().map(|t| t.symbol).collect();
+        return Err(eyre::eyre!("Unknown token '{}'. Configured: {}", token.unwrap_or_default(), symbols.join(", ")));
+    }
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
-    let signer_address = signer.address();
+    let wallet_address = signer.address();
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(signer))
+        .connect_client(rpc_failover::client(url));
+    init_nonce(&provider_with_signer, wallet_address).await?;
+    let gas = gas::DEFAULT_GAS_STRATEGY.parse::<GasStrategy>()?.resolve(&provider_with_signer, Duration::ZERO).await;
 
-    // PARALLEL INIT: gas + nonce + prices
-    let (gas_result, nonce_result, prices_result) = tokio::join!(
-        provider.get_gas_price(),
-        init_nonce(&provider, signer_address),
-        get_current_prices(&provider)
-    );
+    println!("══════════════════════════════════════════════════════════════");
+    println!("  REVOKING ARBITRAGE APPROVALS");
+    println!("══════════════════════════════════════════════════════════════");
+    println!("Wallet:  {:?}", wallet_address);
+    println!("Routers: {}", routers.iter().map(|r| r.name).collect::<Vec<_>>().join(", "));
+    println!("Tokens:  {}\n", tokens.iter().map(|t| t.symbol).collect::<Vec<_>>().join(", "));
 
-    let gas_price = gas_result.unwrap_or(100_000_000_000);
-    nonce_result?;
-    let prices = prices_result?;
+    // Token -> Permit2 is shared by every V4 router; only a full revoke drops it
+    let (revoked, attempted) =
+        wallet::revoke_allowances(&provider_with_signer, wallet_address, &routers, &tokens, router.is_none(), gas).await?;
 
-    println!("  [TIMING] Parallel init: {:?}", total_start.elapsed());
+    println!("\n══════════════════════════════════════════════════════════════");
+    println!("  REVOCATION COMPLETE - {}/{} revocations successful", revoked, attempted);
+    println!("══════════════════════════════════════════════════════════════");
 
-    // Create provider with signer
-    let wallet = EthereumWallet::from(signer);
-    let provider_with_signer = ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_http(url);
+    if revoked < attempted {
+        return Err(eyre::eyre!("{} revocation(s) failed", attempted - revoked));
+    }
+    Ok(())
+}
 
-    // Get routers
-    let sell_router = get_router_by_name(sell_dex)
-        .ok_or_else(|| eyre::eyre!("Unknown sell DEX: {}", sell_dex))?;
-    let buy_router = get_router_by_name(buy_dex)
-        .ok_or_else(|| eyre::eyre!("Unknown buy DEX: {}", buy_dex))?;
+/// FastArb settings, built from the `fast-arb` flags
+struct FastArbRunOptions {
+    sell_dex: String,
+    buy_dex: String,
+    amount: f64,
+    slippage: u32,
+    simulate: bool,
+    gas_strategy: GasStrategy,
+    parallel: bool,
+    optimize_steps: Option<u32>,
+    force: bool,
+    auto_direction: bool,
+    max_gas_gwei: f64,
+    max_adaptive_slippage: Option<u32>,
+    exec_rpc: Option<String>,
+}
 
-    // Get prices
-    let sell_price = prices.iter()
-        .find(|p| p.pool_name.to_lowercase() == sell_dex.to_lowercase())
-        .ok_or_else(|| eyre::eyre!("No price for {}", sell_dex))?.price;
-    let buy_price = prices.iter()
-        .find(|p| p.pool_name.to_lowercase() == buy_dex.to_lowercase())
-        .ok_or_else(|| eyre::eyre!("No price for {}", buy_dex))?.price;
+async fn run_fast_arb(pair: &TokenPair, opts: FastArbRunOptions) -> Result<()> {
+    let FastArbRunOptions {
+        sell_dex,
+        buy_dex,
+        amount,
+        slippage,
+        simulate,
+        gas_strategy,
+        parallel,
+        optimize_steps,
+        force,
+        auto_direction,
+        max_gas_gwei,
+        max_adaptive_slippage,
+        exec_rpc,
+    } = opts;
+    let (sell_dex, buy_dex) = (sell_dex.as_str(), buy_dex.as_str());
+    let total_start = std::time::Instant::now();
+
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+
+    // --exec-rpc: a fork isn't in MONAD_RPC_URLS, so it gets plain HTTP and
+    // can never fail over to a mainnet endpoint
+    let exec_url: reqwest::Url = match &exec_rpc {
+        Some(exec_rpc) => exec_rpc.parse()?,
+        None => url,
+    };
+    let shadow = exec_rpc.is_some();
+    if shadow {
+        println!("══════════════════════════════════════════════════════════════");
+        println!("  SHADOW EXECUTION - TRANSACTIONS DO NOT TOUCH MAINNET");
+        println!("  Prices:    {}", rpc_url);
+        println!("  Execution: {}", exec_url);
+        println!("══════════════════════════════════════════════════════════════");
+        tracing::warn!(target: logging::EVENT_TARGET, pair = logging::pair(), price_rpc = %rpc_url, exec_rpc = %exec_url,
+            "Shadow execution: prices and transactions use different RPCs");
+    }
+    let exec_provider = ProviderBuilder::new().connect_client(rpc_failover::client(exec_url.clone()));
+
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let signer_address = signer.address();
+
+    // PARALLEL INIT: gas + nonce (from the node that executes) + prices
+    let (gas, nonce_result, prices_result) = tokio::join!(
+        gas_strategy.resolve(&exec_provider, Duration::ZERO),
+        init_nonce(&exec_provider, signer_address),
+        get_current_prices(&provider)
+    );
+
+    println!("  Gas ({}): max_fee={} priority={}", gas_strategy, gas.max_fee, gas.priority_fee);
+    nonce_result?;
+    let prices = prices_result?;
+
+    info!(target: logging::EVENT_TARGET, pair = logging::pair(), init_ms = total_start.elapsed().as_millis() as u64,
+        "  [TIMING] Parallel init: {:?}", total_start.elapsed());
+
+    // Create provider with signer
+    let wallet = EthereumWallet::from(signer);
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(wallet)
+        .connect_client(rpc_failover::client(exec_url));
+
+    // Get routers (best fee tier per DEX when --fee-tiers found more than one) and their prices
+    let resolve = |sell_dex: &str, buy_dex: &str| -> Result<_> {
+        let sell_router = config::get_best_tier_router(sell_dex, SwapDirection::Sell, &prices)
+            .ok_or_else(|| eyre::eyre!("Unknown sell DEX: {}", sell_dex))?;
+        let buy_router = config::get_best_tier_router(buy_dex, SwapDirection::Buy, &prices)
+            .ok_or_else(|| eyre::eyre!("Unknown buy DEX: {}", buy_dex))?;
+        let price_of = |dex: &str| prices.iter()
+            .find(|p| p.pool_name.to_lowercase() == dex.to_lowercase())
+            .map(|p| p.price)
+            .ok_or_else(|| eyre::eyre!("No price for {}", dex));
+        let (sell_price, buy_price) = (price_of(sell_router.name)?, price_of(buy_router.name)?);
+        Ok((sell_router, buy_router, sell_price, buy_price))
+    };
+    let (mut sell_router, mut buy_router, mut sell_price, mut buy_price) = resolve(sell_dex, buy_dex)?;
+    if check_arb_direction(sell_router.name, buy_router.name, sell_price, buy_price, force, auto_direction)? {
+        // Re-pick fee tiers for the swapped roles
+        (sell_router, buy_router, sell_price, buy_price) = resolve(buy_dex, sell_dex)?;
+    }
+    let (sell_dex, buy_dex) = (sell_router.name, buy_router.name);
 
     // Pre-validate profitability before execution
     let spread_bps = ((sell_price - buy_price) / buy_price * 10000.0) as i32;
@@ -1595,48 +2740,142 @@ async fn run_fast_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32)
         println!("\n  ⚠️  Warning: Negative net spread. Arb may be unprofitable.");
     }
 
+    // --optimize-size: --amount becomes the ceiling
+    let amount = match optimize_steps {
+        Some(steps) => {
+            println!("\n  Optimizing size (up to {} WMON, {} steps)...", amount, steps);
+            let (size, breakdown) = profit_calculator::optimize_size(
+                &provider, pair, &sell_router, &buy_router, amount, steps, gas.max_fee,
+            ).await?;
+            if breakdown.net_wmon <= 0.0 {
+                println!("  No profitable size: best is {:.4} WMON at {:+.6} WMON net. Not trading.",
+                         size, breakdown.net_wmon);
+                return Ok(());
+            }
+            let breakdown = breakdown.with_gas_token_price(price::gas_token_price(&prices).unwrap_or(buy_price));
+            println!("  Optimal size: {:.4} WMON (net {:+.6} WMON after {:.6} gas = {:.6} USDC)",
+                     size, breakdown.net_wmon, breakdown.gas_wmon, breakdown.gas_quote);
+            size
+        }
+        None => amount,
+    };
+
+    if gas::gas_too_high(gas.max_fee, max_gas_gwei) {
+        return Ok(());
+    }
+
     println!("\n══════════════════════════════════════════════════════════════");
     println!("  FAST ARB | {} -> {}", sell_dex, buy_dex);
     println!("══════════════════════════════════════════════════════════════");
 
-    let result = execute_fast_arb(
-        &provider_with_signer,
-        signer_address,
-        &sell_router,
-        &buy_router,
-        amount,
-        sell_price,
-        buy_price,
-        slippage,
-        gas_price,
-    ).await?;
+    let trade = FastArbTrade { pair, sell_router: &sell_router, buy_router: &buy_router, amount, sell_price, buy_price };
+    let opts = FastArbOptions {
+        slippage_bps: slippage,
+        gas,
+        simulate,
+        retry_slippage_bump_bps: 0,
+        max_adaptive_slippage_bps: max_adaptive_slippage,
+        deadline: None,
+    };
+    let (result, legs) = if parallel {
+        execute_fast_arb_parallel(&provider_with_signer, signer_address, trade, opts).await?
+    } else {
+        (execute_fast_arb(&provider_with_signer, signer_address, trade, opts).await?, Vec::new())
+    };
 
     print_fast_arb_result(&result, sell_dex, buy_dex);
-    println!("  [TIMING] TOTAL: {:?}", total_start.elapsed());
+    print_pending_legs(&legs);
+    info!(target: logging::EVENT_TARGET, pair = logging::pair(), total_ms = total_start.elapsed().as_millis() as u64,
+        "  [TIMING] TOTAL: {:?}", total_start.elapsed());
+    if shadow {
+        println!("  ⚠ SHADOW EXECUTION: result above is from the --exec-rpc node, not mainnet");
+    }
+
+    Ok(())
+}
+
+async fn run_multihop_arb(route: &str, amount: f64, slippage: u32, gas_strategy: GasStrategy) -> Result<()> {
+    let hops = parse_route(route)?;
+
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let signer_address = signer.address();
+
+    let (gas, nonce_result) = tokio::join!(
+        gas_strategy.resolve(&provider, Duration::ZERO),
+        init_nonce(&provider, signer_address)
+    );
+    nonce_result?;
+    println!("  Gas ({}): max_fee={} priority={}", gas_strategy, gas.max_fee, gas.priority_fee);
+
+    let wallet = EthereumWallet::from(signer);
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(wallet)
+        .connect_client(rpc_failover::client(url));
+
+    println!("\n══════════════════════════════════════════════════════════════");
+    println!("  MULTI-HOP ARB | {}", execution::multihop_arb::format_route(&hops));
+    println!("══════════════════════════════════════════════════════════════");
+
+    let result = execute_multihop_arb(&provider_with_signer, signer_address, &hops, amount, slippage, gas).await?;
+    print_multihop_result(&result);
 
     Ok(())
 }
 
-async fn run_atomic_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32, min_profit_bps: i32, force: bool) -> Result<()> {
+/// AtomicArb settings (non-turbo), built from the `atomic-arb` flags
+struct AtomicArbOptions {
+    sell_dex: String,
+    buy_dex: String,
+    amount: f64,
+    slippage: u32,
+    min_profit_bps: i32,
+    force: bool,
+    auto_direction: bool,
+    /// (pool, fee bps) to borrow the input from
+    flashloan: Option<(alloy::primitives::Address, u32)>,
+    gas_strategy: GasStrategy,
+    max_gas_gwei: f64,
+}
+
+async fn run_atomic_arb(opts: AtomicArbOptions) -> Result<()> {
+    let AtomicArbOptions {
+        sell_dex,
+        buy_dex,
+        amount,
+        slippage,
+        min_profit_bps,
+        force,
+        auto_direction,
+        flashloan,
+        gas_strategy,
+        max_gas_gwei,
+    } = opts;
+    let (sell_dex, buy_dex) = (sell_dex.as_str(), buy_dex.as_str());
     let total_start = std::time::Instant::now();
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
 
     // Parallel init
-    let (gas_result, nonce_result, prices_result) = tokio::join!(
-        provider.get_gas_price(),
+    let (gas, nonce_result, prices_result) = tokio::join!(
+        gas_strategy.resolve(&provider, Duration::ZERO),
         init_nonce(&provider, signer_address),
         get_current_prices(&provider)
     );
 
-    let gas_price = gas_result.unwrap_or(100_000_000_000);
+    println!("  Gas ({}): max_fee={} priority={}", gas_strategy, gas.max_fee, gas.priority_fee);
     nonce_result?;
     let prices = prices_result?;
 
@@ -1646,7 +2885,7 @@ async fn run_atomic_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u3
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Get routers
     let sell_router = get_router_by_name(sell_dex)
@@ -1662,6 +2901,13 @@ async fn run_atomic_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u3
         .find(|p| p.pool_name.to_lowercase() == buy_dex.to_lowercase())
         .ok_or_else(|| eyre::eyre!("No price for {}", buy_dex))?.price;
 
+    let (sell_dex, buy_dex, sell_router, buy_router, sell_price, buy_price) =
+        if check_arb_direction(sell_dex, buy_dex, sell_price, buy_price, force, auto_direction)? {
+            (buy_dex, sell_dex, buy_router, sell_router, buy_price, sell_price)
+        } else {
+            (sell_dex, buy_dex, sell_router, buy_router, sell_price, buy_price)
+        };
+
     // Calculate spread in bps for gas strategy
     let spread_bps = if sell_price > buy_price && buy_price > 0.0 {
         ((sell_price - buy_price) / buy_price * 10000.0) as i32
@@ -1674,20 +2920,27 @@ async fn run_atomic_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u3
     println!("  Spread: {} bps", spread_bps);
     println!("==============================================================");
 
-    let result = execute_atomic_arb(
-        &provider_with_signer,
-        signer_address,
-        &sell_router,
-        &buy_router,
+    // The contract call bids the spread-boosted fee, so that's what the ceiling applies to
+    let (max_fee, _) = gas_cache::calculate_gas_price(gas, spread_bps);
+    if gas::gas_too_high(max_fee, max_gas_gwei) {
+        return Ok(());
+    }
+
+    let trade = AtomicArbTrade {
+        sell_router: &sell_router,
+        buy_router: &buy_router,
         amount,
         sell_price,
         buy_price,
-        slippage,
+        slippage_bps: slippage,
         min_profit_bps,
-        gas_price,
         spread_bps,
-        force,
-    ).await?;
+    };
+    let result = if let Some((lender, fee_bps)) = flashloan {
+        execute_atomic_arb_flashloan(&provider_with_signer, signer_address, lender, fee_bps, trade, gas).await?
+    } else {
+        execute_atomic_arb(&provider_with_signer, signer_address, trade, gas, AtomicExecOptions::careful(force)).await?
+    };
 
     print_atomic_arb_result(&result);
     println!("  [TIMING] TOTAL: {:?}", total_start.elapsed());
@@ -1697,14 +2950,15 @@ async fn run_atomic_arb(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u3
 
 /// TURBO MODE: Execute atomic arb with minimal latency (<300ms target)
 /// Skips pre/post balance queries and gas estimation for maximum speed
-async fn run_atomic_arb_turbo(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32) -> Result<()> {
+/// `await_receipt` fetches the receipt in the background instead of never learning the outcome
+async fn run_atomic_arb_turbo(sell_dex: &str, buy_dex: &str, amount: f64, slippage: u32, await_receipt: bool) -> Result<()> {
     let total_start = std::time::Instant::now();
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -1726,7 +2980,7 @@ async fn run_atomic_arb_turbo(sell_dex: &str, buy_dex: &str, amount: f64, slippa
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Get routers
     let sell_router = get_router_by_name(sell_dex)
@@ -1754,35 +3008,46 @@ async fn run_atomic_arb_turbo(sell_dex: &str, buy_dex: &str, amount: f64, slippa
     println!("  Spread: {} bps", spread_bps);
     println!("==============================================================");
 
-    let result = execution::execute_atomic_arb(
-        &provider_with_signer,
-        signer_address,
-        &sell_router,
-        &buy_router,
+    let trade = AtomicArbTrade {
+        sell_router: &sell_router,
+        buy_router: &buy_router,
         amount,
         sell_price,
         buy_price,
-        slippage,
-        0,  // min_profit_bps ignored
-        gas_price,
+        slippage_bps: slippage,
+        min_profit_bps: 0,  // ignored when forced
         spread_bps,
-        true,  // force flag ignored
+    };
+    let receipt_mode = if await_receipt { execution::ReceiptMode::Background } else { execution::ReceiptMode::FireAndForget };
+    let result = execution::execute_atomic_arb(
+        &provider_with_signer,
+        signer_address,
+        trade,
+        GasStrategy::default().fees_from_base(gas_price),
+        AtomicExecOptions::turbo(true, receipt_mode),
     ).await?;
 
     print_atomic_arb_result(&result);
     println!("  [TIMING] TOTAL: {:?} (TURBO)", total_start.elapsed());
 
+    // Off the send path: the process would otherwise exit before the watch finishes
+    if await_receipt {
+        execution::drain_receipt_watches().await;
+        stats::receipt_outcomes().print();
+    }
+
     Ok(())
 }
 
-/// Automated arbitrage: monitors and executes when spread opportunity detected
-async fn run_auto_arb(
+/// AutoArb settings, built from the `auto-arb` flags
+struct AutoArbOptions {
     min_spread_bps: i32,
     amount: f64,
     slippage: u32,
     max_executions: u32,
     cooldown_secs: u64,
     dry_run: bool,
+    paper: bool,
     force: bool,
     track_velocity: bool,
     history_size: usize,
@@ -1790,60 +3055,282 @@ async fn run_auto_arb(
     max_velocity: i32,
     min_final_spread: i32,
     max_baseline: i32,
-) -> Result<()> {
+    max_acceleration: f64,
+    max_impact_bps: u32,
+    min_net_profit_wmon: f64,
+    metrics_port: Option<u16>,
+    gas_strategy: GasStrategy,
+    min_liquidity: Option<u128>,
+    min_survival: f64,
+    survival_horizon_ms: u64,
+    watch_mempool: bool,
+    mempool_min_wmon: f64,
+    max_price_age_ms: u64,
+    retry_slippage_bump: u32,
+    auto_wrap_threshold: Option<f64>,
+    max_wrap_per_session: f64,
+    max_gas_gwei: f64,
+    auto_sweep_profit: Option<f64>,
+    expected_decay_bps: f64,
+    competitive_routes: Vec<gas::CompetitiveRoute>,
+    competitive_priority_multiplier: f64,
+    spread_log: Option<String>,
+    mode: ArbMode,
+    eta_ms: u64,
+    validation_logs: Option<String>,
+    max_adaptive_slippage: Option<u32>,
+    rank_by: RankBy,
+    pnl_denom: PnlDenom,
+    record_features: bool,
+}
+
+/// Automated arbitrage: monitors and executes when spread opportunity detected
+async fn run_auto_arb(pair: &TokenPair, opts: AutoArbOptions) -> Result<()> {
     use chrono::Local;
 
+    let AutoArbOptions {
+        min_spread_bps,
+        amount,
+        slippage,
+        max_executions,
+        cooldown_secs,
+        dry_run,
+        paper,
+        force,
+        track_velocity,
+        history_size,
+        min_velocity,
+        max_velocity,
+        min_final_spread,
+        max_baseline,
+        max_acceleration,
+        max_impact_bps,
+        min_net_profit_wmon,
+        metrics_port,
+        gas_strategy,
+        min_liquidity,
+        min_survival,
+        survival_horizon_ms,
+        watch_mempool,
+        mempool_min_wmon,
+        max_price_age_ms,
+        retry_slippage_bump,
+        auto_wrap_threshold,
+        max_wrap_per_session,
+        max_gas_gwei,
+        auto_sweep_profit,
+        expected_decay_bps,
+        competitive_routes,
+        competitive_priority_multiplier,
+        spread_log,
+        mode,
+        eta_ms,
+        validation_logs,
+        max_adaptive_slippage,
+        rank_by,
+        pnl_denom,
+        record_features,
+    } = opts;
+
     // Load node configuration (auto-detects local vs remote)
     let node_config = NodeConfig::from_env();
     node_config.log_config();
 
     let url: reqwest::Url = node_config.rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
-    // Verify node health before starting
+    // Verify node health and pool configuration before starting
     verify_node_ready(&provider).await?;
+    verify_pools_ready(&provider, &current_price_calls()).await?;
+
+    // Signers: PRIVATE_KEYS (comma-separated) for rotation, otherwise PRIVATE_KEY
+    // A missing or wrong contract falls back to fast arb instead of failing every execution
+    let use_atomic = match execution::verify_atomic_contract(&provider).await? {
+        execution::ContractStatus::Ready => true,
+        execution::ContractStatus::NotConfigured => false,
+        status => {
+            println!("  Atomic contract {:?} unusable ({:?}); falling back to FAST execution", atomic_arb_contract(), status);
+            false
+        }
+    };
+    let mut wallet_pool = WalletPool::from_env()?;
+    if use_atomic && wallet_pool.len() > 1 {
+        // executeArb is onlyOwner, so only the owner can use the shared contract
+        let owner = query_contract_owner(&provider).await?;
+        wallet_pool.retain(|address| address == owner);
+        if wallet_pool.is_empty() {
+            return Err(eyre::eyre!("None of PRIVATE_KEYS owns the atomic contract ({:?})", owner));
+        }
+        println!("  Atomic contract is owner-only; using {:?} without rotation", owner);
+    }
+    let signer_address = wallet_pool.addresses()[0];
 
-    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
-    let signer = PrivateKeySigner::from_str(&private_key)?;
-    let signer_address = signer.address();
+    // Profit sweeps withdraw to the contract owner (the primary wallet), then forward
+    let cold_wallet = match auto_sweep_profit {
+        Some(_) if !use_atomic => return Err(eyre::eyre!("--auto-sweep-profit needs the atomic arb contract")),
+        Some(_) => Some(wallet::sweep::cold_wallet_from_env(signer_address)?),
+        None => None,
+    };
 
-    // Initialize nonce
-    init_nonce(&provider, signer_address).await?;
+    // Initialize nonces (one counter per wallet)
+    wallet_pool.init_nonces(&provider).await?;
 
-    // Create provider with signer (reused for all executions)
-    let wallet = EthereumWallet::from(signer);
-    let provider_with_signer = ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_http(url);
+    // Create providers with signer (one per wallet, reused for all executions)
+    let signer_providers: Vec<_> = wallet_pool
+        .signers()
+        .iter()
+        .map(|signer| {
+            ProviderBuilder::new()
+                .wallet(EthereumWallet::from(signer.clone()))
+                .connect_client(rpc_failover::client(url.clone()))
+        })
+        .collect();
 
     // Initialize stats logger
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
     let stats_file = format!("arb_stats_{}.jsonl", timestamp);
     let mut stats_logger = StatsLogger::new(&stats_file);
 
-    // Initialize spread tracker for velocity analysis
-    let mut spread_tracker = if track_velocity {
-        Some(SpreadTracker::new(history_size))
+    // Eta mode measures survival over the ETA rather than --survival-horizon-ms
+    let survival_horizon_ms = if mode == ArbMode::Eta { eta_ms } else { survival_horizon_ms };
+
+    // Initialize spread tracker for velocity analysis and survival estimates
+    let mut spread_tracker = if track_velocity || min_survival > 0.0 || mode == ArbMode::Eta {
+        Some(
+            SpreadTracker::new(history_size)
+                .with_survival_horizon(survival_horizon_ms as u128)
+                .with_spike_velocity(max_velocity as f64),
+        )
     } else {
         None
     };
 
+    // Velocity filter thresholds: CLI globals plus per-route overrides from --config
+    let route_filter = SpreadFilterConfig {
+        min_velocity: min_velocity as f64,
+        max_velocity: max_velocity as f64,
+        min_final_spread,
+        max_baseline,
+        max_acceleration,
+        ..Default::default()
+    }
+    .with_routes(config::get_route_filters());
+
+    // Observed-to-landed spread haircut: flat, or per route from --config
+    let decay_gate = DecayGate::new(expected_decay_bps, config::get_route_decay());
+
+    // Higher priority fee on contested routes: --competitive-routes plus --config
+    let mut competitive = config::get_competitive_routes();
+    competitive.extend(competitive_routes);
+    let escalation = gas::PriorityEscalation::new(competitive_priority_multiplier, competitive);
+
+    // Execution window measured by MevValidate: the fast-arb latency budget, and --mode eta's gate
+    let execution_window_ms = match validation_logs.as_deref() {
+        Some(logs) => {
+            let (window_ms, lifecycles) = log_analysis::validated_execution_window(logs)?;
+            println!("Execution window: {} ms (measured over {} validated blocks)", window_ms, lifecycles);
+            Some(window_ms)
+        }
+        None => None,
+    };
+
+    // --rank-by executable: depth at the impact the trade is sized against
+    let rank_impact_bps = if max_impact_bps > 0 { max_impact_bps } else { slippage };
+
+    // --mode eta: ETA from --eta-ms then executions
+    let mut eta_gate = match mode {
+        ArbMode::Eta => {
+            let window_ms = execution_window_ms
+                .ok_or_else(|| eyre::eyre!("--mode eta needs --validation-logs (MevValidate output)"))?;
+            Some(EtaGate::new(eta_ms, window_ms, min_survival))
+        }
+        ArbMode::Poll => None,
+    };
+
     // Get polling interval from node config (50ms local, 1000ms remote)
     let poll_interval_ms = node_config.poll_interval.as_millis() as u64;
 
     println!("═══════════════════════════════════════════════════════════════");
     println!("  AUTO-ARB BOT STARTED");
     println!("═══════════════════════════════════════════════════════════════");
-    println!("  Wallet:          {:?}", signer_address);
+    if wallet_pool.len() > 1 {
+        println!("  Wallets:         {} (rotating)", wallet_pool.len());
+        for address in wallet_pool.addresses() {
+            println!("    {:?}", address);
+        }
+    } else {
+        println!("  Wallet:          {:?}", signer_address);
+    }
     println!("  Min Spread:      {} bps", min_spread_bps);
     println!("  Amount per arb:  {} WMON", amount);
     println!("  Slippage:        {} bps", slippage);
+    if let Some(max) = max_adaptive_slippage {
+        println!("  Adaptive slip:   scaled by pool depth, max {} bps", max);
+    }
+    if let Some(window_ms) = execution_window_ms {
+        println!("  Latency budget:  {} ms from detection (fast arb skips swap 2 past it)", window_ms);
+    }
+    if rank_by == RankBy::Executable {
+        println!("  Ranking:         net spread x executable share of {} WMON @ {} bps impact", amount, rank_impact_bps);
+    }
     println!("  Max executions:  {}", if max_executions == 0 { "unlimited".to_string() } else { max_executions.to_string() });
-    println!("  Cooldown:        {} seconds", cooldown_secs);
+    println!("  Cooldown:        {} seconds per route", cooldown_secs);
     println!("  Poll interval:   {} ms {}", poll_interval_ms, if node_config.is_local { "(local node optimized)" } else { "" });
     println!("  Receipt poll:    {} ms", node_config.receipt_poll_interval.as_millis());
     println!("  Dry run:         {}", dry_run);
+    if paper {
+        println!("  Paper trading:   enabled (simulated fills, no transactions)");
+    }
+    println!("  Gas strategy:    {} (max {} gwei)", gas_strategy, max_gas_gwei);
+    if track_velocity && !route_filter.routes.is_empty() {
+        println!("  Route filters:   {} override(s) from config", route_filter.routes.len());
+    }
+    if decay_gate.is_enabled() {
+        println!("  Expected decay:  {} bps ({} route override(s) from config)", decay_gate.flat_bps, decay_gate.routes.len());
+    }
+    if escalation.is_enabled() {
+        println!("  Priority fee:    {}x on {} competitive route(s)", competitive_priority_multiplier, escalation.route_count());
+    }
+    if let Some(gate) = &eta_gate {
+        println!("  Mode:            eta (ETA {} ms vs {} ms window, min survival {:.0}%)",
+            eta_ms, gate.window_ms, gate.min_survival * 100.0);
+    }
+    if let Some(path) = &spread_log {
+        spread_logger::init_spread_csv(path).map_err(|e| eyre::eyre!("Failed to open {}: {}", path, e))?;
+        // Nothing else stops AutoArb early; flush buffered rows on Ctrl+C
+        ctrlc::set_handler(|| {
+            spread_logger::flush_spread_csv();
+            std::process::exit(130);
+        })?;
+        println!("  Spread log:      {}", path);
+    }
+    if record_features {
+        let path = feature_recorder::start().map_err(|e| eyre::eyre!("Failed to open feature log: {}", e))?;
+        println!("  Features:        {}", path);
+    }
+    if let Some(min) = min_liquidity {
+        println!("  Min liquidity:   {}", min);
+    }
+    if min_survival > 0.0 {
+        println!("  Min survival:    {:.0}% over {} ms", min_survival * 100.0, survival_horizon_ms);
+    }
+    println!("  Max price age:   {} ms", max_price_age_ms);
+    if retry_slippage_bump > 0 {
+        println!("  Swap 2 retry:    +{} bps slippage on slippage revert", retry_slippage_bump);
+    }
+    if let Some(threshold) = auto_wrap_threshold {
+        println!("  Auto-wrap:       below {} WMON, up to {} MON per session", threshold, max_wrap_per_session);
+    }
+    if let (Some(threshold), Some(cold)) = (auto_sweep_profit, cold_wallet) {
+        println!("  Profit sweep:    excess >= {} WMON to {:?}", threshold, cold);
+    }
+    if watch_mempool {
+        println!("  Mempool watch:   pre-arm on pending swaps >= {} WMON ({})", mempool_min_wmon, node_config.ws_url);
+    }
     println!("  Stats file:      {}", stats_file);
+    if let Some(port) = metrics_port {
+        metrics::start_server(port).await?;
+    }
     if track_velocity {
         println!("  Velocity track:  enabled (history: {})", history_size);
         println!("  Filter config:");
@@ -1851,6 +3338,9 @@ async fn run_auto_arb(
         println!("    max_velocity:     {} bps/sec", max_velocity);
         println!("    min_final_spread: {} bps", min_final_spread);
         println!("    max_baseline:     {} bps", max_baseline);
+        if max_acceleration > 0.0 {
+            println!("    max_acceleration: {} bps/sec^2", max_acceleration);
+        }
     }
     println!("═══════════════════════════════════════════════════════════════");
     println!();
@@ -1861,38 +3351,141 @@ async fn run_auto_arb(
     println!("    WMON: {:>18.6}", initial_wmon);
     println!("    USDC: {:>18.6}", initial_usdc);
     println!();
+    // Transfers from here on are reconciled against the logged P&L at session end
+    let start_block = provider.get_block_number().await? + 1;
+
+    // Paper mode trades a virtual copy of the contract's inventory
+    let mut paper_account = paper.then(|| PaperAccount::new(initial_wmon, initial_usdc));
+    let mut wrapped_this_session = 0.0;
+
+    // Working capital the profit sweep never touches: starting WMON plus contract top-ups
+    let mut working_float = initial_wmon;
+    let (mut swept_wmon, mut sweep_count) = (0.0, 0u32);
+    let mut sweep_checked_at = 0u32;
 
     let mut execution_count = 0u32;
-    let mut last_execution = std::time::Instant::now() - std::time::Duration::from_secs(cooldown_secs);
-    let mut poll_interval = tokio::time::interval(Duration::from_millis(poll_interval_ms));
+    let mut poll = AdaptivePoll::new(Duration::from_millis(poll_interval_ms));
 
     // Initialize enhanced spread display for better visualization
     let mut arb_spread_display = spread_display::SpreadDisplay::new(min_spread_bps, history_size);
     let mut cumulative_pnl: f64 = 0.0;
+    let mut pnl_report = PnlReport::new(pnl_denom);
+
+    // Executions running on other wallets, plus per-route cooldowns
+    let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel::<AutoArbDone>();
+    let mut routes = RouteState::default();
+    let cooldown = Duration::from_secs(cooldown_secs);
+
+    // Pending swaps to our routers, and the routes they are about to open
+    let mut mempool_rx = if watch_mempool {
+        Some(mempool::watch_pending_swaps(&node_config.rpc_url, &node_config.ws_url, config::get_routers())?)
+    } else {
+        None
+    };
+    let mut pre_arm = mempool::PreArm::new(mempool_min_wmon, mempool::DEFAULT_ARM_WINDOW);
+    let mut last_prices: Vec<PoolPrice> = Vec::new();
 
     loop {
-        poll_interval.tick().await;
+        // A large pending swap cuts the wait short: its pool moves next block
+        match mempool_rx.as_mut() {
+            Some(rx) => loop {
+                tokio::select! {
+                    _ = poll.tick() => break,
+                    Some(signal) = rx.recv() => {
+                        let price = last_prices.iter().find(|p| p.pool_name == signal.pool).map(|p| p.price);
+                        if pre_arm.observe(&signal, price) {
+                            println!("\n  MEMPOOL: pending {:?} of {:.4} on {} - pre-armed",
+                                signal.direction, signal.size, signal.pool);
+                            break;
+                        }
+                    }
+                }
+            },
+            None => {
+                poll.tick().await;
+            }
+        }
 
-        // Check if we've hit max executions
-        if max_executions > 0 && execution_count >= max_executions {
+        // Record arbs that finished since the last poll
+        while let Ok(done) = done_rx.try_recv() {
+            routes.finish(&done.route);
+            if let (Some(gate), Ok(post)) = (eta_gate.as_mut(), &done.post_snapshot) {
+                gate.observe(post.total_execution_ms);
+            }
+            finish_auto_arb(done, &mut stats_logger, &mut cumulative_pnl, &pnl_report, &mut execution_count, max_executions, cooldown_secs);
+        }
+
+        // Check if we've hit max executions (counting ones still in flight)
+        if max_executions > 0 && execution_count + routes.in_flight.len() as u32 >= max_executions {
             println!("\n  Reached max executions ({}). Stopping.", max_executions);
             break;
         }
 
+        // Sweep profit to cold storage after new executions, while none are in flight
+        if let (Some(threshold), Some(cold)) = (auto_sweep_profit, cold_wallet) {
+            if execution_count != sweep_checked_at && routes.in_flight.is_empty() && paper_account.is_none() && !dry_run {
+                sweep_checked_at = execution_count;
+                let (contract_wmon, _) = query_contract_balances(&provider).await?;
+                if let Some(excess) = wallet::sweep::sweepable(contract_wmon, working_float, threshold) {
+                    let gas = gas_strategy.resolve(&provider, Duration::from_millis(poll_interval_ms)).await;
+                    if !gas::gas_too_high(gas.max_fee, max_gas_gwei) {
+                        println!("\n  PROFIT SWEEP: {:.6} WMON above the {:.6} float -> {:?}", excess, working_float, cold);
+                        match wallet::sweep::sweep_profit(&signer_providers[0], signer_address, cold, excess, gas).await {
+                            Ok(sweep) => {
+                                swept_wmon += sweep.amount;
+                                sweep_count += 1;
+                                info!(target: logging::EVENT_TARGET, pair = logging::pair(), swept_wmon = sweep.amount,
+                                    withdraw_tx = %sweep.withdraw_tx, transfer_tx = %sweep.transfer_tx,
+                                    "  PROFIT SWEEP: {:.6} WMON to cold ({:.6} this session)", sweep.amount, swept_wmon);
+                            }
+                            Err(e) => eprintln!("  PROFIT SWEEP failed: {}", e),
+                        }
+                    }
+                }
+            }
+        }
+
         // Fetch current prices
-        let prices = match get_current_prices(&provider).await {
+        let poll_start = std::time::Instant::now();
+        let fetched = get_current_prices_retry(&provider).await;
+        poll.record(poll_start.elapsed());
+        let mut prices = match fetched {
             Ok(p) => p,
             Err(e) => {
                 eprintln!("  Price fetch error: {}", e);
                 continue;
             }
         };
+        metrics::set_poll_latency(poll_start.elapsed().as_millis());
+        pnl_report.observe_price(price::gas_token_price(&prices));
+        let depth = if min_liquidity.is_some() || rank_by == RankBy::Executable {
+            attach_liquidity(&provider, pair, &mut prices).await
+        } else {
+            std::collections::HashMap::new()
+        };
+        if watch_mempool {
+            last_prices = prices.clone();
+        }
 
-        // Calculate spreads
-        let spreads = calculate_spreads(&prices);
+        // Calculate spreads (pairs on thin pools dropped by --min-liquidity)
+        let mut spreads = calculate_spreads(&prices, min_liquidity);
+        if rank_by == RankBy::Executable {
+            spread_filter::rank_by_executable(&mut spreads, amount, |s| {
+                Some(pools::liquidity::executable_size(depth.get(&s.sell_pool)?, depth.get(&s.buy_pool)?, rank_impact_bps))
+            });
+        }
+        spread_logger::log_spread_csv(prices.iter().find_map(|p| p.block_number), spreads.first());
+        let feature_poll = feature_recorder::record_poll(prices.iter().find_map(|p| p.block_number), None, &prices, &spreads);
 
-        // Find best opportunity (first one is best due to sorting)
-        let best_spread = spreads.first();
+        if let Some(spread) = spreads.first() {
+            metrics::set_best_net_spread((spread.net_spread_pct * 100.0) as i32);
+        }
+
+        // Find best opportunity (first one is best due to sorting) among routes
+        // that aren't executing or cooling down
+        let best_spread = spreads
+            .iter()
+            .find(|s| routes.is_ready(&(s.buy_pool.clone(), s.sell_pool.clone()), cooldown));
 
         if let Some(spread) = best_spread {
             // Record spread for velocity tracking (no extra latency - uses existing data)
@@ -1935,364 +3528,912 @@ async fn run_auto_arb(
 
             // Show P&L if tracking
             print!("P&L: {:>+.4} WMON ", cumulative_pnl);
+            print!("poll {}ms ", poll.interval().as_millis());
 
             std::io::Write::flush(&mut std::io::stdout()).ok();
+        }
 
+        // Every ready route above threshold, best first, while a wallet is idle. The
+        // contract's WMON is read once per cycle and each dispatched arb's amount stays
+        // committed against it until that arb finishes, so concurrent routes can't
+        // together spend more than the contract holds.
+        let candidates: Vec<display::SpreadOpportunity> = spreads
+            .iter()
+            .filter(|s| routes.is_ready(&(s.buy_pool.clone(), s.sell_pool.clone()), cooldown))
+            .cloned()
+            .collect();
+        let mut cycle_balances: Option<(f64, f64)> = None;
+        for (rank, spread) in candidates.iter().enumerate() {
             let net_spread_bps = (spread.net_spread_pct * 100.0) as i32;
+            // Velocity history only follows the best route
+            if net_spread_bps < min_spread_bps
+                || (rank > 0 && track_velocity)
+                || (max_executions > 0 && execution_count + routes.in_flight.len() as u32 >= max_executions)
+            {
+                break;
+            }
+            let Some(lease) = wallet_pool.acquire() else { break };
+
+            let route = (spread.buy_pool.clone(), spread.sell_pool.clone());
+            println!();  // New line after the \r print
+            info!(target: logging::EVENT_TARGET, pair = logging::pair(), buy_pool = %spread.buy_pool,
+                sell_pool = %spread.sell_pool, net_bps = net_spread_bps, threshold_bps = min_spread_bps,
+                block = spread.block_number,
+                "\n  OPPORTUNITY DETECTED! Net spread: {} bps (threshold: {} bps)",
+                net_spread_bps, min_spread_bps);
+
+            // Execute only if the spread should still clear the threshold once it has decayed
+            if decay_gate.is_enabled() {
+                let decay = decay_gate.expected_decay(&spread.buy_pool, &spread.sell_pool);
+                let predicted = decay_gate.predicted_spread(net_spread_bps, &spread.buy_pool, &spread.sell_pool);
+                if predicted < min_spread_bps as f64 {
+                    println!("    DECAY: SKIP - {} bps - {:.1} bps expected decay = {:.1} bps (min {} bps)",
+                        net_spread_bps, decay, predicted, min_spread_bps);
+                    continue;
+                }
+                println!("    DECAY: PASS - {:.1} bps expected after {:.1} bps decay", predicted, decay);
+            }
 
-            // Check if spread meets threshold and cooldown has passed
-            let cooldown_elapsed = last_execution.elapsed().as_secs() >= cooldown_secs;
-
-            if net_spread_bps >= min_spread_bps && cooldown_elapsed {
-                println!();  // New line after the \r print
-                println!("\n  OPPORTUNITY DETECTED! Net spread: {} bps (threshold: {} bps)",
-                    net_spread_bps, min_spread_bps);
+            // Analyze spread velocity before execution
+            let velocity_analysis = spread_tracker.as_ref().and_then(|t| t.analyze());
+
+            if let Some(ref analysis) = velocity_analysis {
+                println!("\n  SPREAD VELOCITY ANALYSIS:");
+                println!("    History: {}", spread_tracker.as_ref().unwrap().format_history());
+                println!("    Velocity: {:.2} bps/sec", analysis.velocity_bps_per_sec);
+                println!("    Acceleration: {:.2} bps/sec^2", analysis.acceleration);
+                println!("    Pattern: {}", if analysis.is_spike { "SPIKE (faster than max velocity, likely bot-induced)" } else { "GRADUAL" });
+                println!("    Window: {} ms", analysis.window_duration_ms);
+                println!("    Range: {} to {} bps", analysis.min_spread_in_window, analysis.max_spread_in_window);
+            }
 
-                // Analyze spread velocity before execution
-                let velocity_analysis = spread_tracker.as_ref().and_then(|t| t.analyze());
+            // A spread announced by a pending swap skips the velocity and survival
+            // gates: those guess at where a spread came from, here it's known
+            let armed_by = pre_arm.armed_by(&spread.buy_pool, &spread.sell_pool);
+            if let Some((pool, direction)) = armed_by {
+                println!("    MEMPOOL: PRE-ARMED by pending {:?} on {}", direction, pool);
+            }
 
+            // Apply smart spread filter if velocity tracking enabled
+            if track_velocity && armed_by.is_none() {
                 if let Some(ref analysis) = velocity_analysis {
-                    println!("\n  SPREAD VELOCITY ANALYSIS:");
-                    println!("    History: {}", spread_tracker.as_ref().unwrap().format_history());
-                    println!("    Velocity: {:.2} bps/sec", analysis.velocity_bps_per_sec);
-                    println!("    Acceleration: {:.2} bps/sec^2", analysis.acceleration);
-                    println!("    Pattern: {}", if analysis.is_spike { "SPIKE" } else { "GRADUAL" });
-                    println!("    Window: {} ms", analysis.window_duration_ms);
-                    println!("    Range: {} to {} bps", analysis.min_spread_in_window, analysis.max_spread_in_window);
-                }
-
-                // Apply smart spread filter if velocity tracking enabled
-                if track_velocity {
-                    if let Some(ref analysis) = velocity_analysis {
-                        let filter = SpreadFilterConfig {
-                            min_velocity: min_velocity as f64,
-                            max_velocity: max_velocity as f64,
-                            min_final_spread,
-                            max_baseline,
-                        };
-
-                        match filter.evaluate(analysis) {
-                            FilterResult::Execute => {
-                                println!("    FILTER: PASS - executing arb");
-                            }
-                            FilterResult::Skip { reason } => {
-                                println!("    FILTER: SKIP - {}", reason);
-                                continue;
-                            }
+                    let source = route_filter.source(&spread.buy_pool, &spread.sell_pool);
+                    match route_filter.evaluate(analysis, &spread.buy_pool, &spread.sell_pool) {
+                        FilterResult::Execute => {
+                            println!("    FILTER ({}): PASS - executing arb", source);
+                        }
+                        FilterResult::Skip { reason } => {
+                            println!("    FILTER ({}): SKIP - {} (velocity {:+.1} bps/sec, acceleration {:+.1} bps/sec^2)",
+                                source, reason, analysis.velocity_bps_per_sec, analysis.acceleration);
+                            continue;
                         }
                     }
                 }
+            }
 
-                // Get routers for the opportunity
-                let sell_router = match get_router_by_name(&spread.sell_pool) {
-                    Some(r) => r,
-                    None => {
-                        eprintln!("  Router not found for {}", spread.sell_pool);
-                        continue;
-                    }
-                };
-                let buy_router = match get_router_by_name(&spread.buy_pool) {
-                    Some(r) => r,
-                    None => {
-                        eprintln!("  Router not found for {}", spread.buy_pool);
-                        continue;
-                    }
-                };
-
-                // Get current contract balances (pre-execution)
-                let (contract_wmon_before, contract_usdc_before) = query_contract_balances(&provider).await?;
-
-                // Check if contract has enough WMON
-                if contract_wmon_before < amount {
-                    println!("  Insufficient contract WMON. Have: {:.6}, Need: {:.6}",
-                        contract_wmon_before, amount);
+            // Skip races we're likely to lose: this tier rarely lasts long enough on this route
+            if min_survival > 0.0 && armed_by.is_none() && eta_gate.is_none() {
+                let tier = SpreadTier::from_bps(net_spread_bps);
+                let survival = spread_tracker
+                    .as_ref()
+                    .map(|t| t.estimated_survival_prob(&route, tier))
+                    .unwrap_or(0.0);
+                if survival < min_survival {
+                    println!("    SURVIVAL: SKIP - {:.0}% of {:?} spreads last {} ms (min {:.0}%)",
+                        survival * 100.0, tier, survival_horizon_ms, min_survival * 100.0);
                     continue;
                 }
+                println!("    SURVIVAL: PASS - {:.0}% of {:?} spreads last {} ms", survival * 100.0, tier, survival_horizon_ms);
+            }
 
-                // Calculate expected amounts
-                let expected_usdc = amount * spread.sell_price;
-                let expected_wmon_back = expected_usdc / spread.buy_price;
-
-                // Create pre-execution snapshot (using contract balances)
-                let pre_snapshot = PreExecutionSnapshot {
-                    timestamp: Local::now().to_rfc3339(),
-                    wmon_balance: contract_wmon_before,
-                    usdc_balance: contract_usdc_before,
-                    mon_balance: 0.0, // Contract doesn't hold native MON
-                    sell_dex: spread.sell_pool.clone(),
-                    sell_price: spread.sell_price,
-                    buy_dex: spread.buy_pool.clone(),
-                    buy_price: spread.buy_price,
-                    gross_spread_bps: (spread.gross_spread_pct * 100.0) as i32,
-                    net_spread_bps,
-                    amount_wmon: amount,
-                    expected_usdc,
-                    expected_wmon_back,
-                    slippage_bps: slippage,
-                    // Velocity tracking data (optional)
-                    spread_history: velocity_analysis.as_ref().map(|a| a.snapshots.clone()),
-                    velocity_bps_per_sec: velocity_analysis.as_ref().map(|a| a.velocity_bps_per_sec),
-                    acceleration: velocity_analysis.as_ref().map(|a| a.acceleration),
-                    is_spike_pattern: velocity_analysis.as_ref().map(|a| a.is_spike),
+            // --mode eta: fire only if the arb should land inside the window while the spread lasts
+            if let Some(gate) = &eta_gate {
+                let tier = SpreadTier::from_bps(net_spread_bps);
+                // A pending swap's spread is known rather than guessed; only the window applies
+                let survival = if armed_by.is_some() {
+                    1.0
+                } else {
+                    spread_tracker.as_ref().map(|t| t.estimated_survival_prob(&route, tier)).unwrap_or(0.0)
                 };
-
-                print_pre_execution(&pre_snapshot);
-
-                if dry_run {
-                    println!("\n  [DRY RUN] Would execute arb but dry_run=true. Skipping.");
-
-                    // Log dry run
-                    let record = ArbExecutionRecord {
-                        id: stats_logger.next_id(),
-                        pre: pre_snapshot,
-                        post: None,
-                        success: false,
-                        error: Some("Dry run - execution skipped".to_string()),
-                    };
-                    stats_logger.log_execution(&record);
-
-                    last_execution = std::time::Instant::now();
-                    execution_count += 1;
+                let (fire, verdict) = match gate.evaluate(survival) {
+                    FilterResult::Execute => (true, "FIRE".to_string()),
+                    FilterResult::Skip { reason } => (false, format!("SKIP - {}", reason)),
+                };
+                info!(target: logging::EVENT_TARGET, pair = logging::pair(), route = %format!("{}→{}", spread.buy_pool, spread.sell_pool),
+                    eta_ms = gate.eta_ms as u64, window_ms = gate.window_ms as u64, survival, fire,
+                    "    ETA: {} - {:.0} ms vs {} ms window, {:.0}% of {:?} spreads last {} ms",
+                    verdict, gate.eta_ms, gate.window_ms, survival * 100.0, tier, survival_horizon_ms);
+                if !fire {
                     continue;
                 }
+            }
 
-                // Fetch gas price
-                let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
-
-                // Fix 6: Re-check prices before execution to avoid stale spread
-                println!("  Re-checking prices before execution...");
-                let fresh_prices = match get_current_prices(&provider).await {
-                    Ok(p) => p,
-                    Err(e) => {
-                        eprintln!("  Price recheck failed: {}. Skipping execution.", e);
-                        continue;
-                    }
-                };
-                let fresh_spreads = calculate_spreads(&fresh_prices);
-
-                // Find the same pair in fresh spreads
-                let fresh_spread = fresh_spreads.iter().find(|s| {
-                    s.sell_pool == spread.sell_pool && s.buy_pool == spread.buy_pool
-                });
-
-                if let Some(fs) = fresh_spread {
-                    let fresh_spread_bps = (fs.net_spread_pct * 100.0) as i32;
-                    if fresh_spread_bps < min_spread_bps {
-                        println!("  Spread evaporated! Was {} bps, now {} bps. Skipping.",
-                            net_spread_bps, fresh_spread_bps);
-                        continue;
-                    }
-                    println!("  Fresh spread: {} bps (still above threshold)", fresh_spread_bps);
-                } else {
-                    println!("  WARNING: Could not find matching spread in fresh prices. Proceeding with caution.");
+            // Get routers for the opportunity
+            let sell_router = match get_router_by_name(&spread.sell_pool) {
+                Some(r) => r,
+                None => {
+                    eprintln!("  Router not found for {}", spread.sell_pool);
+                    continue;
+                }
+            };
+            let buy_router = match get_router_by_name(&spread.buy_pool) {
+                Some(r) => r,
+                None => {
+                    eprintln!("  Router not found for {}", spread.buy_pool);
+                    continue;
                 }
+            };
 
-                // Execute arb - use atomic if contract is deployed, otherwise fast_arb
-                println!("\n  EXECUTING ARB...");
-                let exec_start = std::time::Instant::now();
+            // Clamp size to what both pools can absorb without excess price impact
+            let amount = clamp_amount_to_liquidity(
+                &provider, pair, &spread.sell_pool, &spread.buy_pool, amount, max_impact_bps,
+            ).await;
 
-                // Use atomic arb if contract is deployed, otherwise fall back to fast_arb
-                let use_atomic = ATOMIC_ARB_CONTRACT != alloy::primitives::Address::ZERO;
+            // Get current balances of the contract (atomic, once per cycle) or leased wallet (fast), pre-execution
+            let (mut contract_wmon_before, mut contract_usdc_before) = match (&paper_account, cycle_balances) {
+                (Some(account), _) => (account.wmon, account.usdc),
+                (None, Some(balances)) if use_atomic => balances,
+                (None, _) => arb_balances(&provider, lease.address, use_atomic).await?,
+            };
 
-                let arb_result: Result<execution::FastArbResult, eyre::Report> = if use_atomic {
-                    println!("  Using ATOMIC TURBO execution (single TX)...");
-                    match execute_atomic_arb(
-                        &provider_with_signer,
-                        signer_address,
-                        &sell_router,
-                        &buy_router,
-                        amount,
-                        spread.sell_price,
-                        spread.buy_price,
-                        slippage,
-                        0, // min_profit_bps = 0 (any profit)
-                        gas_price,
-                        net_spread_bps, // spread_bps for gas strategy
-                        force, // force execution even if unprofitable
-                    ).await {
-                        Ok(result) => {
-                            print_atomic_arb_result(&result);
-                            // Convert AtomicArbResult to FastArbResult for stats compatibility
-                            let profit = result.profit_wmon();
-                            Ok(execution::FastArbResult {
-                                success: result.success,
-                                swap1_success: result.success,
-                                swap1_tx_hash: result.tx_hash.clone(),
-                                swap1_gas_used: result.gas_used,
-                                swap1_gas_estimated: result.gas_limit,
-                                swap2_success: result.success,
-                                swap2_tx_hash: String::new(), // Atomic has single TX
-                                swap2_gas_used: 0,
-                                swap2_gas_estimated: 0,
-                                wmon_in: result.wmon_in,
-                                usdc_intermediate: 0.0,
-                                wmon_out: result.wmon_in + profit,
-                                usdc_before: 0.0,
-                                usdc_after_swap1: 0.0,
-                                wmon_before: result.wmon_in,
-                                wmon_after_swap2: result.wmon_in + profit,
-                                actual_usdc_received: 0.0,
-                                actual_wmon_received: profit,
-                                swap1_slippage_bps: 0,
-                                swap2_slippage_bps: 0,
-                                wmon_out_actual: Some(result.wmon_in + profit),
-                                estimation_error_bps: None,
-                                gross_profit_wmon: profit,
-                                profit_bps: result.profit_bps,
-                                total_gas_cost_wei: alloy::primitives::U256::ZERO,
-                                total_gas_cost_mon: result.gas_cost_mon,
-                                total_gas_used: result.gas_used,
-                                total_gas_estimated: result.gas_limit,
-                                total_time_ms: result.execution_time_ms,
-                                swap1_time_ms: result.execution_time_ms,
-                                swap2_time_ms: 0,
-                                execution_time_ms: result.execution_time_ms,
-                                error: result.error,
-                            })
+            // Refill inventory from wallet MON. Wraps go out from the primary wallet,
+            // so with rotation only its own (fast) inventory or the contract's qualify.
+            if let Some(threshold) = auto_wrap_threshold {
+                if paper_account.is_none() && (use_atomic || lease.address == signer_address) {
+                    let wallet_mon = provider.get_balance(signer_address).await
+                        .map(|b| b.to::<u128>() as f64 / 1e18)
+                        .unwrap_or(0.0);
+                    if let Some(wrap_amount) = plan_top_up(contract_wmon_before, threshold, threshold + amount,
+                        wallet_mon, wrapped_this_session, max_wrap_per_session)
+                    {
+                        println!("\n  AUTO-WRAP: {} WMON {:.6} below {} - wrapping {:.6} MON ({:.6} of {} MON used this session)",
+                            if use_atomic { "contract" } else { "wallet" }, contract_wmon_before, threshold,
+                            wrap_amount, wrapped_this_session, max_wrap_per_session);
+                        // Count the attempt against the cap even if it fails
+                        wrapped_this_session += wrap_amount;
+                        match top_up_wmon(&provider, &signer_providers[0], &wallet_pool.signers()[0],
+                            &node_config.rpc_url, wrap_amount, use_atomic).await
+                        {
+                            Ok(added) => {
+                                info!(target: logging::EVENT_TARGET, pair = logging::pair(), wrapped_mon = wrap_amount,
+                                    added_wmon = added, session_wrapped_mon = wrapped_this_session,
+                                    "  AUTO-WRAP: added {:.6} WMON ({:.6} / {} MON wrapped this session)",
+                                    added, wrapped_this_session, max_wrap_per_session);
+                                if use_atomic {
+                                    working_float += added;
+                                }
+                                (contract_wmon_before, contract_usdc_before) =
+                                    arb_balances(&provider, lease.address, use_atomic).await?;
+                            }
+                            Err(e) => eprintln!("  AUTO-WRAP failed: {}", e),
                         }
-                        Err(e) => Err(e)
                     }
+                }
+            }
+
+            if use_atomic {
+                cycle_balances = Some((contract_wmon_before, contract_usdc_before));
+            }
+
+            // Check if contract has enough WMON. Routes executing concurrently share the
+            // contract balance; a wallet-funded arb has its wallet to itself.
+            let committed_wmon = if use_atomic { routes.committed_wmon() } else { 0.0 };
+            if contract_wmon_before - committed_wmon < amount {
+                if committed_wmon > 0.0 && contract_wmon_before >= amount {
+                    println!("  Skipping {}→{}: committed capacity exhausted ({:.6} of {:.6} contract WMON committed, need {:.6})",
+                        spread.buy_pool, spread.sell_pool, committed_wmon, contract_wmon_before, amount);
                 } else {
-                    println!("  Using FAST execution (2 TXs) - deploy atomic contract for better results!");
-                    execute_fast_arb(
-                        &provider_with_signer,
-                        signer_address,
-                        &sell_router,
-                        &buy_router,
-                        amount,
-                        spread.sell_price,
-                        spread.buy_price,
-                        slippage,
-                        gas_price,
-                    ).await
-                };
+                    println!("  Insufficient {} WMON. Have: {:.6} ({:.6} committed in flight), Need: {:.6}",
+                        if use_atomic { "contract" } else { "wallet" }, contract_wmon_before, committed_wmon, amount);
+                }
+                continue;
+            }
 
-                let exec_time = exec_start.elapsed().as_millis();
+            // Calculate expected amounts
+            let expected_usdc = amount * spread.sell_price;
+            let expected_wmon_back = expected_usdc / spread.buy_price;
+
+            // Resolve fees (needed for the net-profit gate); percentile history is cached per poll
+            let gas = gas_strategy.resolve(&provider, Duration::from_millis(poll_interval_ms)).await;
+            let gas = escalation.fees_for(gas, &spread.buy_pool, &spread.sell_pool);
+            let competitive = escalation.is_enabled() && escalation.is_competitive(&spread.buy_pool, &spread.sell_pool);
+            info!(target: logging::EVENT_TARGET, pair = logging::pair(), route = %format!("{}→{}", spread.buy_pool, spread.sell_pool),
+                priority_fee = gas.priority_fee, competitive,
+                "  Priority fee: {:.3} gwei{}", gas.priority_fee as f64 / 1e9,
+                if competitive { format!(" ({}x, competitive route)", competitive_priority_multiplier) } else { String::new() });
+
+            // Net-profit gate: gross spread - DEX fees - gas (MON = WMON via the wrapper)
+            let breakdown = profit_calculator::estimate_profit(
+                amount,
+                spread.sell_price,
+                spread.buy_price,
+                spread.sell_fee_bps,
+                spread.buy_fee_bps,
+                if use_atomic { profit_calculator::ATOMIC_ARB_GAS_UNITS } else { profit_calculator::FAST_ARB_GAS_UNITS },
+                gas.max_fee,
+            ).with_gas_token_price(price::gas_token_price(&prices).unwrap_or(spread.buy_price));
+            let net_profit_ok = breakdown.net_wmon > 0.0 && breakdown.net_wmon >= min_net_profit_wmon;
+
+            // Create pre-execution snapshot (using contract balances)
+            let pre_snapshot = PreExecutionSnapshot {
+                timestamp: Local::now().to_rfc3339(),
+                wmon_balance: contract_wmon_before,
+                usdc_balance: contract_usdc_before,
+                mon_balance: 0.0, // Contract doesn't hold native MON
+                sell_dex: spread.sell_pool.clone(),
+                sell_price: spread.sell_price,
+                buy_dex: spread.buy_pool.clone(),
+                buy_price: spread.buy_price,
+                gross_spread_bps: (spread.gross_spread_pct * 100.0) as i32,
+                net_spread_bps,
+                amount_wmon: amount,
+                expected_usdc,
+                expected_wmon_back,
+                slippage_bps: slippage,
+                // Velocity tracking data (optional)
+                spread_history: velocity_analysis.as_ref().map(|a| a.snapshots.clone()),
+                velocity_bps_per_sec: velocity_analysis.as_ref().map(|a| a.velocity_bps_per_sec),
+                acceleration: velocity_analysis.as_ref().map(|a| a.acceleration),
+                is_spike_pattern: velocity_analysis.as_ref().map(|a| a.is_spike),
+                profit_breakdown: Some(breakdown.clone()),
+            };
 
-                // Get post-execution contract balances
-                let (contract_wmon_after, contract_usdc_after) = query_contract_balances(&provider).await?;
+            print_pre_execution(&pre_snapshot);
 
-                // Create post-execution snapshot (using contract balances)
-                let post_snapshot = match &arb_result {
-                    Ok(result) => {
-                        let wmon_delta = contract_wmon_after - contract_wmon_before;
-                        let usdc_delta = contract_usdc_after - contract_usdc_before;
-                        let mon_delta = 0.0; // Contract doesn't hold native MON
-                        let net_profit_bps = if amount > 0.0 {
-                            (wmon_delta / amount * 10000.0) as i32
-                        } else {
-                            0
-                        };
+            if !net_profit_ok && !force {
+                println!("  Net profit {:+.6} WMON below minimum {:.6} WMON. Skipping.",
+                    breakdown.net_wmon, min_net_profit_wmon);
+                continue;
+            }
 
-                        PostExecutionSnapshot {
-                            timestamp: Local::now().to_rfc3339(),
-                            wmon_balance: contract_wmon_after,
-                            usdc_balance: contract_usdc_after,
-                            mon_balance: 0.0, // Contract doesn't hold native MON
-                            swap1_success: result.swap1_success,
-                            swap1_tx_hash: result.swap1_tx_hash.clone(),
-                            swap1_gas_used: result.swap1_gas_used,
-                            swap1_gas_estimated: result.swap1_gas_estimated,
-                            swap2_success: result.swap2_success,
-                            swap2_tx_hash: result.swap2_tx_hash.clone(),
-                            swap2_gas_used: result.swap2_gas_used,
-                            swap2_gas_estimated: result.swap2_gas_estimated,
-                            actual_usdc_received: result.usdc_intermediate,
-                            actual_wmon_back: result.wmon_out,
-                            wmon_delta,
-                            usdc_delta,
-                            mon_delta,
-                            total_gas_cost_mon: result.total_gas_cost_mon,
-                            net_profit_wmon: wmon_delta,
-                            net_profit_bps,
-                            total_execution_ms: exec_time,
-                        }
-                    }
-                    Err(_e) => {
-                        // Failed execution - still record balances
-                        PostExecutionSnapshot {
-                            timestamp: Local::now().to_rfc3339(),
-                            wmon_balance: contract_wmon_after,
-                            usdc_balance: contract_usdc_after,
-                            mon_balance: 0.0, // Contract doesn't hold native MON
-                            swap1_success: false,
-                            swap1_tx_hash: String::new(),
-                            swap1_gas_used: 0,
-                            swap1_gas_estimated: 0,
-                            swap2_success: false,
-                            swap2_tx_hash: String::new(),
-                            swap2_gas_used: 0,
-                            swap2_gas_estimated: 0,
-                            actual_usdc_received: 0.0,
-                            actual_wmon_back: 0.0,
-                            wmon_delta: contract_wmon_after - contract_wmon_before,
-                            usdc_delta: contract_usdc_after - contract_usdc_before,
-                            mon_delta: 0.0, // Contract doesn't hold native MON
-                            total_gas_cost_mon: 0.0,
-                            net_profit_wmon: 0.0,
-                            net_profit_bps: 0,
-                            total_execution_ms: exec_time,
+            // Atomic execution bids the spread-boosted fee (see run_auto_arb_job)
+            let max_fee = if use_atomic { gas_cache::calculate_gas_price(gas, net_spread_bps).0 } else { gas.max_fee };
+            if gas::gas_too_high(max_fee, max_gas_gwei) {
+                continue;
+            }
+
+            // A stalled loop (slow multicall, gas RPC, liquidity reads) leaves the spread stale
+            let price_age = oldest_price_age(&prices, &[&spread.buy_pool, &spread.sell_pool])
+                .unwrap_or(Duration::MAX);
+            if price_age > Duration::from_millis(max_price_age_ms) {
+                println!("  Prices are {}ms old (max {}ms). Skipping stale spread.",
+                    price_age.as_millis(), max_price_age_ms);
+                continue;
+            }
+            // The spread was seen when its oldest price was read; it lasts about one window
+            let deadline = execution_window_ms.map(|window_ms| {
+                let detected_at = std::time::Instant::now().checked_sub(price_age).unwrap_or_else(std::time::Instant::now);
+                detected_at + Duration::from_millis(window_ms as u64)
+            });
+
+            if let Some(account) = paper_account.as_mut() {
+                let sim_start = std::time::Instant::now();
+                let fill = match QuoteFetcher::AtomicQuote.quote_arb(&provider, pair, &sell_router, &buy_router, amount).await {
+                    Ok(quote) => PaperFill {
+                        amount_in: amount,
+                        usdc_intermediate: quote.usdc_intermediate,
+                        wmon_out: quote.wmon_out,
+                        gas_wmon: breakdown.gas_wmon,
+                        gas_units: breakdown.gas_units,
+                    },
+                    Err(e) => {
+                        // LFJ and V4 have no QuoterV2: fill at spot after fees instead
+                        println!("  [PAPER] No quote ({}). Filling at spot after fees.", e);
+                        PaperFill {
+                            amount_in: amount,
+                            usdc_intermediate: expected_usdc,
+                            wmon_out: amount + breakdown.gross_wmon - breakdown.dex_fees_wmon,
+                            gas_wmon: breakdown.gas_wmon,
+                            gas_units: breakdown.gas_units,
                         }
                     }
                 };
-
+                let post_snapshot = account.fill(&fill, sim_start.elapsed().as_millis());
+                println!("\n  [PAPER] Simulated fill: {:.6} WMON -> {:.6} WMON, net {:+.6} WMON",
+                    fill.amount_in, fill.wmon_out, post_snapshot.net_profit_wmon);
                 print_post_execution(&pre_snapshot, &post_snapshot);
+                cumulative_pnl += post_snapshot.net_profit_wmon;
+                println!("  P&L: {} | Session: {}", pnl_report.format(post_snapshot.net_profit_wmon), pnl_report.format(cumulative_pnl));
+                feature_recorder::record_outcome(feature_poll, &spread.buy_pool, &spread.sell_pool, true, true, post_snapshot.net_profit_wmon);
 
-                // Log execution record
                 let record = ArbExecutionRecord {
                     id: stats_logger.next_id(),
                     pre: pre_snapshot,
                     post: Some(post_snapshot),
-                    success: arb_result.as_ref().map(|r| r.success).unwrap_or(false),
-                    error: arb_result.as_ref().err().map(|e| e.to_string()),
+                    success: true,
+                    error: None,
+                    block_number: spread.block_number,
                 };
                 stats_logger.log_execution(&record);
 
-                // Print result summary
-                if let Ok(result) = &arb_result {
-                    print_fast_arb_result(result, &spread.sell_pool, &spread.buy_pool);
-                } else if let Err(e) = &arb_result {
-                    println!("\n  ARB EXECUTION FAILED: {}", e);
-                }
+                routes.finish(&route);
+                execution_count += 1;
+                continue;
+            }
 
-                last_execution = std::time::Instant::now();
+            if dry_run {
+                println!("\n  [DRY RUN] Would execute arb but dry_run=true. Skipping.");
+
+                // Log dry run
+                let record = ArbExecutionRecord {
+                    id: stats_logger.next_id(),
+                    pre: pre_snapshot,
+                    post: None,
+                    success: false,
+                    error: Some("Dry run - execution skipped".to_string()),
+                    block_number: spread.block_number,
+                };
+                stats_logger.log_execution(&record);
+
+                routes.finish(&route);
                 execution_count += 1;
+                continue;
+            }
+
+            // Fix 6: Re-check prices before execution to avoid stale spread
+            println!("  Re-checking prices before execution...");
+            let fresh_prices = match get_current_prices_retry(&provider).await {
+                Ok(p) => p,
+                Err(e) => {
+                    eprintln!("  Price recheck failed: {}. Skipping execution.", e);
+                    continue;
+                }
+            };
+            let fresh_spreads = calculate_spreads(&fresh_prices, None);
+
+            // Find the same pair in fresh spreads
+            let fresh_spread = fresh_spreads.iter().find(|s| {
+                s.sell_pool == spread.sell_pool && s.buy_pool == spread.buy_pool
+            });
+
+            if let Some(fs) = fresh_spread {
+                let fresh_spread_bps = (fs.net_spread_pct * 100.0) as i32;
+                if fresh_spread_bps < min_spread_bps {
+                    println!("  Spread evaporated! Was {} bps, now {} bps. Skipping.",
+                        net_spread_bps, fresh_spread_bps);
+                    continue;
+                }
+                println!("  Fresh spread: {} bps (still above threshold)", fresh_spread_bps);
+            } else {
+                println!("  WARNING: Could not find matching spread in fresh prices. Proceeding with caution.");
+            }
+
+            // Execute on the leased wallet. With several wallets the poll loop keeps
+            // running and the result is recorded when the task reports back.
+            println!("\n  EXECUTING ARB...");
+            if wallet_pool.len() > 1 {
+                println!("  Wallet: {:?} ({} of {} busy)", lease.address, wallet_pool.busy_count(), wallet_pool.len());
+            }
+            routes.start(route.clone(), amount);
+            if let Some(ref mut tracker) = spread_tracker {
+                tracker.discard_pending(&route);
+            }
+
+            let job = AutoArbJob {
+                signer_provider: signer_providers[lease.index].clone(),
+                lease,
+                pair: *pair,
+                sell_router,
+                buy_router,
+                amount,
+                spread: spread.clone(),
+                slippage,
+                gas,
+                net_spread_bps,
+                force,
+                use_atomic,
+                contract_wmon_before,
+                pre_snapshot,
+                route,
+                retry_slippage_bump,
+                max_adaptive_slippage,
+                deadline,
+                feature_poll,
+            };
+            let job_provider = provider.clone();
+            let job_tx = done_tx.clone();
+            tokio::spawn(async move {
+                let _ = job_tx.send(run_auto_arb_job(job_provider, job).await);
+            });
+
+            // Single wallet: wait for the result, as before. The contract balance
+            // moved, so the next candidate re-reads it.
+            if wallet_pool.len() == 1 {
+                if let Some(done) = done_rx.recv().await {
+                    routes.finish(&done.route);
+                    if let (Some(gate), Ok(post)) = (eta_gate.as_mut(), &done.post_snapshot) {
+                        gate.observe(post.total_execution_ms);
+                    }
+                    finish_auto_arb(done, &mut stats_logger, &mut cumulative_pnl, &pnl_report, &mut execution_count, max_executions, cooldown_secs);
+                }
+                cycle_balances = None;
+            }
+        }
+    }
+
+    // Let executions on other wallets finish before summarizing
+    while !routes.in_flight.is_empty() {
+        match done_rx.recv().await {
+            Some(done) => {
+                routes.finish(&done.route);
+                if let (Some(gate), Ok(post)) = (eta_gate.as_mut(), &done.post_snapshot) {
+                    gate.observe(post.total_execution_ms);
+                }
+                finish_auto_arb(done, &mut stats_logger, &mut cumulative_pnl, &pnl_report, &mut execution_count, max_executions, cooldown_secs);
+            }
+            None => break,
+        }
+    }
+
+    // Final summary
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("  AUTO-ARB SESSION COMPLETE");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Total executions: {}", execution_count);
+    pnl_report.observe_price(price::gas_token_to_quote(&provider).await.ok());
+    println!("  Session P&L:      {}", pnl_report.format(cumulative_pnl));
+    println!("  Stats saved to:   {}", stats_file);
+    stats_logger.print_slippage_summary();
+    stats_logger.print_quote_accuracy();
+    rpc_failover::print_endpoint_stats();
+    spread_logger::flush_spread_csv();
+    feature_recorder::finish();
+    if let Some(ref account) = paper_account {
+        account.print_summary();
+    }
+    stats_logger.log_session_summary(paper_account.as_ref().map(PaperAccount::summary));
+
+    let (final_wmon, final_usdc) = query_contract_balances(&provider).await?;
+    println!("\n  Final Contract Balances:");
+    println!("    WMON: {:>18.6} (Delta {:>+.6})", final_wmon, final_wmon - initial_wmon);
+    println!("    USDC: {:>18.6} (Delta {:>+.6})", final_usdc, final_usdc - initial_usdc);
+    if sweep_count > 0 {
+        println!("    Swept to cold: {:.6} WMON in {} sweep(s) (Delta incl. sweeps {:>+.6})",
+            swept_wmon, sweep_count, final_wmon + swept_wmon - initial_wmon);
+    }
+    if paper_account.is_none() {
+        reconcile_session(&provider, start_block, cumulative_pnl).await;
+    }
+    println!("═══════════════════════════════════════════════════════════════");
+
+    Ok(())
+}
+
+/// (buy_pool, sell_pool)
+type Route = (String, String);
+
+/// Per-route execution state for AutoArb
+#[derive(Default)]
+struct RouteState {
+    /// Routes executing now, with the WMON each has committed
+    in_flight: std::collections::HashMap<Route, f64>,
+    /// Last execution per route; `--cooldown-secs` applies to each route separately
+    last_execution: std::collections::HashMap<Route, std::time::Instant>,
+}
+
+impl RouteState {
+    fn is_ready(&self, route: &Route, cooldown: Duration) -> bool {
+        !self.in_flight.contains_key(route)
+            && self.last_execution.get(route).map(|t| t.elapsed() >= cooldown).unwrap_or(true)
+    }
+
+    fn start(&mut self, route: Route, amount: f64) {
+        self.in_flight.insert(route, amount);
+    }
+
+    /// Route done (or dry-run logged): release its WMON and start its cooldown
+    fn finish(&mut self, route: &Route) {
+        self.in_flight.remove(route);
+        self.last_execution.insert(route.clone(), std::time::Instant::now());
+    }
+
+    fn committed_wmon(&self) -> f64 {
+        self.in_flight.values().sum()
+    }
+}
+
+/// One AutoArb execution, run in its own task on a leased wallet
+struct AutoArbJob<S> {
+    signer_provider: S,
+    lease: WalletLease,
+    pair: TokenPair,
+    sell_router: RouterConfig,
+    buy_router: RouterConfig,
+    amount: f64,
+    spread: display::SpreadOpportunity,
+    slippage: u32,
+    gas: gas::GasFees,
+    net_spread_bps: i32,
+    force: bool,
+    use_atomic: bool,
+    contract_wmon_before: f64,
+    pre_snapshot: PreExecutionSnapshot,
+    route: Route,
+    retry_slippage_bump: u32,
+    max_adaptive_slippage: Option<u32>,
+    /// Latency budget for fast arb: swap 2 is skipped past it
+    deadline: Option<std::time::Instant>,
+    /// `--record-features` poll the spread was seen in
+    feature_poll: Option<u64>,
+}
+
+/// Result handed back to the AutoArb loop for logging
+struct AutoArbDone {
+    pre_snapshot: PreExecutionSnapshot,
+    /// Err when the post-execution balances could not be read
+    post_snapshot: Result<PostExecutionSnapshot>,
+    arb_result: error::ArbResult<execution::FastArbResult>,
+    sell_pool: String,
+    buy_pool: String,
+    block_number: Option<u64>,
+    /// Zero when the post-execution balances are unknown
+    wmon_delta: f64,
+    route: Route,
+    feature_poll: Option<u64>,
+}
+
+/// Balances funding an arb: the atomic contract's, or the executing wallet's for fast arb
+async fn arb_balances<P: Provider>(provider: &P, wallet: alloy::primitives::Address, use_atomic: bool) -> Result<(f64, f64)> {
+    if use_atomic {
+        query_contract_balances(provider).await
+    } else {
+        let balances = get_balances(provider, wallet).await?;
+        Ok((balances.wmon_human, balances.usdc_human))
+    }
+}
+
+/// Execute one arb and snapshot balances afterwards. The wallet lease is
+/// released when this returns.
+async fn run_auto_arb_job<P: Provider, S: Provider + Clone + Send + Sync + 'static>(
+    provider: P,
+    job: AutoArbJob<S>,
+) -> AutoArbDone {
+    use chrono::Local;
+
+    let AutoArbJob {
+        signer_provider,
+        lease,
+        pair,
+        sell_router,
+        buy_router,
+        amount,
+        spread,
+        slippage,
+        gas,
+        net_spread_bps,
+        force,
+        use_atomic,
+        contract_wmon_before,
+        pre_snapshot,
+        route,
+        retry_slippage_bump,
+        max_adaptive_slippage,
+        deadline,
+        feature_poll,
+    } = job;
+    let signer_address = lease.address;
+    let contract_usdc_before = pre_snapshot.usdc_balance;
+
+    let exec_start = std::time::Instant::now();
+
+    // Use atomic arb if contract is deployed, otherwise fall back to fast_arb
+    let arb_result: error::ArbResult<execution::FastArbResult> = if use_atomic {
+        println!("  Using ATOMIC TURBO execution (single TX)...");
+        let trade = AtomicArbTrade {
+            sell_router: &sell_router,
+            buy_router: &buy_router,
+            amount,
+            sell_price: spread.sell_price,
+            buy_price: spread.buy_price,
+            slippage_bps: slippage,
+            min_profit_bps: 0, // any profit
+            spread_bps: net_spread_bps, // for gas strategy
+        };
+        // Balances are snapshotted right after, so wait for the receipt
+        match execute_atomic_arb(
+            &signer_provider,
+            signer_address,
+            trade,
+            gas,
+            AtomicExecOptions::turbo(force, execution::ReceiptMode::Wait),
+        ).await {
+            Ok(result) => {
+                print_atomic_arb_result(&result);
+                // Convert AtomicArbResult to FastArbResult for stats compatibility
+                let profit = result.profit_wmon();
+                Ok(execution::FastArbResult {
+                    success: result.success,
+                    swap1_success: result.success,
+                    swap1_tx_hash: result.tx_hash.clone(),
+                    swap1_gas_used: result.gas_used,
+                    swap1_gas_estimated: result.gas_limit,
+                    swap2_success: result.success,
+                    swap2_retry_tx_hash: None,
+                    stranded_usdc: 0.0,
+                    swap2_cancelled: None,
+                    swap2_tx_hash: String::new(), // Atomic has single TX
+                    swap2_gas_used: 0,
+                    swap2_gas_estimated: 0,
+                    wmon_in: result.wmon_in,
+                    usdc_intermediate: 0.0,
+                    wmon_out: result.wmon_in + profit,
+                    usdc_before: 0.0,
+                    usdc_after_swap1: 0.0,
+                    wmon_before: result.wmon_in,
+                    wmon_after_swap2: result.wmon_in + profit,
+                    actual_usdc_received: 0.0,
+                    actual_wmon_received: profit,
+                    swap1_slippage_bps: 0,
+                    swap2_slippage_bps: 0,
+                    wmon_out_actual: Some(result.wmon_in + profit),
+                    estimation_error_bps: None,
+                    swap2_usdc_in: None,
+                    quoted_usdc_intermediate: None,
+                    quoted_wmon_out: None,
+                    gross_profit_wmon: profit,
+                    profit_bps: result.profit_bps,
+                    total_gas_cost_wei: alloy::primitives::U256::ZERO,
+                    total_gas_cost_mon: result.gas_cost_mon,
+                    total_gas_used: result.gas_used,
+                    total_gas_estimated: result.gas_limit,
+                    total_time_ms: result.execution_time_ms,
+                    swap1_time_ms: result.execution_time_ms,
+                    swap2_time_ms: 0,
+                    execution_time_ms: result.execution_time_ms,
+                    error: result.error,
+                })
+            }
+            Err(e) => Err(e)
+        }
+    } else {
+        println!("  Using FAST execution (2 TXs) - deploy atomic contract for better results!");
+        // With QuoterV2 on both legs, quote alongside the arb for quote-vs-fill
+        // tracking. The quote's eth_call lands before swap 1 can be mined.
+        let quotable = QuoteFetcher::quoter_for(&sell_router).is_ok() && QuoteFetcher::quoter_for(&buy_router).is_ok();
+        let quote = async {
+            if quotable {
+                QuoteFetcher::AtomicQuote.quote_arb(&provider, &pair, &sell_router, &buy_router, amount).await.ok()
+            } else {
+                None
+            }
+        };
+        let (arb, quote) = tokio::join!(
+            execute_fast_arb(
+                &signer_provider,
+                signer_address,
+                FastArbTrade {
+                    pair: &pair,
+                    sell_router: &sell_router,
+                    buy_router: &buy_router,
+                    amount,
+                    sell_price: spread.sell_price,
+                    buy_price: spread.buy_price,
+                },
+                FastArbOptions {
+                    slippage_bps: slippage,
+                    gas,
+                    simulate: false, // no pre-simulation in the hot loop
+                    retry_slippage_bump_bps: retry_slippage_bump,
+                    max_adaptive_slippage_bps: max_adaptive_slippage,
+                    deadline,
+                },
+            ),
+            quote
+        );
+        arb.map(|mut result| {
+            if let Some(quote) = &quote {
+                result.attach_quote(quote);
+            }
+            result
+        })
+    };
+
+    let exec_time = exec_start.elapsed().as_millis();
+
+    // Create post-execution snapshot (using contract balances). A failed
+    // balance read travels back with the result instead of ending the loop.
+    let post_balances = arb_balances(&provider, signer_address, use_atomic).await;
+    let post_snapshot = post_balances.map(|(contract_wmon_after, contract_usdc_after)| match &arb_result {
+        Ok(result) => {
+            let wmon_delta = contract_wmon_after - contract_wmon_before;
+            let usdc_delta = contract_usdc_after - contract_usdc_before;
+            let mon_delta = 0.0; // Contract doesn't hold native MON
+            let net_profit_bps = if amount > 0.0 {
+                (wmon_delta / amount * 10000.0) as i32
+            } else {
+                0
+            };
+
+            PostExecutionSnapshot {
+                timestamp: Local::now().to_rfc3339(),
+                wmon_balance: contract_wmon_after,
+                usdc_balance: contract_usdc_after,
+                mon_balance: 0.0, // Contract doesn't hold native MON
+                swap1_success: result.swap1_success,
+                swap1_tx_hash: result.swap1_tx_hash.clone(),
+                swap1_gas_used: result.swap1_gas_used,
+                swap1_gas_estimated: result.swap1_gas_estimated,
+                swap2_success: result.swap2_success,
+                swap2_tx_hash: result.swap2_tx_hash.clone(),
+                swap2_gas_used: result.swap2_gas_used,
+                swap2_gas_estimated: result.swap2_gas_estimated,
+                actual_usdc_received: result.usdc_intermediate,
+                actual_wmon_back: result.wmon_out,
+                wmon_delta,
+                usdc_delta,
+                mon_delta,
+                total_gas_cost_mon: result.total_gas_cost_mon,
+                net_profit_wmon: wmon_delta,
+                net_profit_bps,
+                total_execution_ms: exec_time,
+                simulated: false,
+            }
+        }
+        Err(_e) => {
+            // Failed execution - still record balances
+            PostExecutionSnapshot {
+                timestamp: Local::now().to_rfc3339(),
+                wmon_balance: contract_wmon_after,
+                usdc_balance: contract_usdc_after,
+                mon_balance: 0.0, // Contract doesn't hold native MON
+                swap1_success: false,
+                swap1_tx_hash: String::new(),
+                swap1_gas_used: 0,
+                swap1_gas_estimated: 0,
+                swap2_success: false,
+                swap2_tx_hash: String::new(),
+                swap2_gas_used: 0,
+                swap2_gas_estimated: 0,
+                actual_usdc_received: 0.0,
+                actual_wmon_back: 0.0,
+                wmon_delta: contract_wmon_after - contract_wmon_before,
+                usdc_delta: contract_usdc_after - contract_usdc_before,
+                mon_delta: 0.0, // Contract doesn't hold native MON
+                total_gas_cost_mon: 0.0,
+                net_profit_wmon: 0.0,
+                net_profit_bps: 0,
+                total_execution_ms: exec_time,
+                simulated: false,
+            }
+        }
+    });
+
+    AutoArbDone {
+        pre_snapshot,
+        wmon_delta: post_snapshot.as_ref().map(|post| post.wmon_delta).unwrap_or(0.0),
+        post_snapshot,
+        arb_result,
+        sell_pool: spread.sell_pool,
+        buy_pool: spread.buy_pool,
+        block_number: spread.block_number,
+        route,
+        feature_poll,
+    }
+}
+
+/// Session-end audit: the loop's cumulative P&L against the contract's Transfer logs
+async fn reconcile_session<P: Provider>(provider: &P, start_block: u64, logged_pnl: f64) {
+    let result = match provider.get_block_number().await {
+        Ok(to_block) => stats::reconcile_from_chain(provider, atomic_arb_contract(), start_block, to_block).await,
+        Err(e) => Err(e.into()),
+    };
+    match result {
+        Ok(recon) => recon.print(logged_pnl),
+        Err(e) => tracing::warn!("On-chain P&L reconciliation failed: {}", e),
+    }
+}
 
-                println!("\n  Executions: {} / {}",
-                    execution_count,
-                    if max_executions == 0 { "unlimited".to_string() } else { max_executions.to_string() }
-                );
-                println!("  Cooldown: {} seconds before next execution...\n", cooldown_secs);
-            }
+/// Log a finished AutoArb execution and update session totals
+fn finish_auto_arb(
+    done: AutoArbDone,
+    stats_logger: &mut StatsLogger,
+    cumulative_pnl: &mut f64,
+    pnl_report: &PnlReport,
+    execution_count: &mut u32,
+    max_executions: u32,
+    cooldown_secs: u64,
+) {
+    let AutoArbDone { pre_snapshot, post_snapshot, arb_result, sell_pool, buy_pool, block_number, wmon_delta, feature_poll, .. } = done;
+
+    let post_snapshot = match post_snapshot {
+        Ok(post) => {
+            print_post_execution(&pre_snapshot, &post);
+            Some(post)
+        }
+        Err(e) => {
+            println!("  Post-execution balances unavailable ({}); P&L not counted", e);
+            None
         }
+    };
+
+    // Log execution record
+    let record = ArbExecutionRecord {
+        id: stats_logger.next_id(),
+        pre: pre_snapshot,
+        post: post_snapshot,
+        success: arb_result.as_ref().map(|r| r.success).unwrap_or(false),
+        error: arb_result.as_ref().err().map(|e| e.to_string()),
+        block_number,
+    };
+    stats_logger.log_execution(&record);
+    if let Ok(result) = &arb_result {
+        stats_logger.record_slippage(result);
+        stats_logger.record_quote_accuracy(result, &sell_pool, &buy_pool);
     }
 
-    // Final summary
-    println!("\n═══════════════════════════════════════════════════════════════");
-    println!("  AUTO-ARB SESSION COMPLETE");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!("  Total executions: {}", execution_count);
-    println!("  Stats saved to:   {}", stats_file);
+    *cumulative_pnl += wmon_delta;
+    feature_recorder::record_outcome(feature_poll, &buy_pool, &sell_pool,
+        matches!(&arb_result, Ok(r) if r.success), false, wmon_delta);
+    metrics::record_execution(matches!(&arb_result, Ok(r) if r.success) && wmon_delta > 0.0);
+    metrics::set_cumulative_pnl(*cumulative_pnl);
+
+    // Print result summary
+    if let Ok(result) = &arb_result {
+        print_fast_arb_result(result, &sell_pool, &buy_pool);
+    } else if let Err(e) = &arb_result {
+        info!(target: logging::EVENT_TARGET, pair = logging::pair(), buy_pool = %buy_pool, sell_pool = %sell_pool,
+            outcome = "failed", error = %e, "\n  ARB EXECUTION FAILED: {}", e);
+    }
 
-    let (final_wmon, final_usdc) = query_contract_balances(&provider).await?;
-    println!("\n  Final Contract Balances:");
-    println!("    WMON: {:>18.6} (Delta {:>+.6})", final_wmon, final_wmon - initial_wmon);
-    println!("    USDC: {:>18.6} (Delta {:>+.6})", final_usdc, final_usdc - initial_usdc);
-    println!("═══════════════════════════════════════════════════════════════");
+    *execution_count += 1;
 
-    Ok(())
+    info!(target: logging::EVENT_TARGET, pair = logging::pair(), executions = *execution_count,
+        pnl_wmon = wmon_delta, cumulative_pnl_wmon = *cumulative_pnl,
+        "\n  Executions: {} / {}",
+        execution_count,
+        if max_executions == 0 { "unlimited".to_string() } else { max_executions.to_string() }
+    );
+    println!("  P&L: {} | Session: {}", pnl_report.format(wmon_delta), pnl_report.format(*cumulative_pnl));
+    println!("  Cooldown: {} seconds before {}→{} executes again...\n", cooldown_secs, buy_pool, sell_pool);
 }
 
-/// Production arbitrage bot with safety checks
-async fn run_prod_arb(
+/// ProdArb settings, built from the `prod-arb` flags
+struct ProdArbOptions {
     min_spread_bps: i32,
     amount: f64,
     slippage: u32,
     max_daily_loss: f64,
     max_failures: u32,
-) -> Result<()> {
+    max_impact_bps: u32,
+    metrics_port: Option<u16>,
+    alerts: bool,
+    gas_strategy: GasStrategy,
+    auto_approve: bool,
+    max_gas_gwei: f64,
+    gas_pause_after: u32,
+    max_drawdown_wmon: Option<f64>,
+    drawdown_pause_secs: u64,
+    max_exposure_wmon: Option<f64>,
+    max_adaptive_slippage: Option<u32>,
+    warmup_secs: u64,
+    pnl_denom: PnlDenom,
+}
+
+/// Production arbitrage bot with safety checks
+async fn run_prod_arb(pair: &TokenPair, opts: ProdArbOptions) -> Result<()> {
     use chrono::Local;
 
+    let ProdArbOptions {
+        min_spread_bps,
+        amount,
+        slippage,
+        max_daily_loss,
+        max_failures,
+        max_impact_bps,
+        metrics_port,
+        alerts,
+        gas_strategy,
+        auto_approve,
+        max_gas_gwei,
+        gas_pause_after,
+        max_drawdown_wmon,
+        drawdown_pause_secs,
+        max_exposure_wmon,
+        max_adaptive_slippage,
+        warmup_secs,
+        pnl_denom,
+    } = opts;
+
     // Safety check: enforce positive spread for production
     if min_spread_bps <= 0 {
         return Err(eyre::eyre!(
@@ -2300,12 +4441,19 @@ async fn run_prod_arb(
             min_spread_bps
         ));
     }
+    // Safety check: velocity and baseline need history before the first trade
+    if warmup_secs < MIN_PROD_WARMUP_SECS {
+        return Err(eyre::eyre!(
+            "Production mode requires --warmup-secs of at least {}. Got: {}.",
+            MIN_PROD_WARMUP_SECS, warmup_secs
+        ));
+    }
 
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -2317,7 +4465,7 @@ async fn run_prod_arb(
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     // Initialize stats logger
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
@@ -2331,9 +4479,28 @@ async fn run_prod_arb(
     println!("  Min Spread:      {} bps (ENFORCED POSITIVE)", min_spread_bps);
     println!("  Amount per arb:  {} WMON", amount);
     println!("  Slippage:        {} bps", slippage);
+    if let Some(max) = max_adaptive_slippage {
+        println!("  Adaptive slip:   scaled by pool depth, max {} bps", max);
+    }
     println!("  Max daily loss:  {} WMON", max_daily_loss);
+    if let Some(limit) = max_drawdown_wmon {
+        println!("  Max drawdown:    {} WMON from session high (pause {}s)", limit, drawdown_pause_secs);
+    }
+    if let Some(limit) = max_exposure_wmon {
+        println!("  Max exposure:    {} WMON in unsettled arbs", limit);
+    }
     println!("  Max failures:    {}", max_failures);
+    println!("  Warmup:          {} s (observe only)", warmup_secs);
+    println!("  Max gas:         {} gwei{}", max_gas_gwei,
+        if gas_pause_after > 0 { format!(" (pause after {} skips)", gas_pause_after) } else { String::new() });
     println!("  Stats file:      {}", stats_file);
+    if let Some(port) = metrics_port {
+        metrics::start_server(port).await?;
+    }
+    let alerter = alerts::Alerter::from_env(alerts, signer_address, pair);
+    if alerter.is_some() {
+        println!("  Alerts:          enabled (ALERT_WEBHOOK_URL)");
+    }
     println!("═══════════════════════════════════════════════════════════════");
     println!();
 
@@ -2343,14 +4510,44 @@ async fn run_prod_arb(
     println!("    WMON: {:>18.6}", initial_wmon);
     println!("    USDC: {:>18.6}", initial_usdc);
     println!();
+    // Transfers from here on are reconciled against the logged P&L at session end
+    let start_block = provider.get_block_number().await? + 1;
+
+    // A missing approval would otherwise surface as a reverted (and paid-for) swap
+    let pair_tokens: Vec<_> = config::get_tokens()
+        .into_iter()
+        .filter(|t| t.address == pair.base || t.address == pair.quote)
+        .collect();
+    let missing = wallet::audit_allowances(&provider, signer_address, &config::get_routers(), &pair_tokens).await?;
+    if !missing.is_empty() {
+        if auto_approve {
+            println!("\n  Approving {} missing allowance(s)...", missing.len());
+            let gas = gas_strategy.resolve(&provider, Duration::ZERO).await;
+            let approved = wallet::approve_missing(&provider_with_signer, signer_address, &missing, gas).await?;
+            if approved < missing.len() {
+                return Err(eyre::eyre!("Only {}/{} missing approvals succeeded", approved, missing.len()));
+            }
+        } else {
+            println!("\n  ⚠️  {} missing allowance(s); swaps through those routers will revert (--no-auto-approve)", missing.len());
+        }
+    }
+    println!();
 
     let mut execution_count = 0u32;
     let mut successful_arbs = 0u32;
     let mut consecutive_failures = 0u32;
+    let mut consecutive_gas_skips = 0u32;
     let mut cumulative_pnl: f64 = 0.0;
+    let mut pnl_report = PnlReport::new(pnl_denom);
+    let mut drawdown = stats::DrawdownTracker::default();
+    let mut exposure = stats::ExposureTracker::new(max_exposure_wmon);
+    let mut last_net_spread_bps = 0i32;
     let mut poll_interval = tokio::time::interval(Duration::from_millis(POLL_INTERVAL_MS));
     let cooldown_secs: u64 = 10; // Fixed cooldown for production
     let mut last_execution = std::time::Instant::now() - std::time::Duration::from_secs(cooldown_secs);
+    let mut spread_tracker = SpreadTracker::new(PROD_SPREAD_HISTORY);
+    let mut warmup = Warmup::new(Duration::from_secs(warmup_secs));
+    let mut warmup_polls = 0u32;
 
     loop {
         poll_interval.tick().await;
@@ -2358,18 +4555,51 @@ async fn run_prod_arb(
         // Safety check: stop if cumulative loss exceeds threshold
         if cumulative_pnl < -max_daily_loss {
             println!("\n  MAX DAILY LOSS EXCEEDED ({:.6} WMON). Stopping.", cumulative_pnl);
+            if let Some(a) = &alerter {
+                a.max_daily_loss(cumulative_pnl, max_daily_loss).await;
+            }
             break;
         }
 
+        // Drawdown from the session high: pause and let conditions pass, then
+        // measure the next drawdown from here
+        let current_drawdown = drawdown.update(cumulative_pnl);
+        if let Some(limit) = max_drawdown_wmon.filter(|limit| current_drawdown > *limit) {
+            info!(target: logging::EVENT_TARGET, pair = logging::pair(), drawdown_wmon = current_drawdown,
+                high_water_wmon = drawdown.high_water, cumulative_pnl_wmon = cumulative_pnl,
+                "\n  DRAWDOWN {:.6} WMON from session high {:+.6} (limit {} WMON). Pausing for {} seconds...",
+                current_drawdown, drawdown.high_water, limit, drawdown_pause_secs);
+            if let Some(a) = &alerter {
+                a.drawdown_pause(current_drawdown, limit, cumulative_pnl, drawdown_pause_secs);
+            }
+            tokio::time::sleep(Duration::from_secs(drawdown_pause_secs)).await;
+            drawdown.reset(cumulative_pnl);
+            println!("  Resuming after drawdown pause (P&L {:+.6} WMON)", cumulative_pnl);
+            continue;
+        }
+
         // Safety check: pause if too many consecutive failures
         if consecutive_failures >= max_failures {
             println!("\n  {} consecutive failures. Pausing for 60 seconds...", consecutive_failures);
+            if let Some(a) = &alerter {
+                a.failures_pause(consecutive_failures, last_net_spread_bps, 60);
+            }
             tokio::time::sleep(std::time::Duration::from_secs(60)).await;
             consecutive_failures = 0;
+            metrics::set_consecutive_failures(0);
+            continue;
+        }
+
+        // Sustained gas spike: back off instead of re-checking every poll
+        if gas_pause_after > 0 && consecutive_gas_skips >= gas_pause_after {
+            println!("\n  {} consecutive gas-ceiling skips. Pausing for 60 seconds...", consecutive_gas_skips);
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            consecutive_gas_skips = 0;
             continue;
         }
 
         // Fetch current prices
+        let poll_start = std::time::Instant::now();
         let prices = match get_current_prices(&provider).await {
             Ok(p) => p,
             Err(e) => {
@@ -2377,12 +4607,48 @@ async fn run_prod_arb(
                 continue;
             }
         };
+        metrics::set_poll_latency(poll_start.elapsed().as_millis());
+        pnl_report.observe_price(price::gas_token_price(&prices));
 
         // Calculate spreads
-        let spreads = calculate_spreads(&prices);
+        let spreads = calculate_spreads(&prices, None);
 
         // Find best opportunity (first one is best due to sorting)
         let best_spread = spreads.first();
+        if let Some(spread) = best_spread {
+            metrics::set_best_net_spread((spread.net_spread_pct * 100.0) as i32);
+            spread_tracker.record(
+                &spread.buy_pool,
+                &spread.sell_pool,
+                spread.buy_price,
+                spread.sell_price,
+                (spread.gross_spread_pct * 100.0) as i32,
+                (spread.net_spread_pct * 100.0) as i32,
+            );
+        }
+
+        // Warmup: observe only, so the first spread read (possibly a multicall
+        // artifact) never trades and velocity has a baseline
+        if !warmup.is_done() {
+            warmup_polls += 1;
+            let now = std::time::Instant::now();
+            if !warmup.complete(now) {
+                print!("\r[WARMUP {:>3}s left] {} polls | Best net: {}    ",
+                    warmup.remaining(now).as_secs(), warmup_polls,
+                    best_spread.map(|s| format!("{:+.2}%", s.net_spread_pct)).unwrap_or_else(|| "-".to_string()));
+                std::io::Write::flush(&mut std::io::stdout()).ok();
+                continue;
+            }
+            println!("\n═══════════════════════════════════════════════════════════════");
+            println!("  WARMUP COMPLETE - TRADING ENABLED");
+            println!("═══════════════════════════════════════════════════════════════");
+            println!("  Observed:        {} polls over {} s", warmup_polls, warmup_secs);
+            if let Some(analysis) = spread_tracker.analyze() {
+                println!("  Net spread:      {} to {} bps (velocity {:+.1} bps/s)",
+                    analysis.min_spread_in_window, analysis.max_spread_in_window, analysis.velocity_bps_per_sec);
+            }
+            println!("═══════════════════════════════════════════════════════════════\n");
+        }
 
         if let Some(spread) = best_spread {
             // Display current best opportunity
@@ -2405,6 +4671,11 @@ async fn run_prod_arb(
                 println!();
                 println!("\n  PROFITABLE OPPORTUNITY! Net spread: {} bps (threshold: {} bps)",
                     net_spread_bps, min_spread_bps);
+                if let Some(analysis) = spread_tracker.analyze() {
+                    println!("  Velocity: {:+.1} bps/s | range {} to {} bps over {} ms",
+                        analysis.velocity_bps_per_sec, analysis.min_spread_in_window,
+                        analysis.max_spread_in_window, analysis.window_duration_ms);
+                }
 
                 // Get routers for the opportunity
                 let sell_router = match get_router_by_name(&spread.sell_pool) {
@@ -2422,8 +4693,16 @@ async fn run_prod_arb(
                     }
                 };
 
-                // Get current contract balances (pre-execution)
-                let (contract_wmon_before, contract_usdc_before) = query_contract_balances(&provider).await?;
+                // Clamp size to what both pools can absorb without excess price impact
+                let amount = clamp_amount_to_liquidity(
+                    &provider, pair, &spread.sell_pool, &spread.buy_pool, amount, max_impact_bps,
+                ).await;
+
+                // Get current contract balances (pre-execution), and the wallet USDC an errored arb is measured against
+                let ((contract_wmon_before, contract_usdc_before), wallet_usdc_before) = tokio::try_join!(
+                    query_contract_balances(&provider),
+                    wallet_quote_balance(&provider, pair, signer_address),
+                )?;
 
                 // Check if contract has enough WMON
                 if contract_wmon_before < amount {
@@ -2457,12 +4736,36 @@ async fn run_prod_arb(
                     velocity_bps_per_sec: None,
                     acceleration: None,
                     is_spike_pattern: None,
+                    profit_breakdown: None,
                 };
 
                 print_pre_execution(&pre_snapshot);
 
-                // Fetch gas price
-                let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
+                let gas = gas_strategy.resolve(&provider, Duration::from_millis(POLL_INTERVAL_MS)).await;
+                if gas::gas_too_high(gas.max_fee, max_gas_gwei) {
+                    consecutive_gas_skips += 1;
+                    continue;
+                }
+                consecutive_gas_skips = 0;
+
+                // Stranded legs whose USDC has left the wallet (a rebalance, or a late swap 2) no longer count
+                if exposure.has_stranded() {
+                    match wallet_quote_balance(&provider, pair, signer_address).await {
+                        Ok(usdc) => {
+                            let released = exposure.release_outflow(usdc);
+                            if released > 0.0 {
+                                println!("  Exposure: stranded USDC swapped out, released {:.6} WMON ({:.6} unsettled)",
+                                    released, exposure.in_flight);
+                            }
+                        }
+                        Err(e) => tracing::warn!("Could not re-read wallet USDC for stranded exposure: {}", e),
+                    }
+                }
+                if !exposure.try_open(amount) {
+                    println!("  Exposure cap: {:.6} WMON unsettled + {:.6} would exceed --max-exposure-wmon {}. Skipping.",
+                        exposure.in_flight, amount, max_exposure_wmon.unwrap_or_default());
+                    continue;
+                }
 
                 // Execute fast arb
                 println!("\n  EXECUTING PRODUCTION ARB...");
@@ -2471,19 +4774,31 @@ async fn run_prod_arb(
                 let arb_result = execute_fast_arb(
                     &provider_with_signer,
                     signer_address,
-                    &sell_router,
-                    &buy_router,
-                    amount,
-                    spread.sell_price,
-                    spread.buy_price,
-                    slippage,
-                    gas_price,
+                    FastArbTrade {
+                        pair,
+                        sell_router: &sell_router,
+                        buy_router: &buy_router,
+                        amount,
+                        sell_price: spread.sell_price,
+                        buy_price: spread.buy_price,
+                    },
+                    FastArbOptions {
+                        slippage_bps: slippage,
+                        gas,
+                        simulate: false, // no pre-simulation in the hot loop
+                        retry_slippage_bump_bps: 0,
+                        max_adaptive_slippage_bps: max_adaptive_slippage,
+                        deadline: None,
+                    },
                 ).await;
 
                 let exec_time = exec_start.elapsed().as_millis();
 
-                // Get post-execution contract balances
-                let (contract_wmon_after, contract_usdc_after) = query_contract_balances(&provider).await?;
+                // Get post-execution contract balances, and the wallet's USDC
+                let ((contract_wmon_after, contract_usdc_after), wallet_usdc_after) = tokio::try_join!(
+                    query_contract_balances(&provider),
+                    wallet_quote_balance(&provider, pair, signer_address),
+                )?;
 
                 let wmon_delta = contract_wmon_after - contract_wmon_before;
 
@@ -2520,6 +4835,7 @@ async fn run_prod_arb(
                             net_profit_wmon: wmon_delta,
                             net_profit_bps,
                             total_execution_ms: exec_time,
+                            simulated: false,
                         }
                     }
                     Err(_e) => {
@@ -2545,12 +4861,29 @@ async fn run_prod_arb(
                             net_profit_wmon: 0.0,
                             net_profit_bps: 0,
                             total_execution_ms: exec_time,
+                            simulated: false,
                         }
                     }
                 };
 
                 print_post_execution(&pre_snapshot, &post_snapshot);
 
+                // A stranded swap 2 leaves the notional out until its USDC is swapped
+                // out. An errored arb may have filled swap 1: whatever USDC it added is held.
+                let stranded_usdc = match &arb_result {
+                    Ok(_) if post_snapshot.is_settled() => 0.0,
+                    Ok(result) => result.stranded_usdc,
+                    Err(_) => (wallet_usdc_after - wallet_usdc_before).max(0.0),
+                };
+                exposure.mark_quote(wallet_usdc_after);
+                if stranded_usdc > 1e-6 {
+                    exposure.strand(amount, stranded_usdc);
+                    println!("  ⚠ Arb not settled: {:.6} WMON stays in exposure ({:.6} unsettled) until {:.6} USDC leaves the wallet",
+                        amount, exposure.in_flight, stranded_usdc);
+                } else {
+                    exposure.settle(amount);
+                }
+
                 // Update cumulative P&L
                 cumulative_pnl += wmon_delta;
 
@@ -2561,12 +4894,30 @@ async fn run_prod_arb(
                     post: Some(post_snapshot),
                     success: arb_result.as_ref().map(|r| r.success).unwrap_or(false),
                     error: arb_result.as_ref().err().map(|e| e.to_string()),
+                    block_number: spread.block_number,
                 };
                 stats_logger.log_execution(&record);
+                if let Ok(result) = &arb_result {
+                    stats_logger.record_slippage(result);
+                }
+
+                last_net_spread_bps = net_spread_bps;
+                if let (Some(a), Ok(result)) = (&alerter, &arb_result) {
+                    a.executed(
+                        &spread.sell_pool,
+                        &spread.buy_pool,
+                        net_spread_bps,
+                        wmon_delta,
+                        &[&result.swap1_tx_hash, &result.swap2_tx_hash],
+                    );
+                }
 
                 // Update counters
+                let arb_succeeded = matches!(&arb_result, Ok(r) if r.success) && wmon_delta > 0.0;
+                metrics::record_execution(arb_succeeded);
+                metrics::set_cumulative_pnl(cumulative_pnl);
                 if let Ok(result) = &arb_result {
-                    if result.success && wmon_delta > 0.0 {
+                    if arb_succeeded {
                         successful_arbs += 1;
                         consecutive_failures = 0;
                         print_fast_arb_result(result, &spread.sell_pool, &spread.buy_pool);
@@ -2590,7 +4941,7 @@ async fn run_prod_arb(
                 println!("\n  PRODUCTION STATS:");
                 println!("    Executions:    {}", execution_count);
                 println!("    Successful:    {} ({:.1}% win rate)", successful_arbs, win_rate);
-                println!("    Cumulative P&L: {:+.6} WMON", cumulative_pnl);
+                println!("    Cumulative P&L: {}", pnl_report.format(cumulative_pnl));
                 println!("    Failures:      {} consecutive", consecutive_failures);
                 println!("  Cooldown: {} seconds...\n", cooldown_secs);
             }
@@ -2606,19 +4957,35 @@ async fn run_prod_arb(
     println!("  Win rate:          {:.1}%", if execution_count > 0 {
         (successful_arbs as f64 / execution_count as f64) * 100.0
     } else { 0.0 });
-    println!("  Cumulative P&L:    {:+.6} WMON", cumulative_pnl);
+    pnl_report.observe_price(price::gas_token_to_quote(&provider).await.ok());
+    println!("  Cumulative P&L:    {}", pnl_report.format(cumulative_pnl));
+    println!("  Peak exposure:     {:.6} WMON{}", exposure.peak,
+        max_exposure_wmon.map(|limit| format!(" (cap {})", limit)).unwrap_or_default());
+    if exposure.in_flight > 0.0 {
+        println!("  Unsettled:         {:.6} WMON in stranded legs", exposure.in_flight);
+    }
     println!("  Stats saved to:    {}", stats_file);
+    stats_logger.print_slippage_summary();
+    rpc_failover::print_endpoint_stats();
+    stats_logger.log_session_summary(None);
 
     let (final_wmon, final_usdc) = query_contract_balances(&provider).await?;
     println!("\n  Final Contract Balances:");
     println!("    WMON: {:>18.6} (Delta {:>+.6})", final_wmon, final_wmon - initial_wmon);
     println!("    USDC: {:>18.6} (Delta {:>+.6})", final_usdc, final_usdc - initial_usdc);
+    reconcile_session(&provider, start_block, cumulative_pnl).await;
     println!("═══════════════════════════════════════════════════════════════");
 
     Ok(())
 }
 
-async fn run_fund_contract(amount: f64) -> Result<()> {
+/// Transfer `amount` WMON from the signer to the atomic arb contract
+async fn fund_contract<P: Provider, S: Provider>(
+    provider: &P,
+    provider_with_signer: &S,
+    signer_address: alloy::primitives::Address,
+    amount: f64,
+) -> Result<alloy::rpc::types::TransactionReceipt> {
     use alloy::sol;
     use alloy::sol_types::SolCall;
     use alloy::network::TransactionBuilder;
@@ -2627,25 +4994,10 @@ async fn run_fund_contract(amount: f64) -> Result<()> {
         function transfer(address to, uint256 amount) external returns (bool);
     }
 
-    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
-    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
-
-    let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
-
-    let signer = PrivateKeySigner::from_str(&private_key)?;
-    let signer_address = signer.address();
-    init_nonce(&provider, signer_address).await?;
-
-    let wallet = EthereumWallet::from(signer);
-    let provider_with_signer = ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_http(url);
-
     let amount_wei = to_wei(amount, WMON_DECIMALS);
 
     let transfer_call = transferCall {
-        to: ATOMIC_ARB_CONTRACT,
+        to: atomic_arb_contract(),
         amount: amount_wei,
     };
 
@@ -2663,10 +5015,53 @@ async fn run_fund_contract(amount: f64) -> Result<()> {
         .max_priority_fee_per_gas(gas_price / 10)
         .with_chain_id(143);
 
+    let pending = provider_with_signer.send_transaction(tx).await?;
+    Ok(pending.get_receipt().await?)
+}
+
+/// Wrap MON on the primary wallet and, for atomic execution, move the WMON
+/// into the contract. Returns the WMON added to arb inventory.
+async fn top_up_wmon<P: Provider, S: Provider>(
+    provider: &P,
+    provider_with_signer: &S,
+    signer: &PrivateKeySigner,
+    rpc_url: &str,
+    amount: f64,
+    use_atomic: bool,
+) -> Result<f64> {
+    let wrap = wrap_mon(provider, signer, amount, rpc_url).await?;
+    if !wrap.success {
+        return Err(eyre::eyre!("wrap reverted (tx: {})", wrap.tx_hash));
+    }
+    if use_atomic {
+        let receipt = fund_contract(provider, provider_with_signer, signer.address(), wrap.amount_out).await?;
+        if !receipt.status() {
+            return Err(eyre::eyre!("contract transfer reverted (tx: {:?}); {:.6} WMON left in wallet",
+                receipt.transaction_hash, wrap.amount_out));
+        }
+    }
+    Ok(wrap.amount_out)
+}
+
+async fn run_fund_contract(amount: f64) -> Result<()> {
+    let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
+    let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
+
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+
+    let signer = PrivateKeySigner::from_str(&private_key)?;
+    let signer_address = signer.address();
+    init_nonce(&provider, signer_address).await?;
+
+    let wallet = EthereumWallet::from(signer);
+    let provider_with_signer = ProviderBuilder::new()
+        .wallet(wallet)
+        .connect_client(rpc_failover::client(url));
+
     println!("Funding contract with {} WMON...", amount);
 
-    let pending = provider_with_signer.send_transaction(tx).await?;
-    let receipt = pending.get_receipt().await?;
+    let receipt = fund_contract(&provider, &provider_with_signer, signer_address, amount).await?;
 
     if receipt.status() {
         println!("  Funded contract with {} WMON", amount);
@@ -2692,7 +5087,7 @@ async fn run_withdraw_contract(amount: f64) -> Result<()> {
     let private_key = std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY must be set");
 
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url.clone());
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
 
     let signer = PrivateKeySigner::from_str(&private_key)?;
     let signer_address = signer.address();
@@ -2701,7 +5096,7 @@ async fn run_withdraw_contract(amount: f64) -> Result<()> {
     let wallet = EthereumWallet::from(signer);
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     let gas_price = provider.get_gas_price().await.unwrap_or(100_000_000_000);
 
@@ -2715,7 +5110,7 @@ async fn run_withdraw_contract(amount: f64) -> Result<()> {
     };
 
     let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(ATOMIC_ARB_CONTRACT)
+        .to(atomic_arb_contract())
         .from(signer_address)
         .input(alloy::rpc::types::TransactionInput::new(
             alloy::primitives::Bytes::from(calldata)
@@ -2742,14 +5137,14 @@ async fn run_withdraw_contract(amount: f64) -> Result<()> {
 async fn run_contract_balance() -> Result<()> {
     let rpc_url = std::env::var("MONAD_RPC_URL").expect("MONAD_RPC_URL must be set");
     let url: reqwest::Url = rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
     let (wmon, usdc) = query_contract_balances(&provider).await?;
 
     println!("\n==============================================================");
     println!("  ATOMIC ARB CONTRACT BALANCES");
     println!("==============================================================");
-    println!("  Contract: {:?}", ATOMIC_ARB_CONTRACT);
+    println!("  Contract: {:?}", atomic_arb_contract());
     println!("  WMON: {:>18.6}", wmon);
     println!("  USDC: {:>18.6}", usdc);
     println!("==============================================================");
@@ -2773,7 +5168,7 @@ async fn run_mev_ultra(
 ) -> Result<()> {
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
-    use tokio_tungstenite::{connect_async, tungstenite::Message};
+    use tokio_tungstenite::tungstenite::Message;
     use futures_util::{SinkExt, StreamExt};
 
     // Normalize trigger state
@@ -2817,8 +5212,8 @@ async fn run_mev_ultra(
 
     // Pre-build wallet and providers
     let wallet = EthereumWallet::from(signer);
-    let provider = ProviderBuilder::new().connect_http(url.clone());
-    let provider_with_signer = ProviderBuilder::new().wallet(wallet).connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url.clone()));
+    let provider_with_signer = ProviderBuilder::new().wallet(wallet).connect_client(rpc_failover::client(url));
 
     // Initialize nonce once
     init_nonce(&provider, signer_address).await?;
@@ -2826,13 +5221,14 @@ async fn run_mev_ultra(
     // Build price calls
     let mut price_calls: Vec<PriceCall> = Vec::new();
     for pool in get_v3_pools() {
-        price_calls.push(create_slot0_call(&pool));
+        price_calls.extend(create_v3_price_calls(&pool));
+    }
+    for lfj_pool in get_lfj_pools() {
+        price_calls.push(create_lfj_active_id_call(&lfj_pool));
+        price_calls.push(create_lfj_bin_step_call(&lfj_pool));
     }
-    let lfj_pool = get_lfj_pool();
-    price_calls.push(create_lfj_active_id_call(&lfj_pool));
-    price_calls.push(create_lfj_bin_step_call(&lfj_pool));
     let monday_pool = get_monday_trade_pool();
-    price_calls.push(create_slot0_call(&monday_pool));
+    price_calls.extend(create_v3_price_calls(&monday_pool));
 
     // Ctrl+C handler
     let running = Arc::new(AtomicBool::new(true));
@@ -2843,7 +5239,7 @@ async fn run_mev_ultra(
 
     // Connect to WebSocket
     println!("Connecting to WebSocket: {}...", ws_url);
-    let (ws_stream, _) = connect_async(&ws_url).await?;
+    let (ws_stream, _) = rpc_failover::connect_ws(&ws_url).await?;
     let (mut write, mut read) = ws_stream.split();
 
     // Subscribe to monadNewHeads
@@ -2932,13 +5328,13 @@ async fn run_mev_ultra(
         // Fetch prices immediately on Proposed
         let fetch_start = std::time::Instant::now();
         let prices = match fetch_prices_batched(&provider, price_calls.clone()).await {
-            Ok((p, _)) => p,
+            Ok((p, _, _)) => p,
             Err(_) => continue,
         };
         let fetch_time = fetch_start.elapsed();
 
         // Calculate spreads
-        let spreads = calculate_spreads(&prices);
+        let spreads = calculate_spreads(&prices, None);
         if spreads.is_empty() {
             continue;
         }
@@ -2987,19 +5383,22 @@ async fn run_mev_ultra(
 
         // Execute using TURBO execute_atomic_arb
         let exec_start = std::time::Instant::now();
-        let result = execution::execute_atomic_arb(
-            &provider_with_signer,
-            signer_address,
-            &sell_router,
-            &buy_router,
+        let trade = AtomicArbTrade {
+            sell_router: &sell_router,
+            buy_router: &buy_router,
             amount,
             sell_price,
             buy_price,
-            slippage,
-            0,  // min_profit_bps
-            gas_price,
+            slippage_bps: slippage,
+            min_profit_bps: 0,
             spread_bps,  // spread for gas strategy
-            true,  // force
+        };
+        let result = execution::execute_atomic_arb(
+            &provider_with_signer,
+            signer_address,
+            trade,
+            GasStrategy::default().fees_from_base(gas_price),
+            AtomicExecOptions::turbo(true, execution::ReceiptMode::Wait),
         ).await;
 
         let exec_time = exec_start.elapsed();
@@ -3030,11 +5429,15 @@ async fn run_mev_ultra(
     Ok(())
 }
 
-async fn run_mev_validate(duration: u64, min_spread_bps: i32, output_mode: &str) -> Result<()> {
+async fn run_mev_validate(duration: u64, min_spread_bps: i32, output_mode: &str, record_features: bool) -> Result<()> {
     let node_config = NodeConfig::from_env();
     node_config.log_config();
+    if record_features {
+        let path = feature_recorder::start().map_err(|e| eyre::eyre!("Failed to open feature log: {}", e))?;
+        println!("Recording spread features to {}", path);
+    }
 
-    match output_mode {
+    let result = match output_mode {
         "dashboard" => {
             mev_validation::run_mev_validation_dashboard(
                 &node_config.rpc_url,
@@ -3068,42 +5471,73 @@ async fn run_mev_validate(duration: u64, min_spread_bps: i32, output_mode: &str)
                 min_spread_bps
             ).await
         }
-    }
+    };
+    feature_recorder::finish();
+    result
+}
+
+/// `Dashboard --on-block`: no Proposed block for this long (~5 blocks) hands
+/// refreshes back to the timer until the stream recovers
+const DASHBOARD_BLOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Single-pair dashboard settings, built from the `dashboard` flags
+struct DashboardOptions {
+    min_spread: i32,
+    history: usize,
+    refresh_ms: u64,
+    sound: bool,
+    min_liquidity: Option<u128>,
+    tui: bool,
+    spread_log: Option<String>,
+    /// Refresh on each Proposed block instead of every `refresh_ms`
+    on_block: bool,
 }
 
 /// Live spread dashboard with detailed visualization
-async fn run_dashboard(min_spread: i32, history: usize, refresh_ms: u64, sound: bool) -> Result<()> {
+async fn run_dashboard(opts: DashboardOptions) -> Result<()> {
     use std::io::{stdout, Write};
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Arc;
 
+    let DashboardOptions { min_spread, history, refresh_ms, sound, min_liquidity, tui, spread_log, on_block } = opts;
+
     let node_config = NodeConfig::from_env();
     node_config.log_config();
 
     let url: reqwest::Url = node_config.rpc_url.parse()?;
-    let provider = ProviderBuilder::new().connect_http(url);
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
-    // Verify node health before starting
+    // Verify node health and pool configuration before starting
     verify_node_ready(&provider).await?;
+    verify_pools_ready(&provider, &current_price_calls()).await?;
 
     // Build price calls
     let mut price_calls: Vec<PriceCall> = Vec::new();
 
     for pool in get_v3_pools() {
-        price_calls.push(create_slot0_call(&pool));
+        price_calls.extend(create_v3_price_calls(&pool));
     }
 
-    let lfj_pool = get_lfj_pool();
-    price_calls.push(create_lfj_active_id_call(&lfj_pool));
-    price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+    for lfj_pool in get_lfj_pools() {
+        price_calls.push(create_lfj_active_id_call(&lfj_pool));
+        price_calls.push(create_lfj_bin_step_call(&lfj_pool));
+    }
 
     let monday_pool = get_monday_trade_pool();
-    price_calls.push(create_slot0_call(&monday_pool));
+    price_calls.extend(create_v3_price_calls(&monday_pool));
 
     // Setup display
     let mut display = spread_display::SpreadDisplay::new(min_spread, history);
     display.alert_sound = sound;
 
+    if tui {
+        return tui::run(&provider, price_calls, &mut display, refresh_ms, min_liquidity).await;
+    }
+
+    if let Some(path) = &spread_log {
+        spread_logger::init_spread_csv(path).map_err(|e| eyre::eyre!("Failed to open {}: {}", path, e))?;
+    }
+
     // Enter alternate screen
     spread_display::enter_alternate_screen();
 
@@ -3114,17 +5548,54 @@ async fn run_dashboard(min_spread: i32, history: usize, refresh_ms: u64, sound:
         r.store(false, Ordering::SeqCst);
     })?;
 
-    let mut poll_interval = interval(Duration::from_millis(refresh_ms));
+    // --refresh-ms is the floor; a slow node stretches it to its multicall latency
+    let mut poll = AdaptivePoll::new(Duration::from_millis(refresh_ms));
+
+    // --on-block: refresh on each Proposed block; the timer polls while no block arrives
+    let mut block_stream = if on_block {
+        Some(stream_prices_on_block(&node_config.rpc_url, &node_config.ws_url, price_calls.clone())?)
+    } else {
+        None
+    };
+    let mut block_synced = on_block;
 
     while running.load(Ordering::SeqCst) {
-        poll_interval.tick().await;
+        let streamed = match block_stream.as_mut() {
+            Some(rx) if block_synced => tokio::time::timeout(DASHBOARD_BLOCK_TIMEOUT, rx.recv()).await.unwrap_or_default(),
+            // Back on the timer: pick the stream up again as soon as a block lands
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        block_synced = streamed.is_some();
+        if block_stream.as_ref().is_some_and(|rx| rx.is_closed()) {
+            block_stream = None;
+        }
 
-        let block_num = provider.get_block_number().await.ok();
+        let fetched = match streamed {
+            Some((header, prices)) => {
+                display.commit_state = header.state();
+                display.poll_interval = None;
+                Ok((prices, 0, Some(header.block_number())))
+            }
+            None => {
+                poll.tick().await;
+                let fetch_start = std::time::Instant::now();
+                let fetched = fetch_prices_batched(&provider, price_calls.clone()).await;
+                poll.record(fetch_start.elapsed());
+                display.commit_state = None;
+                display.poll_interval = Some(poll.interval());
+                fetched
+            }
+        };
 
-        match fetch_prices_batched(&provider, price_calls.clone()).await {
-            Ok((prices, _)) => {
-                let spreads = calculate_spreads(&prices);
+        match fetched {
+            Ok((mut prices, _, block_num)) => {
+                if min_liquidity.is_some() {
+                    attach_liquidity(&provider, &TokenPair::wmon_usdc(), &mut prices).await;
+                }
+                let spreads = calculate_spreads(&prices, min_liquidity);
                 display.update(&spreads);
+                spread_logger::log_spread_csv(block_num, spreads.first());
 
                 // Render dashboard
                 spread_display::cursor_home();
@@ -3149,6 +5620,159 @@ async fn run_dashboard(min_spread: i32, history: usize, refresh_ms: u64, sound:
     }
 
     // Restore terminal
+    spread_display::exit_alternate_screen();
+    spread_logger::flush_spread_csv();
+
+    println!("\nDashboard stopped.");
+
+    Ok(())
+}
+
+/// One `Dashboard --pairs` entry: its price calls (pool names prefixed with
+/// the label so the shared batch can be split back up) and display state
+struct PairSection {
+    label: String,
+    pair: TokenPair,
+    price_calls: Vec<PriceCall>,
+    display: spread_display::SpreadDisplay,
+}
+
+fn pair_section_prefix(label: &str) -> String {
+    format!("{}|", label)
+}
+
+impl PairSection {
+    /// This pair's prices out of the combined batch, names unprefixed and
+    /// rescaled to its quote per base
+    fn take_prices(&self, prices: &[PoolPrice]) -> Vec<PoolPrice> {
+        let prefix = pair_section_prefix(&self.label);
+        prices
+            .iter()
+            .filter_map(|p| {
+                let name = p.pool_name.strip_prefix(&prefix)?;
+                Some(PoolPrice {
+                    pool_name: name.to_string(),
+                    price: price::rescale_for_pair(p.price, &self.pair),
+                    ..p.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+/// Price calls for each `--pairs` entry: the configured pools for WMON/USDC,
+/// discovered V3 fee-tier pools for anything else
+async fn build_pair_sections<P: alloy::providers::Provider>(
+    provider: &P,
+    pairs: &str,
+    min_spread: i32,
+    history: usize,
+) -> Result<Vec<PairSection>> {
+    let mut sections = Vec::new();
+    for label in pairs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let pair = get_token_pair(label)
+            .ok_or_else(|| eyre::eyre!("Unknown pair: {}. Expected <base>-<quote> with known token symbols", label))?;
+
+        let is_wmon_usdc = [pair.base, pair.quote].contains(&WMON_ADDRESS) && [pair.base, pair.quote].contains(&USDC_ADDRESS);
+        let calls = if is_wmon_usdc {
+            current_price_calls()
+        } else {
+            let venues = pools::v3_pool::discover_fee_tiers(provider, &pair, &config::get_fee_tiers(&pair)).await?;
+            venues.iter().flat_map(|(pool, _)| create_v3_price_calls(pool)).collect()
+        };
+        if calls.is_empty() {
+            return Err(eyre::eyre!("No pools found for {}", label));
+        }
+        println!("  {:<12} {} price call(s)", label, calls.len());
+
+        let label = label.to_lowercase();
+        let prefix = pair_section_prefix(&label);
+        sections.push(PairSection {
+            price_calls: calls
+                .into_iter()
+                .map(|call| PriceCall { pool_name: format!("{}{}", prefix, call.pool_name), ..call })
+                .collect(),
+            label,
+            pair,
+            display: spread_display::SpreadDisplay::new(min_spread, history),
+        });
+    }
+    Ok(sections)
+}
+
+/// `Dashboard --pairs`: every pair's pools in one multicall per refresh,
+/// rendered as one block per pair
+async fn run_multi_pair_dashboard(
+    pairs: &str,
+    min_spread: i32,
+    history: usize,
+    refresh_ms: u64,
+    sound: bool,
+    min_liquidity: Option<u128>,
+) -> Result<()> {
+    use std::io::{stdout, Write};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let node_config = NodeConfig::from_env();
+    node_config.log_config();
+
+    let url: reqwest::Url = node_config.rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+
+    verify_node_ready(&provider).await?;
+    verify_pools_ready(&provider, &current_price_calls()).await?;
+
+    let mut sections = build_pair_sections(&provider, pairs, min_spread, history).await?;
+    let price_calls: Vec<PriceCall> = sections.iter().flat_map(|s| s.price_calls.clone()).collect();
+
+    spread_display::enter_alternate_screen();
+
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+    ctrlc::set_handler(move || {
+        r.store(false, Ordering::SeqCst);
+    })?;
+
+    let mut poll_interval = interval(Duration::from_millis(refresh_ms));
+
+    while running.load(Ordering::SeqCst) {
+        poll_interval.tick().await;
+
+        match fetch_prices_batched(&provider, price_calls.clone()).await {
+            Ok((prices, _, block_num)) => {
+                let mut global_best_bps: Option<i32> = None;
+                for section in sections.iter_mut() {
+                    let mut pair_prices = section.take_prices(&prices);
+                    if min_liquidity.is_some() {
+                        attach_liquidity(&provider, &section.pair, &mut pair_prices).await;
+                    }
+                    let spreads = calculate_spreads(&pair_prices, min_liquidity);
+                    section.display.update(&spreads);
+                    if let Some(best) = spreads.first() {
+                        let bps = (best.net_spread_pct * 100.0) as i32;
+                        global_best_bps = Some(global_best_bps.map_or(bps, |b| b.max(bps)));
+                    }
+                }
+
+                let blocks: Vec<(&str, &spread_display::SpreadDisplay)> =
+                    sections.iter().map(|s| (s.label.as_str(), &s.display)).collect();
+                spread_display::cursor_home();
+                print!("{}", spread_display::render_multi_pair_dashboard(&blocks, block_num));
+                stdout().flush().ok();
+
+                // Sound alert on the best spread across all pairs
+                if sound && global_best_bps.is_some_and(|bps| bps >= 15) {
+                    print!("\x07");
+                }
+            }
+            Err(e) => {
+                spread_display::cursor_home();
+                println!("\x1b[1;31mError fetching prices: {}\x1b[0m", e);
+            }
+        }
+    }
+
     spread_display::exit_alternate_screen();
 
     println!("\nDashboard stopped.");
@@ -3160,59 +5784,157 @@ async fn run_dashboard(min_spread: i32, history: usize, refresh_ms: u64, sound:
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
 
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::WARN)
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .finish();
-    tracing::subscriber::set_global_default(subscriber)?;
-
     let cli = Cli::parse();
+    logging::init(cli.log_format.parse()?, &cli.pair)?;
+    node_config::set_receipt_overrides(cli.receipt_poll_ms, cli.receipt_timeout_secs);
+    pools::lfj_pool::set_bin_step_refresh(cli.refresh_binstep_every);
+    multicall::set_multicall_chunk(cli.multicall_chunk);
+    display::set_price_sanity_band(cli.price_sanity_pct);
+    execution::routers::set_price_limit(cli.price_limit);
+    let submit_mode = execution::submit::SubmitMode::from_args(&cli.submit, cli.relay_url.as_deref())?;
+    // Signers are optional in public mode (read-only commands have no key)
+    match WalletPool::from_env() {
+        Ok(wallets) => execution::submit::init_submit_mode(submit_mode, wallets.signers())?,
+        Err(e) if submit_mode != execution::submit::SubmitMode::Public => return Err(e),
+        Err(_) => {}
+    }
+    if let Some(path) = &cli.config {
+        config::set_app_config(config::load_from_file(path)?)?;
+    }
+    let pair = get_token_pair(&cli.pair)
+        .ok_or_else(|| eyre::eyre!("Unknown pair: {}. Expected <base>-<quote> with known token symbols", cli.pair))?;
+    let offline = cli.command.as_ref().is_some_and(Commands::is_offline);
+    let pair = if offline { pair } else { init_pair_metadata(pair).await? };
+    if cli.fee_tiers && !offline {
+        init_fee_tiers(&pair).await?;
+    }
+    if !offline {
+        init_pool_fees().await;
+    }
+    // Learned per-router gas limits from past stats files in the working directory
+    match gas::GasProfile::load(std::path::Path::new(".")) {
+        Ok(profile) => {
+            profile.print_summary();
+            gas::init_gas_profile(profile);
+        }
+        Err(e) => tracing::warn!("Could not load gas profile: {}", e),
+    }
 
     match cli.command {
-        Some(Commands::Monitor) | None => {
-            run_monitor().await
+        Some(Commands::Monitor { stream }) => {
+            run_monitor(stream).await
+        }
+        None => {
+            run_monitor(false).await
         }
         Some(Commands::TestSwap { dex, amount, direction, slippage }) => {
-            run_test_swap(&dex, amount, &direction, slippage).await
+            run_test_swap(&pair, &dex, amount, &direction, slippage).await
+        }
+        Some(Commands::Rebalance { target_wmon_pct, dex, tolerance_bps, slippage }) => {
+            run_rebalance(&pair, target_wmon_pct, &dex, tolerance_bps, slippage).await
         }
         Some(Commands::TestAll { amount, direction, slippage }) => {
-            run_test_all(amount, &direction, slippage).await
+            run_test_all(&pair, amount, &direction, slippage).await
         }
         // Wallet commands
         Some(Commands::Balance) => {
             run_balance().await
         }
+        Some(Commands::CancelTx { nonce, bump_pct, gas_strategy }) => {
+            run_cancel_tx(nonce, bump_pct, gas_strategy.parse()?).await
+        }
         Some(Commands::Wrap { amount }) => {
             run_wrap(amount).await
         }
         Some(Commands::Unwrap { amount }) => {
             run_unwrap(amount).await
         }
-        Some(Commands::BuyMon { amount, dex, slippage, keep_wrapped }) => {
-            run_buy_mon(amount, &dex, slippage, keep_wrapped).await
+        Some(Commands::BuyMon { amount, dex, quote_token, slippage, keep_wrapped, use_native }) => {
+            run_buy_mon(amount, &dex, &quote_token, slippage, keep_wrapped, use_native).await
+        }
+        Some(Commands::SellMon { amount, dex, quote_token, slippage, use_wmon, use_native }) => {
+            run_sell_mon(amount, &dex, &quote_token, slippage, use_wmon, use_native).await
+        }
+        Some(Commands::TestArb { sell_dex, buy_dex, amount, slippage, force, auto_direction }) => {
+            run_test_arb(&pair, &sell_dex, &buy_dex, amount, slippage, force, auto_direction).await
+        }
+        Some(Commands::SimulateArb { sell_dex, buy_dex, amount }) => {
+            run_simulate_arb(&pair, &sell_dex, &buy_dex, amount).await
         }
-        Some(Commands::SellMon { amount, dex, slippage, use_wmon }) => {
-            run_sell_mon(amount, &dex, slippage, use_wmon).await
+        Some(Commands::CompareRouters { amount, direction, routers }) => {
+            run_compare_routers(&pair, amount, &direction, routers.as_deref()).await
         }
-        Some(Commands::TestArb { sell_dex, buy_dex, amount, slippage }) => {
-            run_test_arb(&sell_dex, &buy_dex, amount, slippage).await
+        Some(Commands::BreakEven { amount, gas_strategy }) => {
+            run_break_even(amount, gas_strategy.parse()?).await
         }
         Some(Commands::PrepareArb) => {
             run_prepare_arb().await
         }
-        Some(Commands::FastArb { sell_dex, buy_dex, amount, slippage }) => {
-            run_fast_arb(&sell_dex, &buy_dex, amount, slippage).await
+        Some(Commands::RevokeArb { router, token }) => {
+            run_revoke_arb(router.as_deref(), token.as_deref()).await
         }
-        Some(Commands::AtomicArb { sell_dex, buy_dex, amount, slippage, min_profit_bps, force }) => {
-            run_atomic_arb(&sell_dex, &buy_dex, amount, slippage, min_profit_bps, force).await
+        Some(Commands::FastArb { sell_dex, buy_dex, amount, slippage, adaptive_slippage, max_slippage, simulate, gas_strategy, parallel, optimize_size, optimize_steps, force, auto_direction, max_gas_gwei, exec_rpc }) => {
+            let optimize_steps = optimize_size.then_some(optimize_steps);
+            run_fast_arb(&pair, FastArbRunOptions {
+                sell_dex,
+                buy_dex,
+                amount,
+                slippage,
+                simulate,
+                gas_strategy: gas_strategy.parse()?,
+                parallel,
+                optimize_steps,
+                force,
+                auto_direction,
+                max_gas_gwei,
+                max_adaptive_slippage: adaptive_slippage.then_some(max_slippage),
+                exec_rpc,
+            }).await
+        }
+        Some(Commands::MultiHopArb { route, amount, slippage, gas_strategy }) => {
+            run_multihop_arb(&route, amount, slippage, gas_strategy.parse()?).await
+        }
+        Some(Commands::AtomicArb { sell_dex, buy_dex, amount, slippage, min_profit_bps, force, auto_direction, flashloan, flashloan_pool, flashloan_fee_bps, gas_strategy, max_gas_gwei, turbo, await_receipt }) => {
+            if await_receipt && !turbo {
+                return Err(eyre::eyre!("--await-receipt only applies to --turbo (AtomicArb waits for the receipt otherwise)"));
+            }
+            if turbo {
+                if flashloan {
+                    return Err(eyre::eyre!("--turbo trades from the contract balance; drop --flashloan"));
+                }
+                return run_atomic_arb_turbo(&sell_dex, &buy_dex, amount, slippage, await_receipt).await;
+            }
+            let flashloan = if flashloan {
+                let pool = flashloan_pool
+                    .or_else(|| std::env::var("FLASHLOAN_POOL").ok())
+                    .ok_or_else(|| eyre::eyre!("--flashloan needs --flashloan-pool or FLASHLOAN_POOL"))?;
+                Some((alloy::primitives::Address::from_str(&pool)?, flashloan_fee_bps))
+            } else {
+                None
+            };
+            run_atomic_arb(AtomicArbOptions {
+                sell_dex,
+                buy_dex,
+                amount,
+                slippage,
+                min_profit_bps,
+                force,
+                auto_direction,
+                flashloan,
+                gas_strategy: gas_strategy.parse()?,
+                max_gas_gwei,
+            }).await
         }
         Some(Commands::AutoArb {
             min_spread_bps,
             amount,
             slippage,
+            adaptive_slippage,
+            max_slippage,
             max_executions,
             cooldown_secs,
             dry_run,
+            paper,
             force,
             track_velocity,
             history_size,
@@ -3220,17 +5942,119 @@ async fn main() -> Result<()> {
             max_velocity,
             min_final_spread,
             max_baseline,
+            max_acceleration,
+            max_impact_bps,
+            min_net_profit_wmon,
+            metrics_port,
+            gas_strategy,
+            min_liquidity,
+            min_survival,
+            survival_horizon_ms,
+            watch_mempool,
+            mempool_min_wmon,
+            max_price_age_ms,
+            retry_slippage_bump,
+            auto_wrap_threshold,
+            max_wrap_per_session,
+            max_gas_gwei,
+            auto_sweep_profit,
+            expected_decay_bps,
+            competitive_routes,
+            competitive_priority_multiplier,
+            spread_log,
+            mode,
+            eta_ms,
+            validation_logs,
+            rank_by,
+            pnl_denom,
+            record_features,
         }) => {
-            run_auto_arb(min_spread_bps, amount, slippage, max_executions, cooldown_secs, dry_run, force, track_velocity, history_size, min_velocity, max_velocity, min_final_spread, max_baseline).await
+            let competitive_routes = competitive_routes.as_deref().map(gas::parse_competitive_routes).transpose()?.unwrap_or_default();
+            run_auto_arb(&pair, AutoArbOptions {
+                min_spread_bps,
+                amount,
+                slippage,
+                max_executions,
+                cooldown_secs,
+                dry_run,
+                paper,
+                force,
+                track_velocity,
+                history_size,
+                min_velocity,
+                max_velocity,
+                min_final_spread,
+                max_baseline,
+                max_acceleration,
+                max_impact_bps,
+                min_net_profit_wmon,
+                metrics_port,
+                gas_strategy: gas_strategy.parse()?,
+                min_liquidity,
+                min_survival,
+                survival_horizon_ms,
+                watch_mempool,
+                mempool_min_wmon,
+                max_price_age_ms,
+                retry_slippage_bump,
+                auto_wrap_threshold,
+                max_wrap_per_session,
+                max_gas_gwei,
+                auto_sweep_profit,
+                expected_decay_bps,
+                competitive_routes,
+                competitive_priority_multiplier,
+                spread_log,
+                mode,
+                eta_ms,
+                validation_logs,
+                max_adaptive_slippage: adaptive_slippage.then_some(max_slippage),
+                rank_by,
+                pnl_denom,
+                record_features,
+            }).await
         }
         Some(Commands::ProdArb {
             min_spread_bps,
             amount,
             slippage,
+            adaptive_slippage,
+            max_slippage,
             max_daily_loss,
             max_failures,
+            max_impact_bps,
+            metrics_port,
+            alerts,
+            gas_strategy,
+            no_auto_approve,
+            max_gas_gwei,
+            gas_pause_after,
+            max_drawdown_wmon,
+            drawdown_pause_secs,
+            max_exposure_wmon,
+            warmup_secs,
+            pnl_denom,
         }) => {
-            run_prod_arb(min_spread_bps, amount, slippage, max_daily_loss, max_failures).await
+            run_prod_arb(&pair, ProdArbOptions {
+                min_spread_bps,
+                amount,
+                slippage,
+                max_daily_loss,
+                max_failures,
+                max_impact_bps,
+                metrics_port,
+                alerts,
+                gas_strategy: gas_strategy.parse()?,
+                auto_approve: !no_auto_approve,
+                max_gas_gwei,
+                gas_pause_after,
+                max_drawdown_wmon,
+                drawdown_pause_secs,
+                max_exposure_wmon,
+                max_adaptive_slippage: adaptive_slippage.then_some(max_slippage),
+                warmup_secs,
+                pnl_denom,
+            }).await
         }
         Some(Commands::FundContract { amount }) => {
             run_fund_contract(amount).await
@@ -3241,17 +6065,59 @@ async fn main() -> Result<()> {
         Some(Commands::ContractBalance) => {
             run_contract_balance().await
         }
+        Some(Commands::SelfTest) => {
+            execution::routers::self_test::run_self_test(&config::get_routers(), &pair)
+        }
         Some(Commands::TestRevert { dex, gas_limit, method }) => {
             run_test_revert(&dex, gas_limit, &method).await
         }
-        Some(Commands::MevValidate { duration, min_spread, output }) => {
-            run_mev_validate(duration, min_spread, &output).await
+        Some(Commands::MevValidate { duration, min_spread, output, record_features }) => {
+            run_mev_validate(duration, min_spread, &output, record_features).await
+        }
+        Some(Commands::RecordPrices { interval_ms, out, duration_secs }) => {
+            let node_config = NodeConfig::from_env();
+            node_config.log_config();
+            let provider = ProviderBuilder::new().connect_client(rpc_failover::client(node_config.rpc_url.parse()?));
+            verify_pools_ready(&provider, &current_price_calls()).await?;
+            price_recorder::run_record_prices(&provider, current_price_calls, interval_ms, &out, duration_secs).await
+        }
+        Some(Commands::BenchRpc { samples }) => {
+            let node_config = NodeConfig::from_env();
+            node_config.log_config();
+            let provider = ProviderBuilder::new().connect_client(rpc_failover::client(node_config.rpc_url.parse()?));
+            // Any address works for balanceOf; the bot's own wallet if configured
+            let wallet = std::env::var("PRIVATE_KEY").ok()
+                .and_then(|key| PrivateKeySigner::from_str(&key).ok())
+                .map(|signer| signer.address())
+                .unwrap_or(alloy::primitives::Address::ZERO);
+            rpc_bench::run_bench_rpc(&provider, current_price_calls, &pair, wallet, samples, node_config.poll_interval).await
+        }
+        Some(Commands::ExportCsv { input, output }) => {
+            stats::export_csv(&input, &output)
+        }
+        Some(Commands::AnalyzeLogs { file, group_by, min_spread_bps }) => {
+            log_analysis::run_analyze_logs(&file, group_by.parse()?, min_spread_bps)
+        }
+        Some(Commands::Backtest { file, min_spread_bps, slippage_bps, amount }) => {
+            backtest::run_backtest(&file, min_spread_bps, slippage_bps, amount)
         }
         Some(Commands::MevUltra { amount, slippage, min_spread, max_executions, cooldown_secs, trigger_state }) => {
             run_mev_ultra(amount, slippage, min_spread, max_executions, cooldown_secs, &trigger_state).await
         }
-        Some(Commands::Dashboard { min_spread, history, refresh_ms, sound }) => {
-            run_dashboard(min_spread, history, refresh_ms, sound).await
+        Some(Commands::Dashboard { min_spread, history, refresh_ms, sound, min_liquidity, pairs, tui, spread_log, on_block }) => {
+            match pairs {
+                Some(_) if tui => Err(eyre::eyre!("--tui supports a single pair; drop --pairs")),
+                _ if spread_log.is_some() && (tui || pairs.is_some()) => {
+                    Err(eyre::eyre!("--spread-log supports the single-pair, non-TUI dashboard"))
+                }
+                _ if on_block && (tui || pairs.is_some()) => {
+                    Err(eyre::eyre!("--on-block supports the single-pair, non-TUI dashboard"))
+                }
+                Some(pairs) => run_multi_pair_dashboard(&pairs, min_spread, history, refresh_ms, sound, min_liquidity).await,
+                None => {
+                    run_dashboard(DashboardOptions { min_spread, history, refresh_ms, sound, min_liquidity, tui, spread_log, on_block }).await
+                }
+            }
         }
     }
 }
@@ -0,0 +1,287 @@
+//! Mempool-based early spread detection
+//!
+//! `mev_validation` only sees prices once a block is Proposed. A large swap
+//! sitting in the mempool is known earlier: it will move its pool's price in
+//! the next block and open a spread against the other pools.
+//!
+//! `watch_pending_swaps` subscribes to `newPendingTransactions` over WS, keeps
+//! calls to configured routers, decodes the swap and emits a
+//! `PendingSwapSignal`. Calldata that doesn't decode (other router methods,
+//! other tokens, V4 Universal Router batches) is dropped silently.
+//!
+//! `PreArm` turns large signals into armed routes for AutoArb: a pending sell
+//! on X makes X cheap (buy there), a pending buy on X makes it rich (sell there).
+
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::{RouterConfig, RouterType, USDC_ADDRESS, USDC_DECIMALS, WMON_ADDRESS, WMON_DECIMALS};
+use crate::execution::routers::{lfj, monday, pancake_v3, uniswap_v3};
+use crate::execution::SwapDirection;
+use crate::rpc_failover;
+
+/// Delay before reconnecting a dropped pending-tx subscription
+const RECONNECT_DELAY: Duration = Duration::from_millis(1000);
+
+/// How long a pre-armed route stays armed (a couple of Monad blocks)
+pub const DEFAULT_ARM_WINDOW: Duration = Duration::from_secs(2);
+
+/// A pending swap on one of our pools
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSwapSignal {
+    /// Pool name, as in `RouterConfig::name`
+    pub pool: String,
+    pub direction: SwapDirection,
+    /// Input token amount in human units (WMON for Sell, USDC for Buy).
+    /// For exact-output swaps this is the max input.
+    pub size: f64,
+}
+
+impl PendingSwapSignal {
+    /// Size in WMON, valuing USDC input at `price` (USDC per WMON)
+    pub fn size_wmon(&self, price: Option<f64>) -> Option<f64> {
+        match self.direction {
+            SwapDirection::Sell => Some(self.size),
+            SwapDirection::Buy => price.filter(|p| *p > 0.0).map(|p| self.size / p),
+        }
+    }
+}
+
+/// Swap fields pulled out of router calldata
+struct DecodedSwap {
+    token_in: Address,
+    token_out: Address,
+    /// V3 fee tier, or LFJ bin step
+    fee: u32,
+    amount_in: U256,
+}
+
+fn decode_swap(router_type: RouterType, input: &[u8]) -> Option<DecodedSwap> {
+    match router_type {
+        RouterType::UniswapV3 | RouterType::PancakeV3 => {
+            // Both routers accept a bare call or one wrapped in multicall(deadline, data)
+            if let Ok(call) = pancake_v3::multicallCall::abi_decode(input) {
+                return call.data.iter().find_map(|inner| decode_swap(router_type, inner));
+            }
+            if let Ok(call) = uniswap_v3::exactInputSingleCall::abi_decode(input) {
+                let p = call.params;
+                return Some(DecodedSwap { token_in: p.tokenIn, token_out: p.tokenOut, fee: p.fee.to(), amount_in: p.amountIn });
+            }
+            let p = uniswap_v3::exactOutputSingleCall::abi_decode(input).ok()?.params;
+            Some(DecodedSwap { token_in: p.tokenIn, token_out: p.tokenOut, fee: p.fee.to(), amount_in: p.amountInMaximum })
+        }
+        RouterType::MondayTrade => {
+            if let Ok(call) = monday::exactInputSingleCall::abi_decode(input) {
+                let p = call.params;
+                return Some(DecodedSwap { token_in: p.tokenIn, token_out: p.tokenOut, fee: p.fee.to(), amount_in: p.amountIn });
+            }
+            let p = monday::exactOutputSingleCall::abi_decode(input).ok()?.params;
+            Some(DecodedSwap { token_in: p.tokenIn, token_out: p.tokenOut, fee: p.fee.to(), amount_in: p.amountInMaximum })
+        }
+        RouterType::LfjLB => {
+            let call = lfj::swapExactTokensForTokensCall::abi_decode(input).ok()?;
+            // Single-hop only: multi-hop paths don't map onto one of our pools
+            let [token_in, token_out] = call.path.tokenPath[..] else {
+                return None;
+            };
+            let bin_step = call.path.pairBinSteps.first()?.try_into().ok()?;
+            Some(DecodedSwap { token_in, token_out, fee: bin_step, amount_in: call.amountIn })
+        }
+        // Universal Router command batches aren't decoded
+        RouterType::UniswapV4 => None,
+    }
+}
+
+/// Match a pending call against configured routers. None for anything that
+/// isn't a WMON/USDC swap on one of our pools.
+pub fn match_pending_swap(routers: &[RouterConfig], to: Address, input: &[u8]) -> Option<PendingSwapSignal> {
+    routers.iter().filter(|r| r.address == to).find_map(|router| {
+        let swap = decode_swap(router.router_type, input)?;
+        if swap.fee != router.pool_fee {
+            return None;
+        }
+        let (direction, decimals) = match (swap.token_in, swap.token_out) {
+            (WMON_ADDRESS, USDC_ADDRESS) => (SwapDirection::Sell, WMON_DECIMALS),
+            (USDC_ADDRESS, WMON_ADDRESS) => (SwapDirection::Buy, USDC_DECIMALS),
+            _ => return None,
+        };
+        let raw: u128 = swap.amount_in.try_into().ok()?;
+        Some(PendingSwapSignal {
+            pool: router.name.to_string(),
+            direction,
+            size: raw as f64 / 10f64.powi(decimals as i32),
+        })
+    })
+}
+
+/// Stream decoded pending swaps to configured routers.
+///
+/// Subscribes with full transaction objects; nodes that only send hashes are
+/// handled by fetching each transaction over `rpc_url`. Reconnects if the
+/// socket drops; the task exits when the receiver is dropped.
+pub fn watch_pending_swaps(
+    rpc_url: &str,
+    ws_url: &str,
+    routers: Vec<RouterConfig>,
+) -> Result<mpsc::Receiver<PendingSwapSignal>> {
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+    let ws_url = ws_url.to_string();
+    let (tx, rx) = mpsc::channel(64);
+
+    tokio::spawn(async move {
+        loop {
+            match watch_until_disconnect(&provider, &ws_url, &routers, &tx).await {
+                Ok(()) => return, // Receiver dropped
+                Err(e) => eprintln!(
+                    "Mempool stream disconnected: {}. Reconnecting in {}ms...",
+                    e,
+                    RECONNECT_DELAY.as_millis()
+                ),
+            }
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Run one subscription until the socket fails (Err) or the receiver is gone (Ok)
+async fn watch_until_disconnect<P: Provider>(
+    provider: &P,
+    ws_url: &str,
+    routers: &[RouterConfig],
+    tx: &mpsc::Sender<PendingSwapSignal>,
+) -> Result<()> {
+    let (ws_stream, _) = rpc_failover::connect_ws(ws_url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribe_msg = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_subscribe",
+        "params": ["newPendingTransactions", true]
+    });
+    write.send(Message::Text(subscribe_msg.to_string())).await?;
+
+    while let Some(msg) = read.next().await {
+        match msg? {
+            Message::Text(text) => {
+                let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+                let Some(result) = json.pointer("/params/result") else {
+                    continue;
+                };
+
+                let (to, input) = match result.as_str() {
+                    // Hash-only subscription: fetch the transaction
+                    Some(hash) => {
+                        let Ok(hash) = hash.parse::<TxHash>() else { continue };
+                        let Ok(Some(pending)) = provider.get_transaction_by_hash(hash).await else { continue };
+                        let Some(to) = alloy::consensus::Transaction::to(&pending) else { continue };
+                        (to, alloy::consensus::Transaction::input(&pending).clone())
+                    }
+                    None => {
+                        let to = result.get("to").and_then(|v| v.as_str()).and_then(|s| s.parse::<Address>().ok());
+                        let input = result.get("input").and_then(|v| v.as_str()).and_then(|s| s.parse::<Bytes>().ok());
+                        let (Some(to), Some(input)) = (to, input) else { continue };
+                        (to, input)
+                    }
+                };
+
+                if let Some(signal) = match_pending_swap(routers, to, &input) {
+                    if tx.send(signal).await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            Message::Ping(data) => {
+                let _ = write.send(Message::Pong(data)).await;
+            }
+            Message::Close(_) => return Err(eyre!("WebSocket closed")),
+            _ => {}
+        }
+    }
+
+    Err(eyre!("WebSocket closed"))
+}
+
+/// Routes armed by recent large pending swaps
+pub struct PreArm {
+    min_size_wmon: f64,
+    window: Duration,
+    /// Pool -> (pending direction, when seen)
+    armed: HashMap<String, (SwapDirection, Instant)>,
+}
+
+impl PreArm {
+    pub fn new(min_size_wmon: f64, window: Duration) -> Self {
+        Self { min_size_wmon, window, armed: HashMap::new() }
+    }
+
+    /// Arm the signal's pool if the swap is at least `min_size_wmon`.
+    /// `price` is the pool's last known price, used to size USDC input.
+    pub fn observe(&mut self, signal: &PendingSwapSignal, price: Option<f64>) -> bool {
+        let large = signal.size_wmon(price).is_some_and(|size| size >= self.min_size_wmon);
+        if large {
+            self.armed.insert(signal.pool.clone(), (signal.direction, Instant::now()));
+        }
+        large
+    }
+
+    /// The pending swap that predicts the buy_pool -> sell_pool spread, if any:
+    /// a sell pushing `buy_pool` down or a buy pushing `sell_pool` up
+    pub fn armed_by(&self, buy_pool: &str, sell_pool: &str) -> Option<(&str, SwapDirection)> {
+        let live = |pool: &str, direction: SwapDirection| {
+            self.armed
+                .get_key_value(pool)
+                .filter(|(_, (d, seen))| *d == direction && seen.elapsed() < self.window)
+                .map(|(pool, (d, _))| (pool.as_str(), *d))
+        };
+        live(buy_pool, SwapDirection::Sell).or_else(|| live(sell_pool, SwapDirection::Buy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::get_routers;
+    use crate::execution::routers::build_swap_calldata;
+
+    #[test]
+    fn test_match_pending_swap_decodes_our_pools() {
+        let routers = get_routers();
+        let pancake = routers.iter().find(|r| r.name == "PancakeSwap2").unwrap();
+
+        // Pancake's multicall-wrapped call maps to the pool by fee tier
+        let calldata = build_swap_calldata(
+            pancake, WMON_ADDRESS, USDC_ADDRESS, U256::from(2_500u64) * U256::from(10u64).pow(U256::from(18)),
+            U256::ZERO, Address::ZERO, 1,
+        ).unwrap();
+        let signal = match_pending_swap(&routers, pancake.address, &calldata).unwrap();
+        assert_eq!(signal, PendingSwapSignal { pool: "PancakeSwap2".into(), direction: SwapDirection::Sell, size: 2500.0 });
+
+        // Unknown calldata and other routers are ignored
+        assert!(match_pending_swap(&routers, pancake.address, &[0xde, 0xad, 0xbe, 0xef]).is_none());
+        assert!(match_pending_swap(&routers, Address::ZERO, &calldata).is_none());
+
+        // A pending sell on the buy pool arms that route; small swaps don't
+        let mut pre_arm = PreArm::new(1000.0, DEFAULT_ARM_WINDOW);
+        assert!(pre_arm.observe(&signal, None));
+        assert_eq!(pre_arm.armed_by("PancakeSwap2", "Uniswap"), Some(("PancakeSwap2", SwapDirection::Sell)));
+        assert!(pre_arm.armed_by("Uniswap", "PancakeSwap2").is_none());
+        let small_buy = PendingSwapSignal { pool: "LFJ".into(), direction: SwapDirection::Buy, size: 3.0 };
+        assert!(!pre_arm.observe(&small_buy, Some(0.03)));
+    }
+}
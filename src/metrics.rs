@@ -0,0 +1,106 @@
+//! Prometheus metrics for the arb bots
+//!
+//! Counters and gauges are plain atomics updated from the AutoArb/ProdArb loops,
+//! so recording is free in the default build. The `/metrics` HTTP endpoint is
+//! only compiled with `--features metrics` (pulls in axum).
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+static EXECUTIONS_TOTAL: AtomicU64 = AtomicU64::new(0);
+static SUCCESSFUL_ARBS_TOTAL: AtomicU64 = AtomicU64::new(0);
+static CONSECUTIVE_FAILURES: AtomicU64 = AtomicU64::new(0);
+/// f64 stored as raw bits
+static CUMULATIVE_PNL_WMON: AtomicU64 = AtomicU64::new(0);
+static BEST_NET_SPREAD_BPS: AtomicI64 = AtomicI64::new(0);
+static LAST_POLL_LATENCY_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Count one execution attempt; failures bump the consecutive counter
+pub fn record_execution(success: bool) {
+    EXECUTIONS_TOTAL.fetch_add(1, Ordering::Relaxed);
+    if success {
+        SUCCESSFUL_ARBS_TOTAL.fetch_add(1, Ordering::Relaxed);
+        CONSECUTIVE_FAILURES.store(0, Ordering::Relaxed);
+    } else {
+        CONSECUTIVE_FAILURES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn set_consecutive_failures(n: u32) {
+    CONSECUTIVE_FAILURES.store(n as u64, Ordering::Relaxed);
+}
+
+pub fn set_cumulative_pnl(wmon: f64) {
+    CUMULATIVE_PNL_WMON.store(wmon.to_bits(), Ordering::Relaxed);
+}
+
+pub fn set_best_net_spread(bps: i32) {
+    BEST_NET_SPREAD_BPS.store(bps as i64, Ordering::Relaxed);
+}
+
+pub fn set_poll_latency(ms: u128) {
+    LAST_POLL_LATENCY_MS.store(ms as u64, Ordering::Relaxed);
+}
+
+/// Render all metrics in the Prometheus text exposition format
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+pub fn render() -> String {
+    let metrics: [(&str, &str, &str, String); 6] = [
+        ("executions_total", "counter", "Arb executions attempted",
+            EXECUTIONS_TOTAL.load(Ordering::Relaxed).to_string()),
+        ("successful_arbs_total", "counter", "Arb executions with positive WMON delta",
+            SUCCESSFUL_ARBS_TOTAL.load(Ordering::Relaxed).to_string()),
+        ("consecutive_failures", "gauge", "Failed executions since the last success",
+            CONSECUTIVE_FAILURES.load(Ordering::Relaxed).to_string()),
+        ("cumulative_pnl_wmon", "gauge", "Session P&L in WMON",
+            f64::from_bits(CUMULATIVE_PNL_WMON.load(Ordering::Relaxed)).to_string()),
+        ("best_net_spread_bps", "gauge", "Best net spread seen on the last poll",
+            BEST_NET_SPREAD_BPS.load(Ordering::Relaxed).to_string()),
+        ("last_poll_latency_ms", "gauge", "Latency of the last price poll",
+            LAST_POLL_LATENCY_MS.load(Ordering::Relaxed).to_string()),
+    ];
+
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+    }
+    out
+}
+
+/// Serve `/metrics` on 0.0.0.0:`port` in the background
+#[cfg(feature = "metrics")]
+pub async fn start_server(port: u16) -> eyre::Result<()> {
+    use axum::{routing::get, Router};
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    let app = Router::new().route("/metrics", get(|| async { render() }));
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            eprintln!("Metrics server error: {}", e);
+        }
+    });
+
+    println!("  Metrics:         http://0.0.0.0:{}/metrics", port);
+    Ok(())
+}
+
+#[cfg(not(feature = "metrics"))]
+pub async fn start_server(_port: u16) -> eyre::Result<()> {
+    Err(eyre::eyre!("--metrics-port requires building with `--features metrics`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_exposition_format() {
+        set_best_net_spread(-3);
+        set_cumulative_pnl(0.25);
+
+        let out = render();
+        assert!(out.contains("# TYPE executions_total counter\n"));
+        assert!(out.contains("\nbest_net_spread_bps -3\n"));
+        assert!(out.contains("\ncumulative_pnl_wmon 0.25\n"));
+    }
+}
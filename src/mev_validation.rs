@@ -13,14 +13,16 @@ use std::collections::{HashMap, VecDeque};
 use std::fs::OpenOptions;
 use std::io::{stdout, Write};
 use std::time::Instant;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::Message;
 
 use crate::display::calculate_spreads;
+use crate::feature_recorder;
 use crate::multicall::fetch_prices_batched;
+use crate::rpc_failover;
 use crate::pools::{
-    create_lfj_active_id_call, create_lfj_bin_step_call, create_slot0_call, PoolPrice, PriceCall,
+    create_lfj_active_id_call, create_lfj_bin_step_call, create_v3_price_calls, PoolPrice, PriceCall,
 };
-use crate::config::{get_lfj_pool, get_monday_trade_pool, get_v3_pools};
+use crate::config::{get_lfj_pools, get_monday_trade_pool, get_v3_pools};
 
 /// Helper function to get ANSI color code based on spread level
 fn spread_level_color(spread_bps: i32) -> &'static str {
@@ -56,7 +58,7 @@ impl CommitState {
 }
 
 /// Classification of spread at Proposed state
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SpreadTier {
     Noise,       // < 5 bps - ignore
     SubThreshold, // 5-9 bps - watch only
@@ -76,6 +78,17 @@ impl SpreadTier {
         }
     }
 
+    /// Lowest net spread (bps) still in this tier
+    pub fn min_bps(&self) -> i32 {
+        match self {
+            Self::Noise => i32::MIN,
+            Self::SubThreshold => 5,
+            Self::Marginal => 10,
+            Self::Actionable => 15,
+            Self::Critical => 25,
+        }
+    }
+
     #[allow(dead_code)]
     pub fn is_actionable(&self) -> bool {
         matches!(self, Self::Marginal | Self::Actionable | Self::Critical)
@@ -159,7 +172,6 @@ impl MonadBlockHeader {
         u64::from_str_radix(self.number.trim_start_matches("0x"), 16).unwrap_or(0)
     }
 
-    #[allow(dead_code)]
     pub fn state(&self) -> Option<CommitState> {
         CommitState::from_str(&self.commit_state)
     }
@@ -586,13 +598,14 @@ impl MevValidator {
         // Build price calls (same as monitor)
         let mut price_calls: Vec<PriceCall> = Vec::new();
         for pool in get_v3_pools() {
-            price_calls.push(create_slot0_call(&pool));
+            price_calls.extend(create_v3_price_calls(&pool));
+        }
+        for lfj_pool in get_lfj_pools() {
+            price_calls.push(create_lfj_active_id_call(&lfj_pool));
+            price_calls.push(create_lfj_bin_step_call(&lfj_pool));
         }
-        let lfj_pool = get_lfj_pool();
-        price_calls.push(create_lfj_active_id_call(&lfj_pool));
-        price_calls.push(create_lfj_bin_step_call(&lfj_pool));
         let monday_pool = get_monday_trade_pool();
-        price_calls.push(create_slot0_call(&monday_pool));
+        price_calls.extend(create_v3_price_calls(&monday_pool));
 
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
         let log_file = format!("mev_validation_{}.jsonl", timestamp);
@@ -614,11 +627,12 @@ impl MevValidator {
     /// Fetch current prices and calculate best spread
     async fn snapshot_prices(&self, block_number: u64, state: &str) -> Result<PriceSnapshot> {
         let url: reqwest::Url = self.rpc_url.parse()?;
-        let provider = alloy::providers::ProviderBuilder::new().connect_http(url);
+        let provider = alloy::providers::ProviderBuilder::new().connect_client(rpc_failover::client(url));
 
-        let (prices, _) = fetch_prices_batched(&provider, self.price_calls.clone()).await?;
+        let (prices, _, _) = fetch_prices_batched(&provider, self.price_calls.clone()).await?;
 
-        let spreads = calculate_spreads(&prices);
+        let spreads = calculate_spreads(&prices, None);
+        feature_recorder::record_poll(Some(block_number), CommitState::from_str(state), &prices, &spreads);
         let best = spreads.first();
 
         let (best_spread_bps, best_pair) = match best {
@@ -633,7 +647,7 @@ impl MevValidator {
             block_number,
             commit_state: state.to_string(),
             timestamp_ms: self.start_time.elapsed().as_millis(),
-            wall_clock: Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            wall_clock: Local::now().format(crate::log_analysis::WALL_CLOCK_FORMAT).to_string(),
             prices: prices.iter().map(|p| p.into()).collect(),
             best_spread_bps,
             best_pair,
@@ -1077,7 +1091,7 @@ async fn run_validation_core(
     let mut validator = MevValidator::new(rpc_url, ws_url, min_spread_bps, output_mode);
 
     // Connect to WebSocket
-    let (ws_stream, _) = connect_async(ws_url).await?;
+    let (ws_stream, _) = rpc_failover::connect_ws(ws_url).await?;
     let (mut write, mut read) = ws_stream.split();
 
     // Subscribe to monadNewHeads
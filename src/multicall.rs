@@ -1,18 +1,28 @@
-use alloy::primitives::Bytes;
-use alloy::providers::Provider;
+use alloy::primitives::{Address, Bytes};
+use alloy::providers::{Provider, ProviderBuilder};
 use alloy::sol;
 use alloy::sol_types::SolCall;
-use eyre::Result;
+use alloy::transports::{RpcError, TransportErrorKind};
+use eyre::{eyre, Result};
+use futures_util::{SinkExt, StreamExt};
+use futures_util::future::join_all;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use std::time::Duration;
-use tracing::debug;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
 
 use crate::config::MULTICALL3_ADDRESS;
+use crate::mev_validation::MonadBlockHeader;
 use crate::node_config::NodeConfig;
+use crate::rpc_failover;
 use crate::pools::{
-    calculate_lfj_price, decode_active_id_response, decode_bin_step_response,
-    decode_slot0_to_price, CallType, PoolPrice, PriceCall,
+    cache_bin_step, cached_bin_step, calculate_lfj_price, decode_active_id_response, decode_bin_step_response,
+    invalidate_bin_step, CallType, PoolPrice, PriceCall,
 };
+use crate::pools::v3_pool::{decode_liquidity_response, decode_slot0_price_and_tick};
 
 // Multicall3 interface
 sol! {
@@ -31,16 +41,138 @@ sol! {
 
     #[derive(Debug)]
     function aggregate3(Call3[] calldata calls) external payable returns (MulticallResult[] memory returnData);
+
+    #[derive(Debug)]
+    function getBlockNumber() external view returns (uint256 blockNumber);
+
+    #[derive(Debug)]
+    function balanceOf(address account) external view returns (uint256);
 }
 
-/// Executes batched price calls via Multicall3
+/// Set once a call to `MULTICALL3_ADDRESS` comes back empty (no contract there)
+static MULTICALL_MISSING: AtomicBool = AtomicBool::new(false);
+
+/// aggregate3 over `calls`, or None (and individual eth_calls from then on)
+/// when the node has no Multicall3 deployed
+async fn try_aggregate3<P: Provider>(provider: &P, calls: Vec<Call3>) -> Result<Option<Vec<MulticallResult>>> {
+    if MULTICALL_MISSING.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(MULTICALL3_ADDRESS)
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(aggregate3Call { calls }.abi_encode())));
+
+    // No code at the address: most nodes return empty data, some an error
+    let result = match provider.call(tx).await {
+        Err(e) if e.to_string().to_lowercase().contains("no code") => Bytes::new(),
+        result => result?,
+    };
+    if result.is_empty() {
+        warn!("No Multicall3 code at {:?}; falling back to individual eth_calls", MULTICALL3_ADDRESS);
+        MULTICALL_MISSING.store(true, Ordering::Relaxed);
+        return Ok(None);
+    }
+    Ok(Some(aggregate3Call::abi_decode_returns(&result)?))
+}
+
+/// Default `--multicall-chunk`: price calls per aggregate3, far below
+/// typical `eth_call` gas caps
+pub const DEFAULT_MULTICALL_CHUNK: usize = 100;
+
+static MULTICALL_CHUNK: OnceLock<usize> = OnceLock::new();
+
+/// Install `--multicall-chunk` (call once at startup)
+pub fn set_multicall_chunk(chunk_size: usize) {
+    let _ = MULTICALL_CHUNK.set(chunk_size.max(1));
+}
+
+fn multicall_chunk() -> usize {
+    MULTICALL_CHUNK.get().copied().unwrap_or(DEFAULT_MULTICALL_CHUNK)
+}
+
+/// Split `price_calls` into runs of about `chunk_size`, never separating a
+/// pool's follow-up call (LFJ binStep, V3 liquidity) from the call before it
+fn chunk_price_calls(price_calls: &[PriceCall], chunk_size: usize) -> Vec<&[PriceCall]> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < price_calls.len() {
+        let mut end = (start + chunk_size).min(price_calls.len());
+        while end < price_calls.len() && matches!(price_calls[end].call_type, CallType::LfjBinStep | CallType::V3Liquidity) {
+            end += 1;
+        }
+        chunks.push(&price_calls[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+/// Executes batched price calls via Multicall3, `--multicall-chunk` calls
+/// per batch (see `fetch_prices_chunked`).
+///
+/// Returns (prices, elapsed_ms, block_number). The block number comes from
+/// Multicall3's `getBlockNumber()` in the same batch, so it is the block
+/// every price was read at; each `PoolPrice` is stamped with it.
+///
+/// On a node without Multicall3 deployed (the call returns no data) this
+/// switches to `fetch_prices_individual` for the rest of the session.
 pub async fn fetch_prices_batched<P: Provider>(
     provider: &P,
     price_calls: Vec<PriceCall>,
-) -> Result<(Vec<PoolPrice>, u128)> {
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
+    fetch_prices_chunked(provider, price_calls, multicall_chunk()).await
+}
+
+/// `fetch_prices_batched` with the calls split into aggregate3 batches of
+/// `chunk_size`, sent concurrently and merged in call order. Each price is
+/// stamped with its own batch's block; the returned block is set only if
+/// every batch read the same one. A batch that fails falls back to
+/// individual calls for just its pools. A single batch keeps plain
+/// `fetch_prices_batched` behaviour (its errors are returned for retry).
+pub async fn fetch_prices_chunked<P: Provider>(
+    provider: &P,
+    price_calls: Vec<PriceCall>,
+    chunk_size: usize,
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
+    let chunks = chunk_price_calls(&price_calls, chunk_size);
+    if chunks.len() <= 1 {
+        return fetch_price_batch(provider, price_calls).await;
+    }
+
+    let start = std::time::Instant::now();
+    let fetched = join_all(chunks.iter().enumerate().map(|(i, chunk)| async move {
+        match fetch_price_batch(provider, chunk.to_vec()).await {
+            Ok(fetched) => Ok(fetched),
+            Err(e) => {
+                warn!("Multicall chunk {} ({} calls) failed ({}), falling back to individual calls", i + 1, chunk.len(), e);
+                fetch_prices_individual(provider, chunk.to_vec()).await
+            }
+        }
+    }))
+    .await;
+
+    let mut prices = Vec::new();
+    let mut blocks = Vec::new();
+    for result in fetched {
+        let (chunk_prices, _, block_number) = result?;
+        prices.extend(chunk_prices);
+        blocks.push(block_number);
+    }
+    let elapsed_ms = start.elapsed().as_millis();
+    debug!("Chunked multicall completed in {}ms ({} calls in {} chunks)", elapsed_ms, price_calls.len(), blocks.len());
+
+    let block_number = blocks.first().copied().flatten().filter(|b| blocks.iter().all(|x| *x == Some(*b)));
+    Ok((prices, elapsed_ms, block_number))
+}
+
+/// One aggregate3 batch of price calls
+async fn fetch_price_batch<P: Provider>(
+    provider: &P,
+    price_calls: Vec<PriceCall>,
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
     let start = std::time::Instant::now();
 
-    // Build multicall calls
+    // Build multicall calls, plus getBlockNumber() last
     let calls: Vec<Call3> = price_calls
         .iter()
         .map(|pc| Call3 {
@@ -48,49 +180,98 @@ pub async fn fetch_prices_batched<P: Provider>(
             allowFailure: true,
             callData: pc.calldata.clone(),
         })
+        .chain(std::iter::once(Call3 {
+            target: MULTICALL3_ADDRESS,
+            allowFailure: true,
+            callData: Bytes::from(getBlockNumberCall {}.abi_encode()),
+        }))
         .collect();
 
-    // Encode the aggregate3 call
-    let calldata = aggregate3Call { calls }.abi_encode();
+    let Some(decoded) = try_aggregate3(provider, calls).await? else {
+        return fetch_prices_individual(provider, price_calls).await;
+    };
+    let fetched_at = std::time::Instant::now();
 
-    // Execute the call
-    let tx = alloy::rpc::types::TransactionRequest::default()
-        .to(MULTICALL3_ADDRESS)
-        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(
-            calldata,
-        )));
+    let elapsed_ms = start.elapsed().as_millis();
+    debug!("Multicall completed in {}ms", elapsed_ms);
 
-    let result = provider.call(tx).await?;
+    let block_number = decoded
+        .get(price_calls.len())
+        .filter(|r| r.success)
+        .and_then(|r| getBlockNumberCall::abi_decode_returns(&r.returnData).ok())
+        .map(|b| b.to::<u64>());
 
-    // Decode the results
-    let decoded = aggregate3Call::abi_decode_returns(&result)?;
+    let results: Vec<Option<Bytes>> = decoded
+        .into_iter()
+        .take(price_calls.len())
+        .map(|r| r.success.then_some(r.returnData))
+        .collect();
+    let prices = decode_price_results(&price_calls, &results, fetched_at, block_number);
+
+    Ok((prices, elapsed_ms, block_number))
+}
+
+/// Price calls as concurrent plain `eth_call`s, all pinned to the current block,
+/// for nodes without Multicall3. Decoded exactly like `fetch_prices_batched`.
+pub async fn fetch_prices_individual<P: Provider>(
+    provider: &P,
+    price_calls: Vec<PriceCall>,
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
+    let start = std::time::Instant::now();
+    let block_number = provider.get_block_number().await?;
+
+    let results: Vec<Option<Bytes>> = join_all(price_calls.iter().map(|pc| {
+        let tx = alloy::rpc::types::TransactionRequest::default()
+            .to(pc.pool_address)
+            .input(alloy::rpc::types::TransactionInput::new(pc.calldata.clone()));
+        async move { provider.call(tx).block(block_number.into()).await.ok() }
+    }))
+    .await;
+    let fetched_at = std::time::Instant::now();
 
     let elapsed_ms = start.elapsed().as_millis();
-    debug!("Multicall completed in {}ms", elapsed_ms);
+    debug!("Individual eth_calls completed in {}ms ({} calls)", elapsed_ms, price_calls.len());
 
-    // Process results
+    let prices = decode_price_results(&price_calls, &results, fetched_at, Some(block_number));
+    Ok((prices, elapsed_ms, Some(block_number)))
+}
+
+/// Turn per-call return data (None = failed call) into prices. LFJ pools
+/// combine their activeId and binStep results, V3 pools their slot0 and
+/// liquidity results.
+fn decode_price_results(
+    price_calls: &[PriceCall],
+    results: &[Option<Bytes>],
+    fetched_at: std::time::Instant,
+    block_number: Option<u64>,
+) -> Vec<PoolPrice> {
     let mut prices = Vec::new();
 
     // For LFJ, we need to collect activeId and binStep separately
     let mut lfj_active_ids: HashMap<String, u32> = HashMap::new();
     let mut lfj_bin_steps: HashMap<String, u16> = HashMap::new();
     let mut lfj_fee_bps: HashMap<String, u32> = HashMap::new();
+    let mut lfj_addresses: HashMap<String, Address> = HashMap::new();
+    let mut v3_liquidity: HashMap<String, u128> = HashMap::new();
 
-    // The decoded result is the vector of MulticallResult directly
-    for (i, res) in decoded.iter().enumerate() {
-        if !res.success {
+    for (i, res) in results.iter().enumerate() {
+        let Some(return_data) = res else {
             debug!("Call failed for: {}", price_calls[i].pool_name);
             continue;
-        }
+        };
 
         match price_calls[i].call_type {
             CallType::V3Slot0 => {
-                match decode_slot0_to_price(&res.returnData) {
-                    Ok(price) => {
+                match decode_slot0_price_and_tick(return_data) {
+                    Ok((price, tick)) => {
                         prices.push(PoolPrice {
                             pool_name: price_calls[i].pool_name.clone(),
                             price,
                             fee_bps: price_calls[i].fee_bps,
+                            liquidity: None,
+                            tick: Some(tick),
+                            fetched_at,
+                            block_number,
                         });
                     }
                     Err(e) => {
@@ -101,12 +282,31 @@ pub async fn fetch_prices_batched<P: Provider>(
                     }
                 }
             }
+            CallType::V3Liquidity => {
+                match decode_liquidity_response(return_data) {
+                    Ok(liquidity) => {
+                        let pool_name = price_calls[i]
+                            .pool_name
+                            .strip_suffix("_liquidity")
+                            .unwrap_or(&price_calls[i].pool_name)
+                            .to_string();
+                        v3_liquidity.insert(pool_name, liquidity);
+                    }
+                    Err(e) => {
+                        debug!(
+                            "Failed to decode V3 liquidity for {}: {}",
+                            price_calls[i].pool_name, e
+                        );
+                    }
+                }
+            }
             CallType::LfjActiveId => {
-                match decode_active_id_response(&res.returnData) {
+                match decode_active_id_response(return_data) {
                     Ok(active_id) => {
                         let pool_name = price_calls[i].pool_name.clone();
                         lfj_active_ids.insert(pool_name.clone(), active_id);
-                        lfj_fee_bps.insert(pool_name, price_calls[i].fee_bps);
+                        lfj_fee_bps.insert(pool_name.clone(), price_calls[i].fee_bps);
+                        lfj_addresses.insert(pool_name, price_calls[i].pool_address);
                     }
                     Err(e) => {
                         debug!(
@@ -117,7 +317,7 @@ pub async fn fetch_prices_batched<P: Provider>(
                 }
             }
             CallType::LfjBinStep => {
-                match decode_bin_step_response(&res.returnData) {
+                match decode_bin_step_response(return_data) {
                     Ok(bin_step) => {
                         // Remove "_binStep" suffix to get pool name
                         let pool_name = price_calls[i]
@@ -125,9 +325,11 @@ pub async fn fetch_prices_batched<P: Provider>(
                             .strip_suffix("_binStep")
                             .unwrap_or(&price_calls[i].pool_name)
                             .to_string();
+                        cache_bin_step(price_calls[i].pool_address, bin_step);
                         lfj_bin_steps.insert(pool_name, bin_step);
                     }
                     Err(e) => {
+                        invalidate_bin_step(price_calls[i].pool_address);
                         debug!(
                             "Failed to decode LFJ binStep for {}: {}",
                             price_calls[i].pool_name, e
@@ -138,20 +340,96 @@ pub async fn fetch_prices_batched<P: Provider>(
         }
     }
 
-    // Calculate LFJ prices from collected activeId and binStep
+    for price in prices.iter_mut() {
+        price.liquidity = v3_liquidity.get(&price.pool_name).copied();
+    }
+
+    // Calculate LFJ prices from collected activeId and binStep (cached when this batch skipped it)
     for (pool_name, active_id) in lfj_active_ids.iter() {
-        if let Some(bin_step) = lfj_bin_steps.get(pool_name) {
-            let price = calculate_lfj_price(*active_id, *bin_step);
+        let address = lfj_addresses[pool_name];
+        let bin_step = lfj_bin_steps.get(pool_name).copied().or_else(|| cached_bin_step(address));
+        if let Some(bin_step) = bin_step {
+            let price = calculate_lfj_price(*active_id, bin_step);
+            if bin_step == 0 || !price.is_finite() || price <= 0.0 {
+                warn!("Anomalous LFJ price for {} (binStep {}), re-reading bin step next poll", pool_name, bin_step);
+                invalidate_bin_step(address);
+                continue;
+            }
             let fee_bps = lfj_fee_bps.get(pool_name).copied().unwrap_or(15);
             prices.push(PoolPrice {
                 pool_name: pool_name.clone(),
                 price,
                 fee_bps,
+                liquidity: None,
+                tick: None,
+                fetched_at,
+                block_number,
             });
         }
     }
 
-    Ok((prices, elapsed_ms))
+    prices
+}
+
+/// Raw aggregate3 over arbitrary (target, calldata) pairs, all at one block.
+/// Failed calls come back as None.
+pub async fn aggregate_raw<P: Provider>(provider: &P, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+    let calls: Vec<Call3> = calls
+        .iter()
+        .map(|(target, data)| Call3 { target: *target, allowFailure: true, callData: data.clone() })
+        .collect();
+
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(MULTICALL3_ADDRESS)
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(aggregate3Call { calls }.abi_encode())));
+
+    let result = provider.call(tx).await?;
+    let decoded = aggregate3Call::abi_decode_returns(&result)?;
+    Ok(decoded.into_iter().map(|r| r.success.then_some(r.returnData)).collect())
+}
+
+/// `wallet`'s balance of each (token, decimals) in token units, read in one
+/// aggregate3 round trip (individual eth_calls without Multicall3)
+pub async fn query_balances_batched<P: Provider>(
+    provider: &P,
+    wallet: Address,
+    tokens: &[(Address, u8)],
+) -> Result<Vec<f64>> {
+    let calldata = Bytes::from(balanceOfCall { account: wallet }.abi_encode());
+    let calls: Vec<Call3> = tokens
+        .iter()
+        .map(|(token, _)| Call3 { target: *token, allowFailure: true, callData: calldata.clone() })
+        .collect();
+
+    let results: Vec<Option<Bytes>> = match try_aggregate3(provider, calls).await? {
+        Some(decoded) => decoded.into_iter().map(|r| r.success.then_some(r.returnData)).collect(),
+        None => {
+            join_all(tokens.iter().map(|(token, _)| {
+                let tx = alloy::rpc::types::TransactionRequest::default()
+                    .to(*token)
+                    .input(alloy::rpc::types::TransactionInput::new(calldata.clone()));
+                async move { provider.call(tx).await.ok() }
+            }))
+            .await
+        }
+    };
+    decode_balances(tokens, &results)
+}
+
+/// Result `i` is `tokens[i]`'s balanceOf word
+fn decode_balances(tokens: &[(Address, u8)], results: &[Option<Bytes>]) -> Result<Vec<f64>> {
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, (token, decimals))| {
+            let balance = results
+                .get(i)
+                .and_then(|r| r.as_ref())
+                .and_then(|data| balanceOfCall::abi_decode_returns(data).ok())
+                .ok_or_else(|| eyre!("balanceOf failed for token {:?}", token))?;
+            Ok(balance.to::<u128>() as f64 / 10f64.powi(*decimals as i32))
+        })
+        .collect()
 }
 
 /// Fetch prices with node-aware batching optimization
@@ -161,7 +439,7 @@ pub async fn fetch_prices_optimized<P: Provider>(
     provider: &P,
     price_calls: Vec<PriceCall>,
     config: &NodeConfig,
-) -> Result<(Vec<PoolPrice>, u128)> {
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
     let batch_size = config.multicall_batch_size;
     let total_calls = price_calls.len();
 
@@ -173,12 +451,15 @@ pub async fn fetch_prices_optimized<P: Provider>(
     // For large call sets, batch them
     let start = std::time::Instant::now();
     let mut all_prices = Vec::new();
+    // Batches can land on different blocks; only report one if they all agree
+    let mut blocks = Vec::new();
 
     for (i, chunk) in price_calls.chunks(batch_size).enumerate() {
         debug!("Fetching batch {}/{}", i + 1, (total_calls + batch_size - 1) / batch_size);
 
-        let (prices, _) = fetch_prices_batched(provider, chunk.to_vec()).await?;
+        let (prices, _, block_number) = fetch_prices_batched(provider, chunk.to_vec()).await?;
         all_prices.extend(prices);
+        blocks.push(block_number);
 
         // No delay needed for local node, add small delay for remote to avoid rate limits
         if !config.is_local && i < (total_calls / batch_size) {
@@ -190,5 +471,227 @@ pub async fn fetch_prices_optimized<P: Provider>(
     debug!("Optimized multicall completed in {}ms ({} calls in {} batches)",
         elapsed_ms, total_calls, (total_calls + batch_size - 1) / batch_size);
 
-    Ok((all_prices, elapsed_ms))
+    let block_number = blocks.first().copied().flatten().filter(|b| blocks.iter().all(|x| *x == Some(*b)));
+    Ok((all_prices, elapsed_ms, block_number))
+}
+
+/// True for errors worth retrying: transport failures/timeouts and rate limits.
+/// Decode errors and execution reverts are permanent and fail immediately.
+fn is_transient(err: &eyre::Report) -> bool {
+    match err.downcast_ref::<RpcError<TransportErrorKind>>() {
+        Some(RpcError::Transport(_)) => true,
+        // 429 / -32005 "limit exceeded" from rate-limited RPCs
+        Some(RpcError::ErrorResp(payload)) => payload.code == 429 || payload.code == -32005,
+        _ => false,
+    }
+}
+
+/// Exponential backoff with up to 50% jitter (no rand dependency; clock nanos are enough)
+fn backoff_delay(base_delay: Duration, attempt: u32) -> Duration {
+    let exp = base_delay.saturating_mul(1u32 << attempt.min(10));
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let jitter = exp.mul_f64((nanos % 1000) as f64 / 2000.0);
+    exp + jitter
+}
+
+/// `fetch_prices_batched` with retries on transient RPC errors.
+/// Returns the last error once `max_retries` are exhausted.
+pub async fn fetch_prices_batched_retry<P: Provider>(
+    provider: &P,
+    price_calls: Vec<PriceCall>,
+    max_retries: u32,
+    base_delay: Duration,
+) -> Result<(Vec<PoolPrice>, u128, Option<u64>)> {
+    let mut attempt = 0;
+    loop {
+        match fetch_prices_batched(provider, price_calls.clone()).await {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < max_retries && is_transient(&e) => {
+                let delay = backoff_delay(base_delay, attempt);
+                attempt += 1;
+                warn!("Price fetch failed ({}), retry {}/{} in {}ms", e, attempt, max_retries, delay.as_millis());
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Delay before reconnecting a dropped monadNewHeads subscription
+const STREAM_RECONNECT_DELAY: Duration = Duration::from_millis(1000);
+
+/// No message for this long = treat the socket as dead (blocks are ~400ms)
+const STREAM_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stream prices on every Proposed block instead of polling.
+///
+/// Subscribes to `monadNewHeads` on `ws_url` and fires a multicall batch over
+/// `rpc_url` as soon as each Proposed header arrives, sending the header with
+/// the prices. Reconnects automatically if the socket drops; the task exits
+/// when the receiver is dropped.
+pub fn stream_prices_on_block(
+    rpc_url: &str,
+    ws_url: &str,
+    price_calls: Vec<PriceCall>,
+) -> Result<mpsc::Receiver<(MonadBlockHeader, Vec<PoolPrice>)>> {
+    let url: reqwest::Url = rpc_url.parse()?;
+    let provider = ProviderBuilder::new().connect_client(rpc_failover::client(url));
+    let ws_url = ws_url.to_string();
+    let (tx, rx) = mpsc::channel(16);
+
+    tokio::spawn(async move {
+        loop {
+            match stream_until_disconnect(&provider, &ws_url, &price_calls, &tx).await {
+                Ok(()) => return, // Receiver dropped
+                Err(e) => eprintln!(
+                    "Price stream disconnected: {}. Reconnecting in {}ms...",
+                    e,
+                    STREAM_RECONNECT_DELAY.as_millis()
+                ),
+            }
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(STREAM_RECONNECT_DELAY).await;
+        }
+    });
+
+    Ok(rx)
+}
+
+/// Run one subscription until the socket fails (Err) or the receiver is gone (Ok)
+async fn stream_until_disconnect<P: Provider>(
+    provider: &P,
+    ws_url: &str,
+    price_calls: &[PriceCall],
+    tx: &mpsc::Sender<(MonadBlockHeader, Vec<PoolPrice>)>,
+) -> Result<()> {
+    let (ws_stream, _) = rpc_failover::connect_ws(ws_url).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribe_msg = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_subscribe",
+        "params": ["monadNewHeads"]
+    });
+    write.send(Message::Text(subscribe_msg.to_string())).await?;
+
+    loop {
+        let msg = tokio::time::timeout(STREAM_READ_TIMEOUT, read.next())
+            .await
+            .map_err(|_| eyre!("no message for {}s", STREAM_READ_TIMEOUT.as_secs()))?;
+
+        match msg {
+            Some(Ok(Message::Text(text))) => {
+                let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+                let Some(header) = json
+                    .pointer("/params/result")
+                    .and_then(|v| serde_json::from_value::<MonadBlockHeader>(v.clone()).ok())
+                    .filter(|h| h.commit_state == "Proposed")
+                else {
+                    continue;
+                };
+
+                match fetch_prices_batched(provider, price_calls.to_vec()).await {
+                    Ok((prices, _, _)) => {
+                        if tx.send((header, prices)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                    Err(e) => debug!("Multicall on new block failed: {}", e),
+                }
+            }
+            Some(Ok(Message::Ping(data))) => {
+                let _ = write.send(Message::Pong(data)).await;
+            }
+            Some(Ok(Message::Close(_))) | None => return Err(eyre!("WebSocket closed")),
+            Some(Err(e)) => return Err(e.into()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_balances_by_position() {
+        let word = |v: u128| Some(Bytes::from(alloy::primitives::U256::from(v).to_be_bytes::<32>().to_vec()));
+        let tokens = [(Address::with_last_byte(1), 18), (Address::with_last_byte(2), 6)];
+
+        let balances = decode_balances(&tokens, &[word(2_500_000_000_000_000_000), word(1_250_000)]).unwrap();
+        assert_eq!(balances, vec![2.5, 1.25]);
+        assert!(decode_balances(&tokens, &[word(1), None]).is_err());
+    }
+
+    #[test]
+    fn test_retry_classification_and_backoff() {
+        let transport: eyre::Report = RpcError::<TransportErrorKind>::Transport(TransportErrorKind::BackendGone).into();
+        assert!(is_transient(&transport));
+        assert!(!is_transient(&eyre!("buffer overrun while deserializing")));
+
+        let base = Duration::from_millis(100);
+        let d = backoff_delay(base, 2);
+        assert!(d >= Duration::from_millis(400) && d <= Duration::from_millis(600));
+    }
+
+    #[test]
+    fn test_chunk_price_calls_keeps_pool_calls_together() {
+        use crate::config::{get_lfj_pool, get_v3_pools};
+        use crate::pools::{create_lfj_active_id_call, create_lfj_bin_step_call, create_v3_price_calls};
+
+        let mut calls = Vec::new();
+        for pool in get_v3_pools().iter().take(2) {
+            calls.extend(create_v3_price_calls(pool));
+        }
+        calls.push(create_lfj_active_id_call(&get_lfj_pool()));
+        calls.push(create_lfj_bin_step_call(&get_lfj_pool()));
+
+        // Size 3 would cut the second V3 pool's slot0 from its liquidity call
+        let chunks = chunk_price_calls(&calls, 3);
+        assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![4, 2]);
+        let names: Vec<&str> = chunks.iter().flat_map(|c| c.iter().map(|pc| pc.pool_name.as_str())).collect();
+        assert_eq!(names, calls.iter().map(|pc| pc.pool_name.as_str()).collect::<Vec<_>>());
+
+        assert_eq!(chunk_price_calls(&calls, 100).len(), 1);
+        assert_eq!(chunk_price_calls(&calls, 0).len(), 3);
+        assert!(chunk_price_calls(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_decode_price_results_combines_lfj_and_skips_failures() {
+        use crate::config::{get_lfj_pool, get_v3_pools};
+        use crate::pools::{create_lfj_active_id_call, create_lfj_bin_step_call, create_v3_price_calls};
+
+        let lfj = get_lfj_pool();
+        let v3 = get_v3_pools();
+        let mut calls = Vec::new();
+        calls.extend(create_v3_price_calls(&v3[0]));
+        calls.extend(create_v3_price_calls(&v3[1]));
+        calls.push(create_lfj_active_id_call(&lfj));
+        calls.push(create_lfj_bin_step_call(&lfj));
+        let word = |v: u64| Some(Bytes::from(alloy::primitives::U256::from(v).to_be_bytes::<32>().to_vec()));
+        let mut slot0 = alloy::primitives::U256::from(1u128 << 96).to_be_bytes::<32>().to_vec();
+        slot0.extend(alloy::primitives::I256::try_from(-5).unwrap().to_be_bytes::<32>()); // tick
+        slot0.extend([0u8; 32 * 5]);
+        // First V3 slot0 failed; second has an empty active range; LFJ at the zero bin with bin step 10
+        let results = vec![None, word(1), Some(Bytes::from(slot0)), word(0), word(8_388_608), word(10)];
+
+        let prices = decode_price_results(&calls, &results, std::time::Instant::now(), Some(7));
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[0].pool_name, v3[1].name);
+        assert_eq!((prices[0].liquidity, prices[0].tick), (Some(0), Some(-5)));
+        assert!(!prices[0].has_sufficient_liquidity());
+        assert_eq!(prices[1].pool_name, lfj.name);
+        assert!((prices[1].price - 1e12).abs() < 1.0);
+        assert_eq!(prices[1].block_number, Some(7));
+        assert!(prices[1].has_sufficient_liquidity());
+    }
 }
@@ -1,4 +1,109 @@
+use std::collections::VecDeque;
+use std::sync::OnceLock;
 use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+
+/// `--receipt-poll-ms` / `--receipt-timeout-secs`: (poll, timeout), applied to every receipt wait
+static RECEIPT_OVERRIDES: OnceLock<(Option<Duration>, Option<Duration>)> = OnceLock::new();
+
+/// Install the CLI receipt overrides (call once at startup)
+pub fn set_receipt_overrides(poll_ms: Option<u64>, timeout_secs: Option<u64>) {
+    let _ = RECEIPT_OVERRIDES.set((poll_ms.map(Duration::from_millis), timeout_secs.map(Duration::from_secs)));
+}
+
+/// (poll interval, timeout) for a receipt wait: the CLI overrides where given,
+/// otherwise the caller's defaults
+pub fn receipt_wait(default_poll: Duration, default_timeout: Duration) -> (Duration, Duration) {
+    let (poll, timeout) = RECEIPT_OVERRIDES.get().copied().unwrap_or_default();
+    (poll.unwrap_or(default_poll), timeout.unwrap_or(default_timeout))
+}
+
+/// Multicall latencies averaged by `AdaptivePoll`
+const ADAPTIVE_POLL_WINDOW: usize = 20;
+
+/// The next batch waits this multiple of the rolling latency
+const ADAPTIVE_POLL_HEADROOM: f64 = 1.2;
+
+/// Price poll timer that stretches to the node's multicall latency:
+/// the interval is `max(min, rolling_latency * 1.2)`, so a slow node isn't sent
+/// a new batch before the last would realistically complete, while a fast
+/// local node keeps polling at `min`.
+#[derive(Debug)]
+pub struct AdaptivePoll {
+    min: Duration,
+    samples: VecDeque<Duration>,
+    last_tick: Option<Instant>,
+}
+
+impl AdaptivePoll {
+    pub fn new(min: Duration) -> Self {
+        Self { min, samples: VecDeque::with_capacity(ADAPTIVE_POLL_WINDOW), last_tick: None }
+    }
+
+    /// Add one fetch's latency to the rolling window
+    pub fn record(&mut self, latency: Duration) {
+        if self.samples.len() == ADAPTIVE_POLL_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    pub fn rolling_latency(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            n => self.samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+
+    /// Current effective poll interval
+    pub fn interval(&self) -> Duration {
+        self.min.max(self.rolling_latency().mul_f64(ADAPTIVE_POLL_HEADROOM))
+    }
+
+    /// Wait until one effective interval after the previous tick (the first tick is immediate)
+    pub async fn tick(&mut self) {
+        if let Some(last) = self.last_tick {
+            sleep_until(last + self.interval()).await;
+        }
+        self.last_tick = Some(Instant::now());
+    }
+}
+
+/// WS endpoint for an RPC URL: local nodes serve WS on 8081, remote ones on the same host
+fn derive_ws_url(rpc_url: &str) -> String {
+    if rpc_url.contains("127.0.0.1") || rpc_url.contains("localhost") {
+        DEFAULT_MONAD_WS.to_string()
+    } else {
+        rpc_url.replace("https://", "wss://").replace("http://", "ws://")
+    }
+}
+
+fn url_list(var: &str) -> Option<Vec<String>> {
+    let urls: Vec<String> = std::env::var(var)
+        .ok()?
+        .split(',')
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .collect();
+    (!urls.is_empty()).then_some(urls)
+}
+
+/// (RPC URLs, WS URLs), primary first, never empty. `MONAD_RPC_URLS` /
+/// `MONAD_WS_URLS` take precedence over the single-URL variables; WS URLs
+/// are derived from the RPC list when neither WS variable is set.
+pub fn endpoint_urls_from_env() -> (Vec<String>, Vec<String>) {
+    // MONAD PORTS: 8080 (HTTP) and 8081 (WS) - NOT Ethereum's 8545/8546!
+    let rpc_urls = url_list("MONAD_RPC_URLS").unwrap_or_else(|| {
+        vec![std::env::var("MONAD_RPC_URL").unwrap_or_else(|_| DEFAULT_MONAD_RPC.to_string())]
+    });
+
+    let mut ws_urls = url_list("MONAD_WS_URLS")
+        .or_else(|| std::env::var("MONAD_WS_URL").ok().map(|u| vec![u]))
+        .unwrap_or_else(|| rpc_urls.iter().map(|u| derive_ws_url(u)).collect());
+    ws_urls.dedup();
+
+    (rpc_urls, ws_urls)
+}
 
 /// Configuration for Monad node connection
 /// Automatically detects local vs remote node and optimizes settings accordingly
@@ -10,10 +115,16 @@ pub struct NodeConfig {
     pub rpc_url: String,
     /// WebSocket endpoint
     pub ws_url: String,
+    /// `rpc_url` followed by its failover endpoints (`MONAD_RPC_URLS`)
+    pub rpc_urls: Vec<String>,
+    /// `ws_url` followed by its failover endpoints (`MONAD_WS_URLS`)
+    pub ws_urls: Vec<String>,
     /// Polling interval for price updates
     pub poll_interval: Duration,
     /// Polling interval for transaction receipts
     pub receipt_poll_interval: Duration,
+    /// How long to wait for a receipt before a final direct lookup
+    pub receipt_timeout: Duration,
     /// Gas buffer multiplier (1.10 = 10% buffer)
     pub gas_buffer: f64,
     /// Whether to skip block confirmations (safe for local node)
@@ -26,28 +137,15 @@ impl NodeConfig {
     /// Create configuration from environment variables
     /// Automatically detects local node by URL pattern and applies optimizations
     pub fn from_env() -> Self {
-        // MONAD PORTS: 8080 (HTTP) and 8081 (WS) - NOT Ethereum's 8545/8546!
-        let rpc_url = std::env::var("MONAD_RPC_URL")
-            .unwrap_or_else(|_| "http://127.0.0.1:8080".to_string());
-
-        // Derive WS URL from RPC URL or use explicit setting
-        let ws_url = std::env::var("MONAD_WS_URL").unwrap_or_else(|_| {
-            if rpc_url.contains("127.0.0.1") || rpc_url.contains("localhost") {
-                // Local node: use port 8081
-                "ws://127.0.0.1:8081".to_string()
-            } else {
-                // Remote: derive from RPC URL
-                rpc_url.replace("https://", "wss://").replace("http://", "ws://")
-            }
-        });
+        let (rpc_urls, ws_urls) = endpoint_urls_from_env();
 
         // Detect if we're connecting to a local node
-        let is_local = Self::detect_local_node(&rpc_url);
+        let is_local = Self::detect_local_node(&rpc_urls[0]);
 
         if is_local {
-            Self::local_config(rpc_url, ws_url)
+            Self::local_config(rpc_urls, ws_urls)
         } else {
-            Self::remote_config(rpc_url, ws_url)
+            Self::remote_config(rpc_urls, ws_urls)
         }
     }
 
@@ -65,13 +163,18 @@ impl NodeConfig {
     /// - No network latency to the node
     /// - State is immediately consistent (no propagation delay)
     /// - No rate limits from public RPC providers
-    fn local_config(rpc_url: String, ws_url: String) -> Self {
+    fn local_config(rpc_urls: Vec<String>, ws_urls: Vec<String>) -> Self {
+        let (receipt_poll_interval, receipt_timeout) =
+            receipt_wait(Duration::from_millis(2), Duration::from_secs(5)); // Ultra-fast 2ms polling
         Self {
             is_local: true,
-            rpc_url,
-            ws_url,
+            rpc_url: rpc_urls[0].clone(),
+            ws_url: ws_urls[0].clone(),
+            rpc_urls,
+            ws_urls,
             poll_interval: Duration::from_millis(20),        // 20x faster than remote (50ms)
-            receipt_poll_interval: Duration::from_millis(2), // Ultra-fast 2ms polling
+            receipt_poll_interval,
+            receipt_timeout,
             gas_buffer: 1.10,                                // 10% (tighter, saves cost)
             skip_block_wait: true,                           // State is consistent locally
             multicall_batch_size: 100,                       // Larger batches OK
@@ -83,13 +186,18 @@ impl NodeConfig {
     /// - Network latency
     /// - RPC rate limits
     /// - State propagation delays
-    fn remote_config(rpc_url: String, ws_url: String) -> Self {
+    fn remote_config(rpc_urls: Vec<String>, ws_urls: Vec<String>) -> Self {
+        let (receipt_poll_interval, receipt_timeout) =
+            receipt_wait(Duration::from_millis(100), Duration::from_secs(30)); // Standard polling
         Self {
             is_local: false,
-            rpc_url,
-            ws_url,
+            rpc_url: rpc_urls[0].clone(),
+            ws_url: ws_urls[0].clone(),
+            rpc_urls,
+            ws_urls,
             poll_interval: Duration::from_millis(1000),       // Standard 1s polling
-            receipt_poll_interval: Duration::from_millis(100), // Standard polling
+            receipt_poll_interval,
+            receipt_timeout,
             gas_buffer: 1.15,                                  // 15% buffer for safety
             skip_block_wait: false,                            // Wait for propagation
             multicall_batch_size: 50,                          // Conservative batch size
@@ -100,13 +208,19 @@ impl NodeConfig {
     pub fn log_config(&self) {
         println!("=== Monad Node Configuration ===");
         println!("RPC URL: {}", self.rpc_url);
+        if self.rpc_urls.len() > 1 {
+            println!("RPC Failover: {}", self.rpc_urls[1..].join(", "));
+        }
         println!("WS URL: {}", self.ws_url);
+        if self.ws_urls.len() > 1 {
+            println!("WS Failover: {}", self.ws_urls[1..].join(", "));
+        }
         println!("Local Node: {} {}",
             self.is_local,
             if self.is_local { "(optimizations enabled)" } else { "(conservative mode)" }
         );
         println!("Poll Interval: {:?}", self.poll_interval);
-        println!("Receipt Poll: {:?}", self.receipt_poll_interval);
+        println!("Receipt Poll: {:?} (timeout {:?})", self.receipt_poll_interval, self.receipt_timeout);
         println!("Gas Buffer: {:.0}%", (self.gas_buffer - 1.0) * 100.0);
         println!("Skip Block Wait: {}", self.skip_block_wait);
         println!("Multicall Batch: {}", self.multicall_batch_size);
@@ -148,3 +262,25 @@ pub const MONAD_BLOCK_TIME_MS: u64 = 1000;
 
 /// Monad finality time in milliseconds (near-instant)
 pub const MONAD_FINALITY_MS: u64 = 1000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_poll_tracks_latency_above_min() {
+        let mut poll = AdaptivePoll::new(Duration::from_millis(100));
+        assert_eq!(poll.interval(), Duration::from_millis(100));
+
+        // Fast node: latency * 1.2 stays under the minimum
+        poll.record(Duration::from_millis(30));
+        assert_eq!(poll.interval(), Duration::from_millis(100));
+
+        // Slow batches stretch the interval to the rolling average * 1.2
+        for _ in 0..ADAPTIVE_POLL_WINDOW {
+            poll.record(Duration::from_millis(500));
+        }
+        assert_eq!(poll.rolling_latency(), Duration::from_millis(500));
+        assert_eq!(poll.interval(), Duration::from_millis(600));
+    }
+}
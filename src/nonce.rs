@@ -1,27 +1,183 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, TxHash};
 use alloy::providers::Provider;
 use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, OnceLock};
 
 /// Global nonce manager - initialized once, used for all transactions
 static NONCE: OnceLock<AtomicU64> = OnceLock::new();
 static WALLET_ADDRESS: OnceLock<Address> = OnceLock::new();
 
+/// Counters for additional wallets (multi-wallet rotation); the first
+/// wallet passed to init_nonce stays in NONCE/WALLET_ADDRESS above.
+// address -> (counter, persistence key)
+type ExtraNonces = Option<HashMap<Address, (Arc<AtomicU64>, Option<String>)>>;
+static EXTRA_NONCES: Mutex<ExtraNonces> = Mutex::new(None);
+
+/// Opt-in persistence: set NONCE_PERSIST=1 to track the last sent nonce in
+/// NONCE_STATE_FILE across restarts (keyed by "<address>:<chain_id>").
+/// Nonces are recorded by `mark_sent` once their TX is accepted, not when reserved.
+const NONCE_PERSIST_ENV: &str = "NONCE_PERSIST";
+const NONCE_STATE_FILE: &str = ".nonce_state.json";
+
+/// State key, only set when persistence is enabled
+static PERSIST_KEY: OnceLock<String> = OnceLock::new();
+/// Serializes read-modify-write of the state file
+static PERSIST_LOCK: Mutex<()> = Mutex::new(());
+
+fn persistence_enabled() -> bool {
+    std::env::var(NONCE_PERSIST_ENV)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn state_key(wallet_address: Address, chain_id: u64) -> String {
+    format!("{:?}:{}", wallet_address, chain_id)
+}
+
+/// Last nonce sent from a wallet and the TX that used it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SentNonce {
+    nonce: u64,
+    tx_hash: TxHash,
+}
+
+fn read_state() -> HashMap<String, SentNonce> {
+    read_state_from(Path::new(NONCE_STATE_FILE))
+}
+
+fn read_state_from(path: &Path) -> HashMap<String, SentNonce> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Write via a temp file and rename, so a crash mid-write leaves the old
+/// state intact instead of a truncated file
+fn write_state_to(path: &Path, state: &HashMap<String, SentNonce>) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(state)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// max(chain pending, in-flight + 1) so a restart never reuses the nonce of a
+/// fire-and-forget TX the node still holds
+fn start_nonce(pending: u64, in_flight: Option<u64>) -> u64 {
+    match in_flight {
+        Some(in_flight) => pending.max(in_flight + 1),
+        None => pending,
+    }
+}
+
+/// Last sent nonce for this key, if any was persisted
+fn load_persisted(key: &str) -> Option<SentNonce> {
+    read_state().get(key).copied()
+}
+
+/// Record `sent` for `key`. Never moves the stored nonce backwards.
+fn persist_sent(key: &str, sent: SentNonce) {
+    let _guard = PERSIST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut state = read_state();
+    let entry = state.entry(key.to_string()).or_insert(sent);
+    if sent.nonce >= entry.nonce {
+        *entry = sent;
+    }
+    if let Err(e) = write_state_to(Path::new(NONCE_STATE_FILE), &state) {
+        tracing::warn!("Failed to persist nonce state: {}", e);
+    }
+}
+
+/// Record that `nonce` went out in `tx_hash`. No-op unless persistence is on;
+/// the file write runs on the blocking pool, off the send path.
+pub fn mark_sent(wallet_address: Address, nonce: u64, tx_hash: TxHash) {
+    let key = if WALLET_ADDRESS.get() == Some(&wallet_address) {
+        PERSIST_KEY.get().cloned()
+    } else {
+        extra_counter(wallet_address).and_then(|(_, key)| key)
+    };
+    let Some(key) = key else { return };
+
+    let sent = SentNonce { nonce, tx_hash };
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn_blocking(move || persist_sent(&key, sent));
+        }
+        Err(_) => persist_sent(&key, sent),
+    }
+}
+
+/// The nonce to continue from: the chain's pending nonce, or one past the
+/// persisted last sent nonce while the node still knows that TX (sent by a
+/// previous run, not yet counted as pending). A persisted TX the node has
+/// dropped or never saw is ignored, with a warning.
+pub async fn reconcile<P: Provider>(provider: &P, wallet_address: Address) -> Result<u64> {
+    let pending = provider.get_transaction_count(wallet_address).pending().await?;
+    if !persistence_enabled() {
+        return Ok(pending);
+    }
+
+    let chain_id = provider.get_chain_id().await?;
+    let Some(last) = load_persisted(&state_key(wallet_address, chain_id)) else {
+        return Ok(pending);
+    };
+    if last.nonce < pending {
+        return Ok(pending);
+    }
+
+    if provider.get_transaction_by_hash(last.tx_hash).await?.is_some() {
+        tracing::warn!(
+            "Persisted nonce {} is ahead of pending nonce {} for {:?} - {:?} is still pending",
+            last.nonce, pending, wallet_address, last.tx_hash
+        );
+        Ok(start_nonce(pending, Some(last.nonce)))
+    } else {
+        tracing::warn!(
+            "Persisted nonce {} for {:?} has no known TX ({:?}) - using pending nonce {}",
+            last.nonce, wallet_address, last.tx_hash, pending
+        );
+        Ok(pending)
+    }
+}
+
+/// After a "nonce too low" rejection: move `wallet_address`'s counter up to
+/// the reconciled nonce (never backwards). Returns that nonce.
+pub async fn resync<P: Provider>(provider: &P, wallet_address: Address) -> Result<u64> {
+    let pending = reconcile(provider, wallet_address).await?;
+    if WALLET_ADDRESS.get() == Some(&wallet_address) {
+        if let Some(counter) = NONCE.get() {
+            counter.fetch_max(pending, Ordering::SeqCst);
+        }
+    } else if let Some((counter, _)) = extra_counter(wallet_address) {
+        counter.fetch_max(pending, Ordering::SeqCst);
+    }
+    Ok(pending)
+}
+
 /// Initialize the nonce manager by fetching current nonce from RPC.
 /// Must be called once at startup before any transactions.
 /// Safe to call multiple times - subsequent calls are no-ops.
 pub async fn init_nonce<P: Provider>(provider: &P, wallet_address: Address) -> Result<u64> {
     // Store wallet address for validation
-    let _ = WALLET_ADDRESS.set(wallet_address);
+    let primary = *WALLET_ADDRESS.get_or_init(|| wallet_address);
+
+    if primary != wallet_address {
+        return init_extra_nonce(provider, wallet_address).await;
+    }
 
     // If already initialized, return current value
     if let Some(nonce) = NONCE.get() {
         return Ok(nonce.load(Ordering::SeqCst));
     }
 
-    // Fetch from RPC
-    let nonce = provider.get_transaction_count(wallet_address).await?;
+    let (nonce, key) = fetch_start_nonce(provider, wallet_address).await?;
+    if let Some(key) = key {
+        let _ = PERSIST_KEY.set(key);
+    }
 
     // Initialize atomic counter
     let _ = NONCE.set(AtomicU64::new(nonce));
@@ -29,6 +185,40 @@ pub async fn init_nonce<P: Provider>(provider: &P, wallet_address: Address) -> R
     Ok(nonce)
 }
 
+/// Register a non-primary wallet. No-op if already registered.
+async fn init_extra_nonce<P: Provider>(provider: &P, wallet_address: Address) -> Result<u64> {
+    if let Some(current) = extra_counter(wallet_address) {
+        return Ok(current.0.load(Ordering::SeqCst));
+    }
+
+    let (nonce, key) = fetch_start_nonce(provider, wallet_address).await?;
+    let mut extras = EXTRA_NONCES.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = extras
+        .get_or_insert_with(HashMap::new)
+        .entry(wallet_address)
+        .or_insert((Arc::new(AtomicU64::new(nonce)), key));
+    Ok(entry.0.load(Ordering::SeqCst))
+}
+
+fn extra_counter(wallet_address: Address) -> Option<(Arc<AtomicU64>, Option<String>)> {
+    EXTRA_NONCES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|m| m.get(&wallet_address).cloned())
+}
+
+/// Starting nonce from RPC, plus the persistence key when persistence is on
+async fn fetch_start_nonce<P: Provider>(provider: &P, wallet_address: Address) -> Result<(u64, Option<String>)> {
+    if persistence_enabled() {
+        let start = reconcile(provider, wallet_address).await?;
+        let chain_id = provider.get_chain_id().await?;
+        Ok((start, Some(state_key(wallet_address, chain_id))))
+    } else {
+        Ok((provider.get_transaction_count(wallet_address).await?, None))
+    }
+}
+
 /// Get the next nonce and increment the counter atomically.
 /// Panics if init_nonce() was not called first.
 pub fn next_nonce() -> u64 {
@@ -38,6 +228,33 @@ pub fn next_nonce() -> u64 {
         .fetch_add(1, Ordering::SeqCst)
 }
 
+/// Next nonce for a specific wallet. The primary wallet shares the
+/// `next_nonce()` counter, so single-wallet callers see no difference.
+/// Panics if init_nonce() was not called for this wallet.
+pub fn next_nonce_for(wallet_address: Address) -> u64 {
+    if WALLET_ADDRESS.get() == Some(&wallet_address) {
+        return next_nonce();
+    }
+
+    let (counter, _) = extra_counter(wallet_address)
+        .unwrap_or_else(|| panic!("Nonce manager not initialized for {:?}. Call init_nonce() first.", wallet_address));
+    counter.fetch_add(1, Ordering::SeqCst)
+}
+
+/// Hand back `nonce` after its TX failed to send, so the next reservation
+/// reuses it instead of leaving a gap. Only possible while it is still the
+/// wallet's latest reservation; returns false otherwise.
+pub fn release(wallet_address: Address, nonce: u64) -> bool {
+    let rewind = |counter: &AtomicU64| {
+        counter.compare_exchange(nonce + 1, nonce, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    };
+    if WALLET_ADDRESS.get() == Some(&wallet_address) {
+        NONCE.get().is_some_and(rewind)
+    } else {
+        extra_counter(wallet_address).is_some_and(|(counter, _)| rewind(&counter))
+    }
+}
+
 /// Reserve multiple nonces atomically (Issue 8: Batch nonce reservation)
 /// Returns a vector of nonces for use in parallel transaction building.
 #[allow(dead_code)]
@@ -74,3 +291,26 @@ pub async fn reset_nonce<P: Provider>(provider: &P) -> Result<u64> {
 
     Ok(nonce)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_nonce_and_state_file() {
+        // An in-flight nonce ahead of the chain wins; a stale one doesn't
+        assert_eq!(start_nonce(10, None), 10);
+        assert_eq!(start_nonce(10, Some(12)), 13);
+        assert_eq!(start_nonce(10, Some(4)), 10);
+
+        let key = state_key(Address::repeat_byte(0xab), 143);
+        assert_eq!(key, format!("0x{}:143", "ab".repeat(20)));
+
+        let path = std::env::temp_dir().join(format!("nonce_state_test_{}.json", std::process::id()));
+        let sent = SentNonce { nonce: 12, tx_hash: TxHash::repeat_byte(0x01) };
+        write_state_to(&path, &HashMap::from([(key.clone(), sent)])).unwrap();
+        assert_eq!(read_state_from(&path).get(&key), Some(&sent));
+        assert!(!path.with_extension("json.tmp").exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
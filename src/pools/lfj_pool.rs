@@ -1,9 +1,13 @@
-use alloy::primitives::Bytes;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
 use alloy::sol;
 use alloy::sol_types::SolCall;
 use eyre::Result;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
 
-use crate::config::PoolConfig;
+use crate::config::{get_bin_steps, get_lfj_pools, get_routers, tier_name, PoolConfig, RouterConfig, RouterType, TokenPair};
+use crate::multicall::aggregate_raw;
 use crate::pools::traits::{CallType, PriceCall};
 
 // LFJ Liquidity Book interface
@@ -13,6 +17,21 @@ sol! {
 
     #[derive(Debug)]
     function getBinStep() external view returns (uint16 binStep);
+
+    #[derive(Debug)]
+    function getFactory() external view returns (address factory);
+
+    #[derive(Debug)]
+    struct LBPairInformation {
+        uint16 binStep;
+        address LBPair;
+        bool createdByOwner;
+        bool ignoredForRouting;
+    }
+
+    #[derive(Debug)]
+    function getLBPairInformation(address tokenA, address tokenB, uint256 binStep)
+        external view returns (LBPairInformation memory lbPairInformation);
 }
 
 /// Creates the calldata for getActiveId() call
@@ -41,6 +60,112 @@ pub fn create_lfj_bin_step_call(pool: &PoolConfig) -> PriceCall {
     }
 }
 
+/// Find the configured LFJ pools' siblings for `pair` on their other bin steps.
+///
+/// Each configured LFJ pool is asked for its LBFactory, then the factory for
+/// `getLBPairInformation(pair, binStep)` over the pool's `bin_steps`. Pairs
+/// the factory marks as ignored for routing are skipped. Every other pair not
+/// already configured comes back as a (pool, router) named "<dex>@<binStep>";
+/// the router is the DEX's router with `pool_fee` set to the bin step.
+pub async fn discover_bin_steps<P: Provider>(provider: &P, pair: &TokenPair) -> Result<Vec<(PoolConfig, RouterConfig)>> {
+    let pools = get_lfj_pools();
+    let dexes: Vec<(&PoolConfig, RouterConfig)> = get_routers()
+        .into_iter()
+        .filter(|r| r.router_type == RouterType::LfjLB)
+        .filter_map(|r| Some((pools.iter().find(|p| p.address == r.pool_address)?, r)))
+        .collect();
+
+    let factory_calls: Vec<(Address, Bytes)> = dexes
+        .iter()
+        .map(|(pool, _)| (pool.address, Bytes::from(getFactoryCall {}.abi_encode())))
+        .collect();
+    let factories: Vec<Option<Address>> = aggregate_raw(provider, &factory_calls)
+        .await?
+        .into_iter()
+        .map(|r| r.and_then(|d| getFactoryCall::abi_decode_returns(&d).ok()))
+        .collect();
+
+    let mut probes: Vec<(usize, u16)> = Vec::new();
+    for (i, (pool, _)) in dexes.iter().enumerate() {
+        if factories[i].is_some() {
+            probes.extend(get_bin_steps(pool.name).into_iter().map(|step| (i, step)));
+        }
+    }
+
+    let pair_calls: Vec<(Address, Bytes)> = probes
+        .iter()
+        .map(|&(i, step)| {
+            let call = getLBPairInformationCall { tokenA: pair.base, tokenB: pair.quote, binStep: U256::from(step) };
+            (factories[i].unwrap_or_default(), Bytes::from(call.abi_encode()))
+        })
+        .collect();
+    let results = aggregate_raw(provider, &pair_calls).await?;
+
+    let mut venues: Vec<(PoolConfig, RouterConfig)> = Vec::new();
+    for (&(i, step), result) in probes.iter().zip(results) {
+        let Some(info) = result.and_then(|d| getLBPairInformationCall::abi_decode_returns(&d).ok()) else { continue };
+        let address = info.LBPair;
+        if address == Address::ZERO
+            || info.ignoredForRouting
+            || pools.iter().any(|p| p.address == address)
+            || venues.iter().any(|(p, _)| p.address == address)
+        {
+            continue;
+        }
+        let (dex_pool, dex_router) = &dexes[i];
+        let name: &'static str = Box::leak(tier_name(dex_router.name, step as u32).into_boxed_str());
+        venues.push((
+            PoolConfig { name, address, pool_type: dex_pool.pool_type, fee_bps: step as u32 },
+            RouterConfig { name, pool_address: address, pool_fee: step as u32, ..dex_router.clone() },
+        ));
+    }
+    Ok(venues)
+}
+
+/// Default `--refresh-binstep-every`: polls between bin step re-reads
+pub const DEFAULT_BIN_STEP_REFRESH: u32 = 100;
+
+static BIN_STEP_REFRESH: OnceLock<u32> = OnceLock::new();
+
+/// Bin step per LFJ pool and polls since it was read. Bin step is fixed per
+/// pair in practice, so price polls reuse it instead of re-reading every cycle.
+static BIN_STEPS: Mutex<Option<HashMap<Address, (u16, u32)>>> = Mutex::new(None);
+
+/// Install `--refresh-binstep-every` (1 = every poll, 0 = only after an anomaly)
+pub fn set_bin_step_refresh(every: u32) {
+    let _ = BIN_STEP_REFRESH.set(every);
+}
+
+pub fn cached_bin_step(pool: Address) -> Option<u16> {
+    BIN_STEPS.lock().unwrap().as_ref()?.get(&pool).map(|&(bin_step, _)| bin_step)
+}
+
+/// Store a freshly read bin step and restart its refresh countdown
+pub fn cache_bin_step(pool: Address, bin_step: u16) {
+    BIN_STEPS.lock().unwrap().get_or_insert_with(HashMap::new).insert(pool, (bin_step, 0));
+}
+
+/// Drop a pool's bin step (decode anomaly) so the next poll reads it again
+pub fn invalidate_bin_step(pool: Address) {
+    if let Some(cache) = BIN_STEPS.lock().unwrap().as_mut() {
+        cache.remove(&pool);
+    }
+}
+
+/// Whether this poll must include getBinStep for `pool`: nothing cached yet, or
+/// the refresh interval has passed. Each call counts as one poll.
+pub fn bin_step_due(pool: Address) -> bool {
+    let every = BIN_STEP_REFRESH.get().copied().unwrap_or(DEFAULT_BIN_STEP_REFRESH);
+    let mut cache = BIN_STEPS.lock().unwrap();
+    match cache.as_mut().and_then(|c| c.get_mut(&pool)) {
+        Some((_, polls)) => {
+            *polls += 1;
+            every > 0 && *polls >= every
+        }
+        None => true,
+    }
+}
+
 /// Decodes the getActiveId response
 pub fn decode_active_id_response(data: &[u8]) -> Result<u32> {
     let decoded = getActiveIdCall::abi_decode_returns(data)?;
@@ -73,3 +198,24 @@ pub fn calculate_lfj_price(active_id: u32, bin_step: u16) -> f64 {
     // For LFJ, if token0 is the lower address (WMON < USDC), price is token1/token0
     raw_price * 1e12
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_step_cached_until_refresh() {
+        let pool = Address::with_last_byte(0x1f);
+        assert!(bin_step_due(pool));
+
+        cache_bin_step(pool, 10);
+        assert_eq!(cached_bin_step(pool), Some(10));
+        let due: Vec<bool> = (0..DEFAULT_BIN_STEP_REFRESH).map(|_| bin_step_due(pool)).collect();
+        assert!(due[..due.len() - 1].iter().all(|d| !d));
+        assert!(due[due.len() - 1]);
+
+        invalidate_bin_step(pool);
+        assert_eq!(cached_bin_step(pool), None);
+        assert!(bin_step_due(pool));
+    }
+}
@@ -0,0 +1,256 @@
+//! Liquidity-aware trade sizing
+//!
+//! Estimates the largest WMON trade a pool can absorb while keeping price
+//! impact under a threshold. Used by AutoArb/ProdArb to clamp `amount` on
+//! thin pools instead of eating the slippage.
+//!
+//! V3-style pools (Uniswap, PancakeSwap, MondayTrade): uses in-range
+//! `liquidity()` and assumes no tick is crossed (conservative for deep books).
+//! LFJ: no single liquidity value, so depth is approximated from the active
+//! bin's reserves, assuming neighbouring bins hold similar depth.
+//!
+//! `attach_liquidity` fills `PoolPrice::liquidity` for the `--min-liquidity`
+//! spread filter and returns the snapshots for `--rank-by executable`.
+
+use alloy::primitives::{Address, Bytes};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+use std::collections::HashMap;
+
+use crate::config::{get_all_pools, PoolConfig, PoolType, TokenPair};
+use crate::execution::SwapDirection;
+use crate::pools::lfj_pool::{
+    calculate_lfj_price, decode_active_id_response, decode_bin_step_response, getActiveIdCall,
+    getBinStepCall,
+};
+use crate::pools::v3_pool::{decode_slot0_response, liquidityCall, slot0Call};
+use crate::pools::PoolPrice;
+
+sol! {
+    #[derive(Debug)]
+    function getBin(uint24 id) external view returns (uint128 binReserveX, uint128 binReserveY);
+}
+
+/// On-chain depth snapshot of a pool (token0/X = WMON, token1/Y = USDC),
+/// with the pair's decimals for converting raw amounts
+#[derive(Debug, Clone)]
+pub enum PoolLiquidity {
+    /// V3 in-range liquidity and sqrt(token1/token0) in raw units
+    ConcentratedLiquidity { liquidity: u128, sqrt_price: f64, base_decimals: u8 },
+    /// LFJ active bin reserves (human units) and bin step in bps
+    Bin { reserve_wmon: f64, reserve_usdc: f64, bin_step: u16, price: f64, base_decimals: u8, quote_decimals: u8 },
+}
+
+impl PoolLiquidity {
+    /// V3-equivalent liquidity. Bin pools have no single value, so it is derived
+    /// from reserve magnitude: L = value_in_token1 / (2 * sqrt(P)), the same L a
+    /// V3 position holding those reserves at this price would have.
+    pub fn effective_liquidity(&self) -> u128 {
+        match *self {
+            PoolLiquidity::ConcentratedLiquidity { liquidity, .. } => liquidity,
+            PoolLiquidity::Bin { reserve_wmon, reserve_usdc, price, base_decimals, quote_decimals, .. } => {
+                let raw_price = price * 10f64.powi(quote_decimals as i32 - base_decimals as i32);
+                if raw_price <= 0.0 {
+                    return 0;
+                }
+                let value_raw = reserve_usdc * 10f64.powi(quote_decimals as i32)
+                    + reserve_wmon * 10f64.powi(base_decimals as i32) * raw_price;
+                (value_raw / (2.0 * raw_price.sqrt())) as u128
+            }
+        }
+    }
+}
+
+impl PoolLiquidity {
+    /// Pool depth in WMON: V3 virtual reserve L / √P, or the active bin's value
+    pub fn depth_wmon(&self) -> f64 {
+        match *self {
+            PoolLiquidity::ConcentratedLiquidity { liquidity, sqrt_price, base_decimals } => {
+                if sqrt_price <= 0.0 {
+                    return 0.0;
+                }
+                liquidity as f64 / sqrt_price / 10f64.powi(base_decimals as i32)
+            }
+            PoolLiquidity::Bin { reserve_wmon, reserve_usdc, price, .. } => {
+                reserve_wmon + if price > 0.0 { reserve_usdc / price } else { 0.0 }
+            }
+        }
+    }
+}
+
+async fn eth_call<P: Provider>(provider: &P, to: Address, calldata: Vec<u8>) -> Result<Bytes> {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(to)
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(calldata)));
+    Ok(provider.call(tx).await?)
+}
+
+/// Fetch the liquidity snapshot needed by `max_trade_for_slippage`
+pub async fn fetch_pool_liquidity<P: Provider>(provider: &P, pool: &PoolConfig, pair: &TokenPair) -> Result<PoolLiquidity> {
+    match pool.pool_type {
+        PoolType::UniswapV3 | PoolType::PancakeV3 | PoolType::MondayTrade => {
+            let (slot0, liq) = tokio::join!(
+                eth_call(provider, pool.address, slot0Call {}.abi_encode()),
+                eth_call(provider, pool.address, liquidityCall {}.abi_encode()),
+            );
+            let sqrt_price_x96 = decode_slot0_response(&slot0?)?;
+            let liquidity = liquidityCall::abi_decode_returns(&liq?)?;
+
+            let sqrt_price_x96: f64 = sqrt_price_x96.to_string().parse().unwrap_or(0.0);
+            Ok(PoolLiquidity::ConcentratedLiquidity {
+                liquidity,
+                sqrt_price: sqrt_price_x96 / 2.0_f64.powi(96),
+                base_decimals: pair.base_decimals,
+            })
+        }
+        PoolType::LiquidityBook => {
+            let (active, step) = tokio::join!(
+                eth_call(provider, pool.address, getActiveIdCall {}.abi_encode()),
+                eth_call(provider, pool.address, getBinStepCall {}.abi_encode()),
+            );
+            let active_id = decode_active_id_response(&active?)?;
+            let bin_step = decode_bin_step_response(&step?)?;
+
+            let bin = eth_call(
+                provider,
+                pool.address,
+                getBinCall { id: alloy::primitives::Uint::<24, 1>::from(active_id) }.abi_encode(),
+            )
+            .await?;
+            let reserves = getBinCall::abi_decode_returns(&bin)?;
+
+            Ok(PoolLiquidity::Bin {
+                reserve_wmon: u128_to_human(reserves.binReserveX, pair.base_decimals),
+                reserve_usdc: u128_to_human(reserves.binReserveY, pair.quote_decimals),
+                bin_step,
+                price: calculate_lfj_price(active_id, bin_step),
+                base_decimals: pair.base_decimals,
+                quote_decimals: pair.quote_decimals,
+            })
+        }
+    }
+}
+
+/// Fill `liquidity` on each price from its pool's on-chain depth.
+/// Pools whose fetch fails are left at None (and dropped by the filter).
+/// Returns the fetched snapshots keyed by pool name.
+pub async fn attach_liquidity<P: Provider>(provider: &P, pair: &TokenPair, prices: &mut [PoolPrice]) -> HashMap<String, PoolLiquidity> {
+    let pools = get_all_pools();
+    let fetches = prices.iter().map(|price| {
+        let pool = pools.iter().find(|p| p.name.eq_ignore_ascii_case(&price.pool_name)).cloned();
+        async move {
+            match pool {
+                Some(pool) => fetch_pool_liquidity(provider, &pool, pair).await.ok(),
+                None => None,
+            }
+        }
+    });
+    let results = futures_util::future::join_all(fetches).await;
+
+    let mut snapshots = HashMap::new();
+    for (price, liq) in prices.iter_mut().zip(results) {
+        price.liquidity = liq.as_ref().map(|l| l.effective_liquidity());
+        if let Some(liq) = liq {
+            snapshots.insert(price.pool_name.clone(), liq);
+        }
+    }
+    snapshots
+}
+
+/// Largest WMON round trip that sells into `sell` and buys back from `buy`
+/// within `max_slippage_bps` of impact on each leg
+pub fn executable_size(sell: &PoolLiquidity, buy: &PoolLiquidity, max_slippage_bps: u32) -> f64 {
+    max_trade_for_slippage(sell, SwapDirection::Sell, max_slippage_bps)
+        .min(max_trade_for_slippage(buy, SwapDirection::Buy, max_slippage_bps))
+}
+
+fn u128_to_human(raw: u128, decimals: u8) -> f64 {
+    raw as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Largest trade (in WMON) keeping price impact under `max_slippage_bps`.
+///
+/// `Sell` = WMON in (price falls), `Buy` = USDC in for WMON out (price rises);
+/// for `Buy` the result is the WMON received.
+pub fn max_trade_for_slippage(liq: &PoolLiquidity, direction: SwapDirection, max_slippage_bps: u32) -> f64 {
+    let s = (max_slippage_bps as f64 / 10_000.0).min(0.9999);
+
+    match *liq {
+        PoolLiquidity::ConcentratedLiquidity { liquidity, sqrt_price, base_decimals } => {
+            if sqrt_price <= 0.0 {
+                return 0.0;
+            }
+            let l = liquidity as f64;
+            // price = sqrt_price^2, so an impact of s moves sqrt_price by sqrt(1 - s)
+            let sqrt_price_after = match direction {
+                SwapDirection::Sell => sqrt_price * (1.0 - s).sqrt(),
+                SwapDirection::Buy => sqrt_price / (1.0 - s).sqrt(),
+            };
+            // Δx = L * |1/√P' - 1/√P| (raw token0 units)
+            let dx_raw = l * (1.0 / sqrt_price_after - 1.0 / sqrt_price).abs();
+            dx_raw / 10f64.powi(base_decimals as i32)
+        }
+        PoolLiquidity::Bin { reserve_wmon, reserve_usdc, bin_step, price, .. } => {
+            // Within the active bin LB swaps at a constant price; each further
+            // bin moves the price by bin_step bps
+            let active_bin_wmon = match direction {
+                SwapDirection::Sell if price > 0.0 => reserve_usdc / price,
+                SwapDirection::Sell => 0.0,
+                SwapDirection::Buy => reserve_wmon,
+            };
+            let extra_bins = max_slippage_bps as f64 / bin_step.max(1) as f64;
+            active_bin_wmon * (1.0 + extra_bins)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v3_sell_impact_matches_formula() {
+        // Raw sqrt(price): 0.03 USDC/WMON -> 0.03e-12 raw
+        let sqrt_price = (0.03e-12_f64).sqrt();
+        let liq = PoolLiquidity::ConcentratedLiquidity { liquidity: 1_000_000_000_000_000_000, sqrt_price, base_decimals: 18 };
+
+        let max_wmon = max_trade_for_slippage(&liq, SwapDirection::Sell, 100);
+        assert!(max_wmon > 0.0);
+
+        // Doubling liquidity doubles the tradeable size
+        let deeper = PoolLiquidity::ConcentratedLiquidity { liquidity: 2_000_000_000_000_000_000, sqrt_price, base_decimals: 18 };
+        let max_deeper = max_trade_for_slippage(&deeper, SwapDirection::Sell, 100);
+        assert!((max_deeper / max_wmon - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bin_depth_grows_with_threshold() {
+        let liq = PoolLiquidity::Bin {
+            reserve_wmon: 1000.0, reserve_usdc: 30.0, bin_step: 10, price: 0.03, base_decimals: 18, quote_decimals: 6,
+        };
+
+        // 0 bps: only the active bin, at constant price
+        assert!((max_trade_for_slippage(&liq, SwapDirection::Sell, 0) - 1000.0).abs() < 1e-9);
+        assert!(max_trade_for_slippage(&liq, SwapDirection::Buy, 20) > 1000.0);
+    }
+
+    #[test]
+    fn test_bin_effective_liquidity_matches_v3_equivalent() {
+        // A V3 position with L at sqrt price s holds x = L/s token0 and y = L*s token1
+        let l = 1e20_f64;
+        let sqrt_price = (0.03e-12_f64).sqrt();
+        let bin = PoolLiquidity::Bin {
+            reserve_wmon: l / sqrt_price / 1e18,
+            reserve_usdc: l * sqrt_price / 1e6,
+            bin_step: 10,
+            price: 0.03,
+            base_decimals: 18,
+            quote_decimals: 6,
+        };
+
+        let effective = bin.effective_liquidity() as f64;
+        assert!((effective / l - 1.0).abs() < 1e-6);
+    }
+}
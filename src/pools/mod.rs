@@ -1,11 +1,12 @@
 pub mod lfj_pool;
+pub mod liquidity;
 pub mod monday_pool; // Documentation only - Monday Trade uses V3-style slot0()
 pub mod traits;
 pub mod v3_pool;
 
 pub use lfj_pool::{
-    calculate_lfj_price, create_lfj_active_id_call, create_lfj_bin_step_call,
-    decode_active_id_response, decode_bin_step_response,
+    bin_step_due, cache_bin_step, cached_bin_step, calculate_lfj_price, create_lfj_active_id_call,
+    create_lfj_bin_step_call, decode_active_id_response, decode_bin_step_response, invalidate_bin_step,
 };
-pub use traits::{CallType, PoolPrice, PriceCall};
-pub use v3_pool::{create_slot0_call, decode_slot0_to_price};
+pub use traits::{oldest_price_age, CallType, PoolPrice, PriceCall};
+pub use v3_pool::{create_slot0_call, create_v3_price_calls, decode_slot0_to_price};
@@ -1,9 +1,11 @@
 use alloy::primitives::{Address, Bytes};
+use std::time::{Duration, Instant};
 
 /// Type of call for decoding purposes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CallType {
     V3Slot0, // Used for Uniswap V3, PancakeSwap V3, and Monday Trade (all V3-style)
+    V3Liquidity,
     LfjActiveId,
     LfjBinStep,
 }
@@ -24,10 +26,62 @@ pub struct PoolPrice {
     pub pool_name: String,
     pub price: f64, // Price in USDC per WMON
     pub fee_bps: u32,
+    /// In-range liquidity: read with every V3 price (`liquidity()`); LFJ's is
+    /// only filled by `attach_liquidity` (`--min-liquidity`)
+    pub liquidity: Option<u128>,
+    /// Current tick of a V3-style pool (None for LFJ)
+    pub tick: Option<i32>,
+    /// When the multicall that read this price returned
+    pub fetched_at: Instant,
+    /// Block the multicall executed against (None if it couldn't be read)
+    pub block_number: Option<u64>,
 }
 
 impl PoolPrice {
     pub fn fee_percent(&self) -> f64 {
         self.fee_bps as f64 / 10000.0
     }
+
+    /// A decoded price is usable only if finite and positive (empty/zero slot0 decodes to 0)
+    pub fn is_price_valid(&self) -> bool {
+        self.price.is_finite() && self.price > 0.0
+    }
+
+    /// False when the pool's active range is known to be empty: slot0 still
+    /// reports a price there, but no trade can fill at it. Unknown depth passes.
+    pub fn has_sufficient_liquidity(&self) -> bool {
+        self.liquidity != Some(0)
+    }
+}
+
+/// Age of the oldest price among `pools` (None if none of them are present)
+pub fn oldest_price_age(prices: &[PoolPrice], pools: &[&str]) -> Option<Duration> {
+    prices
+        .iter()
+        .filter(|p| pools.contains(&p.pool_name.as_str()))
+        .map(|p| p.fetched_at.elapsed())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_price_age_only_counts_named_pools() {
+        let price = |name: &str, age_ms: u64| PoolPrice {
+            pool_name: name.to_string(),
+            price: 0.03,
+            fee_bps: 30,
+            liquidity: None,
+            tick: None,
+            fetched_at: Instant::now() - Duration::from_millis(age_ms),
+            block_number: Some(1),
+        };
+        let prices = vec![price("Uniswap", 100), price("LFJ", 900), price("MondayTrade", 5000)];
+
+        let age = oldest_price_age(&prices, &["Uniswap", "LFJ"]).unwrap();
+        assert!(age >= Duration::from_millis(900) && age < Duration::from_millis(5000));
+        assert!(oldest_price_age(&prices, &["PancakeSwap1"]).is_none());
+    }
 }
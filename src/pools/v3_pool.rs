@@ -1,9 +1,11 @@
-use alloy::primitives::{Bytes, U160};
+use alloy::primitives::{Address, Bytes, U160};
+use alloy::providers::Provider;
 use alloy::sol;
 use alloy::sol_types::SolCall;
 use eyre::Result;
 
-use crate::config::PoolConfig;
+use crate::config::{get_routers, get_v3_pools, tier_name, PoolConfig, RouterConfig, RouterType, TokenPair};
+use crate::multicall::aggregate_raw;
 use crate::pools::traits::{CallType, PriceCall};
 use crate::price::sqrt_price_x96_to_price;
 
@@ -19,6 +21,15 @@ sol! {
         uint8 feeProtocol,
         bool unlocked
     );
+
+    #[derive(Debug)]
+    function liquidity() external view returns (uint128);
+
+    #[derive(Debug)]
+    function factory() external view returns (address);
+
+    #[derive(Debug)]
+    function getPool(address tokenA, address tokenB, uint24 fee) external view returns (address);
 }
 
 /// Creates the calldata for slot0() call
@@ -34,6 +45,23 @@ pub fn create_slot0_call(pool: &PoolConfig) -> PriceCall {
     }
 }
 
+/// Creates the calldata for liquidity() (in-range liquidity at the current tick)
+pub fn create_v3_liquidity_call(pool: &PoolConfig) -> PriceCall {
+    PriceCall {
+        pool_name: format!("{}_liquidity", pool.name),
+        pool_address: pool.address,
+        calldata: Bytes::from(liquidityCall {}.abi_encode()),
+        fee_bps: pool.fee_bps,
+        call_type: CallType::V3Liquidity,
+    }
+}
+
+/// slot0() and liquidity() for a V3-style pool, so its price carries the
+/// active range's depth
+pub fn create_v3_price_calls(pool: &PoolConfig) -> [PriceCall; 2] {
+    [create_slot0_call(pool), create_v3_liquidity_call(pool)]
+}
+
 /// Decodes the slot0 response and extracts sqrtPriceX96
 pub fn decode_slot0_response(data: &[u8]) -> Result<U160> {
     let decoded = slot0Call::abi_decode_returns(data)?;
@@ -46,6 +74,86 @@ pub fn decode_slot0_to_price(data: &[u8]) -> Result<f64> {
     Ok(sqrt_price_x96_to_price(sqrt_price_x96))
 }
 
+/// Decodes slot0 response into (price, current tick)
+pub fn decode_slot0_price_and_tick(data: &[u8]) -> Result<(f64, i32)> {
+    let decoded = slot0Call::abi_decode_returns(data)?;
+    Ok((sqrt_price_x96_to_price(decoded.sqrtPriceX96), decoded.tick.as_i32()))
+}
+
+/// Decodes the liquidity() response
+pub fn decode_liquidity_response(data: &[u8]) -> Result<u128> {
+    Ok(liquidityCall::abi_decode_returns(data)?)
+}
+
+/// Find the configured V3 DEXes' pools for `pair` on the other fee `tiers`.
+///
+/// Each configured Uniswap/Pancake V3 pool is asked for its factory, then the
+/// factory for `getPool(pair, tier)`. Every pool found that isn't already
+/// configured comes back as a (pool, router) pair named "<dex>@<tier>"; the
+/// router is the DEX's router with `pool_fee` set to the tier.
+pub async fn discover_fee_tiers<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    tiers: &[u32],
+) -> Result<Vec<(PoolConfig, RouterConfig)>> {
+    let pools = get_v3_pools();
+    // DEXes sharing a factory (PancakeSwap1/2) are probed once, under the first name
+    let mut dexes: Vec<(&PoolConfig, RouterConfig)> = Vec::new();
+    for router in get_routers() {
+        if !matches!(router.router_type, RouterType::UniswapV3 | RouterType::PancakeV3) {
+            continue;
+        }
+        if let Some(pool) = pools.iter().find(|p| p.address == router.pool_address) {
+            dexes.push((pool, router));
+        }
+    }
+
+    let factory_calls: Vec<(Address, Bytes)> = dexes
+        .iter()
+        .map(|(pool, _)| (pool.address, Bytes::from(factoryCall {}.abi_encode())))
+        .collect();
+    let factories: Vec<Option<Address>> = aggregate_raw(provider, &factory_calls)
+        .await?
+        .into_iter()
+        .map(|r| r.and_then(|d| factoryCall::abi_decode_returns(&d).ok()))
+        .collect();
+
+    let mut probes: Vec<(usize, u32)> = Vec::new();
+    let mut seen_factories: Vec<Address> = Vec::new();
+    for (i, factory) in factories.iter().enumerate() {
+        let Some(factory) = factory else { continue };
+        if seen_factories.contains(factory) {
+            continue;
+        }
+        seen_factories.push(*factory);
+        probes.extend(tiers.iter().map(|&tier| (i, tier)));
+    }
+
+    let pool_calls: Vec<(Address, Bytes)> = probes
+        .iter()
+        .map(|&(i, tier)| {
+            let call = getPoolCall { tokenA: pair.base, tokenB: pair.quote, fee: tier.try_into().unwrap_or_default() };
+            (factories[i].unwrap_or_default(), Bytes::from(call.abi_encode()))
+        })
+        .collect();
+    let results = aggregate_raw(provider, &pool_calls).await?;
+
+    let mut venues = Vec::new();
+    for (&(i, tier), result) in probes.iter().zip(results) {
+        let Some(address) = result.and_then(|d| getPoolCall::abi_decode_returns(&d).ok()) else { continue };
+        if address == Address::ZERO || pools.iter().any(|p| p.address == address) {
+            continue;
+        }
+        let (dex_pool, dex_router) = &dexes[i];
+        let name: &'static str = Box::leak(tier_name(dex_router.name, tier).into_boxed_str());
+        venues.push((
+            PoolConfig { name, address, pool_type: dex_pool.pool_type, fee_bps: tier / 100 },
+            RouterConfig { name, pool_address: address, pool_fee: tier, ..dex_router.clone() },
+        ));
+    }
+    Ok(venues)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,5 +166,11 @@ mod tests {
 
         // slot0() selector is 0x3850c7bd
         assert_eq!(&call.calldata[..4], &[0x38, 0x50, 0xc7, 0xbd]);
+
+        // liquidity() selector is 0x1a686502, named after its pool like LFJ's binStep
+        let [_, liq] = create_v3_price_calls(&pools[0]);
+        assert_eq!(&liq.calldata[..4], &[0x1a, 0x68, 0x65, 0x02]);
+        assert_eq!(liq.pool_name, format!("{}_liquidity", pools[0].name));
+        assert_eq!(liq.call_type, CallType::V3Liquidity);
     }
 }
@@ -1,6 +1,10 @@
 use alloy::primitives::U160;
+use alloy::providers::Provider;
+use eyre::{eyre, Result};
 
-use crate::config::{USDC_DECIMALS, WMON_DECIMALS};
+use crate::config::{get_v3_pools, TokenPair, USDC_DECIMALS, WMON_DECIMALS};
+use crate::multicall::fetch_prices_batched;
+use crate::pools::{create_slot0_call, PoolPrice};
 
 /// Converts sqrtPriceX96 to human-readable price (USDC per WMON)
 ///
@@ -37,6 +41,48 @@ pub fn sqrt_price_x96_to_price(sqrt_price_x96: U160) -> f64 {
     price_ratio * decimal_adjustment
 }
 
+/// Re-express a price decoded with the WMON/USDC assumptions above (token0
+/// 18 decimals, quoted in token1) as `pair`'s quote per base. Identity for
+/// WMON/USDC; other pairs get their decimals and token order applied.
+pub fn rescale_for_pair(price: f64, pair: &TokenPair) -> f64 {
+    let base_is_token0 = pair.base < pair.quote;
+    let (dec0, dec1) = if base_is_token0 {
+        (pair.base_decimals, pair.quote_decimals)
+    } else {
+        (pair.quote_decimals, pair.base_decimals)
+    };
+    let wmon_usdc_adjustment = 10.0_f64.powi((WMON_DECIMALS as i32) - (USDC_DECIMALS as i32));
+    let token1_per_token0 = price / wmon_usdc_adjustment * 10.0_f64.powi(dec0 as i32 - dec1 as i32);
+
+    if base_is_token0 {
+        token1_per_token0
+    } else if token1_per_token0 > 0.0 {
+        1.0 / token1_per_token0
+    } else {
+        0.0
+    }
+}
+
+/// Gas token (MON) price in the quote token from WMON/USDC pool prices.
+/// The wrapper pegs WMON to MON 1:1, so a WMON/USDC mid is a MON/USDC mid.
+/// Median across pools, so one stale pool can't skew gas accounting.
+pub fn gas_token_price(prices: &[PoolPrice]) -> Option<f64> {
+    let mut mids: Vec<f64> = prices.iter().map(|p| p.price).filter(|p| *p > 0.0).collect();
+    if mids.is_empty() {
+        return None;
+    }
+    mids.sort_by(|a, b| a.total_cmp(b));
+    let mid = mids.len() / 2;
+    Some(if mids.len().is_multiple_of(2) { (mids[mid - 1] + mids[mid]) / 2.0 } else { mids[mid] })
+}
+
+/// Current MON price in the quote token (USDC per MON), read from the V3 WMON/USDC pools
+pub async fn gas_token_to_quote<P: Provider>(provider: &P) -> Result<f64> {
+    let calls = get_v3_pools().iter().map(create_slot0_call).collect();
+    let (prices, _, _) = fetch_prices_batched(provider, calls).await?;
+    gas_token_price(&prices).ok_or_else(|| eyre!("No WMON/USDC pool price to value gas with"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +99,17 @@ mod tests {
         assert!(price > 0.0 && price < 1.0, "Price {} is out of expected range", price);
     }
 
+    #[test]
+    fn test_rescale_for_pair() {
+        use crate::config::get_token_pair;
+
+        let wmon_usdc = get_token_pair("wmon-usdc").unwrap();
+        assert_eq!(rescale_for_pair(0.03, &wmon_usdc), 0.03);
+        // Same pool quoted the other way round
+        let usdc_wmon = get_token_pair("usdc-wmon").unwrap();
+        assert!((rescale_for_pair(0.04, &usdc_wmon) - 25.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_zero_price() {
         let sqrt_price = U160::ZERO;
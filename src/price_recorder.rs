@@ -0,0 +1,130 @@
+//! Long-running pool price recorder for offline analysis
+//!
+//! `RecordPrices` polls every pool with one multicall per `--interval-ms` and
+//! appends the set as a JSONL line (timestamp, block, prices). Unlike
+//! `MevValidate` there is no WS subscription or block lifecycle tracking, so
+//! it is cheap enough to leave running. With `--duration-secs 0` it runs until
+//! stopped and writes one file per UTC day (`prices.jsonl` becomes
+//! `prices_2025-03-01.jsonl`).
+
+use alloy::providers::Provider;
+use chrono::{NaiveDate, Utc};
+use eyre::{eyre, Result};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::log_analysis::WALL_CLOCK_FORMAT;
+use crate::logging;
+use crate::mev_validation::PoolPriceRecord;
+use crate::multicall::fetch_prices_batched;
+use crate::pools::PriceCall;
+
+/// One poll of every pool
+#[derive(Debug, Serialize)]
+pub struct PriceRecord {
+    pub wall_clock: String,
+    pub block_number: Option<u64>,
+    pub pair: String,
+    pub prices: Vec<PoolPriceRecord>,
+}
+
+/// `out` with the UTC date before its extension
+pub fn dated_path(out: &Path, date: NaiveDate) -> PathBuf {
+    let stem = out.file_stem().and_then(|s| s.to_str()).unwrap_or("prices");
+    let name = match out.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, date, ext),
+        None => format!("{}_{}", stem, date),
+    };
+    out.with_file_name(name)
+}
+
+fn open_append(path: &Path) -> Result<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| eyre!("Failed to open {}: {}", path.display(), e))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Poll `price_calls()` every `interval_ms` and append each set to `out`;
+/// `duration_secs == 0` runs forever, rotating the file daily
+pub async fn run_record_prices<P: Provider>(
+    provider: &P,
+    price_calls: impl Fn() -> Vec<PriceCall>,
+    interval_ms: u64,
+    out: &str,
+    duration_secs: u64,
+) -> Result<()> {
+    let out = Path::new(out);
+    let rotate = duration_secs == 0;
+    let mut day = Utc::now().date_naive();
+    let mut path = if rotate { dated_path(out, day) } else { out.to_path_buf() };
+    let mut writer = open_append(&path)?;
+
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  PRICE RECORDER");
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Output:          {}{}", path.display(), if rotate { " (rotates daily, UTC)" } else { "" });
+    println!("  Interval:        {} ms", interval_ms);
+    println!("  Duration:        {}", if rotate { "until stopped".to_string() } else { format!("{} s", duration_secs) });
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    let start = Instant::now();
+    let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(1)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let (mut written, mut failed) = (0u64, 0u64);
+
+    while rotate || start.elapsed() < Duration::from_secs(duration_secs) {
+        interval.tick().await;
+
+        let (prices, _, block_number) = match fetch_prices_batched(provider, price_calls()).await {
+            Ok(result) => result,
+            Err(e) => {
+                failed += 1;
+                tracing::warn!("Price fetch failed: {}", e);
+                continue;
+            }
+        };
+
+        let now = Utc::now();
+        if rotate && now.date_naive() != day {
+            writer.flush()?;
+            day = now.date_naive();
+            path = dated_path(out, day);
+            writer = open_append(&path)?;
+            println!("  Rotated to {}", path.display());
+        }
+
+        let record = PriceRecord {
+            wall_clock: now.format(WALL_CLOCK_FORMAT).to_string(),
+            block_number,
+            pair: logging::pair().to_string(),
+            prices: prices.iter().map(PoolPriceRecord::from).collect(),
+        };
+        writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+        writer.flush()?;
+        written += 1;
+        if written % 1000 == 0 {
+            println!("  {} records ({} failed polls) -> {}", written, failed, path.display());
+        }
+    }
+
+    println!("\n  Recorded {} price sets ({} failed polls) to {}", written, failed, path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dated_path_keeps_directory_and_extension() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert_eq!(dated_path(Path::new("logs/prices.jsonl"), date), PathBuf::from("logs/prices_2025-03-01.jsonl"));
+        assert_eq!(dated_path(Path::new("prices"), date), PathBuf::from("prices_2025-03-01"));
+    }
+}
@@ -0,0 +1,85 @@
+//! Inventory rebalancing
+//!
+//! One-directional arbs drift the wallet towards all-WMON or all-USDC.
+//! `plan_rebalance` sizes the single swap that restores a target WMON share
+//! (by value at the current price); `cheapest_dex` picks the pool giving the
+//! best fee-adjusted price for that direction.
+
+use crate::execution::SwapDirection;
+use crate::pools::PoolPrice;
+
+/// Swap needed to reach the target WMON share
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RebalancePlan {
+    pub direction: SwapDirection,
+    /// Human units of the input token (WMON for Sell, USDC for Buy)
+    pub amount_in: f64,
+    /// Current WMON share of wallet value, in percent
+    pub current_wmon_pct: f64,
+}
+
+/// Plan the swap moving `wmon`/`usdc` to `target_wmon_pct` at `price` (USDC per WMON).
+/// Returns None when already within `tolerance_bps` of the target (or nothing to trade).
+pub fn plan_rebalance(
+    wmon: f64,
+    usdc: f64,
+    price: f64,
+    target_wmon_pct: f64,
+    tolerance_bps: u32,
+) -> Option<RebalancePlan> {
+    if price <= 0.0 {
+        return None;
+    }
+    let total_wmon = wmon + usdc / price;
+    if total_wmon <= 0.0 {
+        return None;
+    }
+
+    let current_wmon_pct = wmon / total_wmon * 100.0;
+    // 1 bps of the share = 0.01 percentage points
+    if (current_wmon_pct - target_wmon_pct).abs() * 100.0 <= tolerance_bps as f64 {
+        return None;
+    }
+
+    let wmon_delta = total_wmon * target_wmon_pct / 100.0 - wmon;
+    let (direction, amount_in) = if wmon_delta < 0.0 {
+        (SwapDirection::Sell, -wmon_delta)
+    } else {
+        (SwapDirection::Buy, wmon_delta * price)
+    };
+    Some(RebalancePlan { direction, amount_in, current_wmon_pct })
+}
+
+/// Pool with the best fee-adjusted price: highest for selling WMON, lowest for buying
+pub fn cheapest_dex(prices: &[PoolPrice], direction: SwapDirection) -> Option<&PoolPrice> {
+    let effective = |p: &PoolPrice| match direction {
+        SwapDirection::Sell => p.price * (1.0 - p.fee_percent()),
+        SwapDirection::Buy => p.price * (1.0 + p.fee_percent()),
+    };
+    let valid = prices.iter().filter(|p| p.is_price_valid());
+    match direction {
+        SwapDirection::Sell => valid.max_by(|a, b| effective(a).total_cmp(&effective(b))),
+        SwapDirection::Buy => valid.min_by(|a, b| effective(a).total_cmp(&effective(b))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_rebalance_sizes_and_tolerance() {
+        // 100 WMON + 0 USDC at 0.03 -> sell half the WMON for 50%
+        let plan = plan_rebalance(100.0, 0.0, 0.03, 50.0, 10).unwrap();
+        assert_eq!(plan.direction, SwapDirection::Sell);
+        assert!((plan.amount_in - 50.0).abs() < 1e-9);
+
+        // All USDC -> buy with 3 USDC worth to reach 50%
+        let plan = plan_rebalance(0.0, 6.0, 0.03, 50.0, 10).unwrap();
+        assert_eq!(plan.direction, SwapDirection::Buy);
+        assert!((plan.amount_in - 3.0).abs() < 1e-9);
+
+        // 50.05% is inside a 10 bps band
+        assert!(plan_rebalance(50.05, 1.4985, 0.03, 50.0, 10).is_none());
+    }
+}
@@ -0,0 +1,154 @@
+//! RPC round-trip and multicall latency benchmark
+//!
+//! `BenchRpc` times `--samples` sequential calls of each read the bots make
+//! every poll: `eth_blockNumber`, the gas price, the full price multicall and
+//! the wallet balance multicall. It prints p50/p95/p99 per call next to the
+//! `NodeConfig` poll interval, and warns when an endpoint's latency is too
+//! erratic to poll tightly against. Read-only: nothing is signed or sent.
+
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use eyre::Result;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use crate::config::TokenPair;
+use crate::multicall::{fetch_prices_batched, query_balances_batched};
+use crate::pools::PriceCall;
+
+/// Coefficient of variation (stddev / mean) above which an endpoint is flagged unstable
+const HIGH_VARIANCE_CV: f64 = 0.5;
+
+/// Latency distribution of one call, in milliseconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub errors: usize,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl LatencyStats {
+    /// None if every sample failed
+    pub fn from_samples(mut ms: Vec<f64>, errors: usize) -> Option<Self> {
+        if ms.is_empty() {
+            return None;
+        }
+        ms.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: usize| ms[(ms.len() * p).div_ceil(100).max(1) - 1];
+        let mean = ms.iter().sum::<f64>() / ms.len() as f64;
+        let variance = ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / ms.len() as f64;
+        Some(Self {
+            samples: ms.len(),
+            errors,
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    pub fn is_unstable(&self) -> bool {
+        self.mean > 0.0 && self.stddev / self.mean > HIGH_VARIANCE_CV
+    }
+}
+
+/// Run `call` `samples` times back to back, timing each
+async fn time_call<F, Fut, T>(samples: u32, mut call: F) -> Option<LatencyStats>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut ms = Vec::with_capacity(samples as usize);
+    let mut errors = 0;
+    for _ in 0..samples {
+        let start = Instant::now();
+        match call().await {
+            Ok(_) => ms.push(start.elapsed().as_secs_f64() * 1000.0),
+            Err(e) => {
+                errors += 1;
+                tracing::warn!("Bench call failed: {}", e);
+            }
+        }
+    }
+    LatencyStats::from_samples(ms, errors)
+}
+
+/// Time each poll-loop read `samples` times and print the table
+pub async fn run_bench_rpc<P: Provider>(
+    provider: &P,
+    price_calls: impl Fn() -> Vec<PriceCall>,
+    pair: &TokenPair,
+    wallet: Address,
+    samples: u32,
+    poll_interval: Duration,
+) -> Result<()> {
+    let samples = samples.max(1);
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  RPC LATENCY BENCHMARK ({} samples per call)", samples);
+    println!("═══════════════════════════════════════════════════════════════\n");
+
+    let tokens = [(pair.base, pair.base_decimals), (pair.quote, pair.quote_decimals)];
+    let results = [
+        ("eth_blockNumber", time_call(samples, || async { Ok(provider.get_block_number().await?) }).await),
+        ("eth_gasPrice", time_call(samples, || async { Ok(provider.get_gas_price().await?) }).await),
+        ("price multicall", time_call(samples, || fetch_prices_batched(provider, price_calls())).await),
+        ("balance multicall", time_call(samples, || query_balances_batched(provider, wallet, &tokens)).await),
+    ];
+
+    println!("  {:<18} {:>9} {:>9} {:>9} {:>9} {:>9} {:>7}", "Call", "p50 ms", "p95 ms", "p99 ms", "mean", "stddev", "errors");
+    println!("  {}", "-".repeat(76));
+    for (name, stats) in &results {
+        match stats {
+            Some(s) => println!("  {:<18} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>7}{}",
+                name, s.p50, s.p95, s.p99, s.mean, s.stddev, s.errors,
+                if s.is_unstable() { "  ⚠ unstable" } else { "" }),
+            None => println!("  {:<18} {:>9} {:>9} {:>9} {:>9} {:>9} {:>7}", name, "-", "-", "-", "-", "-", samples),
+        }
+    }
+
+    println!();
+    println!("  Poll interval:     {} ms (NodeConfig)", poll_interval.as_millis());
+    if let Some((_, Some(prices))) = results.iter().find(|(name, _)| *name == "price multicall") {
+        if prices.p95 > poll_interval.as_millis() as f64 {
+            println!("  ⚠ Price multicall p95 ({:.0} ms) exceeds the poll interval: polls will overlap", prices.p95);
+        } else {
+            println!("  Price multicall p95 leaves {:.0} ms of each poll free", poll_interval.as_millis() as f64 - prices.p95);
+        }
+    }
+    let unstable: Vec<&str> = results
+        .iter()
+        .filter(|(_, stats)| stats.as_ref().is_some_and(LatencyStats::is_unstable))
+        .map(|(name, _)| *name)
+        .collect();
+    if !unstable.is_empty() {
+        println!("  ⚠ High latency variance (stddev > {:.0}% of mean) on {}: endpoint looks unstable",
+            HIGH_VARIANCE_CV * 100.0, unstable.join(", "));
+    }
+    println!("\n═══════════════════════════════════════════════════════════════");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles_and_variance() {
+        let steady = LatencyStats::from_samples((1..=100).map(|i| 50.0 + (i % 5) as f64).collect(), 2).unwrap();
+        assert_eq!((steady.samples, steady.errors), (100, 2));
+        assert_eq!((steady.p50, steady.p95, steady.p99), (52.0, 54.0, 54.0));
+        assert!(!steady.is_unstable());
+
+        // One slow call in ten doubles the spread
+        let spiky = LatencyStats::from_samples((0..10).map(|i| if i == 0 { 900.0 } else { 20.0 }).collect(), 0).unwrap();
+        assert_eq!((spiky.p50, spiky.p99), (20.0, 900.0));
+        assert!(spiky.is_unstable());
+
+        assert!(LatencyStats::from_samples(Vec::new(), 5).is_none());
+    }
+}
@@ -0,0 +1,303 @@
+//! RPC endpoint failover
+//!
+//! `MONAD_RPC_URLS` (comma-separated, primary first) turns every HTTP provider
+//! built through `client` into one shared failover transport: after
+//! `FAILOVER_AFTER_ERRORS` consecutive transport errors on the active
+//! endpoint, subsequent calls go to the next one. While failed over, a
+//! background task sends the primary an `eth_blockNumber` every
+//! `PRIMARY_RETRY_INTERVAL` (never a live request, which could be a
+//! transaction), and the first probe that answers moves traffic back.
+//! JSON-RPC error responses (reverts, nonce errors) come from a healthy node
+//! and don't count. Every endpoint's client times out after
+//! `REQUEST_TIMEOUT`, so a hung node counts as a transport error.
+//!
+//! WebSocket streams (`MONAD_WS_URLS`, derived from the RPC list if unset)
+//! fail over at connect time: `connect_ws` tries the primary first and then
+//! each fallback, so every reconnect retries the primary.
+//!
+//! Every failover is logged with the endpoint that took over;
+//! `print_endpoint_stats` reports requests and error rates per endpoint.
+
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::http::{reqwest, Http};
+use alloy::transports::{TransportError, TransportFut};
+use eyre::{eyre, Result};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::handshake::client::Response;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tower::Service;
+
+use crate::node_config::endpoint_urls_from_env;
+
+/// Consecutive transport errors on the active endpoint before rotating
+const FAILOVER_AFTER_ERRORS: u32 = 3;
+
+/// How often a failed-over transport probes the primary
+const PRIMARY_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Per-request timeout on every endpoint's HTTP client
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for the primary's `eth_blockNumber` probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+struct Endpoint {
+    url: String,
+    client: reqwest::Client,
+    transport: Http<reqwest::Client>,
+    requests: AtomicU64,
+    errors: AtomicU64,
+}
+
+struct FailoverState {
+    endpoints: Vec<Endpoint>,
+    /// Index of the endpoint taking traffic
+    active: AtomicUsize,
+    consecutive_errors: AtomicU32,
+    /// Set while a background task is probing the primary
+    probing: AtomicBool,
+    primary_retry: Duration,
+}
+
+impl FailoverState {
+    fn new(urls: &[reqwest::Url], primary_retry: Duration) -> Self {
+        let endpoints = urls
+            .iter()
+            .map(|url| {
+                let client = reqwest::Client::builder()
+                    .timeout(REQUEST_TIMEOUT)
+                    .build()
+                    .unwrap_or_else(|_| reqwest::Client::new());
+                Endpoint {
+                    url: url.to_string(),
+                    transport: Http::with_client(client.clone(), url.clone()),
+                    client,
+                    requests: AtomicU64::new(0),
+                    errors: AtomicU64::new(0),
+                }
+            })
+            .collect();
+        Self {
+            endpoints,
+            active: AtomicUsize::new(0),
+            consecutive_errors: AtomicU32::new(0),
+            probing: AtomicBool::new(false),
+            primary_retry,
+        }
+    }
+
+    /// Endpoint for the next call (probes never carry live requests)
+    fn select(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Returns true when this result moved traffic off the active endpoint
+    fn record(&self, index: usize, ok: bool) -> bool {
+        let endpoint = &self.endpoints[index];
+        endpoint.requests.fetch_add(1, Ordering::Relaxed);
+        if !ok {
+            endpoint.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        // A call that went out before a switch doesn't steer traffic
+        let active = self.active.load(Ordering::SeqCst);
+        if index != active {
+            return false;
+        }
+        if ok {
+            self.consecutive_errors.store(0, Ordering::SeqCst);
+            return false;
+        }
+        if self.consecutive_errors.fetch_add(1, Ordering::SeqCst) + 1 < FAILOVER_AFTER_ERRORS {
+            return false;
+        }
+        let next = (active + 1) % self.endpoints.len();
+        if self.active.compare_exchange(active, next, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+            return false;
+        }
+        self.consecutive_errors.store(0, Ordering::SeqCst);
+        tracing::warn!("RPC failover: {} failed {} times in a row, {} took over",
+            endpoint.url, FAILOVER_AFTER_ERRORS, self.endpoints[next].url);
+        true
+    }
+
+    /// Move traffic back to the primary after a successful probe
+    fn primary_recovered(&self) {
+        let active = self.active.swap(0, Ordering::SeqCst);
+        self.consecutive_errors.store(0, Ordering::SeqCst);
+        if active != 0 {
+            tracing::warn!("RPC failover: primary {} recovered, taking over from {}",
+                self.endpoints[0].url, self.endpoints[active].url);
+        }
+    }
+}
+
+/// True when `endpoint` answers `eth_blockNumber` within `PROBE_TIMEOUT`
+async fn probe(endpoint: &Endpoint) -> bool {
+    let body = r#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber","params":[]}"#;
+    let request = endpoint.client
+        .post(endpoint.url.as_str())
+        .header("content-type", "application/json")
+        .body(body)
+        .timeout(PROBE_TIMEOUT)
+        .send();
+    let Ok(Ok(response)) = tokio::time::timeout(PROBE_TIMEOUT, request).await else { return false };
+    if !response.status().is_success() {
+        return false;
+    }
+    let Ok(Ok(bytes)) = tokio::time::timeout(PROBE_TIMEOUT, response.bytes()).await else { return false };
+    serde_json::from_slice::<serde_json::Value>(&bytes).is_ok_and(|v| v.get("result").is_some())
+}
+
+/// Probe the primary every `primary_retry` until it answers or traffic is back on it
+async fn probe_primary(state: Arc<FailoverState>) {
+    loop {
+        tokio::time::sleep(state.primary_retry).await;
+        if state.active.load(Ordering::SeqCst) == 0 {
+            break;
+        }
+        let primary = &state.endpoints[0];
+        primary.requests.fetch_add(1, Ordering::Relaxed);
+        if probe(primary).await {
+            state.primary_recovered();
+            break;
+        }
+        primary.errors.fetch_add(1, Ordering::Relaxed);
+    }
+    state.probing.store(false, Ordering::SeqCst);
+}
+
+/// HTTP transport over several endpoints; clones share one state
+#[derive(Clone)]
+pub struct FailoverTransport(Arc<FailoverState>);
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let state = self.0.clone();
+        Box::pin(async move {
+            let index = state.select();
+            let mut transport = state.endpoints[index].transport.clone();
+            let result = transport.call(req).await;
+            let failed_over = state.record(index, result.is_ok());
+            if failed_over && !state.probing.swap(true, Ordering::SeqCst) {
+                tokio::spawn(probe_primary(state.clone()));
+            }
+            result
+        })
+    }
+}
+
+/// The shared transport, when `MONAD_RPC_URLS` lists more than one endpoint
+fn shared() -> Option<&'static FailoverTransport> {
+    static SHARED: OnceLock<Option<FailoverTransport>> = OnceLock::new();
+    SHARED
+        .get_or_init(|| {
+            let (rpc_urls, _) = endpoint_urls_from_env();
+            let urls: Vec<reqwest::Url> = rpc_urls.iter().filter_map(|u| u.parse().ok()).collect();
+            (urls.len() > 1).then(|| FailoverTransport(Arc::new(FailoverState::new(&urls, PRIMARY_RETRY_INTERVAL))))
+        })
+        .as_ref()
+}
+
+/// RPC client for `url`: the failover transport if `url` is one of its
+/// endpoints, otherwise plain HTTP
+pub fn client(url: reqwest::Url) -> RpcClient {
+    match shared() {
+        Some(failover) if failover.0.endpoints.iter().any(|e| e.url == url.as_str()) => {
+            let is_local = failover.0.endpoints[0].transport.guess_local();
+            RpcClient::new(failover.clone(), is_local)
+        }
+        _ => RpcClient::new_http(url),
+    }
+}
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Connect to `preferred`, then each other `MONAD_WS_URLS` endpoint in turn
+pub async fn connect_ws(preferred: &str) -> Result<(WsStream, Response)> {
+    let (_, ws_urls) = endpoint_urls_from_env();
+    let mut candidates = vec![preferred.to_string()];
+    candidates.extend(ws_urls.into_iter().filter(|u| u != preferred));
+
+    let mut last_error = None;
+    for (i, url) in candidates.iter().enumerate() {
+        match connect_async(url.as_str()).await {
+            Ok(connected) => {
+                if i > 0 {
+                    tracing::warn!("WS failover: {} unavailable, {} took over", preferred, url);
+                }
+                return Ok(connected);
+            }
+            Err(e) => {
+                tracing::warn!("WS connect to {} failed: {}", url, e);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(eyre!("All WS endpoints failed; last error: {}",
+        last_error.map(|e| e.to_string()).unwrap_or_else(|| "no endpoints".to_string())))
+}
+
+/// Requests and error rate per RPC endpoint (silent without a failover list)
+pub fn print_endpoint_stats() {
+    let Some(failover) = shared() else { return };
+    let active = failover.0.active.load(Ordering::SeqCst);
+    println!("\n  RPC endpoints:");
+    for (i, endpoint) in failover.0.endpoints.iter().enumerate() {
+        let requests = endpoint.requests.load(Ordering::Relaxed);
+        let errors = endpoint.errors.load(Ordering::Relaxed);
+        let rate = if requests > 0 { errors as f64 / requests as f64 * 100.0 } else { 0.0 };
+        println!("    {} {:<40} {:>8} req {:>6} err ({:.1}%)",
+            if i == active { "*" } else { " " }, endpoint.url, requests, errors, rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_after_consecutive_errors_and_returns_to_primary() {
+        let urls: Vec<reqwest::Url> = ["http://a.example", "http://b.example"].iter().map(|u| u.parse().unwrap()).collect();
+        let state = FailoverState::new(&urls, Duration::from_secs(3600));
+
+        // A success resets the streak
+        state.record(0, false);
+        state.record(0, false);
+        state.record(0, true);
+        state.record(0, false);
+        assert_eq!(state.select(), 0);
+
+        assert!(!state.record(0, false));
+        assert!(state.record(0, false));
+        assert_eq!(state.select(), 1);
+        assert_eq!(state.endpoints[0].errors.load(Ordering::Relaxed), 5);
+
+        // Live traffic stays on the fallback; a late primary result doesn't steer it
+        state.record(0, true);
+        assert_eq!(state.select(), 1);
+
+        // Only a successful probe moves traffic back
+        state.primary_recovered();
+        assert_eq!(state.select(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_probe_fails_on_unreachable_endpoint() {
+        let urls: Vec<reqwest::Url> = vec!["http://127.0.0.1:1".parse().unwrap()];
+        let state = FailoverState::new(&urls, Duration::from_secs(3600));
+        assert!(!probe(&state.endpoints[0]).await);
+    }
+}
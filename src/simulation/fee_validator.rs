@@ -0,0 +1,132 @@
+//! On-chain check of configured V3 pool fees
+//!
+//! `RouterConfig.pool_fee` (and `PoolConfig.fee_bps`) are compiled in or come
+//! from the config file. A pool configured on the wrong tier silently skews
+//! `effective_price_0_to_1` and every net-spread number, so at startup
+//! `FeeValidator` reads each V3 pool's `fee()` once, warns on any mismatch,
+//! and caches the on-chain fees in `config` for the rest of the session.
+
+use alloy::primitives::{Address, Bytes};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+use std::collections::HashMap;
+
+use crate::config::{get_routers, get_v3_pools, set_validated_fees};
+use crate::multicall::aggregate_raw;
+
+sol! {
+    #[derive(Debug)]
+    function fee() external view returns (uint24);
+}
+
+/// Configured vs on-chain fee for one V3 pool (both in 1e-6 units, 500 = 0.05%)
+#[derive(Debug, Clone)]
+pub struct PoolFeeInfo {
+    pub name: &'static str,
+    pub address: Address,
+    pub configured_fee: u32,
+    /// None if the `fee()` call failed
+    pub onchain_fee: Option<u32>,
+}
+
+impl PoolFeeInfo {
+    pub fn is_mismatch(&self) -> bool {
+        self.onchain_fee.is_some_and(|fee| fee != self.configured_fee)
+    }
+}
+
+/// Fee check results for every configured V3 pool
+#[derive(Debug, Clone, Default)]
+pub struct FeeValidator {
+    pub pools: Vec<PoolFeeInfo>,
+}
+
+impl FeeValidator {
+    /// Read `fee()` from every V3 pool in one multicall. The configured fee is
+    /// the router's `pool_fee`, falling back to the pool's `fee_bps`.
+    pub async fn fetch<P: Provider>(provider: &P) -> Result<Self> {
+        let routers = get_routers();
+        let pools = get_v3_pools();
+        let calls: Vec<(Address, Bytes)> =
+            pools.iter().map(|p| (p.address, Bytes::from(feeCall {}.abi_encode()))).collect();
+        let results = aggregate_raw(provider, &calls).await?;
+
+        let pools = pools
+            .iter()
+            .zip(results)
+            .map(|(pool, result)| PoolFeeInfo {
+                name: pool.name,
+                address: pool.address,
+                configured_fee: routers
+                    .iter()
+                    .find(|r| r.pool_address == pool.address)
+                    .map_or(pool.fee_bps * 100, |r| r.pool_fee),
+                onchain_fee: result
+                    .and_then(|d| feeCall::abi_decode_returns(&d).ok())
+                    .map(|fee| fee.to::<u32>()),
+            })
+            .collect();
+        Ok(Self { pools })
+    }
+
+    pub fn mismatches(&self) -> Vec<&PoolFeeInfo> {
+        self.pools.iter().filter(|p| p.is_mismatch()).collect()
+    }
+
+    /// On-chain fee by pool address, for pools whose `fee()` read succeeded
+    pub fn onchain_fees(&self) -> HashMap<Address, u32> {
+        self.pools.iter().filter_map(|p| Some((p.address, p.onchain_fee?))).collect()
+    }
+
+    pub fn print_report(&self) {
+        let checked = self.pools.iter().filter(|p| p.onchain_fee.is_some()).count();
+        println!("  Pool fees: {}/{} V3 pool(s) checked on-chain", checked, self.pools.len());
+        for p in &self.pools {
+            match p.onchain_fee {
+                Some(_) if p.is_mismatch() => {}
+                Some(fee) => println!("    {:<18} {} ✓", p.name, fee),
+                None => println!("    {:<18} fee() call failed, keeping configured {}", p.name, p.configured_fee),
+            }
+        }
+        for p in self.mismatches() {
+            let onchain = p.onchain_fee.unwrap_or_default();
+            println!("  ⚠️  FEE MISMATCH {} ({:?}): configured {} but pool fee() is {}. Using {} for this session.",
+                p.name, p.address, p.configured_fee, onchain, onchain);
+            tracing::warn!(pool = p.name, configured = p.configured_fee, onchain, "Configured pool fee disagrees with pool contract");
+        }
+    }
+}
+
+/// Validate the V3 pool fees once and cache the on-chain values for the session
+pub async fn validate_pool_fees<P: Provider>(provider: &P) -> Result<FeeValidator> {
+    let validator = FeeValidator::fetch(provider).await?;
+    validator.print_report();
+    set_validated_fees(validator.onchain_fees())?;
+    Ok(validator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_mismatch_detection() {
+        let info = |configured_fee, onchain_fee| PoolFeeInfo {
+            name: "Uniswap",
+            address: Address::ZERO,
+            configured_fee,
+            onchain_fee,
+        };
+        let validator = FeeValidator { pools: vec![info(3000, Some(3000)), info(3000, Some(500)), info(500, None)] };
+
+        assert!(!validator.pools[0].is_mismatch());
+        assert!(validator.pools[1].is_mismatch());
+        assert!(!validator.pools[2].is_mismatch());
+        assert_eq!(validator.mismatches().len(), 1);
+        // Failed reads aren't cached, so those pools keep their configured fee
+        assert_eq!(validator.onchain_fees().len(), 1);
+        assert_eq!(validator.onchain_fees()[&Address::ZERO], 500);
+    }
+}
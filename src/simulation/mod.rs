@@ -0,0 +1,6 @@
+pub mod fee_validator;
+pub mod paper;
+pub mod profit_calculator;
+pub mod quote_fetcher;
+
+pub use quote_fetcher::{print_arb_quote, QuoteFetcher};
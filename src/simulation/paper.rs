@@ -0,0 +1,137 @@
+//! Paper trading against a virtual balance
+//!
+//! `AutoArb --paper` runs the full decision pipeline live but, instead of
+//! sending transactions, fills each triggered arb at its `QuoteFetcher`
+//! quote minus modeled gas. `PaperAccount` holds the virtual WMON/USDC and
+//! turns each fill into a `PostExecutionSnapshot` flagged `simulated`, so
+//! paper sessions log exactly like real ones.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::stats::PostExecutionSnapshot;
+
+/// Simulated outcome of one arb
+#[derive(Debug, Clone, Copy)]
+pub struct PaperFill {
+    pub amount_in: f64,
+    pub usdc_intermediate: f64,
+    pub wmon_out: f64,
+    /// Modeled gas, charged in WMON (MON ~= WMON)
+    pub gas_wmon: f64,
+    pub gas_units: u64,
+}
+
+/// Paper session results for the session summary line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperSummary {
+    pub trades: u64,
+    pub wins: u64,
+    pub win_rate: f64,
+    pub cumulative_pnl_wmon: f64,
+    pub final_wmon: f64,
+    pub final_usdc: f64,
+}
+
+/// Virtual WMON/USDC inventory
+#[derive(Debug, Clone)]
+pub struct PaperAccount {
+    pub wmon: f64,
+    pub usdc: f64,
+    initial_wmon: f64,
+    trades: u64,
+    wins: u64,
+}
+
+impl PaperAccount {
+    pub fn new(wmon: f64, usdc: f64) -> Self {
+        Self { wmon, usdc, initial_wmon: wmon, trades: 0, wins: 0 }
+    }
+
+    /// Apply a fill to the balance and snapshot it like a real execution
+    pub fn fill(&mut self, fill: &PaperFill, elapsed_ms: u128) -> PostExecutionSnapshot {
+        let wmon_delta = fill.wmon_out - fill.amount_in - fill.gas_wmon;
+        self.wmon += wmon_delta;
+        self.trades += 1;
+        if wmon_delta > 0.0 {
+            self.wins += 1;
+        }
+
+        PostExecutionSnapshot {
+            timestamp: Local::now().to_rfc3339(),
+            wmon_balance: self.wmon,
+            usdc_balance: self.usdc,
+            mon_balance: 0.0,
+            swap1_success: true,
+            swap1_tx_hash: String::new(),
+            swap1_gas_used: fill.gas_units,
+            swap1_gas_estimated: fill.gas_units,
+            swap2_success: true,
+            swap2_tx_hash: String::new(),
+            swap2_gas_used: 0,
+            swap2_gas_estimated: 0,
+            actual_usdc_received: fill.usdc_intermediate,
+            actual_wmon_back: fill.wmon_out,
+            wmon_delta,
+            usdc_delta: 0.0,
+            mon_delta: 0.0,
+            total_gas_cost_mon: fill.gas_wmon,
+            net_profit_wmon: wmon_delta,
+            net_profit_bps: if fill.amount_in > 0.0 { (wmon_delta / fill.amount_in * 10_000.0) as i32 } else { 0 },
+            total_execution_ms: elapsed_ms,
+            simulated: true,
+        }
+    }
+
+    pub fn cumulative_pnl(&self) -> f64 {
+        self.wmon - self.initial_wmon
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.trades > 0 {
+            self.wins as f64 / self.trades as f64
+        } else {
+            0.0
+        }
+    }
+
+    pub fn summary(&self) -> PaperSummary {
+        PaperSummary {
+            trades: self.trades,
+            wins: self.wins,
+            win_rate: self.win_rate(),
+            cumulative_pnl_wmon: self.cumulative_pnl(),
+            final_wmon: self.wmon,
+            final_usdc: self.usdc,
+        }
+    }
+
+    pub fn print_summary(&self) {
+        println!("\n  Paper Trading (simulated):");
+        println!("    Trades:    {} ({} profitable, {:.1}% win rate)", self.trades, self.wins, self.win_rate() * 100.0);
+        println!("    P&L:       {:>+.6} WMON", self.cumulative_pnl());
+        println!("    Balances:  {:.6} WMON / {:.6} USDC", self.wmon, self.usdc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paper_fills_track_pnl_and_win_rate() {
+        let mut account = PaperAccount::new(100.0, 5.0);
+        let win = PaperFill { amount_in: 10.0, usdc_intermediate: 0.3, wmon_out: 10.05, gas_wmon: 0.01, gas_units: 500_000 };
+        let loss = PaperFill { wmon_out: 9.99, ..win };
+
+        let post = account.fill(&win, 3);
+        assert!(post.simulated);
+        assert!((post.net_profit_wmon - 0.04).abs() < 1e-9);
+        assert_eq!(post.net_profit_bps, 40);
+        account.fill(&loss, 3);
+
+        assert!((account.cumulative_pnl() - 0.02).abs() < 1e-9);
+        assert_eq!(account.win_rate(), 0.5);
+        assert_eq!(account.usdc, 5.0);
+    }
+}
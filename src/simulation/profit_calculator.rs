@@ -0,0 +1,320 @@
+//! Profit estimation and position sizing
+//!
+//! `estimate_profit` breaks an arb priced off pool spreads down into gross
+//! spread, DEX fees and gas, all in WMON; `breakdown_from_quote` does the
+//! same from on-chain quotes, which already include price impact. Gas is paid
+//! in MON, which WMON pegs 1:1, and can also be valued in USDC.
+//!
+//! Spread-based profit scales linearly with size, but real output doesn't:
+//! both pools' price impact grows with the trade while gas stays flat.
+//! `optimize_size` sweeps sizes up to a ceiling, quotes each with
+//! `QuoteFetcher`, and keeps the one with the highest net WMON after gas.
+//! Net profit is concave in size, so the sweep stops at the first decrease.
+
+use alloy::providers::Provider;
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::config::{RouterConfig, TokenPair};
+use super::quote_fetcher::{ArbQuote, QuoteFetcher};
+
+/// Gas budget for a fast arb (two swaps, ~250k limit each)
+pub const FAST_ARB_GAS_UNITS: u64 = 500_000;
+
+/// Gas budget for an atomic arb (single contract call)
+pub const ATOMIC_ARB_GAS_UNITS: u64 = 400_000;
+
+/// Expected profit components for one arb, in WMON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitBreakdown {
+    /// WMON gained from the price difference alone (no fees, no gas)
+    pub gross_wmon: f64,
+    /// WMON lost to both pools' swap fees
+    pub dex_fees_wmon: f64,
+    /// Gas cost in WMON (gas_units * max fee, charged on limit even on revert)
+    pub gas_wmon: f64,
+    pub net_wmon: f64,
+    pub gas_units: u64,
+    pub gas_price_wei: u128,
+    /// MON price in the quote token (USDC per MON); 0 if not priced
+    #[serde(default)]
+    pub gas_token_price: f64,
+    /// Gas cost in the quote token
+    #[serde(default)]
+    pub gas_quote: f64,
+    /// Net profit in the quote token
+    #[serde(default)]
+    pub net_quote: f64,
+}
+
+impl ProfitBreakdown {
+    /// Value the gas and net profit in the quote token at `mon_price` (USDC per MON, = per WMON)
+    pub fn with_gas_token_price(mut self, mon_price: f64) -> Self {
+        self.gas_token_price = mon_price;
+        self.gas_quote = self.gas_wmon * mon_price;
+        self.net_quote = self.net_wmon * mon_price;
+        self
+    }
+}
+
+/// Currency P&L is reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PnlDenom {
+    Wmon,
+    Usdc,
+}
+
+impl FromStr for PnlDenom {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "wmon" => Ok(Self::Wmon),
+            "usdc" => Ok(Self::Usdc),
+            _ => Err(eyre!("Unknown P&L denomination '{}'. Expected wmon or usdc", s)),
+        }
+    }
+}
+
+/// Formats realized WMON P&L in the `--pnl-denom` currency
+#[derive(Debug, Clone)]
+pub struct PnlReport {
+    pub denom: PnlDenom,
+    /// Latest WMON/USDC price seen (USDC per WMON)
+    wmon_price: Option<f64>,
+}
+
+impl PnlReport {
+    pub fn new(denom: PnlDenom) -> Self {
+        Self { denom, wmon_price: None }
+    }
+
+    /// Record the current WMON/USDC price; None (no pool priced) keeps the last one
+    pub fn observe_price(&mut self, wmon_price: Option<f64>) {
+        if let Some(price) = wmon_price.filter(|p| p.is_finite() && *p > 0.0) {
+            self.wmon_price = Some(price);
+        }
+    }
+
+    /// `pnl_wmon` in the report currency, with the WMON figure alongside.
+    /// Falls back to WMON only, with a note, when no price has been seen.
+    pub fn format(&self, pnl_wmon: f64) -> String {
+        match (self.denom, self.wmon_price) {
+            (PnlDenom::Wmon, _) => format!("{:+.6} WMON", pnl_wmon),
+            (PnlDenom::Usdc, Some(price)) => {
+                format!("{:+.6} USDC ({:+.6} WMON @ {:.6} USDC/WMON)", pnl_wmon * price, pnl_wmon, price)
+            }
+            (PnlDenom::Usdc, None) => format!("{:+.6} WMON (no WMON/USDC price, USDC unavailable)", pnl_wmon),
+        }
+    }
+}
+
+/// Estimate profit for selling `amount` WMON at `sell_price` and buying back at `buy_price`.
+/// Prices are pool mid prices (USDC/WMON); fees are in bps.
+pub fn estimate_profit(
+    amount: f64,
+    sell_price: f64,
+    buy_price: f64,
+    sell_fee_bps: u32,
+    buy_fee_bps: u32,
+    gas_units: u64,
+    gas_price_wei: u128,
+) -> ProfitBreakdown {
+    let wmon_back_no_fees = if buy_price > 0.0 { amount * sell_price / buy_price } else { 0.0 };
+    let wmon_back = wmon_back_no_fees
+        * (1.0 - sell_fee_bps as f64 / 10_000.0)
+        * (1.0 - buy_fee_bps as f64 / 10_000.0);
+
+    let gross_wmon = wmon_back_no_fees - amount;
+    let dex_fees_wmon = wmon_back_no_fees - wmon_back;
+    let gas_wmon = gas_units as f64 * gas_price_wei as f64 / 1e18;
+
+    ProfitBreakdown {
+        gross_wmon,
+        dex_fees_wmon,
+        gas_wmon,
+        net_wmon: gross_wmon - dex_fees_wmon - gas_wmon,
+        gas_units,
+        gas_price_wei,
+        gas_token_price: 0.0,
+        gas_quote: 0.0,
+        net_quote: 0.0,
+    }
+}
+
+/// Fixed-point steps `break_even_net_bps` takes before giving up
+const BREAK_EVEN_ITERATIONS: usize = 64;
+
+/// Net spread (bps, after DEX fees) at which an arb of `amount` WMON just
+/// pays for `gas_units` of gas. `max_fee_at(net_bps)` is the fee bid at that
+/// spread: flat for fast arb, spread-boosted for atomic arb
+/// (`gas_cache::calculate_gas_price`). None if the bid grows faster with the
+/// spread than the spread pays for it, or gas alone is over 100% of `amount`.
+pub fn break_even_net_bps(amount: f64, gas_units: u64, max_fee_at: impl Fn(i32) -> u128) -> Option<f64> {
+    if amount <= 0.0 {
+        return None;
+    }
+    let gas_bps = |max_fee: u128| gas_units as f64 * max_fee as f64 / 1e18 / amount * 10_000.0;
+    let mut bps = gas_bps(max_fee_at(0));
+    for _ in 0..BREAK_EVEN_ITERATIONS {
+        if bps >= 10_000.0 {
+            return None;
+        }
+        let next = gas_bps(max_fee_at(bps.ceil() as i32));
+        if next <= bps {
+            return Some(bps);
+        }
+        bps = next;
+    }
+    None
+}
+
+/// Candidate sizes: `steps` even increments up to and including `max_amount`
+pub fn sweep_sizes(max_amount: f64, steps: u32) -> Vec<f64> {
+    let steps = steps.max(1);
+    (1..=steps).map(|i| max_amount * i as f64 / steps as f64).collect()
+}
+
+/// Profit breakdown of a quoted arb. Quotes already include fees and price
+/// impact, so fees are backed out of the quoted output and impact stays in gross.
+pub fn breakdown_from_quote(
+    quote: &ArbQuote,
+    sell_fee_bps: u32,
+    buy_fee_bps: u32,
+    gas_units: u64,
+    gas_price_wei: u128,
+) -> ProfitBreakdown {
+    let fee_factor = (1.0 - sell_fee_bps as f64 / 10_000.0) * (1.0 - buy_fee_bps as f64 / 10_000.0);
+    let wmon_back_no_fees = if fee_factor > 0.0 { quote.wmon_out / fee_factor } else { quote.wmon_out };
+
+    let gross_wmon = wmon_back_no_fees - quote.amount_in;
+    let dex_fees_wmon = wmon_back_no_fees - quote.wmon_out;
+    let gas_wmon = gas_units as f64 * gas_price_wei as f64 / 1e18;
+
+    ProfitBreakdown {
+        gross_wmon,
+        dex_fees_wmon,
+        gas_wmon,
+        net_wmon: gross_wmon - dex_fees_wmon - gas_wmon,
+        gas_units,
+        gas_price_wei,
+        gas_token_price: 0.0,
+        gas_quote: 0.0,
+        net_quote: 0.0,
+    }
+}
+
+/// Size (<= `max_amount`) with the highest quoted net profit for a fast arb
+/// selling on `sell_pool` and buying back on `buy_pool`
+pub async fn optimize_size<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    sell_pool: &RouterConfig,
+    buy_pool: &RouterConfig,
+    max_amount: f64,
+    steps: u32,
+    gas_price_wei: u128,
+) -> Result<(f64, ProfitBreakdown)> {
+    let mut best: Option<(f64, ProfitBreakdown)> = None;
+
+    for amount in sweep_sizes(max_amount, steps) {
+        let quote = match QuoteFetcher::AtomicQuote.quote_arb(provider, pair, sell_pool, buy_pool, amount).await {
+            Ok(quote) => quote,
+            // Larger sizes can exhaust a pool's liquidity; keep what we have
+            Err(e) if best.is_some() => {
+                println!("    {:>12.4} WMON: quote failed ({}), stopping sweep", amount, e);
+                break;
+            }
+            Err(e) => return Err(e),
+        };
+        let breakdown = breakdown_from_quote(
+            &quote,
+            sell_pool.pool_fee / 100,
+            buy_pool.pool_fee / 100,
+            FAST_ARB_GAS_UNITS,
+            gas_price_wei,
+        );
+        println!("    {:>12.4} WMON -> net {:+.6} WMON", amount, breakdown.net_wmon);
+
+        match best {
+            Some((_, ref b)) if breakdown.net_wmon <= b.net_wmon => break,
+            _ => best = Some((amount, breakdown)),
+        }
+    }
+
+    best.ok_or_else(|| eyre!("No sizes to sweep (max_amount {})", max_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gas_can_make_spread_unprofitable() {
+        // 50 bps gross, 10 bps fees, 100 WMON -> ~0.4 WMON before gas
+        let cheap = estimate_profit(100.0, 1.005, 1.0, 5, 5, 500_000, 100_000_000_000);
+        assert!((cheap.gross_wmon - 0.5).abs() < 1e-9);
+        assert!(cheap.net_wmon > 0.0);
+
+        // Same trade with 1000 gwei gas: 0.5 MON of gas wipes it out
+        let expensive = estimate_profit(100.0, 1.005, 1.0, 5, 5, 500_000, 1_000_000_000_000);
+        assert!(expensive.net_wmon < 0.0);
+
+        // 0.05 MON of gas at 0.03 USDC/MON
+        let priced = cheap.with_gas_token_price(0.03);
+        assert!((priced.gas_quote - 0.0015).abs() < 1e-12);
+        assert!((priced.net_quote - priced.net_wmon * 0.03).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_break_even_with_flat_and_boosted_fees() {
+        // 500k gas at 100 gwei = 0.05 WMON = 5 bps of 100 WMON
+        let flat = break_even_net_bps(100.0, 500_000, |_| 100_000_000_000).unwrap();
+        assert!((flat - 5.0).abs() < 1e-9);
+
+        // +1 gwei per 10 bps: 1 WMON pays 400 bps before the boost, ~667 bps after
+        let boosted = |bps: i32| 100_000_000_000 + (bps.max(0) as u128 / 10) * 1_000_000_000;
+        let atomic = break_even_net_bps(1.0, 400_000, boosted).unwrap();
+        assert!(atomic > 650.0 && atomic < 667.0, "{}", atomic);
+
+        // 0.1 WMON: each bps of spread raises the gas by 4 bps, no break-even
+        assert!(break_even_net_bps(0.1, 400_000, boosted).is_none());
+        assert!(break_even_net_bps(0.0, 400_000, |_| 1).is_none());
+    }
+
+    #[test]
+    fn test_pnl_report_converts_and_falls_back() {
+        let mut report = PnlReport::new("USDC".parse().unwrap());
+        assert_eq!(report.format(1.5), "+1.500000 WMON (no WMON/USDC price, USDC unavailable)");
+
+        report.observe_price(Some(0.04));
+        report.observe_price(None);
+        assert_eq!(report.format(-2.0), "-0.080000 USDC (-2.000000 WMON @ 0.040000 USDC/WMON)");
+        assert_eq!(PnlReport::new(PnlDenom::Wmon).format(0.25), "+0.250000 WMON");
+        assert!("eth".parse::<PnlDenom>().is_err());
+    }
+
+    #[test]
+    fn test_sweep_sizes_and_breakdown() {
+        assert_eq!(sweep_sizes(100.0, 4), vec![25.0, 50.0, 75.0, 100.0]);
+        assert_eq!(sweep_sizes(100.0, 0), vec![100.0]);
+
+        // 100 WMON in, 100.3 out after 2x 30 bps fees
+        let quote = ArbQuote {
+            block_number: 1,
+            amount_in: 100.0,
+            usdc_intermediate: 3.0,
+            wmon_out: 100.3,
+            sell_spot: 0.03,
+            buy_spot: 0.03,
+            sell_impact_bps: 0.0,
+            buy_impact_bps: 0.0,
+            gas_estimate: 0,
+        };
+        let b = breakdown_from_quote(&quote, 30, 30, 500_000, 100_000_000_000);
+        assert!((b.gas_wmon - 0.05).abs() < 1e-12);
+        assert!((b.net_wmon - (0.3 - 0.05)).abs() < 1e-9);
+        assert!(b.dex_fees_wmon > 0.5 && b.dex_fees_wmon < 0.7);
+    }
+}
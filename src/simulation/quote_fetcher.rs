@@ -0,0 +1,366 @@
+//! Read-only arb quotes via QuoterV2
+//!
+//! `AtomicQuote` packs both legs' quotes, both pools' `slot0` and the block
+//! number into one Multicall3 `eth_call`, so every number comes from the
+//! same block. Leg 2 can't consume leg 1's output inside one call, so it is
+//! quoted for the spot-expected USDC (from a slot0 probe) and scaled to leg
+//! 1's actual output (error is second order in price impact).
+//!
+//! `quote_routers` quotes one leg of a given size on every router in the same
+//! kind of single call, for comparing fills rather than spot prices.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+
+use crate::config::{RouterConfig, RouterType, TokenPair, MULTICALL3_ADDRESS};
+use crate::execution::SwapDirection;
+use crate::multicall::aggregate_raw;
+use crate::pools::decode_slot0_to_price;
+use crate::pools::v3_pool::slot0Call;
+
+sol! {
+    #[derive(Debug)]
+    struct QuoteExactInputSingleParams {
+        address tokenIn;
+        address tokenOut;
+        uint256 amountIn;
+        uint24 fee;
+        uint160 sqrtPriceLimitX96;
+    }
+
+    #[derive(Debug)]
+    function quoteExactInputSingle(QuoteExactInputSingleParams memory params) external returns (
+        uint256 amountOut,
+        uint160 sqrtPriceX96After,
+        uint32 initializedTicksCrossed,
+        uint256 gasEstimate
+    );
+
+    #[derive(Debug)]
+    function getBlockNumber() external view returns (uint256 blockNumber);
+}
+
+/// How arb quotes are fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteFetcher {
+    /// Both legs in a single multicall at the current block
+    AtomicQuote,
+}
+
+/// Expected outcome of sell-then-buy for `amount_in` base tokens
+#[derive(Debug, Clone)]
+pub struct ArbQuote {
+    pub block_number: u64,
+    pub amount_in: f64,
+    pub usdc_intermediate: f64,
+    pub wmon_out: f64,
+    pub sell_spot: f64,
+    pub buy_spot: f64,
+    /// Execution shortfall vs spot after the pool fee, per leg
+    pub sell_impact_bps: f64,
+    pub buy_impact_bps: f64,
+    pub gas_estimate: u64,
+}
+
+impl ArbQuote {
+    pub fn net_profit(&self) -> f64 {
+        self.wmon_out - self.amount_in
+    }
+
+    pub fn profit_bps(&self) -> f64 {
+        if self.amount_in > 0.0 {
+            self.net_profit() / self.amount_in * 10_000.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// One router's fill for a single swap of a fixed size
+#[derive(Debug, Clone)]
+pub struct RouterQuote {
+    pub router: &'static str,
+    pub spot: f64,
+    /// Output at spot after the pool fee (what an infinitely deep pool would give)
+    pub ideal_out: f64,
+    pub amount_out: f64,
+    /// Realized USDC per WMON
+    pub effective_price: f64,
+}
+
+impl RouterQuote {
+    /// Shortfall vs `ideal_out`, i.e. price impact beyond the pool fee
+    pub fn impact_bps(&self) -> f64 {
+        if self.ideal_out > 0.0 {
+            (self.ideal_out - self.amount_out) / self.ideal_out * 10_000.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Every quotable router's fill for one swap, best fill first
+#[derive(Debug, Clone)]
+pub struct RouterComparison {
+    pub block_number: u64,
+    pub quotes: Vec<RouterQuote>,
+    /// Routers that couldn't be quoted, with the reason
+    pub skipped: Vec<(&'static str, String)>,
+}
+
+impl RouterComparison {
+    /// Best fee-adjusted spot and best fill, when they're different routers
+    pub fn spot_vs_fill_mismatch(&self) -> Option<(&RouterQuote, &RouterQuote)> {
+        let best_fill = self.quotes.first()?;
+        let best_spot = self.quotes.iter().max_by(|a, b| a.ideal_out.total_cmp(&b.ideal_out))?;
+        (best_spot.router != best_fill.router).then_some((best_spot, best_fill))
+    }
+}
+
+impl QuoteFetcher {
+    /// QuoterV2 address for a router, or why it can't be quoted
+    pub fn quoter_for(router: &RouterConfig) -> Result<Address> {
+        if router.router_type == RouterType::LfjLB {
+            return Err(eyre!(
+                "{}: LFJ's LBQuoter uses findBestPathFromAmountIn(route, amountIn), not QuoterV2's \
+                 quoteExactInputSingle; read-only quotes aren't supported for LFJ",
+                router.name
+            ));
+        }
+        if router.router_type == RouterType::UniswapV4 {
+            return Err(eyre!(
+                "{}: V4Quoter takes a PoolKey and its pools have no slot0 address; read-only quotes aren't supported for V4",
+                router.name
+            ));
+        }
+        router.quoter.ok_or_else(|| {
+            eyre!(
+                "{}: no quoter configured. Add `quoter = \"0x...\"` (QuoterV2) to its [[routers]] entry in --config",
+                router.name
+            )
+        })
+    }
+
+    /// Quote selling `amount` base on `sell` and buying it back on `buy`
+    pub async fn quote_arb<P: Provider>(
+        &self,
+        provider: &P,
+        pair: &TokenPair,
+        sell: &RouterConfig,
+        buy: &RouterConfig,
+        amount: f64,
+    ) -> Result<ArbQuote> {
+        match self {
+            Self::AtomicQuote => atomic_quote(provider, pair, sell, buy, amount).await,
+        }
+    }
+
+    /// Quote swapping `amount` of `direction`'s input token on each of `routers`
+    pub async fn quote_routers<P: Provider>(
+        &self,
+        provider: &P,
+        pair: &TokenPair,
+        routers: &[RouterConfig],
+        direction: SwapDirection,
+        amount: f64,
+    ) -> Result<RouterComparison> {
+        match self {
+            Self::AtomicQuote => atomic_router_quotes(provider, pair, routers, direction, amount).await,
+        }
+    }
+}
+
+fn to_units(amount: f64, decimals: u8) -> U256 {
+    U256::from((amount * 10f64.powi(decimals as i32)) as u128)
+}
+
+fn from_units(amount: U256, decimals: u8) -> f64 {
+    amount.to::<u128>() as f64 / 10f64.powi(decimals as i32)
+}
+
+fn quote_call(router: &RouterConfig, token_in: Address, token_out: Address, amount_in: U256) -> Bytes {
+    Bytes::from(
+        quoteExactInputSingleCall {
+            params: QuoteExactInputSingleParams {
+                tokenIn: token_in,
+                tokenOut: token_out,
+                amountIn: amount_in,
+                fee: alloy::primitives::Uint::from(router.pool_fee),
+                sqrtPriceLimitX96: alloy::primitives::U160::ZERO,
+            },
+        }
+        .abi_encode(),
+    )
+}
+
+async fn atomic_quote<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    sell: &RouterConfig,
+    buy: &RouterConfig,
+    amount: f64,
+) -> Result<ArbQuote> {
+    let sell_quoter = QuoteFetcher::quoter_for(sell)?;
+    let buy_quoter = QuoteFetcher::quoter_for(buy)?;
+
+    // Leg 2's input is sized from the sell pool's spot price; every number
+    // reported comes from the single multicall below
+    let slot0 = Bytes::from(slot0Call {}.abi_encode());
+    let probe = aggregate_raw(provider, &[(sell.pool_address, slot0.clone())]).await?;
+    let sell_spot_probe = probe[0]
+        .as_ref()
+        .ok_or_else(|| eyre!("{}: slot0 failed on {:?}", sell.name, sell.pool_address))
+        .and_then(|d| decode_slot0_to_price(d))?;
+
+    let sell_fee = sell.pool_fee as f64 / 1_000_000.0;
+    let buy_fee = buy.pool_fee as f64 / 1_000_000.0;
+    let est_usdc = amount * sell_spot_probe * (1.0 - sell_fee);
+
+    let calls = [
+        (MULTICALL3_ADDRESS, Bytes::from(getBlockNumberCall {}.abi_encode())),
+        (sell.pool_address, slot0.clone()),
+        (buy.pool_address, slot0),
+        (sell_quoter, quote_call(sell, pair.base, pair.quote, to_units(amount, pair.base_decimals))),
+        (buy_quoter, quote_call(buy, pair.quote, pair.base, to_units(est_usdc, pair.quote_decimals))),
+    ];
+    let results = aggregate_raw(provider, &calls).await?;
+    let get = |i: usize, what: &str| results[i].as_ref().ok_or_else(|| eyre!("{} call reverted", what));
+
+    let block_number = getBlockNumberCall::abi_decode_returns(get(0, "getBlockNumber")?)?.to::<u64>();
+    let sell_spot = decode_slot0_to_price(get(1, sell.name)?)?;
+    let buy_spot = decode_slot0_to_price(get(2, buy.name)?)?;
+    let leg1 = quoteExactInputSingleCall::abi_decode_returns(get(3, "sell quote")?)?;
+    let leg2 = quoteExactInputSingleCall::abi_decode_returns(get(4, "buy quote")?)?;
+
+    let usdc_intermediate = from_units(leg1.amountOut, pair.quote_decimals);
+    let leg2_out_est = from_units(leg2.amountOut, pair.base_decimals);
+    let wmon_out = if est_usdc > 0.0 { leg2_out_est * usdc_intermediate / est_usdc } else { 0.0 };
+
+    let sell_ideal = amount * sell_spot * (1.0 - sell_fee);
+    let buy_ideal = est_usdc / buy_spot * (1.0 - buy_fee);
+    let impact = |ideal: f64, actual: f64| if ideal > 0.0 { (ideal - actual) / ideal * 10_000.0 } else { 0.0 };
+
+    Ok(ArbQuote {
+        block_number,
+        amount_in: amount,
+        usdc_intermediate,
+        wmon_out,
+        sell_spot,
+        buy_spot,
+        sell_impact_bps: impact(sell_ideal, usdc_intermediate),
+        buy_impact_bps: impact(buy_ideal, leg2_out_est),
+        gas_estimate: (leg1.gasEstimate + leg2.gasEstimate).to::<u64>(),
+    })
+}
+
+async fn atomic_router_quotes<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    routers: &[RouterConfig],
+    direction: SwapDirection,
+    amount: f64,
+) -> Result<RouterComparison> {
+    let (token_in, token_out, decimals_in, decimals_out) = direction.tokens(pair);
+    let mut skipped = Vec::new();
+    let mut quotable = Vec::new();
+    for router in routers {
+        match QuoteFetcher::quoter_for(router) {
+            Ok(quoter) => quotable.push((router, quoter)),
+            Err(e) => skipped.push((router.name, e.to_string())),
+        }
+    }
+
+    let slot0 = Bytes::from(slot0Call {}.abi_encode());
+    let mut calls = vec![(MULTICALL3_ADDRESS, Bytes::from(getBlockNumberCall {}.abi_encode()))];
+    for (router, quoter) in &quotable {
+        calls.push((router.pool_address, slot0.clone()));
+        calls.push((*quoter, quote_call(router, token_in, token_out, to_units(amount, decimals_in))));
+    }
+    let results = aggregate_raw(provider, &calls).await?;
+    let block_number = results[0]
+        .as_ref()
+        .ok_or_else(|| eyre!("getBlockNumber call reverted"))
+        .and_then(|d| Ok(getBlockNumberCall::abi_decode_returns(d)?.to::<u64>()))?;
+
+    let mut quotes = Vec::new();
+    for (i, (router, _)) in quotable.iter().enumerate() {
+        let spot = results[1 + 2 * i].as_ref().map(|d| decode_slot0_to_price(d));
+        let out = results[2 + 2 * i].as_ref().map(|d| quoteExactInputSingleCall::abi_decode_returns(d));
+        let (spot, out) = match (spot, out) {
+            (Some(Ok(spot)), Some(Ok(out))) => (spot, out),
+            _ => {
+                skipped.push((router.name, "slot0 or quote reverted".to_string()));
+                continue;
+            }
+        };
+        let fee = router.fee_fraction();
+        let amount_out = from_units(out.amountOut, decimals_out);
+        let (ideal_out, effective_price) = match direction {
+            SwapDirection::Sell => (amount * spot * (1.0 - fee), amount_out / amount),
+            SwapDirection::Buy => (amount / spot * (1.0 - fee), if amount_out > 0.0 { amount / amount_out } else { 0.0 }),
+        };
+        quotes.push(RouterQuote { router: router.name, spot, ideal_out, amount_out, effective_price });
+    }
+    quotes.sort_by(|a, b| b.amount_out.total_cmp(&a.amount_out));
+
+    Ok(RouterComparison { block_number, quotes, skipped })
+}
+
+/// Print a quote in the TestArb layout
+pub fn print_arb_quote(sell: &RouterConfig, buy: &RouterConfig, quote: &ArbQuote) {
+    println!("\n═══════════════════════════════════════════════════════════════");
+    println!("  SIMULATED ARB (eth_call only) | block {}", quote.block_number);
+    println!("═══════════════════════════════════════════════════════════════");
+    println!("  Sell on {:<14} spot {:.6} USDC/WMON", sell.name, quote.sell_spot);
+    println!("  Buy on  {:<14} spot {:.6} USDC/WMON", buy.name, quote.buy_spot);
+    println!("───────────────────────────────────────────────────────────────");
+    println!("  Input:              {:.6} WMON", quote.amount_in);
+    println!("  Expected USDC:      {:.6} (impact {:.1} bps)", quote.usdc_intermediate, quote.sell_impact_bps);
+    println!("  Expected WMON out:  {:.6} (impact {:.1} bps)", quote.wmon_out, quote.buy_impact_bps);
+    let color = if quote.net_profit() > 0.0 { "32" } else { "31" };
+    println!(
+        "  Net profit:         \x1b[1;{}m{:+.6} WMON ({:+.1} bps)\x1b[0m before gas",
+        color,
+        quote.net_profit(),
+        quote.profit_bps()
+    );
+    println!("  Quoter gas est.:    {}", quote.gas_estimate);
+    println!("═══════════════════════════════════════════════════════════════\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::get_router_by_name;
+
+    #[test]
+    fn test_spot_vs_fill_mismatch() {
+        let quote = |router, ideal_out, amount_out| RouterQuote { router, spot: 0.0, ideal_out, amount_out, effective_price: 0.0 };
+        // Deep pool fills better despite the worse spot
+        let mut comparison = RouterComparison {
+            block_number: 1,
+            quotes: vec![quote("Deep", 99.0, 98.5), quote("Thin", 100.0, 95.0)],
+            skipped: Vec::new(),
+        };
+        let (spot, fill) = comparison.spot_vs_fill_mismatch().unwrap();
+        assert_eq!((spot.router, fill.router), ("Thin", "Deep"));
+        assert_eq!(comparison.quotes[1].impact_bps(), 500.0);
+
+        comparison.quotes.remove(1);
+        assert!(comparison.spot_vs_fill_mismatch().is_none());
+    }
+
+    #[test]
+    fn test_quoter_for_rejects_lfj_and_unconfigured() {
+        let lfj = get_router_by_name("lfj").unwrap();
+        assert!(QuoteFetcher::quoter_for(&lfj).unwrap_err().to_string().contains("LBQuoter"));
+
+        let mut uni = get_router_by_name("uniswap").unwrap();
+        assert!(QuoteFetcher::quoter_for(&uni).unwrap_err().to_string().contains("no quoter configured"));
+        uni.quoter = Some(Address::repeat_byte(1));
+        assert_eq!(QuoteFetcher::quoter_for(&uni).unwrap(), Address::repeat_byte(1));
+    }
+}
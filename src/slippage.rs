@@ -0,0 +1,87 @@
+//! Trade-size-aware slippage tolerance
+//!
+//! A flat `--slippage` is too loose for small trades and too tight for large
+//! ones. With `--adaptive-slippage`, `--slippage` becomes the tolerance for a
+//! trade of `REFERENCE_DEPTH_SHARE` of the pool's depth and scales linearly
+//! with the trade's share from there: on a constant-product curve a trade of
+//! 1% of depth moves the price about 2%, and impact grows about linearly with
+//! size. The result is floored at `MIN_SLIPPAGE_BPS` (price drift between
+//! quote and inclusion, whatever the size) and capped by `--max-slippage`.
+
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+
+use crate::config::{get_all_pools, TokenPair};
+use crate::pools::liquidity::fetch_pool_liquidity;
+
+/// Trade size, as a share of pool depth, that gets exactly the base tolerance
+const REFERENCE_DEPTH_SHARE: f64 = 0.01;
+
+/// Tolerance floor for small trades
+pub const MIN_SLIPPAGE_BPS: u32 = 10;
+
+/// Default `--max-slippage` ceiling
+pub const DEFAULT_MAX_SLIPPAGE_BPS: &str = "500";
+
+/// Tolerance for trading `amount` against `pool_liquidity` (pool depth, same
+/// units as `amount`); `base_bps` applies at 1% of depth. Unknown depth keeps `base_bps`.
+pub fn slippage_for_amount(amount: f64, pool_liquidity: f64, base_bps: u32) -> u32 {
+    if pool_liquidity <= 0.0 || amount <= 0.0 {
+        return base_bps;
+    }
+    let share = amount / pool_liquidity;
+    let scaled = base_bps as f64 * share / REFERENCE_DEPTH_SHARE;
+    (scaled.round() as u32).max(MIN_SLIPPAGE_BPS)
+}
+
+/// Tolerance for an `amount` WMON arb across `sell_pool` and `buy_pool`, set by
+/// the thinner pool and capped at `max_bps`. Falls back to `base_bps` (capped)
+/// if either pool's depth can't be read.
+pub async fn adaptive_slippage<P: Provider>(
+    provider: &P,
+    pair: &TokenPair,
+    sell_pool: Address,
+    buy_pool: Address,
+    amount: f64,
+    base_bps: u32,
+    max_bps: u32,
+) -> u32 {
+    let pools = get_all_pools();
+    let find = |address: Address| pools.iter().find(|p| p.address == address).cloned();
+    let (Some(sell), Some(buy)) = (find(sell_pool), find(buy_pool)) else {
+        return base_bps.min(max_bps);
+    };
+
+    let (sell_liq, buy_liq) = tokio::join!(
+        fetch_pool_liquidity(provider, &sell, pair),
+        fetch_pool_liquidity(provider, &buy, pair),
+    );
+    match (sell_liq, buy_liq) {
+        (Ok(sell_liq), Ok(buy_liq)) => {
+            let depth = sell_liq.depth_wmon().min(buy_liq.depth_wmon());
+            slippage_for_amount(amount, depth, base_bps).min(max_bps)
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::warn!("Pool depth read failed ({}), using flat slippage", e);
+            base_bps.min(max_bps)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolerance_scales_with_depth_share() {
+        // 1% of depth gets the base tolerance
+        assert_eq!(slippage_for_amount(10.0, 1000.0, 200), 200);
+        // Small trades tighten, down to the floor
+        assert_eq!(slippage_for_amount(2.5, 1000.0, 200), 50);
+        assert_eq!(slippage_for_amount(0.01, 1000.0, 200), MIN_SLIPPAGE_BPS);
+        // Large trades widen
+        assert_eq!(slippage_for_amount(30.0, 1000.0, 200), 600);
+        // Unknown depth: flat
+        assert_eq!(slippage_for_amount(30.0, 0.0, 200), 200);
+    }
+}
@@ -9,11 +9,12 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::io::{stdout, Write};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 
 use crate::display::SpreadOpportunity;
+use crate::mev_validation::CommitState;
 use crate::pools::PoolPrice;
 
 /// Spread alert levels for color coding
@@ -164,6 +165,14 @@ pub struct SpreadDisplay {
     pub last_block: u64,
     /// Alert sound enabled
     pub alert_sound: bool,
+    /// Effective (latency-adapted) poll interval, shown in the header when set
+    pub poll_interval: Option<Duration>,
+    /// Polling suspended from the TUI
+    pub paused: bool,
+    /// Commit state of the block the prices were read on (`--on-block`); None when polling on the timer
+    pub commit_state: Option<CommitState>,
+    /// Route ("BuyPool→SellPool") highlighted in the TUI
+    pub focused_route: Option<String>,
 }
 
 impl SpreadDisplay {
@@ -175,6 +184,10 @@ impl SpreadDisplay {
             last_update: Instant::now(),
             last_block: 0,
             alert_sound: true,
+            poll_interval: None,
+            paused: false,
+            commit_state: None,
+            focused_route: None,
         }
     }
 
@@ -210,21 +223,7 @@ impl SpreadDisplay {
             self.min_display_bps
         ));
 
-        // Collect and sort by spread
-        let mut active_pairs: Vec<_> = self
-            .pair_histories
-            .iter()
-            .filter_map(|(key, hist)| {
-                let last = *hist.history.back()?;
-                if last >= self.min_display_bps {
-                    Some((key, hist, last))
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        active_pairs.sort_by(|a, b| b.2.cmp(&a.2)); // Sort descending by spread
+        let active_pairs = self.active_routes();
 
         if active_pairs.is_empty() {
             output.push_str(&format!(
@@ -301,6 +300,48 @@ impl SpreadDisplay {
     }
 }
 
+/// TUI view and keybinding actions
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
+impl SpreadDisplay {
+    /// Routes whose latest spread passes the filter, best first
+    pub fn active_routes(&self) -> Vec<(&String, &PairHistory, i32)> {
+        let mut routes: Vec<_> = self
+            .pair_histories
+            .iter()
+            .filter_map(|(key, hist)| {
+                let last = *hist.history.back()?;
+                (last >= self.min_display_bps).then_some((key, hist, last))
+            })
+            .collect();
+        routes.sort_by_key(|r| std::cmp::Reverse(r.2));
+        routes
+    }
+
+    pub fn toggle_sound(&mut self) {
+        self.alert_sound = !self.alert_sound;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Move the min-spread filter by `delta_bps`
+    pub fn adjust_min_display(&mut self, delta_bps: i32) {
+        self.min_display_bps = self.min_display_bps.saturating_add(delta_bps);
+    }
+
+    /// Focus the next route (alphabetical), wrapping back to none after the last
+    pub fn focus_next(&mut self) {
+        let mut keys: Vec<&String> = self.pair_histories.keys().collect();
+        keys.sort();
+        let next = match &self.focused_route {
+            None => keys.first(),
+            Some(current) => keys.iter().find(|k| k.as_str() > current.as_str()),
+        };
+        self.focused_route = next.map(|k| k.to_string());
+    }
+}
+
 /// Render full dashboard with prices and spreads
 pub fn render_full_dashboard(
     display: &SpreadDisplay,
@@ -322,10 +363,21 @@ pub fn render_full_dashboard(
         "\x1b[2K╠══════════════════════════════════════════════════════════════════════════╣\n",
     );
     out.push_str(&format!(
-        "\x1b[2K║  {} │ Block: {:>12} │ Latency: {:>4}ms                ║\n",
+        "\x1b[2K║  {} │ Block: {:>12} │ Latency: {:>4}ms │ Poll: {:>5}  ║\n",
         now,
-        block.map(|b| b.to_string()).unwrap_or_else(|| "?".into()),
-        display.last_update.elapsed().as_millis()
+        match (block, display.commit_state) {
+            (Some(b), Some(state)) => format!("{} {:?}", b, state),
+            (Some(b), None) => b.to_string(),
+            (None, _) => "?".into(),
+        },
+        display.last_update.elapsed().as_millis(),
+        if display.paused {
+            "PAUSE".into()
+        } else if display.commit_state.is_some() {
+            "block".into()
+        } else {
+            display.poll_interval.map(|p| format!("{}ms", p.as_millis())).unwrap_or_else(|| "-".into())
+        }
     ));
     out.push_str(
         "\x1b[2K╠══════════════════════════════════════════════════════════════════════════╣\x1b[0m\n",
@@ -407,8 +459,10 @@ pub fn render_full_dashboard(
             _ => "\x1b[90m-\x1b[0m",
         };
 
+        let marker = if display.focused_route.as_ref() == Some(key) { "▶" } else { " " };
         out.push_str(&format!(
-            "\x1b[2K  {}{:<26}\x1b[0m {:>+8} {}{:>7}\x1b[0m {}{:>3}\x1b[0m {:>12} {:>10}\n",
+            "\x1b[2K {}{}{:<26}\x1b[0m {:>+8} {}{:>7}\x1b[0m {}{:>3}\x1b[0m {:>12} {:>10}\n",
+            marker,
             level.color_code(),
             key,
             spread_bps,
@@ -440,6 +494,84 @@ pub fn render_full_dashboard(
     out
 }
 
+/// Routes a pair block shows in the multi-pair dashboard
+const ROUTES_PER_PAIR: usize = 3;
+
+/// Sectioned dashboard for `Dashboard --pairs`: one block per pair with its
+/// best routes' spread, level, trend and sparkline
+pub fn render_multi_pair_dashboard(sections: &[(&str, &SpreadDisplay)], block: Option<u64>) -> String {
+    let mut out = String::new();
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+
+    out.push_str("\x1b[2K\x1b[1;36m");
+    out.push_str(
+        "╔══════════════════════════════════════════════════════════════════════════╗\n",
+    );
+    out.push_str(
+        "\x1b[2K║                 MONAD MEV SPREAD DASHBOARD (MULTI-PAIR)                 ║\n",
+    );
+    out.push_str(
+        "\x1b[2K╠══════════════════════════════════════════════════════════════════════════╣\n",
+    );
+    out.push_str(&format!(
+        "\x1b[2K║  {} │ Block: {:>12} │ Pairs: {:>2}                     ║\n",
+        now,
+        block.map(|b| b.to_string()).unwrap_or_else(|| "?".into()),
+        sections.len()
+    ));
+    out.push_str(
+        "\x1b[2K╠══════════════════════════════════════════════════════════════════════════╣\x1b[0m\n",
+    );
+
+    for (label, display) in sections {
+        let mut routes: Vec<_> = display
+            .pair_histories
+            .iter()
+            .filter_map(|(k, h)| Some((k, h, *h.history.back()?)))
+            .collect();
+        routes.sort_by_key(|r| std::cmp::Reverse(r.2));
+
+        let best = routes.first().map(|r| r.2);
+        let best_level = best.map(SpreadLevel::from_bps).unwrap_or(SpreadLevel::Dead);
+        out.push_str(&format!(
+            "\x1b[2K\x1b[1m  {:<12}\x1b[0m best: {}{}\x1b[0m\n",
+            label.to_uppercase(),
+            best_level.color_code(),
+            best.map(|b| format!("{:+} bps {}", b, best_level.label())).unwrap_or_else(|| "no spreads".into())
+        ));
+        out.push_str(
+            "\x1b[2K  ─────────────────────────────────────────────────────────────────────\n",
+        );
+
+        for (key, hist, spread_bps) in routes.iter().take(ROUTES_PER_PAIR) {
+            let level = SpreadLevel::from_bps(*spread_bps);
+            let trend = hist.trend();
+            out.push_str(&format!(
+                "\x1b[2K  {}{:<26}\x1b[0m {:>+8} {}{:>7}\x1b[0m {}{:>3}\x1b[0m {:>12}\n",
+                level.color_code(),
+                key,
+                spread_bps,
+                level.color_code(),
+                level.label(),
+                trend.color(),
+                trend.arrow(),
+                hist.sparkline(),
+            ));
+        }
+        // Fixed block height so the screen doesn't jump
+        for _ in routes.len().min(ROUTES_PER_PAIR)..ROUTES_PER_PAIR {
+            out.push_str("\x1b[2K\n");
+        }
+        out.push_str("\x1b[2K\n");
+    }
+
+    out.push_str(
+        "\x1b[2K\x1b[1;36m╚══════════════════════════════════════════════════════════════════════════╝\x1b[0m\n",
+    );
+
+    out
+}
+
 /// Enter alternate screen buffer for clean display
 pub fn enter_alternate_screen() {
     print!("\x1b[?1049h"); // Enter alternate screen
@@ -464,3 +596,44 @@ pub fn cursor_home() {
 pub fn is_interactive() -> bool {
     atty::is(atty::Stream::Stdout)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keybinding_state() {
+        let mut display = SpreadDisplay::new(5, 10);
+        for (key, bps) in [("LFJ→Uniswap", 12), ("Uniswap→LFJ", 3)] {
+            let mut hist = PairHistory::new(key.to_string(), 10);
+            hist.push(bps);
+            display.pair_histories.insert(key.to_string(), hist);
+        }
+        assert_eq!(display.active_routes().len(), 1);
+        display.adjust_min_display(-5);
+        assert_eq!(display.active_routes().len(), 2);
+
+        display.focus_next();
+        assert_eq!(display.focused_route.as_deref(), Some("LFJ→Uniswap"));
+        display.focus_next();
+        assert_eq!(display.focused_route.as_deref(), Some("Uniswap→LFJ"));
+        display.focus_next();
+        assert_eq!(display.focused_route, None);
+
+        display.toggle_pause();
+        display.toggle_sound();
+        assert!(display.paused && !display.alert_sound);
+    }
+
+    #[test]
+    fn test_header_shows_commit_state_when_block_synced() {
+        let mut display = SpreadDisplay::new(5, 10);
+        display.poll_interval = Some(Duration::from_millis(250));
+        let timer = render_full_dashboard(&display, &[], Some(1234));
+        assert!(timer.contains("Block:         1234") && timer.contains("250ms"));
+
+        display.commit_state = Some(CommitState::Proposed);
+        let synced = render_full_dashboard(&display, &[], Some(1234));
+        assert!(synced.contains("1234 Proposed") && synced.contains("Poll: block"));
+    }
+}
@@ -1,5 +1,27 @@
 //! Smart spread filter based on velocity analysis
+//!
+//! Thresholds are global, with optional per-route overrides keyed by
+//! (buy_pool, sell_pool) from the config file's `[[route_filters]]`.
+//!
+//! `DecayGate` is the expected Proposed→Finalized decay AutoArb takes off an
+//! observed spread before comparing it to `--min-spread-bps`: flat from
+//! `--expected-decay-bps`, or per route from `[[route_decay]]`, which
+//! `AnalyzeLogs --group-by decay` prints from validation logs.
+//!
+//! `EtaGate` is `AutoArb --mode eta`: fire only if the expected execution
+//! latency fits inside the Proposed→Finalized window measured by
+//! `MevValidate` and the route's spread usually lasts that long.
+//!
+//! `RankBy::Executable` (`AutoArb --rank-by executable`) reorders spreads by
+//! how much of the trade the pools can absorb, so a thin pool's outsized
+//! spread doesn't beat a slightly smaller one on deep pools.
 
+use eyre::{eyre, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::display::SpreadOpportunity;
 use crate::spread_tracker::VelocityAnalysis;
 
 #[derive(Debug, Clone)]
@@ -8,6 +30,22 @@ pub struct SpreadFilterConfig {
     pub max_velocity: f64,      // 100.0 - Skip bot signatures
     pub min_final_spread: i32,  // 9 - Require margin
     pub max_baseline: i32,      // 2 - Fresh opportunities only
+    /// Max |acceleration| (bps/sec^2) - skip erratic, sandwich-prone moves; 0 disables
+    pub max_acceleration: f64,
+    /// Per-route overrides, keyed by lowercase (buy_pool, sell_pool)
+    pub routes: HashMap<(String, String), RouteFilterOverride>,
+}
+
+/// Thresholds for one route; unset fields use the global value
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RouteFilterOverride {
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub min_velocity: Option<f64>,
+    pub max_velocity: Option<f64>,
+    pub min_final_spread: Option<i32>,
+    pub max_baseline: Option<i32>,
+    pub max_acceleration: Option<f64>,
 }
 
 impl Default for SpreadFilterConfig {
@@ -17,18 +55,205 @@ impl Default for SpreadFilterConfig {
             max_velocity: 100.0,
             min_final_spread: 9,
             max_baseline: 2,
+            max_acceleration: 0.0,
+            routes: HashMap::new(),
+        }
+    }
+}
+
+/// Expected spread decay for one route, in bps
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RouteDecay {
+    pub buy_pool: String,
+    pub sell_pool: String,
+    pub decay_bps: f64,
+}
+
+/// Haircut applied to an observed spread before the min-spread check
+#[derive(Debug, Clone, Default)]
+pub struct DecayGate {
+    pub flat_bps: f64,
+    /// Per-route decay, keyed by lowercase (buy_pool, sell_pool)
+    pub routes: HashMap<(String, String), f64>,
+}
+
+impl DecayGate {
+    /// `flat_bps` for every route, unless `routes` has one (later entries win)
+    pub fn new(flat_bps: f64, routes: Vec<RouteDecay>) -> Self {
+        let routes = routes
+            .into_iter()
+            .map(|r| (route_key(&r.buy_pool, &r.sell_pool), r.decay_bps))
+            .collect();
+        Self { flat_bps, routes }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.flat_bps != 0.0 || !self.routes.is_empty()
+    }
+
+    pub fn expected_decay(&self, buy_pool: &str, sell_pool: &str) -> f64 {
+        self.routes.get(&route_key(buy_pool, sell_pool)).copied().unwrap_or(self.flat_bps)
+    }
+
+    /// Spread expected to be left by the time the arb lands
+    pub fn predicted_spread(&self, observed_bps: i32, buy_pool: &str, sell_pool: &str) -> f64 {
+        observed_bps as f64 - self.expected_decay(buy_pool, sell_pool)
+    }
+}
+
+/// Survival required over the ETA when `--min-survival` is 0
+pub const DEFAULT_ETA_MIN_SURVIVAL: f64 = 0.5;
+
+/// Weight of each measured execution in the rolling ETA
+const ETA_ALPHA: f64 = 0.3;
+
+/// How AutoArb decides to fire on a spread that passes the thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArbMode {
+    /// Fire on every spread that passes the filters
+    Poll,
+    /// Fire only if the ETA beats the validated execution window
+    Eta,
+}
+
+impl FromStr for ArbMode {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "poll" => Ok(Self::Poll),
+            "eta" => Ok(Self::Eta),
+            _ => Err(eyre!("Unknown mode '{}'. Expected poll or eta", s)),
+        }
+    }
+}
+
+/// Expected execution latency against the validated execution window
+#[derive(Debug, Clone)]
+pub struct EtaGate {
+    /// Detection to landed (ms): `--eta-ms`, then a rolling average of executions
+    pub eta_ms: f64,
+    /// `execution_window_ms` from validation logs (fastest Proposed→Finalized - 100 ms)
+    pub window_ms: u128,
+    /// Probability the spread must have of lasting the ETA
+    pub min_survival: f64,
+}
+
+impl EtaGate {
+    pub fn new(eta_ms: u64, window_ms: u128, min_survival: f64) -> Self {
+        let min_survival = if min_survival > 0.0 { min_survival } else { DEFAULT_ETA_MIN_SURVIVAL };
+        Self { eta_ms: eta_ms as f64, window_ms, min_survival }
+    }
+
+    /// Fold a measured execution time into the ETA
+    pub fn observe(&mut self, execution_ms: u128) {
+        self.eta_ms = ETA_ALPHA * execution_ms as f64 + (1.0 - ETA_ALPHA) * self.eta_ms;
+    }
+
+    /// `survival`: the route's estimated probability its spread lasts the ETA
+    pub fn evaluate(&self, survival: f64) -> FilterResult {
+        if self.eta_ms > self.window_ms as f64 {
+            return FilterResult::Skip { reason: "ETA exceeds execution window" };
         }
+        if survival < self.min_survival {
+            return FilterResult::Skip { reason: "spread unlikely to survive ETA" };
+        }
+        FilterResult::Execute
     }
 }
 
+/// Which spread AutoArb takes first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankBy {
+    /// Highest net spread
+    Spread,
+    /// Net spread scaled by the share of `amount` the pools can absorb
+    Executable,
+}
+
+impl FromStr for RankBy {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "spread" => Ok(Self::Spread),
+            "executable" => Ok(Self::Executable),
+            _ => Err(eyre!("Unknown ranking '{}'. Expected spread or executable", s)),
+        }
+    }
+}
+
+/// `net_spread_bps * min(1, executable_size / amount)`
+pub fn executable_score(net_spread_bps: f64, executable_size: f64, amount: f64) -> f64 {
+    if amount <= 0.0 {
+        return net_spread_bps;
+    }
+    net_spread_bps * (executable_size / amount).clamp(0.0, 1.0)
+}
+
+/// Re-sort `spreads` (best first) by `executable_score`. `executable_size`
+/// gives the largest trade a spread's pools absorb; spreads without a depth
+/// reading score 0 and fall behind every measured positive spread.
+pub fn rank_by_executable(
+    spreads: &mut [SpreadOpportunity],
+    amount: f64,
+    executable_size: impl Fn(&SpreadOpportunity) -> Option<f64>,
+) {
+    let score = |s: &SpreadOpportunity| {
+        executable_size(s).map_or(0.0, |size| executable_score(s.net_spread_pct * 100.0, size, amount))
+    };
+    spreads.sort_by(|a, b| score(b).total_cmp(&score(a)));
+}
+
 #[derive(Debug)]
 pub enum FilterResult {
     Execute,
     Skip { reason: &'static str },
 }
 
+fn route_key(buy_pool: &str, sell_pool: &str) -> (String, String) {
+    (buy_pool.to_lowercase(), sell_pool.to_lowercase())
+}
+
 impl SpreadFilterConfig {
-    pub fn evaluate(&self, analysis: &VelocityAnalysis) -> FilterResult {
+    /// Add route overrides (later entries for the same route win)
+    pub fn with_routes(mut self, routes: Vec<RouteFilterOverride>) -> Self {
+        for route in routes {
+            self.routes.insert(route_key(&route.buy_pool, &route.sell_pool), route);
+        }
+        self
+    }
+
+    /// Thresholds that apply to a route: its override on top of the globals
+    fn for_route(&self, buy_pool: &str, sell_pool: &str) -> Self {
+        match self.routes.get(&route_key(buy_pool, sell_pool)) {
+            Some(o) => Self {
+                min_velocity: o.min_velocity.unwrap_or(self.min_velocity),
+                max_velocity: o.max_velocity.unwrap_or(self.max_velocity),
+                min_final_spread: o.min_final_spread.unwrap_or(self.min_final_spread),
+                max_baseline: o.max_baseline.unwrap_or(self.max_baseline),
+                max_acceleration: o.max_acceleration.unwrap_or(self.max_acceleration),
+                routes: HashMap::new(),
+            },
+            None => Self { routes: HashMap::new(), ..self.clone() },
+        }
+    }
+
+    /// Which config `evaluate` uses for a route, for logging
+    pub fn source(&self, buy_pool: &str, sell_pool: &str) -> String {
+        if self.routes.contains_key(&route_key(buy_pool, sell_pool)) {
+            format!("route {}→{}", buy_pool, sell_pool)
+        } else {
+            "global".to_string()
+        }
+    }
+
+    pub fn evaluate(&self, analysis: &VelocityAnalysis, buy_pool: &str, sell_pool: &str) -> FilterResult {
+        let cfg = self.for_route(buy_pool, sell_pool);
+        cfg.evaluate_thresholds(analysis)
+    }
+
+    fn evaluate_thresholds(&self, analysis: &VelocityAnalysis) -> FilterResult {
         let velocity = analysis.velocity_bps_per_sec;
         let baseline = analysis.min_spread_in_window;
         let final_spread = analysis.spread_at_trigger;
@@ -40,6 +265,14 @@ impl SpreadFilterConfig {
             };
         }
 
+        // REJECT: Spread not stable or gently rising - likely to move against
+        // us between swap 1 and swap 2
+        if self.max_acceleration > 0.0 && analysis.acceleration.abs() > self.max_acceleration {
+            return FilterResult::Skip {
+                reason: "acceleration out of bounds - sandwich-prone"
+            };
+        }
+
         // REJECT: Dead spread (GRADUAL pattern, no momentum)
         if velocity.abs() < self.min_velocity && baseline == final_spread {
             return FilterResult::Skip {
@@ -64,3 +297,109 @@ impl SpreadFilterConfig {
         FilterResult::Execute
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(velocity: f64, baseline: i32, final_spread: i32) -> VelocityAnalysis {
+        VelocityAnalysis {
+            snapshots: Vec::new(),
+            velocity_bps_per_sec: velocity,
+            acceleration: 0.0,
+            is_spike: false,
+            spread_at_trigger: final_spread,
+            max_spread_in_window: final_spread,
+            min_spread_in_window: baseline,
+            window_duration_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_route_override_falls_back_to_global() {
+        let filter = SpreadFilterConfig::default().with_routes(vec![RouteFilterOverride {
+            buy_pool: "PancakeSwap1".into(),
+            sell_pool: "LFJ".into(),
+            min_final_spread: Some(20),
+            ..Default::default()
+        }]);
+        let a = analysis(30.0, 0, 12);
+
+        assert!(matches!(filter.evaluate(&a, "pancakeswap1", "lfj"), FilterResult::Skip { .. }));
+        assert!(matches!(filter.evaluate(&a, "Uniswap", "MondayTrade"), FilterResult::Execute));
+        assert_eq!(filter.source("PancakeSwap1", "LFJ"), "route PancakeSwap1→LFJ");
+        assert_eq!(filter.source("LFJ", "PancakeSwap1"), "global");
+    }
+
+    #[test]
+    fn test_acceleration_bound() {
+        let filter = SpreadFilterConfig { max_acceleration: 50.0, ..Default::default() };
+        let mut a = analysis(30.0, 0, 12);
+        a.acceleration = 40.0;
+        assert!(matches!(filter.evaluate(&a, "LFJ", "Uniswap"), FilterResult::Execute));
+
+        a.acceleration = -80.0;
+        assert!(matches!(filter.evaluate(&a, "LFJ", "Uniswap"), FilterResult::Skip { .. }));
+
+        // 0 disables the bound
+        let unbounded = SpreadFilterConfig::default();
+        assert!(matches!(unbounded.evaluate(&a, "LFJ", "Uniswap"), FilterResult::Execute));
+    }
+
+    #[test]
+    fn test_decay_gate_route_overrides_flat() {
+        let gate = DecayGate::new(3.0, vec![RouteDecay {
+            buy_pool: "LFJ".into(),
+            sell_pool: "Uniswap".into(),
+            decay_bps: 8.5,
+        }]);
+        assert!(gate.is_enabled());
+        assert_eq!(gate.predicted_spread(20, "lfj", "uniswap"), 11.5);
+        assert_eq!(gate.predicted_spread(20, "Uniswap", "LFJ"), 17.0);
+        assert!(!DecayGate::new(0.0, Vec::new()).is_enabled());
+    }
+
+    #[test]
+    fn test_eta_gate_window_and_survival() {
+        let mut gate = EtaGate::new(300, 400, 0.0);
+        assert_eq!(gate.min_survival, DEFAULT_ETA_MIN_SURVIVAL);
+        assert!(matches!(gate.evaluate(0.7), FilterResult::Execute));
+        assert!(matches!(gate.evaluate(0.2), FilterResult::Skip { .. }));
+
+        // Slow executions push the ETA past the window
+        gate.observe(700);
+        gate.observe(700);
+        assert!(gate.eta_ms > 400.0);
+        assert!(matches!(gate.evaluate(0.9), FilterResult::Skip { reason: "ETA exceeds execution window" }));
+        assert_eq!("ETA".parse::<ArbMode>().unwrap(), ArbMode::Eta);
+    }
+
+    #[test]
+    fn test_rank_by_executable_prefers_deep_pools() {
+        let spread = |buy: &str, net_pct: f64| SpreadOpportunity {
+            buy_pool: buy.into(),
+            buy_price: 0.0,
+            buy_fee_bps: 0,
+            sell_pool: "Uniswap".into(),
+            sell_price: 0.0,
+            sell_fee_bps: 0,
+            gross_spread_pct: net_pct,
+            net_spread_pct: net_pct,
+            block_number: None,
+        };
+        assert_eq!(executable_score(30.0, 5.0, 10.0), 15.0);
+        assert_eq!(executable_score(30.0, 50.0, 10.0), 30.0);
+
+        // 30 bps on a pool absorbing half the trade loses to 20 bps on a deep one
+        let mut spreads = vec![spread("LFJ", 0.30), spread("PancakeSwap", 0.20), spread("MondayTrade", 0.50)];
+        rank_by_executable(&mut spreads, 10.0, |s| match s.buy_pool.as_str() {
+            "LFJ" => Some(5.0),
+            "PancakeSwap" => Some(100.0),
+            _ => None,
+        });
+        let order: Vec<&str> = spreads.iter().map(|s| s.buy_pool.as_str()).collect();
+        assert_eq!(order, ["PancakeSwap", "LFJ", "MondayTrade"]);
+        assert_eq!("executable".parse::<RankBy>().unwrap(), RankBy::Executable);
+        assert!("depth".parse::<RankBy>().is_err());
+    }
+}
@@ -1,13 +1,117 @@
 //! Spread event logging for analysis
+//!
+//! `--spread-log <file>` (AutoArb, Dashboard) appends one CSV row per poll
+//! with the best route, for plotting spread over time. Rows are buffered and
+//! flushed about once a second, on shutdown, and when the log is dropped.
 
 use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 use serde::Serialize;
 
+use crate::display::SpreadOpportunity;
 use crate::spread_display::{SpreadDisplay, SpreadLevel, Trend};
 
+/// `--spread-log` header row
+pub const SPREAD_CSV_HEADER: &str = "unix_ms,block,best_buy_pool,best_sell_pool,gross_bps,net_bps,velocity";
+
+/// Buffered rows are written out at least this often
+const SPREAD_CSV_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Per-poll best-spread CSV; `velocity` is the net spread's change in bps/sec
+/// since the previous row, left empty when the best route changed
+pub struct SpreadCsvLog {
+    writer: BufWriter<File>,
+    last_flush: Instant,
+    /// (buy_pool, sell_pool, net_bps, unix_ms) of the previous row
+    last: Option<(String, String, i32, u128)>,
+}
+
+impl SpreadCsvLog {
+    pub fn open(path: &str) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_new = file.metadata()?.len() == 0;
+        let mut writer = BufWriter::with_capacity(64 * 1024, file);
+        if is_new {
+            writeln!(writer, "{}", SPREAD_CSV_HEADER)?;
+        }
+        Ok(Self { writer, last_flush: Instant::now(), last: None })
+    }
+
+    /// Format this poll's row and remember it for the next velocity
+    pub fn format_row(&mut self, unix_ms: u128, block: Option<u64>, best: Option<&SpreadOpportunity>) -> String {
+        let block = block.map(|b| b.to_string()).unwrap_or_default();
+        let Some(best) = best else {
+            self.last = None;
+            return format!("{},{},,,,,", unix_ms, block);
+        };
+
+        let gross_bps = (best.gross_spread_pct * 100.0).round() as i32;
+        let net_bps = (best.net_spread_pct * 100.0).round() as i32;
+        let velocity = match &self.last {
+            Some((buy, sell, prev_bps, prev_ms))
+                if *buy == best.buy_pool && *sell == best.sell_pool && unix_ms > *prev_ms =>
+            {
+                format!("{:.2}", (net_bps - prev_bps) as f64 * 1000.0 / (unix_ms - prev_ms) as f64)
+            }
+            _ => String::new(),
+        };
+        self.last = Some((best.buy_pool.clone(), best.sell_pool.clone(), net_bps, unix_ms));
+        format!("{},{},{},{},{},{},{}", unix_ms, block, best.buy_pool, best.sell_pool, gross_bps, net_bps, velocity)
+    }
+
+    /// Buffer one poll's row; only touches the file when the flush interval is up
+    pub fn log(&mut self, block: Option<u64>, best: Option<&SpreadOpportunity>) {
+        let unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        let row = self.format_row(unix_ms, block, best);
+        let _ = writeln!(self.writer, "{}", row);
+        if self.last_flush.elapsed() >= SPREAD_CSV_FLUSH_INTERVAL {
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        let _ = self.writer.flush();
+        self.last_flush = Instant::now();
+    }
+}
+
+impl Drop for SpreadCsvLog {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// The running command's `--spread-log`, reachable from the Ctrl+C handler
+static SPREAD_CSV: Mutex<Option<SpreadCsvLog>> = Mutex::new(None);
+
+/// Open `--spread-log` for this run
+pub fn init_spread_csv(path: &str) -> std::io::Result<()> {
+    let log = SpreadCsvLog::open(path)?;
+    *SPREAD_CSV.lock().unwrap_or_else(|e| e.into_inner()) = Some(log);
+    Ok(())
+}
+
+/// Append a row if `--spread-log` is set
+pub fn log_spread_csv(block: Option<u64>, best: Option<&SpreadOpportunity>) {
+    if let Some(log) = SPREAD_CSV.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        log.log(block, best);
+    }
+}
+
+/// Write out buffered rows (call on shutdown)
+pub fn flush_spread_csv() {
+    if let Some(log) = SPREAD_CSV.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        log.flush();
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct SpreadEvent {
     pub timestamp: String,
@@ -99,3 +203,38 @@ pub fn log_significant_spreads(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opportunity(buy: &str, sell: &str, net_pct: f64) -> SpreadOpportunity {
+        SpreadOpportunity {
+            buy_pool: buy.to_string(),
+            buy_price: 1.0,
+            buy_fee_bps: 5,
+            sell_pool: sell.to_string(),
+            sell_price: 1.0,
+            sell_fee_bps: 5,
+            gross_spread_pct: net_pct + 0.1,
+            net_spread_pct: net_pct,
+            block_number: None,
+        }
+    }
+
+    #[test]
+    fn test_velocity_only_for_same_route() {
+        let path = std::env::temp_dir().join(format!("spread_csv_test_{}.csv", std::process::id()));
+        let mut log = SpreadCsvLog::open(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(log.format_row(1_000, Some(7), Some(&opportunity("A", "B", 0.20))), "1000,7,A,B,30,20,");
+        assert_eq!(log.format_row(1_500, Some(8), Some(&opportunity("A", "B", 0.25))), "1500,8,A,B,35,25,10.00");
+        assert_eq!(log.format_row(2_000, Some(9), Some(&opportunity("B", "A", 0.25))), "2000,9,B,A,35,25,");
+        assert_eq!(log.format_row(2_500, None, None), "2500,,,,,,");
+
+        drop(log);
+        let written = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written.lines().next(), Some(SPREAD_CSV_HEADER));
+    }
+}
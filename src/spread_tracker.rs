@@ -2,11 +2,27 @@
 //!
 //! Tracks spread history to identify spike vs gradual patterns before arb execution.
 //! Uses a ring buffer for O(1) insertion with bounded memory.
+//!
+//! Also learns, per route and `SpreadTier`, how often a spread is still in its
+//! tier `survival_horizon_ms` later (the live counterpart of mev_validation's
+//! persistence rate). A spread whose route is no longer the best one at the
+//! horizon counts as decayed.
 
-use std::collections::VecDeque;
-use std::time::Instant;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 use serde::{Serialize, Deserialize};
 
+use crate::mev_validation::SpreadTier;
+
+/// (buy_pool, sell_pool)
+pub type Route = (String, String);
+
+/// Weight of each new outcome in the rolling survival estimate
+const SURVIVAL_ALPHA: f64 = 0.1;
+pub const DEFAULT_SURVIVAL_HORIZON_MS: u128 = 500;
+/// Velocity (bps/sec) above which a move is flagged as a spike
+pub const DEFAULT_SPIKE_VELOCITY: f64 = 10.0;
+
 /// Single spread snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpreadSnapshot {
@@ -25,7 +41,7 @@ pub struct VelocityAnalysis {
     pub snapshots: Vec<SpreadSnapshot>,  // Last N snapshots (oldest first)
     pub velocity_bps_per_sec: f64,       // Rate of change
     pub acceleration: f64,                // 2nd derivative
-    pub is_spike: bool,                   // velocity > threshold
+    pub is_spike: bool,                   // velocity > spike threshold (likely bot-induced)
     pub spread_at_trigger: i32,
     pub max_spread_in_window: i32,
     pub min_spread_in_window: i32,
@@ -37,6 +53,12 @@ pub struct SpreadTracker {
     history: VecDeque<SpreadSnapshot>,
     capacity: usize,
     start_time: Instant,
+    survival_horizon_ms: u128,
+    spike_velocity: f64,
+    /// Spreads waiting for their horizon: (route, tier, first seen ms)
+    pending: Vec<(Route, SpreadTier, u128)>,
+    /// Rolling survival probability per route and tier (seeded at 0)
+    survival: HashMap<(Route, SpreadTier), f64>,
 }
 
 impl SpreadTracker {
@@ -45,9 +67,25 @@ impl SpreadTracker {
             history: VecDeque::with_capacity(capacity),
             capacity,
             start_time: Instant::now(),
+            survival_horizon_ms: DEFAULT_SURVIVAL_HORIZON_MS,
+            spike_velocity: DEFAULT_SPIKE_VELOCITY,
+            pending: Vec::new(),
+            survival: HashMap::new(),
         }
     }
 
+    /// How long a spread must last to count as survived (~ execution latency)
+    pub fn with_survival_horizon(mut self, horizon_ms: u128) -> Self {
+        self.survival_horizon_ms = horizon_ms;
+        self
+    }
+
+    /// Flag moves faster than `bps_per_sec` as spikes (AutoArb passes its --max-velocity)
+    pub fn with_spike_velocity(mut self, bps_per_sec: f64) -> Self {
+        self.spike_velocity = bps_per_sec;
+        self
+    }
+
     /// Record current best spread - called every poll cycle
     /// MUST be non-blocking and fast
     pub fn record(
@@ -69,12 +107,51 @@ impl SpreadTracker {
             net_spread_bps,
         };
 
+        self.update_survival(&snapshot);
+
         if self.history.len() >= self.capacity {
             self.history.pop_front();
         }
         self.history.push_back(snapshot);
     }
 
+    /// Resolve spreads that reached the horizon against this poll, then start
+    /// watching this poll's spread
+    fn update_survival(&mut self, snap: &SpreadSnapshot) {
+        let now = snap.timestamp_ms;
+        let route: Route = (snap.buy_pool.clone(), snap.sell_pool.clone());
+        let horizon = self.survival_horizon_ms;
+
+        let mut resolved = Vec::new();
+        self.pending.retain(|(r, tier, seen_at)| {
+            if now.saturating_sub(*seen_at) < horizon {
+                return true;
+            }
+            let survived = *r == route && snap.net_spread_bps >= tier.min_bps();
+            resolved.push(((r.clone(), *tier), survived));
+            false
+        });
+        for (key, survived) in resolved {
+            let p = self.survival.entry(key).or_insert(0.0);
+            *p += SURVIVAL_ALPHA * (if survived { 1.0 } else { 0.0 } - *p);
+        }
+
+        let tier = SpreadTier::from_bps(snap.net_spread_bps);
+        if tier != SpreadTier::Noise && !self.pending.iter().any(|(r, t, _)| *r == route && *t == tier) {
+            self.pending.push((route, tier, now));
+        }
+    }
+
+    /// Stop watching a route we're about to trade (our own fill would read as decay)
+    pub fn discard_pending(&mut self, route: &Route) {
+        self.pending.retain(|(r, _, _)| r != route);
+    }
+
+    /// Rolling probability that a `tier` spread on `route` lasts the horizon (0 until observed)
+    pub fn estimated_survival_prob(&self, route: &Route, tier: SpreadTier) -> f64 {
+        self.survival.get(&(route.clone(), tier)).copied().unwrap_or(0.0)
+    }
+
     /// Analyze velocity at trigger time - returns None if insufficient data
     pub fn analyze(&self) -> Option<VelocityAnalysis> {
         if self.history.len() < 2 {
@@ -124,7 +201,7 @@ impl SpreadTracker {
             snapshots,
             velocity_bps_per_sec,
             acceleration,
-            is_spike: velocity_bps_per_sec > 10.0, // Threshold: 10 bps/sec = spike
+            is_spike: velocity_bps_per_sec > self.spike_velocity,
             spread_at_trigger,
             max_spread_in_window: max_spread,
             min_spread_in_window: min_spread,
@@ -141,3 +218,80 @@ impl SpreadTracker {
             .join(" -> ")
     }
 }
+
+/// Observe-only period before a bot trades: spreads are recorded, nothing executes
+#[derive(Debug, Clone, Copy)]
+pub struct Warmup {
+    until: Instant,
+    done: bool,
+}
+
+impl Warmup {
+    pub fn new(duration: Duration) -> Self {
+        Self { until: Instant::now() + duration, done: duration.is_zero() }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// True exactly once: on the first call at or after the end of the warmup
+    pub fn complete(&mut self, now: Instant) -> bool {
+        if self.done || now < self.until {
+            return false;
+        }
+        self.done = true;
+        true
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.until.saturating_duration_since(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(buy: &str, sell: &str, bps: i32, at_ms: u128) -> SpreadSnapshot {
+        SpreadSnapshot {
+            timestamp_ms: at_ms,
+            buy_pool: buy.to_string(),
+            sell_pool: sell.to_string(),
+            buy_price: 0.03,
+            sell_price: 0.03,
+            gross_spread_bps: bps,
+            net_spread_bps: bps,
+        }
+    }
+
+    #[test]
+    fn test_survival_estimate_tracks_outcomes() {
+        let mut tracker = SpreadTracker::new(10).with_survival_horizon(100);
+        let route: Route = ("LFJ".to_string(), "Uniswap".to_string());
+        assert_eq!(tracker.estimated_survival_prob(&route, SpreadTier::Marginal), 0.0);
+
+        // 12 bps still at 11 bps 150ms later: survived
+        tracker.update_survival(&snap("LFJ", "Uniswap", 12, 0));
+        tracker.update_survival(&snap("LFJ", "Uniswap", 11, 150));
+        let p = tracker.estimated_survival_prob(&route, SpreadTier::Marginal);
+        assert!((p - SURVIVAL_ALPHA).abs() < 1e-12);
+
+        // The 11 bps spread is being watched again; another route is best at its horizon: decayed
+        tracker.update_survival(&snap("Uniswap", "LFJ", 3, 300));
+        assert!(tracker.estimated_survival_prob(&route, SpreadTier::Marginal) < p);
+    }
+
+    #[test]
+    fn test_warmup_completes_once() {
+        let mut warmup = Warmup::new(Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(!warmup.complete(start));
+        assert!(warmup.remaining(start) > Duration::from_secs(29));
+
+        let later = start + Duration::from_secs(31);
+        assert!(warmup.complete(later) && warmup.is_done());
+        assert!(!warmup.complete(later));
+        assert!(Warmup::new(Duration::ZERO).is_done());
+    }
+}
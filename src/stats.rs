@@ -1,13 +1,27 @@
 //! Arbitrage execution statistics and logging
 //!
 //! Tracks detailed stats for every arb attempt to understand real-world behavior.
+//!
+//! `reconcile_from_chain` rebuilds a session's P&L from ERC20 Transfer logs,
+//! independent of the balance deltas the arb loops add up.
 
+use alloy::primitives::{Address, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::Filter;
+use alloy::sol;
+use alloy::sol_types::SolEvent;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{OpenOptions, File};
-use std::io::{Write, BufWriter};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Write, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+use crate::config::{dex_name, get_all_pools, get_routers, USDC_ADDRESS, USDC_DECIMALS, WMON_ADDRESS, WMON_DECIMALS};
+use crate::execution::FastArbResult;
+use crate::simulation::profit_calculator::ProfitBreakdown;
+use crate::simulation::paper::PaperSummary;
 use crate::spread_tracker::SpreadSnapshot;
 
 /// Detailed snapshot before arb execution
@@ -41,6 +55,10 @@ pub struct PreExecutionSnapshot {
     pub acceleration: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_spike_pattern: Option<bool>,
+
+    // Expected profit breakdown (optional - AutoArb net-profit gate)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profit_breakdown: Option<ProfitBreakdown>,
 }
 
 /// Detailed snapshot after arb execution
@@ -76,6 +94,17 @@ pub struct PostExecutionSnapshot {
 
     // Timing
     pub total_execution_ms: u128,
+
+    /// Paper-trading fill (`AutoArb --paper`): no transactions were sent
+    #[serde(default)]
+    pub simulated: bool,
+}
+
+impl PostExecutionSnapshot {
+    /// No leg left open: swap 1 never filled, or swap 2 closed it
+    pub fn is_settled(&self) -> bool {
+        !self.swap1_success || self.swap2_success
+    }
 }
 
 /// Complete arb execution record
@@ -86,12 +115,251 @@ pub struct ArbExecutionRecord {
     pub post: Option<PostExecutionSnapshot>,
     pub success: bool,
     pub error: Option<String>,
+    /// Block the spread's prices were read at
+    #[serde(default)]
+    pub block_number: Option<u64>,
+}
+
+/// Realized slippage counts per bucket for one swap leg
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlippageHistogram {
+    #[serde(rename = "<5")]
+    pub under_5: u64,
+    #[serde(rename = "5-10")]
+    pub from_5_to_10: u64,
+    #[serde(rename = "10-25")]
+    pub from_10_to_25: u64,
+    #[serde(rename = "25-50")]
+    pub from_25_to_50: u64,
+    #[serde(rename = "50+")]
+    pub over_50: u64,
+}
+
+impl SlippageHistogram {
+    /// Count one fill; negative slippage (better than expected) lands in <5
+    pub fn record(&mut self, slippage_bps: i32) {
+        match slippage_bps {
+            i32::MIN..=4 => self.under_5 += 1,
+            5..=9 => self.from_5_to_10 += 1,
+            10..=24 => self.from_10_to_25 += 1,
+            25..=49 => self.from_25_to_50 += 1,
+            _ => self.over_50 += 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.under_5 + self.from_5_to_10 + self.from_10_to_25 + self.from_25_to_50 + self.over_50
+    }
+
+    /// (label, count) in bucket order
+    pub fn buckets(&self) -> [(&'static str, u64); 5] {
+        [
+            ("<5", self.under_5),
+            ("5-10", self.from_5_to_10),
+            ("10-25", self.from_10_to_25),
+            ("25-50", self.from_25_to_50),
+            ("50+", self.over_50),
+        ]
+    }
+}
+
+/// Peak-to-trough drop in session P&L, for ProdArb's `--max-drawdown-wmon` pause
+#[derive(Debug, Clone, Default)]
+pub struct DrawdownTracker {
+    /// Highest cumulative P&L since the session started (or the last pause)
+    pub high_water: f64,
+}
+
+impl DrawdownTracker {
+    /// Raise the high-water mark if `pnl` beats it; returns the current drawdown
+    pub fn update(&mut self, pnl: f64) -> f64 {
+        self.high_water = self.high_water.max(pnl);
+        self.high_water - pnl
+    }
+
+    /// After a pause: measure the next drawdown from where P&L stands now
+    pub fn reset(&mut self, pnl: f64) {
+        self.high_water = pnl;
+    }
+}
+
+/// WMON notional of arbs executed but not yet settled, for ProdArb's `--max-exposure-wmon`
+#[derive(Debug, Clone, Default)]
+pub struct ExposureTracker {
+    /// Cap on in-flight notional; None only tracks
+    pub limit: Option<f64>,
+    pub in_flight: f64,
+    /// Highest `in_flight` this session
+    pub peak: f64,
+    /// Unsettled arbs as (amount, quote token still held from swap 1)
+    stranded: Vec<(f64, f64)>,
+    /// Wallet quote balance when last observed, to measure outflows from
+    quote_mark: Option<f64>,
+}
+
+impl ExposureTracker {
+    pub fn new(limit: Option<f64>) -> Self {
+        Self { limit, ..Self::default() }
+    }
+
+    /// Count `amount` as in flight; false (and nothing counted) if that would exceed the limit
+    pub fn try_open(&mut self, amount: f64) -> bool {
+        if self.limit.is_some_and(|limit| self.in_flight + amount > limit + 1e-12) {
+            return false;
+        }
+        self.in_flight += amount;
+        self.peak = self.peak.max(self.in_flight);
+        true
+    }
+
+    /// Release an arb's `amount` once its post-execution snapshot shows it settled
+    pub fn settle(&mut self, amount: f64) {
+        self.in_flight = (self.in_flight - amount).max(0.0);
+    }
+
+    /// Keep an unsettled arb's `amount` in flight until the `stranded_quote`
+    /// it left in the wallet has been swapped out (see `release_outflow`)
+    pub fn strand(&mut self, amount: f64, stranded_quote: f64) {
+        self.stranded.push((amount, stranded_quote));
+    }
+
+    pub fn has_stranded(&self) -> bool {
+        !self.stranded.is_empty()
+    }
+
+    /// Wallet quote balance after an arb, so its own fills (and exact-output
+    /// profit kept in the quote token) never count as an outflow
+    pub fn mark_quote(&mut self, quote: f64) {
+        self.quote_mark = Some(quote);
+    }
+
+    /// Apply the quote outflow since the last mark to the stranded arbs,
+    /// oldest first, and settle those whose held quote is gone. Returns the WMON released.
+    pub fn release_outflow(&mut self, quote_now: f64) -> f64 {
+        let mut outflow = self.quote_mark.map_or(0.0, |mark| (mark - quote_now).max(0.0));
+        self.quote_mark = Some(quote_now);
+        let mut released = 0.0;
+        self.stranded.retain_mut(|(amount, held)| {
+            let taken = outflow.min(*held);
+            *held -= taken;
+            outflow -= taken;
+            let gone = *held <= 1e-6;
+            if gone {
+                released += *amount;
+            }
+            !gone
+        });
+        self.settle(released);
+        released
+    }
+}
+
+/// Quoter prediction vs realized fill per DEX, as |actual - quoted| / quoted in bps
+#[derive(Debug, Clone, Default)]
+pub struct QuoteAccuracy {
+    /// DEX -> (fills, sum of absolute errors in bps)
+    dexes: BTreeMap<String, (u64, f64)>,
+}
+
+impl QuoteAccuracy {
+    pub fn record(&mut self, dex: &str, quoted: f64, actual: f64) {
+        if quoted <= 0.0 {
+            return;
+        }
+        let error_bps = ((actual - quoted) / quoted * 10_000.0).abs();
+        let entry = self.dexes.entry(dex_name(dex).to_string()).or_default();
+        entry.0 += 1;
+        entry.1 += error_bps;
+    }
+
+    /// (dex, fills, mean absolute error in bps)
+    pub fn mean_abs_error_bps(&self) -> Vec<(&str, u64, f64)> {
+        self.dexes
+            .iter()
+            .map(|(dex, (count, sum))| (dex.as_str(), *count, sum / *count as f64))
+            .collect()
+    }
+}
+
+/// What a background receipt fetch learned about a TX sent without waiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiptOutcome {
+    Confirmed { gas_used: u64 },
+    Reverted { gas_used: u64 },
+    /// No receipt within the watch timeout (dropped, or still pending)
+    TimedOut,
+}
+
+/// Session tally of `ReceiptOutcome`s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiptOutcomes {
+    pub confirmed: u64,
+    pub reverted: u64,
+    pub timed_out: u64,
+    pub gas_used: u64,
+}
+
+impl ReceiptOutcomes {
+    pub fn record(&mut self, outcome: ReceiptOutcome) {
+        match outcome {
+            ReceiptOutcome::Confirmed { gas_used } => {
+                self.confirmed += 1;
+                self.gas_used += gas_used;
+            }
+            ReceiptOutcome::Reverted { gas_used } => {
+                self.reverted += 1;
+                self.gas_used += gas_used;
+            }
+            ReceiptOutcome::TimedOut => self.timed_out += 1,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.confirmed + self.reverted + self.timed_out
+    }
+
+    /// Print the tally (silent if nothing was watched)
+    pub fn print(&self) {
+        if self.total() == 0 {
+            return;
+        }
+        println!("\n  Background receipts ({} watched):", self.total());
+        println!("    Confirmed: {} | Reverted: {} | Timed out: {} | Gas used: {}",
+            self.confirmed, self.reverted, self.timed_out, self.gas_used);
+    }
+}
+
+/// Outcomes recorded by background receipt watches this session
+static RECEIPT_OUTCOMES: Mutex<ReceiptOutcomes> = Mutex::new(ReceiptOutcomes { confirmed: 0, reverted: 0, timed_out: 0, gas_used: 0 });
+
+pub fn record_receipt_outcome(outcome: ReceiptOutcome) {
+    RECEIPT_OUTCOMES.lock().unwrap_or_else(|e| e.into_inner()).record(outcome);
+}
+
+pub fn receipt_outcomes() -> ReceiptOutcomes {
+    *RECEIPT_OUTCOMES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Last line of a session's JSONL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub timestamp: String,
+    pub executions: u64,
+    pub swap1_slippage: SlippageHistogram,
+    pub swap2_slippage: SlippageHistogram,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paper: Option<PaperSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_receipts: Option<ReceiptOutcomes>,
 }
 
 /// Stats logger that writes to JSON Lines file
 pub struct StatsLogger {
     file_path: PathBuf,
     execution_count: u64,
+    swap1_slippage: SlippageHistogram,
+    swap2_slippage: SlippageHistogram,
+    quote_accuracy: QuoteAccuracy,
 }
 
 impl StatsLogger {
@@ -100,9 +368,71 @@ impl StatsLogger {
         Self {
             file_path,
             execution_count: 0,
+            swap1_slippage: SlippageHistogram::default(),
+            swap2_slippage: SlippageHistogram::default(),
+            quote_accuracy: QuoteAccuracy::default(),
+        }
+    }
+
+    /// Add a two-TX result's per-leg slippage (atomic results have no per-leg fill)
+    pub fn record_slippage(&mut self, result: &FastArbResult) {
+        if result.swap2_tx_hash.is_empty() {
+            return;
+        }
+        if result.swap1_success {
+            self.swap1_slippage.record(result.swap1_slippage_bps);
+        }
+        if result.swap2_success {
+            self.swap2_slippage.record(result.swap2_slippage_bps);
+        }
+    }
+
+    /// Add each leg's quoter prediction vs fill, for legs that were quoted and filled
+    pub fn record_quote_accuracy(&mut self, result: &FastArbResult, sell_dex: &str, buy_dex: &str) {
+        if let (Some(quoted), true) = (result.quoted_usdc_intermediate, result.swap1_success) {
+            self.quote_accuracy.record(sell_dex, quoted, result.usdc_intermediate);
+        }
+        if let (Some(quoted), true) = (result.quoted_wmon_out, result.swap2_success) {
+            self.quote_accuracy.record(buy_dex, quoted, result.wmon_out);
+        }
+    }
+
+    /// Print mean absolute quote error per DEX (call at session end; silent if nothing was quoted)
+    pub fn print_quote_accuracy(&self) {
+        let rows = self.quote_accuracy.mean_abs_error_bps();
+        if rows.is_empty() {
+            return;
+        }
+        println!("\n  Quote vs Fill (mean |error| bps):");
+        println!("    {:<16} {:>6} {:>10}", "DEX", "Fills", "Error");
+        for (dex, count, error) in rows {
+            println!("    {:<16} {:>6} {:>10.1}", dex, count, error);
         }
     }
 
+    /// Print the slippage histograms (call at session end)
+    pub fn print_slippage_summary(&self) {
+        println!("\n  Realized Slippage (bps):");
+        println!("    {:<8} {:>8} {:>8}", "Bucket", "Swap 1", "Swap 2");
+        for ((label, swap1), (_, swap2)) in self.swap1_slippage.buckets().iter().zip(self.swap2_slippage.buckets()) {
+            println!("    {:<8} {:>8} {:>8}", label, swap1, swap2);
+        }
+        println!("    {:<8} {:>8} {:>8}", "Total", self.swap1_slippage.total(), self.swap2_slippage.total());
+    }
+
+    /// Append the session summary line (`{"session_summary": {...}}`)
+    pub fn log_session_summary(&self, paper: Option<PaperSummary>) {
+        let summary = SessionSummary {
+            timestamp: Local::now().to_rfc3339(),
+            executions: self.execution_count,
+            swap1_slippage: self.swap1_slippage.clone(),
+            swap2_slippage: self.swap2_slippage.clone(),
+            paper,
+            background_receipts: Some(receipt_outcomes()).filter(|r| r.total() > 0),
+        };
+        self.append_line(&serde_json::json!({ "session_summary": summary }));
+    }
+
     pub fn next_id(&mut self) -> u64 {
         self.execution_count += 1;
         self.execution_count
@@ -110,6 +440,10 @@ impl StatsLogger {
 
     /// Log a complete execution record (append as JSON line)
     pub fn log_execution(&self, record: &ArbExecutionRecord) {
+        self.append_line(record);
+    }
+
+    fn append_line<T: Serialize>(&self, value: &T) {
         match OpenOptions::new()
             .create(true)
             .append(true)
@@ -117,7 +451,7 @@ impl StatsLogger {
         {
             Ok(file) => {
                 let mut writer = BufWriter::new(file);
-                if let Ok(json) = serde_json::to_string(record) {
+                if let Ok(json) = serde_json::to_string(value) {
                     let _ = writeln!(writer, "{}", json);
                 }
             }
@@ -128,6 +462,163 @@ impl StatsLogger {
     }
 }
 
+const CSV_HEADER: &str =
+    "id,timestamp,pair,gross_bps,net_bps,amount,actual_wmon_received,gas_cost_mon,success,error";
+
+/// Quote a CSV field if it contains a delimiter, quote or newline
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// One flat CSV row; failed executions leave the profit columns empty
+fn csv_row(record: &ArbExecutionRecord) -> String {
+    let (wmon_received, gas_cost) = match (&record.post, record.success) {
+        (Some(post), true) => (post.actual_wmon_back.to_string(), post.total_gas_cost_mon.to_string()),
+        _ => (String::new(), String::new()),
+    };
+    [
+        record.id.to_string(),
+        csv_field(&record.pre.timestamp),
+        csv_field(&format!("{}->{}", record.pre.sell_dex, record.pre.buy_dex)),
+        record.pre.gross_spread_bps.to_string(),
+        record.pre.net_spread_bps.to_string(),
+        record.pre.amount_wmon.to_string(),
+        wmon_received,
+        gas_cost,
+        record.success.to_string(),
+        csv_field(record.error.as_deref().unwrap_or("")),
+    ]
+    .join(",")
+}
+
+/// Flatten an arb_stats JSONL into a CSV (one row per execution record).
+/// Session summary and unparseable lines are skipped.
+pub fn export_csv(jsonl_path: &str, csv_path: &str) -> eyre::Result<()> {
+    let input = File::open(Path::new(jsonl_path))
+        .map_err(|e| eyre::eyre!("Failed to open {}: {}", jsonl_path, e))?;
+    let mut writer = BufWriter::new(File::create(Path::new(csv_path))?);
+    writeln!(writer, "{}", CSV_HEADER)?;
+
+    let (mut rows, mut skipped) = (0usize, 0usize);
+    for line in BufReader::new(input).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ArbExecutionRecord>(&line) {
+            Ok(record) => {
+                writeln!(writer, "{}", csv_row(&record))?;
+                rows += 1;
+            }
+            Err(_) => skipped += 1,
+        }
+    }
+    writer.flush()?;
+
+    println!("  Exported {} rows to {} ({} non-record lines skipped)", rows, csv_path, skipped);
+    Ok(())
+}
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+}
+
+/// Blocks per eth_getLogs request (RPCs cap the range)
+const LOG_CHUNK_BLOCKS: u64 = 1000;
+
+/// WMON/USDC flows of one address over a block range, from Transfer logs.
+/// Transfers with a configured pool or router are trading; anything else
+/// (funding, withdrawals, manual transfers) is external and not P&L.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainReconciliation {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub transfers: usize,
+    pub trade_wmon: f64,
+    pub trade_usdc: f64,
+    pub external_wmon: f64,
+    pub external_usdc: f64,
+}
+
+impl ChainReconciliation {
+    /// Book one transfer of `token` as seen from `holder`
+    pub fn add_transfer(&mut self, holder: Address, venues: &[Address], token: Address, from: Address, to: Address, value: U256) {
+        let is_wmon = token == WMON_ADDRESS;
+        if from == to || !(is_wmon || token == USDC_ADDRESS) {
+            return;
+        }
+        let decimals = if is_wmon { WMON_DECIMALS } else { USDC_DECIMALS };
+        let (counterparty, sign) = if from == holder { (to, -1.0) } else { (from, 1.0) };
+        let amount = sign * f64::from(value) / 10f64.powi(decimals as i32);
+        let slot = match (venues.contains(&counterparty), is_wmon) {
+            (true, true) => &mut self.trade_wmon,
+            (true, false) => &mut self.trade_usdc,
+            (false, true) => &mut self.external_wmon,
+            (false, false) => &mut self.external_usdc,
+        };
+        *slot += amount;
+        self.transfers += 1;
+    }
+
+    /// Chain-derived realized P&L (trading WMON flow) minus the loop's logged P&L
+    pub fn discrepancy(&self, logged_pnl_wmon: f64) -> f64 {
+        self.trade_wmon - logged_pnl_wmon
+    }
+
+    pub fn print(&self, logged_pnl_wmon: f64) {
+        let discrepancy = self.discrepancy(logged_pnl_wmon);
+        println!("\n  On-chain Reconciliation (blocks {}-{}, {} transfers):", self.from_block, self.to_block, self.transfers);
+        println!("    Trading:   WMON {:>+14.6}  USDC {:>+12.6}", self.trade_wmon, self.trade_usdc);
+        println!("    External:  WMON {:>+14.6}  USDC {:>+12.6}", self.external_wmon, self.external_usdc);
+        println!("    Realized P&L (chain):  {:+.6} WMON", self.trade_wmon);
+        println!("    Logged P&L:            {:+.6} WMON", logged_pnl_wmon);
+        if discrepancy.abs() > 1e-6 {
+            println!("    ⚠️  Discrepancy:        {:+.6} WMON (chain - logged)", discrepancy);
+        } else {
+            println!("    ✓ Matches the logged P&L");
+        }
+    }
+}
+
+/// Recompute realized P&L for `contract` from WMON/USDC Transfer logs in
+/// `from_block..=to_block`
+pub async fn reconcile_from_chain<P: Provider>(
+    provider: &P,
+    contract: Address,
+    from_block: u64,
+    to_block: u64,
+) -> eyre::Result<ChainReconciliation> {
+    let venues: Vec<Address> = get_all_pools()
+        .iter()
+        .map(|p| p.address)
+        .chain(get_routers().iter().flat_map(|r| [r.address, r.pool_address]))
+        .collect();
+    let mut recon = ChainReconciliation { from_block, to_block, ..Default::default() };
+
+    let mut start = from_block;
+    while start <= to_block {
+        let end = (start + LOG_CHUNK_BLOCKS - 1).min(to_block);
+        let base = Filter::new()
+            .address(vec![WMON_ADDRESS, USDC_ADDRESS])
+            .event_signature(Transfer::SIGNATURE_HASH)
+            .from_block(start)
+            .to_block(end);
+        let outgoing = provider.get_logs(&base.clone().topic1(contract.into_word())).await?;
+        let incoming = provider.get_logs(&base.topic2(contract.into_word())).await?;
+        for log in outgoing.iter().chain(&incoming) {
+            let Ok(transfer) = log.log_decode::<Transfer>() else { continue };
+            let Transfer { from, to, value } = transfer.inner.data;
+            recon.add_transfer(contract, &venues, log.address(), from, to, value);
+        }
+        start = end + 1;
+    }
+    Ok(recon)
+}
+
 /// Print pre-execution snapshot to console
 pub fn print_pre_execution(snap: &PreExecutionSnapshot) {
     println!();
@@ -152,6 +643,18 @@ pub fn print_pre_execution(snap: &PreExecutionSnapshot) {
     println!("║    Expected: {:>12.6} USDC (intermediate)            ║", snap.expected_usdc);
     println!("║    Expected: {:>12.6} WMON (output)                  ║", snap.expected_wmon_back);
     println!("║    Slippage: {:>12} bps                              ║", snap.slippage_bps);
+    if let Some(ref p) = snap.profit_breakdown {
+        println!("╠══════════════════════════════════════════════════════════════╣");
+        println!("║  EXPECTED PROFIT (WMON):                                     ║");
+        println!("║    Gross:    {:>+12.6}                                ║", p.gross_wmon);
+        println!("║    DEX Fees: {:>+12.6}                                ║", -p.dex_fees_wmon);
+        println!("║    Gas:      {:>+12.6} ({} gas @ {} gwei)       ║", -p.gas_wmon, p.gas_units, p.gas_price_wei / 1_000_000_000);
+        if p.gas_token_price > 0.0 {
+            println!("║              {:>+12.6} USDC (MON @ {:.6})              ║", -p.gas_quote, p.gas_token_price);
+        }
+        let net_color = if p.net_wmon > 0.0 { "32" } else { "31" };
+        println!("║    Net:      \x1b[1;{}m{:>+12.6}\x1b[0m                                ║", net_color, p.net_wmon);
+    }
     println!("╚══════════════════════════════════════════════════════════════╝");
 }
 
@@ -196,3 +699,140 @@ pub fn print_post_execution(pre: &PreExecutionSnapshot, post: &PostExecutionSnap
     println!("║  Execution Time: {:>6} ms                                   ║", post.total_execution_ms);
     println!("╚══════════════════════════════════════════════════════════════╝");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slippage_buckets_and_summary_keys() {
+        let mut hist = SlippageHistogram::default();
+        for bps in [-3, 4, 5, 12, 25, 49, 50, 300] {
+            hist.record(bps);
+        }
+        assert_eq!(hist.buckets().map(|(_, n)| n), [2, 1, 1, 2, 2]);
+
+        let json = serde_json::to_value(&hist).unwrap();
+        assert_eq!(json["<5"], 2);
+        assert_eq!(json["50+"], 2);
+    }
+
+    #[test]
+    fn test_receipt_outcomes_tally() {
+        let mut outcomes = ReceiptOutcomes::default();
+        outcomes.record(ReceiptOutcome::Confirmed { gas_used: 300_000 });
+        outcomes.record(ReceiptOutcome::Reverted { gas_used: 120_000 });
+        outcomes.record(ReceiptOutcome::TimedOut);
+        assert_eq!(outcomes, ReceiptOutcomes { confirmed: 1, reverted: 1, timed_out: 1, gas_used: 420_000 });
+        assert_eq!(outcomes.total(), 3);
+    }
+
+    #[test]
+    fn test_drawdown_is_from_high_water_mark() {
+        let mut drawdown = DrawdownTracker::default();
+        assert_eq!(drawdown.update(0.3), 0.0);
+        assert!((drawdown.update(0.1) - 0.2).abs() < 1e-12);
+        // A loss from zero still counts from the 0.3 high
+        assert!((drawdown.update(-0.05) - 0.35).abs() < 1e-12);
+
+        drawdown.reset(-0.05);
+        assert_eq!(drawdown.update(-0.05), 0.0);
+    }
+
+    #[test]
+    fn test_exposure_cap_and_peak() {
+        let mut exposure = ExposureTracker::new(Some(2.0));
+        assert!(exposure.try_open(1.5));
+        // A second arb before the first settles would put 3 WMON out
+        assert!(!exposure.try_open(1.5));
+        assert!(exposure.try_open(0.5));
+        exposure.settle(1.5);
+        assert!(exposure.try_open(1.5));
+        assert_eq!((exposure.in_flight, exposure.peak), (2.0, 2.0));
+
+        let mut uncapped = ExposureTracker::new(None);
+        assert!(uncapped.try_open(100.0));
+
+        // Two legs strand 3 USDC each; a profitable arb in between raises the
+        // balance, which must not hide the stranded USDC leaving later
+        let mut exposure = ExposureTracker::new(Some(3.0));
+        assert!(exposure.try_open(1.0));
+        exposure.strand(1.0, 3.0);
+        exposure.mark_quote(103.0);
+        assert!(exposure.try_open(1.0));
+        exposure.strand(1.0, 3.0);
+        exposure.mark_quote(106.0);
+        assert!(exposure.try_open(1.0));
+        exposure.settle(1.0);
+        exposure.mark_quote(106.5);
+        assert_eq!(exposure.release_outflow(106.5), 0.0);
+        // 4 USDC out: the oldest leg is cleared, the next keeps 2
+        assert_eq!(exposure.release_outflow(102.5), 1.0);
+        assert!(exposure.has_stranded());
+        assert_eq!(exposure.release_outflow(100.5), 1.0);
+        assert!(!exposure.has_stranded());
+        assert_eq!(exposure.in_flight, 0.0);
+    }
+
+    #[test]
+    fn test_quote_accuracy_is_mean_absolute_per_dex() {
+        let mut accuracy = QuoteAccuracy::default();
+        accuracy.record("Uniswap", 100.0, 100.1); // +10 bps
+        accuracy.record("Uniswap@500", 100.0, 99.7); // -30 bps, same DEX
+        accuracy.record("MondayTrade", 50.0, 50.0);
+        accuracy.record("LFJ", 0.0, 1.0); // no quote
+
+        let rows = accuracy.mean_abs_error_bps();
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].0, rows[0].1), ("MondayTrade", 1));
+        assert_eq!((rows[1].0, rows[1].1), ("Uniswap", 2));
+        assert!((rows[1].2 - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reconciliation_separates_trading_from_external() {
+        let holder = Address::with_last_byte(1);
+        let pool = Address::with_last_byte(2);
+        let owner = Address::with_last_byte(3);
+        let wmon = |x: u64| U256::from(x) * U256::from(10u64).pow(U256::from(18));
+        let usdc = |x: u64| U256::from(x) * U256::from(1_000_000u64);
+
+        let mut recon = ChainReconciliation::default();
+        recon.add_transfer(holder, &[pool], WMON_ADDRESS, owner, holder, wmon(50)); // funding
+        recon.add_transfer(holder, &[pool], WMON_ADDRESS, holder, pool, wmon(10)); // sell leg
+        recon.add_transfer(holder, &[pool], USDC_ADDRESS, pool, holder, usdc(3));
+        recon.add_transfer(holder, &[pool], USDC_ADDRESS, holder, pool, usdc(3)); // buy leg
+        recon.add_transfer(holder, &[pool], WMON_ADDRESS, pool, holder, wmon(11));
+
+        assert_eq!(recon.transfers, 5);
+        assert!((recon.trade_wmon - 1.0).abs() < 1e-9);
+        assert!(recon.trade_usdc.abs() < 1e-9);
+        assert!((recon.external_wmon - 50.0).abs() < 1e-9);
+        assert!((recon.discrepancy(0.75) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_csv_row_blanks_profit_on_failure() {
+        let pre: PreExecutionSnapshot = serde_json::from_value(serde_json::json!({
+            "timestamp": "2025-01-01T00:00:00+00:00", "wmon_balance": 10.0, "usdc_balance": 0.0,
+            "mon_balance": 1.0, "sell_dex": "Uniswap", "sell_price": 0.03, "buy_dex": "LFJ",
+            "buy_price": 0.0299, "gross_spread_bps": 33, "net_spread_bps": 12, "amount_wmon": 1.5,
+            "expected_usdc": 0.045, "expected_wmon_back": 1.501, "slippage_bps": 50
+        }))
+        .unwrap();
+        let record = ArbExecutionRecord {
+            id: 7,
+            pre,
+            post: None,
+            success: false,
+            error: Some("Swap 1 reverted: \"STF\", too little".to_string()),
+            block_number: None,
+        };
+
+        assert_eq!(CSV_HEADER.split(',').count(), 10);
+        assert_eq!(
+            csv_row(&record),
+            "7,2025-01-01T00:00:00+00:00,Uniswap->LFJ,33,12,1.5,,,false,\"Swap 1 reverted: \"\"STF\"\", too little\""
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Interactive spread dashboard (`Dashboard --tui`)
+//!
+//! A ratatui/crossterm front end over the same `SpreadDisplay` state the ANSI
+//! dashboard renders, so filter, focus and pause live in one place. Keys:
+//! `s` sound, `Tab` focus next route, `+`/`-` min-spread filter, `p` pause
+//! polling, `q`/`Esc`/`Ctrl+C` quit. Only compiled with `--features tui`.
+//!
+//! The terminal is restored on every exit path: errors and normal exit via a
+//! drop guard, panics via the hook `ratatui::try_init` installs.
+
+use alloy::providers::Provider;
+use eyre::Result;
+
+use crate::pools::PriceCall;
+use crate::spread_display::SpreadDisplay;
+
+#[cfg(feature = "tui")]
+pub async fn run<P: Provider>(
+    provider: &P,
+    price_calls: Vec<PriceCall>,
+    display: &mut SpreadDisplay,
+    refresh_ms: u64,
+    min_liquidity: Option<u128>,
+) -> Result<()> {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use crossterm::event::{self, Event, KeyEventKind};
+
+    use crate::display::calculate_spreads;
+    use crate::multicall::fetch_prices_batched;
+    use crate::node_config::AdaptivePoll;
+    use crate::config::TokenPair;
+    use crate::pools::liquidity::attach_liquidity;
+
+    let mut terminal = TerminalGuard(ratatui::try_init()?);
+
+    // crossterm's reader blocks, so keys come in from a thread
+    let (tx, mut keys) = tokio::sync::mpsc::unbounded_channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let reader_stop = stop.clone();
+    std::thread::spawn(move || {
+        while !reader_stop.load(Ordering::Relaxed) {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(true) => match event::read() {
+                    Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                        if tx.send(key).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                },
+                Ok(false) => {}
+                Err(_) => break,
+            }
+        }
+    });
+
+    let mut poll = AdaptivePoll::new(Duration::from_millis(refresh_ms));
+    let mut prices = Vec::new();
+    let mut block = None;
+    let mut error: Option<String> = None;
+
+    let result = loop {
+        if let Err(e) = terminal.0.draw(|frame| draw(frame, display, &prices, block, error.as_deref())) {
+            break Err(e.into());
+        }
+
+        tokio::select! {
+            _ = poll.tick(), if !display.paused => {
+                let fetch_start = Instant::now();
+                let fetched = fetch_prices_batched(provider, price_calls.clone()).await;
+                poll.record(fetch_start.elapsed());
+                display.poll_interval = Some(poll.interval());
+
+                match fetched {
+                    Ok((mut fetched_prices, _, block_num)) => {
+                        if min_liquidity.is_some() {
+                            attach_liquidity(provider, &TokenPair::wmon_usdc(), &mut fetched_prices).await;
+                        }
+                        let spreads = calculate_spreads(&fetched_prices, min_liquidity);
+                        display.update(&spreads);
+
+                        // Sound alert for HOT+ spreads
+                        let best_bps = spreads.first().map(|s| (s.net_spread_pct * 100.0) as i32);
+                        if display.alert_sound && best_bps.is_some_and(|bps| bps >= 15) {
+                            let _ = terminal.0.backend_mut().write_all(b"\x07");
+                        }
+                        prices = fetched_prices;
+                        block = block_num;
+                        error = None;
+                    }
+                    Err(e) => error = Some(e.to_string()),
+                }
+            }
+            key = keys.recv() => {
+                let Some(key) = key else { break Ok(()) };
+                if !handle_key(display, key) {
+                    break Ok(());
+                }
+            }
+        }
+    };
+
+    stop.store(true, Ordering::Relaxed);
+    drop(terminal);
+    println!("\nDashboard stopped.");
+    result
+}
+
+#[cfg(not(feature = "tui"))]
+pub async fn run<P: Provider>(
+    _provider: &P,
+    _price_calls: Vec<PriceCall>,
+    _display: &mut SpreadDisplay,
+    _refresh_ms: u64,
+    _min_liquidity: Option<u128>,
+) -> Result<()> {
+    Err(eyre::eyre!("--tui requires building with `--features tui`"))
+}
+
+/// Restores the terminal when the dashboard exits, however it exits
+#[cfg(feature = "tui")]
+struct TerminalGuard(ratatui::DefaultTerminal);
+
+#[cfg(feature = "tui")]
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+/// Apply a keypress to the display state; false means quit
+#[cfg(feature = "tui")]
+fn handle_key(display: &mut SpreadDisplay, key: crossterm::event::KeyEvent) -> bool {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char('s') => display.toggle_sound(),
+        KeyCode::Tab | KeyCode::Char('f') => display.focus_next(),
+        KeyCode::Char('+') | KeyCode::Char('=') => display.adjust_min_display(1),
+        KeyCode::Char('-') => display.adjust_min_display(-1),
+        KeyCode::Char('p') | KeyCode::Char(' ') => display.toggle_pause(),
+        _ => {}
+    }
+    true
+}
+
+#[cfg(feature = "tui")]
+fn level_color(level: crate::spread_display::SpreadLevel) -> ratatui::style::Style {
+    use crate::spread_display::SpreadLevel;
+    use ratatui::style::{Color, Modifier, Style};
+
+    match level {
+        SpreadLevel::Dead => Style::default().fg(Color::DarkGray),
+        SpreadLevel::Noise => Style::default().fg(Color::White),
+        SpreadLevel::Watching => Style::default().fg(Color::Yellow),
+        SpreadLevel::Ready => Style::default().fg(Color::Green),
+        SpreadLevel::Hot | SpreadLevel::Critical => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn draw(
+    frame: &mut ratatui::Frame,
+    display: &SpreadDisplay,
+    prices: &[crate::pools::PoolPrice],
+    block: Option<u64>,
+    error: Option<&str>,
+) {
+    use ratatui::layout::{Constraint, Layout};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::Line;
+    use ratatui::widgets::{Block, Paragraph, Row, Table};
+
+    use crate::spread_display::SpreadLevel;
+
+    let [header_area, prices_area, spreads_area, footer_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(prices.len() as u16 + 3),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let poll = if display.paused {
+        "PAUSED".to_string()
+    } else {
+        display.poll_interval.map(|p| format!("{}ms", p.as_millis())).unwrap_or_else(|| "-".into())
+    };
+    let status = match error {
+        Some(e) => Line::styled(format!("Error fetching prices: {}", e), Style::default().fg(Color::Red)),
+        None => Line::from(format!(
+            "{} │ Block: {} │ Poll: {} │ Filter: >={}bps │ Sound: {}",
+            chrono::Local::now().format("%H:%M:%S%.3f"),
+            block.map(|b| b.to_string()).unwrap_or_else(|| "?".into()),
+            poll,
+            display.min_display_bps,
+            if display.alert_sound { "on" } else { "off" },
+        )),
+    };
+    frame.render_widget(
+        Paragraph::new(status).block(Block::bordered().title(" MONAD MEV SPREAD DASHBOARD ")),
+        header_area,
+    );
+
+    let mut sorted_prices = prices.to_vec();
+    sorted_prices.sort_by(|a, b| b.price.total_cmp(&a.price));
+    let best_price = sorted_prices.first().map(|p| p.price).unwrap_or(0.0);
+    let price_rows = sorted_prices.iter().enumerate().map(|(i, p)| {
+        let diff = if i == 0 {
+            "BEST".to_string()
+        } else if best_price > 0.0 {
+            format!("{:+.2}%", (p.price - best_price) / best_price * 100.0)
+        } else {
+            "-".to_string()
+        };
+        Row::new(vec![p.pool_name.clone(), format!("{:.6}", p.price), diff, format!("{:.2}%", p.fee_bps as f64 / 100.0)])
+    });
+    frame.render_widget(
+        Table::new(price_rows, [Constraint::Length(16), Constraint::Length(14), Constraint::Length(10), Constraint::Length(8)])
+            .header(Row::new(vec!["POOL", "PRICE", "VS BEST", "FEE"]).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::bordered().title(" Prices (USDC/WMON) ")),
+        prices_area,
+    );
+
+    let spread_rows = display.active_routes().into_iter().map(|(key, hist, bps)| {
+        let level = SpreadLevel::from_bps(bps);
+        let trend = hist.trend();
+        let mut style = level_color(level);
+        if display.focused_route.as_ref() == Some(key) {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        Row::new(vec![key.clone(), format!("{:+}", bps), level.label().to_string(), trend.arrow().to_string(), hist.sparkline()])
+            .style(style)
+    });
+    frame.render_widget(
+        Table::new(
+            spread_rows,
+            [Constraint::Length(28), Constraint::Length(8), Constraint::Length(9), Constraint::Length(3), Constraint::Length(12)],
+        )
+        .header(Row::new(vec!["ROUTE", "NET BPS", "LEVEL", "", "TREND"]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::bordered().title(" Spreads ")),
+        spreads_area,
+    );
+
+    frame.render_widget(
+        Paragraph::new("q quit │ s sound │ Tab focus │ +/- filter │ p pause").style(Style::default().fg(Color::DarkGray)),
+        footer_area,
+    );
+}
@@ -0,0 +1,318 @@
+//! Router allowance audit
+//!
+//! `PrepareArb` approves every router once with U256::MAX. If one of those
+//! approvals failed or has since been spent down, the first sign is a
+//! reverting swap. `audit_allowances` reads every (token, spender) allowance
+//! in one multicall, prints them, and returns the ones that are too low;
+//! `approve_missing` re-approves them. `revoke_allowances` (`RevokeArb`)
+//! sets them back to 0.
+//!
+//! V4 routers pull tokens through Permit2, so they need two allowances:
+//! token -> Permit2 (ERC20) and Permit2 -> Universal Router (Permit2's own).
+
+use alloy::network::TransactionBuilder;
+use alloy::primitives::{Address, Bytes, TxHash, U160, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::{RouterConfig, RouterType, TokenConfig};
+use crate::execution::routers::uniswap_v4::{build_permit2_approve, PERMIT2};
+use crate::gas::GasFees;
+use crate::multicall::aggregate_raw;
+use crate::nonce::next_nonce;
+
+// Monad mainnet chain ID
+const MONAD_CHAIN_ID: u64 = 143;
+
+/// Anything below this counts as missing (MAX approvals sit far above it)
+const MIN_ALLOWANCE: U256 = U256::from_limbs([0, 0, 1, 0]); // 2^128
+
+sol! {
+    #[derive(Debug)]
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    #[derive(Debug)]
+    function approve(address spender, uint256 amount) external returns (bool);
+
+    interface IPermit2 {
+        #[derive(Debug)]
+        function allowance(address user, address token, address spender)
+            external
+            view
+            returns (uint160 amount, uint48 expiration, uint48 nonce);
+
+        #[derive(Debug)]
+        function approve(address token, address spender, uint160 amount, uint48 expiration) external;
+    }
+}
+
+/// An address that needs an allowance on each token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spender {
+    pub name: String,
+    pub address: Address,
+    /// Allowance lives in Permit2 rather than the token
+    pub via_permit2: bool,
+}
+
+/// A (token, spender) pair whose allowance is too low
+#[derive(Debug, Clone)]
+pub struct MissingApproval {
+    pub token: Address,
+    pub token_symbol: &'static str,
+    pub spender: Spender,
+    pub current: U256,
+}
+
+/// Spenders the configured routers need, deduplicated by address
+/// (both Pancake pools share one SmartRouter)
+pub fn required_spenders(routers: &[RouterConfig]) -> Vec<Spender> {
+    let mut spenders: Vec<Spender> = Vec::new();
+    let mut push = |spender: Spender| {
+        if !spenders.iter().any(|s| s.address == spender.address && s.via_permit2 == spender.via_permit2) {
+            spenders.push(spender);
+        }
+    };
+    for router in routers {
+        if router.router_type == RouterType::UniswapV4 {
+            push(Spender { name: "Permit2".to_string(), address: PERMIT2, via_permit2: false });
+            push(Spender { name: router.name.to_string(), address: router.address, via_permit2: true });
+        } else {
+            push(Spender { name: router.name.to_string(), address: router.address, via_permit2: false });
+        }
+    }
+    spenders
+}
+
+fn allowance_call(owner: Address, token: Address, spender: &Spender) -> (Address, Bytes) {
+    if spender.via_permit2 {
+        let call = IPermit2::allowanceCall { user: owner, token, spender: spender.address };
+        (PERMIT2, Bytes::from(call.abi_encode()))
+    } else {
+        let call = allowanceCall { owner, spender: spender.address };
+        (token, Bytes::from(call.abi_encode()))
+    }
+}
+
+/// Usable allowance from a raw return value (expired Permit2 allowances count as 0)
+fn decode_allowance(spender: &Spender, data: &Bytes, now: u64) -> U256 {
+    if spender.via_permit2 {
+        match IPermit2::allowanceCall::abi_decode_returns(data) {
+            Ok(ret) if ret.expiration.to::<u64>() > now => U256::from(ret.amount),
+            _ => U256::ZERO,
+        }
+    } else {
+        allowanceCall::abi_decode_returns(data).unwrap_or_default()
+    }
+}
+
+/// (to, calldata) setting `spender`'s allowance on `token` to MAX, or to 0 when revoking
+fn approval_call(token: Address, spender: &Spender, revoke: bool) -> (Address, Bytes) {
+    match (spender.via_permit2, revoke) {
+        (true, false) => (PERMIT2, build_permit2_approve(token, spender.address)),
+        (true, true) => {
+            let call = IPermit2::approveCall { token, spender: spender.address, amount: U160::ZERO, expiration: Default::default() };
+            (PERMIT2, Bytes::from(call.abi_encode()))
+        }
+        (false, _) => {
+            let amount = if revoke { U256::ZERO } else { U256::MAX };
+            (token, Bytes::from(approveCall { spender: spender.address, amount }.abi_encode()))
+        }
+    }
+}
+
+fn spender_label(spender: &Spender) -> String {
+    if spender.via_permit2 { format!("{} (Permit2)", spender.name) } else { spender.name.clone() }
+}
+
+fn format_allowance(amount: U256) -> String {
+    if amount == U256::MAX {
+        "MAX".to_string()
+    } else if amount >= MIN_ALLOWANCE {
+        format!("~2^{}", amount.bit_len() - 1)
+    } else {
+        amount.to_string()
+    }
+}
+
+/// Current allowance for each (token, spender), in one multicall
+async fn read_allowances<P: Provider>(provider: &P, owner: Address, pairs: &[(&TokenConfig, &Spender)]) -> Result<Vec<U256>> {
+    let calls: Vec<(Address, Bytes)> = pairs
+        .iter()
+        .map(|(token, spender)| allowance_call(owner, token.address, spender))
+        .collect();
+    let results = aggregate_raw(provider, &calls).await?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    Ok(pairs
+        .iter()
+        .zip(results)
+        .map(|((_, spender), result)| result.map(|d| decode_allowance(spender, &d, now)).unwrap_or_default())
+        .collect())
+}
+
+/// Send one approval transaction and wait for it to land
+async fn send_approval<P: Provider>(provider_with_signer: &P, owner: Address, to: Address, calldata: Bytes, gas: GasFees) -> Result<TxHash> {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(to)
+        .from(owner)
+        .input(alloy::rpc::types::TransactionInput::new(calldata))
+        .gas_limit(100_000)
+        .nonce(next_nonce())
+        .max_fee_per_gas(gas.max_fee)
+        .max_priority_fee_per_gas(gas.priority_fee)
+        .with_chain_id(MONAD_CHAIN_ID);
+
+    let pending = provider_with_signer.send_transaction(tx).await.map_err(|e| eyre!("failed to send tx: {}", e))?;
+    let receipt = pending.get_receipt().await.map_err(|e| eyre!("failed to get receipt: {}", e))?;
+    if !receipt.status() {
+        return Err(eyre!("reverted"));
+    }
+    Ok(receipt.transaction_hash)
+}
+
+/// Read every token/router allowance for `owner`, print them, and return the missing ones
+pub async fn audit_allowances<P: Provider>(
+    provider: &P,
+    owner: Address,
+    routers: &[RouterConfig],
+    tokens: &[TokenConfig],
+) -> Result<Vec<MissingApproval>> {
+    let spenders = required_spenders(routers);
+    let pairs: Vec<(&TokenConfig, &Spender)> = tokens
+        .iter()
+        .flat_map(|t| spenders.iter().map(move |s| (t, s)))
+        .collect();
+    let allowances = read_allowances(provider, owner, &pairs).await?;
+
+    println!("  Allowances ({:?}):", owner);
+    println!("    {:<6} {:<20} {:>10}  Status", "Token", "Spender", "Allowance");
+    let mut missing = Vec::new();
+    for ((token, spender), current) in pairs.into_iter().zip(allowances) {
+        let ok = current >= MIN_ALLOWANCE;
+        println!("    {:<6} {:<20} {:>10}  {}", token.symbol, spender_label(spender), format_allowance(current), if ok { "✓" } else { "✗ MISSING" });
+        if !ok {
+            missing.push(MissingApproval { token: token.address, token_symbol: token.symbol, spender: spender.clone(), current });
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Send an approval for each missing allowance. Returns how many confirmed.
+pub async fn approve_missing<P: Provider>(
+    provider_with_signer: &P,
+    owner: Address,
+    missing: &[MissingApproval],
+    gas: GasFees,
+) -> Result<usize> {
+    let mut approved = 0;
+    for m in missing {
+        let (to, calldata) = approval_call(m.token, &m.spender, false);
+        match send_approval(provider_with_signer, owner, to, calldata, gas).await {
+            Ok(tx_hash) => {
+                println!("  ✓ {} -> {} approved, was {} (tx: {:?})",
+                    m.token_symbol, m.spender.name, format_allowance(m.current), tx_hash);
+                approved += 1;
+            }
+            Err(e) => println!("  ✗ {} -> {} approval {}", m.token_symbol, m.spender.name, e),
+        }
+    }
+    Ok(approved)
+}
+
+/// Set every (token, spender) allowance `routers` use back to 0 and print
+/// before/after. The shared token -> Permit2 approval is kept unless
+/// `include_permit2`. Returns (revoked, attempted).
+pub async fn revoke_allowances<P: Provider>(
+    provider_with_signer: &P,
+    owner: Address,
+    routers: &[RouterConfig],
+    tokens: &[TokenConfig],
+    include_permit2: bool,
+    gas: GasFees,
+) -> Result<(usize, usize)> {
+    let spenders: Vec<Spender> = required_spenders(routers)
+        .into_iter()
+        .filter(|s| include_permit2 || s.via_permit2 || s.address != PERMIT2)
+        .collect();
+    let pairs: Vec<(&TokenConfig, &Spender)> = tokens
+        .iter()
+        .flat_map(|t| spenders.iter().map(move |s| (t, s)))
+        .collect();
+    let before = read_allowances(provider_with_signer, owner, &pairs).await?;
+
+    let (mut revoked, mut attempted) = (0, 0);
+    for ((token, spender), current) in pairs.iter().zip(&before) {
+        if current.is_zero() {
+            continue;
+        }
+        attempted += 1;
+        let (to, calldata) = approval_call(token.address, spender, true);
+        match send_approval(provider_with_signer, owner, to, calldata, gas).await {
+            Ok(tx_hash) => {
+                println!("  ✓ {} -> {} revoked (tx: {:?})", token.symbol, spender_label(spender), tx_hash);
+                revoked += 1;
+            }
+            Err(e) => println!("  ✗ {} -> {} revoke {}", token.symbol, spender_label(spender), e),
+        }
+    }
+
+    let after = read_allowances(provider_with_signer, owner, &pairs).await?;
+    println!("\n  Allowances ({:?}):", owner);
+    println!("    {:<6} {:<20} {:>10} {:>10}", "Token", "Spender", "Before", "After");
+    for (((token, spender), before), after) in pairs.iter().zip(&before).zip(&after) {
+        println!("    {:<6} {:<20} {:>10} {:>10}", token.symbol, spender_label(spender), format_allowance(*before), format_allowance(*after));
+    }
+    Ok((revoked, attempted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{get_routers, GasMode, V4PoolParams};
+
+    #[test]
+    fn test_required_spenders_dedups_and_adds_permit2() {
+        let mut routers = get_routers();
+        let spenders = required_spenders(&routers);
+        // Two Pancake pools share one router
+        assert_eq!(spenders.len(), routers.len() - 1);
+        assert!(spenders.iter().all(|s| !s.via_permit2));
+
+        let v4 = RouterConfig {
+            name: "UniswapV4",
+            address: Address::repeat_byte(0x44),
+            router_type: RouterType::UniswapV4,
+            pool_address: Address::ZERO,
+            pool_fee: 500,
+            quoter: None,
+            gas_mode: GasMode::Eip1559,
+            v4_pool: Some(V4PoolParams::standard(500)),
+            usdc_buffer_bps: 10,
+            supports_native: false,
+        };
+        routers.push(v4);
+        let spenders = required_spenders(&routers);
+        assert!(spenders.contains(&Spender { name: "Permit2".into(), address: PERMIT2, via_permit2: false }));
+        assert!(spenders.iter().any(|s| s.via_permit2 && s.address == Address::repeat_byte(0x44)));
+        assert_eq!(format_allowance(U256::MAX), "MAX");
+    }
+
+    #[test]
+    fn test_revoke_calls_zero_the_allowance() {
+        let token = Address::repeat_byte(0x22);
+        let router = Spender { name: "Uniswap".into(), address: Address::repeat_byte(0x33), via_permit2: false };
+        let (to, calldata) = approval_call(token, &router, true);
+        assert_eq!(to, token);
+        assert_eq!(approveCall::abi_decode(&calldata).unwrap().amount, U256::ZERO);
+
+        let v4 = Spender { via_permit2: true, ..router };
+        let (to, calldata) = approval_call(token, &v4, true);
+        assert_eq!(to, PERMIT2);
+        let call = IPermit2::approveCall::abi_decode(&calldata).unwrap();
+        assert_eq!((call.token, call.amount), (token, U160::ZERO));
+    }
+}
@@ -0,0 +1,92 @@
+//! Cancel a stuck transaction
+//!
+//! A fire-and-forget TX priced below the market can sit in the mempool and
+//! block every later nonce. `cancel_nonce` replaces it with a 0-value
+//! self-transfer at the same nonce and higher fees (replace-by-fee).
+//! `execute_fast_arb` sends the same `cancel_request` for a swap 2 that
+//! outlived its receipt timeout.
+
+use alloy::network::TransactionBuilder;
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use eyre::{eyre, Result};
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::gas::GasFees;
+
+// Monad mainnet chain ID
+const MONAD_CHAIN_ID: u64 = 143;
+
+/// Plain transfer
+const TRANSFER_GAS: u64 = 21_000;
+
+/// How long to wait for the replacement to mine
+const CANCEL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub enum CancelOutcome {
+    /// The nonce was already used on-chain; nothing sent
+    AlreadyMined { confirmed_nonce: u64 },
+    /// Our self-transfer took the nonce
+    Replaced { tx_hash: TxHash, gas_used: u64 },
+}
+
+/// Nonce `nonce` is already mined if the confirmed count is past it
+async fn is_mined<P: Provider>(provider: &P, owner: Address, nonce: u64) -> Result<(bool, u64)> {
+    let confirmed = provider.get_transaction_count(owner).latest().await?;
+    Ok((nonce < confirmed, confirmed))
+}
+
+/// 0-value self-transfer taking `nonce`, priced at `gas`
+pub fn cancel_request(owner: Address, nonce: u64, gas: GasFees) -> TransactionRequest {
+    TransactionRequest::default()
+        .to(owner)
+        .from(owner)
+        .value(U256::ZERO)
+        .gas_limit(TRANSFER_GAS)
+        .nonce(nonce)
+        .max_fee_per_gas(gas.max_fee)
+        .max_priority_fee_per_gas(gas.priority_fee)
+        .with_chain_id(MONAD_CHAIN_ID)
+}
+
+/// Replace whatever is pending at `nonce` with a 0-value self-transfer priced at `gas`
+pub async fn cancel_nonce<P: Provider>(
+    provider_with_signer: &P,
+    owner: Address,
+    nonce: u64,
+    gas: GasFees,
+) -> Result<CancelOutcome> {
+    let (mined, confirmed_nonce) = is_mined(provider_with_signer, owner, nonce).await?;
+    if mined {
+        return Ok(CancelOutcome::AlreadyMined { confirmed_nonce });
+    }
+
+    let pending = match provider_with_signer.send_transaction(cancel_request(owner, nonce, gas)).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            // The original may have mined between the check and the send
+            if let (true, confirmed_nonce) = is_mined(provider_with_signer, owner, nonce).await? {
+                return Ok(CancelOutcome::AlreadyMined { confirmed_nonce });
+            }
+            return Err(eyre!("Replacement for nonce {} rejected: {}", nonce, e));
+        }
+    };
+    let tx_hash = *pending.tx_hash();
+    println!("  Replacement sent: {:?}", tx_hash);
+
+    match timeout(CANCEL_TIMEOUT, pending.get_receipt()).await {
+        Ok(Ok(receipt)) => Ok(CancelOutcome::Replaced { tx_hash, gas_used: receipt.gas_used }),
+        Ok(Err(e)) => Err(eyre!("Failed to get replacement receipt: {}", e)),
+        Err(_) => match is_mined(provider_with_signer, owner, nonce).await? {
+            // Nonce is used but not by us: the original won
+            (true, confirmed_nonce) => Ok(CancelOutcome::AlreadyMined { confirmed_nonce }),
+            (false, _) => Err(eyre!(
+                "Replacement {:?} not mined after {}s; retry with a larger --bump-pct",
+                tx_hash, CANCEL_TIMEOUT.as_secs()
+            )),
+        },
+    }
+}
@@ -1,5 +1,12 @@
+pub mod allowance;
 pub mod balance;
+pub mod cancel;
+pub mod permit;
+pub mod pool;
+pub mod sweep;
 pub mod wrap;
 
+pub use allowance::{audit_allowances, approve_missing, revoke_allowances};
 pub use balance::{get_balances, WalletBalances, print_balances};
-pub use wrap::{wrap_mon, unwrap_wmon, WrapResult, print_wrap_result};
+pub use pool::{WalletLease, WalletPool};
+pub use wrap::{wrap_mon, unwrap_wmon, plan_top_up, WrapResult, print_wrap_result};
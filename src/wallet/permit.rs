@@ -0,0 +1,233 @@
+//! EIP-2612 permit approvals
+//!
+//! `PrepareArb` sends one approve per (token, router), and every swap path
+//! relies on it. For tokens with EIP-2612 `permit` and routers with
+//! `selfPermit` (Uniswap SwapRouter02, PancakeSwap SmartRouter), a sequential
+//! fast arb whose router has no allowance yet can carry the approval in the
+//! swap instead: `build_permit` signs a permit for the swap's input amount and
+//! `with_self_permit` prepends `selfPermit` to the router multicall, so the
+//! swap transaction approves itself.
+//!
+//! The atomic-arb contract trades from its own balance and approves routers
+//! on chain (`setupApprovals`), so it never needs a permit.
+//!
+//! Support is detected by calling `DOMAIN_SEPARATOR()` and `nonces(owner)`;
+//! tokens without either keep the classic approve.
+
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+use eyre::{eyre, Result};
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::config::{RouterConfig, RouterType};
+use crate::execution::routers::{pancake_v3, uniswap_v3};
+use crate::multicall::aggregate_raw;
+
+/// keccak256("Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)")
+const PERMIT_TYPEHASH: B256 = alloy::primitives::b256!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9");
+
+/// Allowances at or above this never need a permit (MAX approvals sit far above it)
+const APPROVED_ALLOWANCE: U256 = U256::from_limbs([0, 0, 1, 0]); // 2^128
+
+sol! {
+    #[derive(Debug)]
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+
+    #[derive(Debug)]
+    function nonces(address owner) external view returns (uint256);
+
+    #[derive(Debug)]
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    #[derive(Debug)]
+    function selfPermit(address token, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external payable;
+}
+
+/// A token's EIP-712 domain and the owner's next permit nonce
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermitDomain {
+    pub domain_separator: B256,
+    pub nonce: U256,
+}
+
+/// Signed permit, ready for `selfPermit`
+#[derive(Debug, Clone)]
+pub struct SignedPermit {
+    pub token: Address,
+    pub value: U256,
+    pub deadline: U256,
+    pub v: u8,
+    pub r: B256,
+    pub s: B256,
+}
+
+impl SignedPermit {
+    pub fn self_permit_calldata(&self) -> Bytes {
+        Bytes::from(
+            selfPermitCall { token: self.token, value: self.value, deadline: self.deadline, v: self.v, r: self.r, s: self.s }
+                .abi_encode(),
+        )
+    }
+}
+
+/// Routers whose multicall can run `selfPermit` ahead of the swap
+pub fn supports_self_permit(router_type: RouterType) -> bool {
+    matches!(router_type, RouterType::UniswapV3 | RouterType::PancakeV3)
+}
+
+fn decode_domain(separator: Option<&Bytes>, nonce: Option<&Bytes>) -> Option<PermitDomain> {
+    let domain_separator = DOMAIN_SEPARATORCall::abi_decode_returns(separator?).ok()?;
+    let nonce = noncesCall::abi_decode_returns(nonce?).ok()?;
+    Some(PermitDomain { domain_separator, nonce })
+}
+
+/// EIP-712 digest the token checks the permit signature against
+pub fn permit_digest(domain: &PermitDomain, owner: Address, spender: Address, value: U256, deadline: U256) -> B256 {
+    let struct_hash = keccak256((PERMIT_TYPEHASH, owner, spender, value, domain.nonce, deadline).abi_encode());
+    let mut preimage = Vec::with_capacity(66);
+    preimage.extend_from_slice(b"\x19\x01");
+    preimage.extend_from_slice(domain.domain_separator.as_slice());
+    preimage.extend_from_slice(struct_hash.as_slice());
+    keccak256(preimage)
+}
+
+/// Sign a permit letting `spender` pull `value` of `token` from `signer` until `deadline`
+pub fn build_permit(
+    signer: &PrivateKeySigner,
+    domain: &PermitDomain,
+    token: Address,
+    spender: Address,
+    value: U256,
+    deadline: u64,
+) -> Result<SignedPermit> {
+    let deadline = U256::from(deadline);
+    let digest = permit_digest(domain, signer.address(), spender, value, deadline);
+    let signature = signer.sign_hash_sync(&digest).map_err(|e| eyre!("Permit signing failed: {}", e))?;
+    Ok(SignedPermit {
+        token,
+        value,
+        deadline,
+        v: 27 + signature.v() as u8,
+        r: B256::from(signature.r()),
+        s: B256::from(signature.s()),
+    })
+}
+
+/// Run `permit` in the same router call as `swap_calldata`
+pub fn with_self_permit(router_type: RouterType, permit: &SignedPermit, swap_calldata: Bytes) -> Result<Bytes> {
+    match router_type {
+        RouterType::UniswapV3 => Ok(Bytes::from(
+            uniswap_v3::multicallCall { data: vec![permit.self_permit_calldata(), swap_calldata] }.abi_encode(),
+        )),
+        RouterType::PancakeV3 => {
+            // Already multicall(deadline, [swap]); put the permit first
+            let mut call = pancake_v3::multicallCall::abi_decode(&swap_calldata)?;
+            call.data.insert(0, permit.self_permit_calldata());
+            Ok(Bytes::from(call.abi_encode()))
+        }
+        other => Err(eyre!("{:?} router has no selfPermit", other)),
+    }
+}
+
+/// (token, router) pairs already approved, and tokens without permit; neither needs checking again
+static SKIP_PERMIT: Mutex<Option<HashSet<(Address, Address)>>> = Mutex::new(None);
+
+fn skip_permit(token: Address, spender: Address) -> bool {
+    let skip = SKIP_PERMIT.lock().unwrap_or_else(|e| e.into_inner());
+    skip.as_ref().is_some_and(|s| s.contains(&(token, spender)) || s.contains(&(token, Address::ZERO)))
+}
+
+fn mark_skip(token: Address, spender: Address) {
+    SKIP_PERMIT.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert_with(HashSet::new).insert((token, spender));
+}
+
+/// Prepend a signed permit to `swap_calldata` when `router` has no allowance
+/// for `amount_in` of `token_in` and the token supports permit. Any failure
+/// leaves the calldata as built (the swap then relies on a classic approve).
+pub async fn attach_self_permit<P: Provider>(
+    provider: &P,
+    signer: Option<&PrivateKeySigner>,
+    router: &RouterConfig,
+    token_in: Address,
+    amount_in: U256,
+    deadline: u64,
+    swap_calldata: Bytes,
+) -> Bytes {
+    let Some(signer) = signer else { return swap_calldata };
+    if !supports_self_permit(router.router_type) || skip_permit(token_in, router.address) {
+        return swap_calldata;
+    }
+
+    let owner = signer.address();
+    let calls = [
+        (token_in, Bytes::from(allowanceCall { owner, spender: router.address }.abi_encode())),
+        (token_in, Bytes::from(DOMAIN_SEPARATORCall {}.abi_encode())),
+        (token_in, Bytes::from(noncesCall { owner }.abi_encode())),
+    ];
+    let results = match aggregate_raw(provider, &calls).await {
+        Ok(results) => results,
+        Err(e) => {
+            tracing::warn!("Permit check failed, sending without permit: {}", e);
+            return swap_calldata;
+        }
+    };
+
+    let allowance = results[0].as_ref().and_then(|d| allowanceCall::abi_decode_returns(d).ok()).unwrap_or_default();
+    if allowance >= APPROVED_ALLOWANCE {
+        mark_skip(token_in, router.address);
+        return swap_calldata;
+    }
+    let Some(domain) = decode_domain(results[1].as_ref(), results[2].as_ref()) else {
+        mark_skip(token_in, Address::ZERO);
+        return swap_calldata;
+    };
+    if allowance >= amount_in {
+        return swap_calldata;
+    }
+
+    match build_permit(signer, &domain, token_in, router.address, amount_in, deadline)
+        .and_then(|permit| with_self_permit(router.router_type, &permit, swap_calldata.clone()))
+    {
+        Ok(calldata) => {
+            println!("    Permit: {} approves itself via selfPermit (nonce {})", router.name, domain.nonce);
+            calldata
+        }
+        Err(e) => {
+            tracing::warn!("Permit build failed, sending without permit: {}", e);
+            swap_calldata
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Signature;
+
+    #[test]
+    fn test_permit_signature_recovers_owner_and_wraps_swap() {
+        assert_eq!(keccak256("Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"), PERMIT_TYPEHASH);
+
+        let signer = PrivateKeySigner::random();
+        let domain = PermitDomain { domain_separator: B256::repeat_byte(0xab), nonce: U256::from(3) };
+        let token = Address::repeat_byte(0x22);
+        let spender = Address::repeat_byte(0x33);
+        let permit = build_permit(&signer, &domain, token, spender, U256::from(1_000_000), 1_700_000_000).unwrap();
+
+        let digest = permit_digest(&domain, signer.address(), spender, permit.value, permit.deadline);
+        let signature = Signature::from_scalars_and_parity(permit.r, permit.s, permit.v == 28);
+        assert_eq!(signature.recover_address_from_prehash(&digest).unwrap(), signer.address());
+
+        let swap = pancake_v3::build_exact_input_single(token, spender, 500, signer.address(), U256::from(1), U256::ZERO, 1).unwrap();
+        let wrapped = with_self_permit(RouterType::PancakeV3, &permit, swap).unwrap();
+        let call = pancake_v3::multicallCall::abi_decode(&wrapped).unwrap();
+        assert_eq!(call.data.len(), 2);
+        assert_eq!(call.data[0][..4], selfPermitCall::SELECTOR);
+        assert!(with_self_permit(RouterType::LfjLB, &permit, Bytes::new()).is_err());
+    }
+}
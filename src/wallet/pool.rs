@@ -0,0 +1,161 @@
+//! Multi-wallet rotation
+//!
+//! One wallet means one arb in flight (nonces serialize). `PRIVATE_KEYS`
+//! (comma-separated) loads several signers; `acquire` hands out an idle
+//! wallet round-robin so AutoArb can run opportunities concurrently.
+//! With only `PRIVATE_KEY` set the pool holds a single wallet.
+
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use alloy::signers::local::PrivateKeySigner;
+use eyre::{eyre, Result};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use crate::nonce::init_nonce;
+
+#[derive(Debug)]
+struct PoolState {
+    /// Busy flag per wallet
+    slots: Vec<bool>,
+    /// Round-robin start for the next acquire
+    cursor: usize,
+}
+
+/// Pool of signers, each with its own nonce counter
+pub struct WalletPool {
+    signers: Vec<PrivateKeySigner>,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl WalletPool {
+    /// Load from `PRIVATE_KEYS`, falling back to `PRIVATE_KEY`
+    pub fn from_env() -> Result<Self> {
+        let keys: Vec<String> = match std::env::var("PRIVATE_KEYS") {
+            Ok(list) if !list.trim().is_empty() => list
+                .split(',')
+                .map(|k| k.trim().to_string())
+                .filter(|k| !k.is_empty())
+                .collect(),
+            _ => vec![std::env::var("PRIVATE_KEY").map_err(|_| eyre!("PRIVATE_KEY or PRIVATE_KEYS must be set"))?],
+        };
+
+        let mut signers: Vec<PrivateKeySigner> = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            let signer = PrivateKeySigner::from_str(key).map_err(|e| eyre!("Invalid private key #{}: {}", i + 1, e))?;
+            if !signers.iter().any(|s| s.address() == signer.address()) {
+                signers.push(signer);
+            }
+        }
+
+        Ok(Self::new(signers))
+    }
+
+    pub fn new(signers: Vec<PrivateKeySigner>) -> Self {
+        let slots = vec![false; signers.len()];
+        Self {
+            signers,
+            state: Arc::new(Mutex::new(PoolState { slots, cursor: 0 })),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.signers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty()
+    }
+
+    pub fn signers(&self) -> &[PrivateKeySigner] {
+        &self.signers
+    }
+
+    pub fn addresses(&self) -> Vec<Address> {
+        self.signers.iter().map(|s| s.address()).collect()
+    }
+
+    /// Fetch a starting nonce for every wallet (first wallet is the primary)
+    pub async fn init_nonces<P: Provider>(&self, provider: &P) -> Result<()> {
+        for address in self.addresses() {
+            init_nonce(provider, address).await?;
+        }
+        Ok(())
+    }
+
+    /// Drop wallets that don't satisfy `keep` (e.g. not the atomic contract owner)
+    pub fn retain(&mut self, keep: impl Fn(Address) -> bool) {
+        let mut state = self.state.lock().unwrap();
+        let mut i = 0;
+        self.signers.retain(|s| {
+            let kept = keep(s.address());
+            if !kept {
+                state.slots.remove(i);
+            } else {
+                i += 1;
+            }
+            kept
+        });
+        state.cursor = 0;
+    }
+
+    /// Next idle wallet (round-robin)
+    pub fn acquire(&self) -> Option<WalletLease> {
+        let mut state = self.state.lock().unwrap();
+        let n = state.slots.len();
+        for offset in 0..n {
+            let index = (state.cursor + offset) % n;
+            if !state.slots[index] {
+                state.slots[index] = true;
+                state.cursor = (index + 1) % n;
+                return Some(WalletLease {
+                    index,
+                    address: self.signers[index].address(),
+                    state: Arc::clone(&self.state),
+                });
+            }
+        }
+        None
+    }
+
+    /// Wallets currently executing
+    pub fn busy_count(&self) -> usize {
+        self.state.lock().unwrap().slots.iter().filter(|busy| **busy).count()
+    }
+}
+
+/// Exclusive use of one wallet; released on drop
+pub struct WalletLease {
+    pub index: usize,
+    pub address: Address,
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl Drop for WalletLease {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.slots[self.index] = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_skips_busy_wallets() {
+        let pool = WalletPool::new(vec![PrivateKeySigner::random(), PrivateKeySigner::random()]);
+        let addrs = pool.addresses();
+
+        let a = pool.acquire().unwrap();
+        let b = pool.acquire().unwrap();
+        assert_eq!((a.address, b.address), (addrs[0], addrs[1]));
+        assert!(pool.acquire().is_none());
+
+        // Released wallet is handed out again; rotation resumes after the last lease
+        drop(a);
+        assert_eq!(pool.acquire().unwrap().address, addrs[0]);
+        drop(b);
+        assert_eq!(pool.busy_count(), 0);
+    }
+}
@@ -0,0 +1,129 @@
+//! Sweep arb profit out of the hot contract to a cold wallet
+//!
+//! AutoArb `--auto-sweep-profit` moves contract WMON above the working float
+//! (the starting balance plus auto-wrap top-ups) to `COLD_WALLET`. The
+//! contract's `withdrawToken` only pays its owner, so a sweep is two
+//! transactions: withdraw the excess to the owner, then transfer it on.
+
+use alloy::network::TransactionBuilder;
+use alloy::primitives::{Address, Bytes, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::{eyre, Result};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::config::{atomic_arb_contract, WMON_ADDRESS, WMON_DECIMALS};
+use crate::gas::GasFees;
+use crate::nonce::next_nonce_for;
+
+// Monad mainnet chain ID
+const MONAD_CHAIN_ID: u64 = 143;
+
+/// Gas limit for the withdraw and the ERC20 transfer
+const SWEEP_GAS_LIMIT: u64 = 100_000;
+
+/// How long to wait for each sweep TX to mine
+const SWEEP_TIMEOUT: Duration = Duration::from_secs(30);
+
+sol! {
+    function withdrawToken(address token, uint256 amount) external;
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+#[derive(Debug, Clone)]
+pub struct SweepResult {
+    pub amount: f64,
+    pub withdraw_tx: TxHash,
+    pub transfer_tx: TxHash,
+}
+
+/// `COLD_WALLET` from the environment; must differ from the hot `owner`
+pub fn cold_wallet_from_env(owner: Address) -> Result<Address> {
+    let raw = std::env::var("COLD_WALLET").map_err(|_| eyre!("--auto-sweep-profit requires COLD_WALLET to be set"))?;
+    let cold = Address::from_str(raw.trim()).map_err(|e| eyre!("Invalid COLD_WALLET {}: {}", raw, e))?;
+    if cold == owner || cold == Address::ZERO {
+        return Err(eyre!("COLD_WALLET must be a separate wallet from the hot signer"));
+    }
+    Ok(cold)
+}
+
+/// WMON above `working_float` once the excess reaches `threshold`, rounded down
+/// to 1e-6 so float rounding never eats into the float
+pub fn sweepable(contract_wmon: f64, working_float: f64, threshold: f64) -> Option<f64> {
+    let excess = ((contract_wmon - working_float) * 1e6).floor() / 1e6;
+    (excess > 0.0 && excess >= threshold).then_some(excess)
+}
+
+fn to_wei(amount: f64) -> U256 {
+    U256::from((amount * 10f64.powi(WMON_DECIMALS as i32)) as u128)
+}
+
+async fn send_and_confirm<S: Provider>(
+    provider_with_signer: &S,
+    owner: Address,
+    to: Address,
+    calldata: Vec<u8>,
+    gas: GasFees,
+) -> Result<TxHash> {
+    let tx = alloy::rpc::types::TransactionRequest::default()
+        .to(to)
+        .from(owner)
+        .input(alloy::rpc::types::TransactionInput::new(Bytes::from(calldata)))
+        .gas_limit(SWEEP_GAS_LIMIT)
+        .nonce(next_nonce_for(owner))
+        .max_fee_per_gas(gas.max_fee)
+        .max_priority_fee_per_gas(gas.priority_fee)
+        .with_chain_id(MONAD_CHAIN_ID);
+
+    let pending = provider_with_signer.send_transaction(tx).await?;
+    let tx_hash = *pending.tx_hash();
+    let receipt = timeout(SWEEP_TIMEOUT, pending.get_receipt())
+        .await
+        .map_err(|_| eyre!("{:?} not mined after {}s", tx_hash, SWEEP_TIMEOUT.as_secs()))??;
+    if !receipt.status() {
+        return Err(eyre!("{:?} reverted", tx_hash));
+    }
+    Ok(tx_hash)
+}
+
+/// Withdraw `amount` WMON from the contract to `owner` and forward it to `cold_wallet`
+pub async fn sweep_profit<S: Provider>(
+    provider_with_signer: &S,
+    owner: Address,
+    cold_wallet: Address,
+    amount: f64,
+    gas: GasFees,
+) -> Result<SweepResult> {
+    let amount_wei = to_wei(amount);
+
+    let withdraw = withdrawTokenCall { token: WMON_ADDRESS, amount: amount_wei }.abi_encode();
+    let withdraw_tx = send_and_confirm(provider_with_signer, owner, atomic_arb_contract(), withdraw, gas)
+        .await
+        .map_err(|e| eyre!("withdraw failed: {}", e))?;
+
+    let forward = transferCall { to: cold_wallet, amount: amount_wei }.abi_encode();
+    let transfer_tx = send_and_confirm(provider_with_signer, owner, WMON_ADDRESS, forward, gas)
+        .await
+        .map_err(|e| eyre!("withdrew {:.6} WMON ({:?}) but the transfer to cold failed: {}", amount, withdraw_tx, e))?;
+
+    Ok(SweepResult { amount, withdraw_tx, transfer_tx })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_only_takes_excess_over_float() {
+        assert_eq!(sweepable(105.0, 100.0, 1.0), Some(5.0));
+        assert_eq!(sweepable(100.5, 100.0, 1.0), None);
+        assert_eq!(sweepable(99.0, 100.0, 0.0), None);
+
+        // Rounds down, never past the float
+        let excess = sweepable(101.2345678, 100.0, 1.0).unwrap();
+        assert!(excess <= 1.2345678 && excess > 1.234);
+    }
+}
@@ -8,6 +8,7 @@ use eyre::{eyre, Result};
 
 use crate::config::{WMON_ADDRESS, WMON_DECIMALS};
 use crate::nonce::next_nonce;
+use crate::rpc_failover;
 
 // Monad mainnet chain ID
 const MONAD_CHAIN_ID: u64 = 143;
@@ -101,7 +102,7 @@ pub async fn wrap_mon<P: Provider>(
     let url: reqwest::Url = rpc_url.parse()?;
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     println!("  -> Wrapping MON to WMON...");
 
@@ -143,6 +144,29 @@ pub async fn wrap_mon<P: Provider>(
     })
 }
 
+/// MON left in the wallet for gas when auto-wrapping
+pub const WRAP_GAS_RESERVE_MON: f64 = 1.0;
+
+/// MON to wrap when arb inventory (`wmon`) is below `threshold`: enough to get
+/// back to `target`, limited by the wallet's MON above the gas reserve and by
+/// what's left of the session cap. None when no top-up is due or possible.
+pub fn plan_top_up(
+    wmon: f64,
+    threshold: f64,
+    target: f64,
+    wallet_mon: f64,
+    wrapped_so_far: f64,
+    session_cap: f64,
+) -> Option<f64> {
+    if wmon >= threshold {
+        return None;
+    }
+    let amount = (target - wmon)
+        .min(wallet_mon - WRAP_GAS_RESERVE_MON)
+        .min(session_cap - wrapped_so_far);
+    (amount > 0.0).then_some(amount)
+}
+
 /// Unwrap WMON to MON
 /// Burns WMON tokens, receives native MON
 pub async fn unwrap_wmon<P: Provider>(
@@ -194,7 +218,7 @@ pub async fn unwrap_wmon<P: Provider>(
     let url: reqwest::Url = rpc_url.parse()?;
     let provider_with_signer = ProviderBuilder::new()
         .wallet(wallet)
-        .connect_http(url);
+        .connect_client(rpc_failover::client(url));
 
     println!("  -> Unwrapping WMON to MON...");
 
@@ -266,3 +290,21 @@ pub fn print_wrap_result(result: &WrapResult) {
     println!("╚══════════════════════════════════════════════════════════════╝");
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_top_up_respects_mon_and_session_cap() {
+        // Above threshold: nothing to do
+        assert_eq!(plan_top_up(60.0, 50.0, 110.0, 500.0, 0.0, 1000.0), None);
+        // Refill to target
+        assert_eq!(plan_top_up(40.0, 50.0, 110.0, 500.0, 0.0, 1000.0), Some(70.0));
+        // Wallet MON minus gas reserve
+        assert_eq!(plan_top_up(40.0, 50.0, 110.0, 21.0, 0.0, 1000.0), Some(20.0));
+        // Remaining session cap, then exhausted
+        assert_eq!(plan_top_up(40.0, 50.0, 110.0, 500.0, 990.0, 1000.0), Some(10.0));
+        assert_eq!(plan_top_up(40.0, 50.0, 110.0, 500.0, 1000.0, 1000.0), None);
+    }
+}